@@ -0,0 +1,72 @@
+use shader_sense::symbols::symbols::{
+    ShaderParameter, ShaderSignature, ShaderSymbol, ShaderSymbolData, ShaderSymbolList,
+};
+
+use super::GlslIntrinsicParser;
+
+impl GlslIntrinsicParser {
+    // Atomic counter operations from GLSL 4.60 are missing from the reference pages.
+    pub fn add_atomic_counters(&self, symbols: &mut ShaderSymbolList) {
+        fn new_glsl_atomic_counter(
+            label: &str,
+            description: &str,
+            parameters: &[(&str, &str)],
+        ) -> ShaderSymbol {
+            let mut signature_parameters = vec![ShaderParameter {
+                ty: "atomic_uint".into(),
+                label: "c".into(),
+                description: "Atomic counter to operate on.".into(),
+            }];
+            signature_parameters.extend(parameters.iter().map(|(label, description)| {
+                ShaderParameter {
+                    ty: "uint".into(),
+                    label: label.to_string(),
+                    description: description.to_string(),
+                }
+            }));
+            ShaderSymbol {
+                label: label.into(),
+                description: description.into(),
+                version: "460".into(),
+                stages: vec![],
+                link: Some("https://www.khronos.org/opengl/wiki/Atomic_Counter".into()),
+                data: ShaderSymbolData::Functions {
+                    signatures: vec![ShaderSignature {
+                        returnType: "uint".into(),
+                        description: "Value of the counter before the operation.".into(),
+                        parameters: signature_parameters,
+                    }],
+                    workgroup_size: None,
+                },
+                range: None,
+                scope_stack: None,
+                snippet: None,
+            }
+        }
+        let operations = [
+            ("atomicCounterAdd", "Atomically adds data to the atomic counter c and returns its prior value."),
+            ("atomicCounterSubtract", "Atomically subtracts data from the atomic counter c and returns its prior value."),
+            ("atomicCounterMin", "Atomically assigns the minimum of data and the atomic counter c to c and returns its prior value."),
+            ("atomicCounterMax", "Atomically assigns the maximum of data and the atomic counter c to c and returns its prior value."),
+            ("atomicCounterAnd", "Atomically assigns the bitwise AND of data and the atomic counter c to c and returns its prior value."),
+            ("atomicCounterOr", "Atomically assigns the bitwise OR of data and the atomic counter c to c and returns its prior value."),
+            ("atomicCounterXor", "Atomically assigns the bitwise XOR of data and the atomic counter c to c and returns its prior value."),
+            ("atomicCounterExchange", "Atomically assigns data to the atomic counter c and returns its prior value."),
+        ];
+        for (label, description) in operations {
+            symbols.functions.push(new_glsl_atomic_counter(
+                label,
+                description,
+                &[("data", "Operand of the operation.")],
+            ));
+        }
+        symbols.functions.push(new_glsl_atomic_counter(
+            "atomicCounterCompSwap",
+            "Atomically compares compare with the atomic counter c. If they are equal, data is assigned to c. Returns the prior value of c.",
+            &[
+                ("compare", "Value to compare the counter to."),
+                ("data", "Value assigned to the counter if equal to compare."),
+            ],
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+use shader_sense::{
+    shader::ShaderStage,
+    symbols::symbols::{ShaderMember, ShaderSymbol, ShaderSymbolData, ShaderSymbolList},
+};
+
+use super::GlslIntrinsicParser;
+
+impl GlslIntrinsicParser {
+    pub fn add_builtins(&self, symbols: &mut ShaderSymbolList) {
+        fn new_glsl_member(label: &str, ty: &str, description: &str) -> ShaderMember {
+            ShaderMember {
+                ty: ty.into(),
+                label: label.into(),
+                description: description.into(),
+            }
+        }
+        fn new_glsl_builtin(
+            label: &str,
+            ty: &str,
+            description: &str,
+            stages: Vec<ShaderStage>,
+        ) -> ShaderSymbol {
+            ShaderSymbol {
+                label: label.into(),
+                description: description.into(),
+                version: "150".into(),
+                stages,
+                link: Some("https://www.khronos.org/opengl/wiki/Built-in_Variable_(GLSL)".into()),
+                data: ShaderSymbolData::Variables {
+                    ty: ty.into(),
+                    qualifier: "".into(),
+                    register: None,
+                },
+                range: None,
+                scope_stack: None,
+                snippet: None,
+            }
+        }
+        // Per vertex block used by gl_in & gl_out arrays. Not in documentation.
+        symbols.types.push(ShaderSymbol {
+            label: "gl_PerVertex".into(),
+            description: "Built-in per-vertex block of the vertex processing stages.".into(),
+            version: "150".into(),
+            stages: vec![],
+            link: Some("https://www.khronos.org/opengl/wiki/Built-in_Variable_(GLSL)".into()),
+            data: ShaderSymbolData::Struct {
+                members: vec![
+                    new_glsl_member(
+                        "gl_Position",
+                        "vec4",
+                        "The clip-space output position of the vertex.",
+                    ),
+                    new_glsl_member(
+                        "gl_PointSize",
+                        "float",
+                        "The pixel width/height of the point being rasterized.",
+                    ),
+                    new_glsl_member(
+                        "gl_ClipDistance",
+                        "float[]",
+                        "Distances to the user-defined clipping planes.",
+                    ),
+                    new_glsl_member(
+                        "gl_CullDistance",
+                        "float[]",
+                        "Distances to the user-defined culling planes.",
+                    ),
+                ],
+                methods: vec![],
+            },
+            range: None,
+            scope_stack: None,
+            snippet: None,
+        });
+        symbols.variables.push(new_glsl_builtin(
+            "gl_in",
+            "gl_PerVertex",
+            "Array of per-vertex inputs from the previous stage, indexed by vertex.",
+            vec![
+                ShaderStage::TesselationControl,
+                ShaderStage::TesselationEvaluation,
+                ShaderStage::Geometry,
+            ],
+        ));
+        symbols.variables.push(new_glsl_builtin(
+            "gl_out",
+            "gl_PerVertex",
+            "Array of per-vertex outputs of the patch, indexed by vertex.",
+            vec![ShaderStage::TesselationControl],
+        ));
+        symbols.variables.push(new_glsl_builtin(
+            "gl_InvocationID",
+            "int",
+            "Index of the output patch vertex or geometry instance of the current invocation.",
+            vec![ShaderStage::TesselationControl, ShaderStage::Geometry],
+        ));
+    }
+}
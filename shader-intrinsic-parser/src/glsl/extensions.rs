@@ -15,6 +15,7 @@ impl GlslIntrinsicParser {
             data: todo!(),
             range: None,
             scope_stack: None,
+            snippet: None,
         });
         list
     }
@@ -0,0 +1,122 @@
+use shader_sense::symbols::symbols::{
+    ShaderParameter, ShaderSignature, ShaderSymbol, ShaderSymbolData, ShaderSymbolList,
+    ShaderVectorType,
+};
+
+use super::GlslIntrinsicParser;
+
+// Symbols are only available once the extension is enabled, version hold the extension name.
+const GL_ARB_GPU_SHADER_INT64: &str = "GL_ARB_gpu_shader_int64";
+const GL_ARB_GPU_SHADER_INT64_LINK: &str =
+    "https://registry.khronos.org/OpenGL/extensions/ARB/ARB_gpu_shader_int64.txt";
+
+impl GlslIntrinsicParser {
+    // 64-bit integer types & functions from GL_ARB_gpu_shader_int64 are missing from the reference pages.
+    pub fn add_int64(&self, symbols: &mut ShaderSymbolList) {
+        fn new_glsl_int64_vector(
+            label: &str,
+            description: &str,
+            scalar: &str,
+            dimension: u32,
+        ) -> ShaderSymbol {
+            ShaderSymbol {
+                label: label.into(),
+                description: description.into(),
+                version: GL_ARB_GPU_SHADER_INT64.into(),
+                stages: vec![],
+                link: Some(GL_ARB_GPU_SHADER_INT64_LINK.into()),
+                data: ShaderSymbolData::Types {
+                    ty: label.into(),
+                    vector: Some(ShaderVectorType {
+                        scalar: scalar.into(),
+                        dimension,
+                    }),
+                },
+                range: None,
+                scope_stack: None,
+                snippet: None,
+            }
+        }
+        fn new_glsl_int64_function(
+            label: &str,
+            description: &str,
+            return_type: &str,
+            parameter: (&str, &str),
+        ) -> ShaderSymbol {
+            ShaderSymbol {
+                label: label.into(),
+                description: description.into(),
+                version: GL_ARB_GPU_SHADER_INT64.into(),
+                stages: vec![],
+                link: Some(GL_ARB_GPU_SHADER_INT64_LINK.into()),
+                data: ShaderSymbolData::Functions {
+                    signatures: vec![ShaderSignature {
+                        returnType: return_type.into(),
+                        description: "".into(),
+                        parameters: vec![ShaderParameter {
+                            ty: parameter.0.into(),
+                            label: parameter.1.into(),
+                            description: "".into(),
+                        }],
+                    }],
+                    workgroup_size: None,
+                },
+                range: None,
+                scope_stack: None,
+                snippet: None,
+            }
+        }
+        symbols.types.push(new_glsl_int64_vector(
+            "int64_t",
+            "a signed, two's complement, 64-bit integer",
+            "int64_t",
+            1,
+        ));
+        symbols.types.push(new_glsl_int64_vector(
+            "uint64_t",
+            "an unsigned 64-bit integer",
+            "uint64_t",
+            1,
+        ));
+        for component in 2..=4 {
+            symbols.types.push(new_glsl_int64_vector(
+                format!("i64vec{}", component).as_str(),
+                format!(
+                    "Vector with {} components of signed 64-bit integers",
+                    component
+                )
+                .as_str(),
+                "int64_t",
+                component,
+            ));
+            symbols.types.push(new_glsl_int64_vector(
+                format!("u64vec{}", component).as_str(),
+                format!(
+                    "Vector with {} components of unsigned 64-bit integers",
+                    component
+                )
+                .as_str(),
+                "uint64_t",
+                component,
+            ));
+        }
+        let functions = [
+            ("packInt2x32", "packInt2x32 returns a signed 64-bit integer obtained by packing the components of v. The first component specifies the 32 least significant bits, the second component specifies the 32 most significant bits.", "int64_t", ("ivec2", "v")),
+            ("packUint2x32", "packUint2x32 returns an unsigned 64-bit integer obtained by packing the components of v. The first component specifies the 32 least significant bits, the second component specifies the 32 most significant bits.", "uint64_t", ("uvec2", "v")),
+            ("unpackInt2x32", "unpackInt2x32 returns a signed integer vector built from the 32 least significant bits of v in its first component and the 32 most significant bits in its second component.", "ivec2", ("int64_t", "v")),
+            ("unpackUint2x32", "unpackUint2x32 returns an unsigned integer vector built from the 32 least significant bits of v in its first component and the 32 most significant bits in its second component.", "uvec2", ("uint64_t", "v")),
+            ("doubleBitsToInt64", "doubleBitsToInt64 returns the encoding of its double-precision floating-point parameter as a signed 64-bit integer. The floating-point bit-level representation is preserved.", "genI64Type", ("genDType", "value")),
+            ("doubleBitsToUint64", "doubleBitsToUint64 returns the encoding of its double-precision floating-point parameter as an unsigned 64-bit integer. The floating-point bit-level representation is preserved.", "genU64Type", ("genDType", "value")),
+            ("int64BitsToDouble", "int64BitsToDouble returns the double-precision floating-point value corresponding to the signed 64-bit integer encoding of value.", "genDType", ("genI64Type", "value")),
+            ("uint64BitsToDouble", "uint64BitsToDouble returns the double-precision floating-point value corresponding to the unsigned 64-bit integer encoding of value.", "genDType", ("genU64Type", "value")),
+        ];
+        for (label, description, return_type, parameter) in functions {
+            symbols.functions.push(new_glsl_int64_function(
+                label,
+                description,
+                return_type,
+                parameter,
+            ));
+        }
+    }
+}
@@ -8,10 +8,67 @@ use xmltree::Element;
 
 use crate::glsl::{get_childs, merge_text};
 
-use super::GlslIntrinsicParser;
+use super::{get_refpage_cache_path, GlslIntrinsicParser, GlslRefpage, GLSL_REFPAGES};
+
+// Versions are written as "110, 100 es", with the earliest desktop & ES versions.
+fn merge_glsl_version(lhs: &str, rhs: &str) -> String {
+    // No version means available everywhere.
+    if lhs.is_empty() || rhs.is_empty() {
+        return "".into();
+    }
+    let mut desktop_version: Option<u32> = None;
+    let mut es_version: Option<u32> = None;
+    for entry in lhs
+        .split(',')
+        .chain(rhs.split(','))
+        .map(|entry| entry.trim())
+    {
+        let (version, earliest_version) = match entry.strip_suffix("es") {
+            Some(version) => (version.trim(), &mut es_version),
+            None => (entry, &mut desktop_version),
+        };
+        if let Ok(version) = version.parse::<u32>() {
+            *earliest_version =
+                Some(earliest_version.map_or(version, |earliest| earliest.min(version)));
+        }
+    }
+    match (desktop_version, es_version) {
+        (Some(desktop_version), Some(es_version)) => {
+            format!("{}, {} es", desktop_version, es_version)
+        }
+        (Some(desktop_version), None) => desktop_version.to_string(),
+        (None, Some(es_version)) => format!("{} es", es_version),
+        (None, None) => "".into(),
+    }
+}
+
+fn merge_symbol(symbols: &mut Vec<ShaderSymbol>, symbol: ShaderSymbol) {
+    match symbols
+        .iter_mut()
+        .find(|existing| existing.label == symbol.label)
+    {
+        Some(existing) => existing.version = merge_glsl_version(&existing.version, &symbol.version),
+        None => symbols.push(symbol),
+    }
+}
 
 impl GlslIntrinsicParser {
     pub fn add_methods(&self, symbols: &mut ShaderSymbolList, cache_path: &str) {
+        for refpage in &GLSL_REFPAGES {
+            let refpage_path = get_refpage_cache_path(cache_path, refpage);
+            if !std::path::Path::new(&refpage_path).is_dir() {
+                println!("No cache found at {}, skipping.", refpage_path);
+                continue;
+            }
+            self.add_refpage_methods(symbols, &refpage_path, refpage);
+        }
+    }
+    fn add_refpage_methods(
+        &self,
+        symbols: &mut ShaderSymbolList,
+        cache_path: &str,
+        refpage: &GlslRefpage,
+    ) {
         let paths = std::fs::read_dir(cache_path).expect("Failed to read dir");
         for path_dir in paths {
             let path = path_dir.expect("Failed to parse path").path();
@@ -24,7 +81,8 @@ impl GlslIntrinsicParser {
                 .to_string_lossy()
                 .to_string();
             let link = format!(
-                "https://registry.khronos.org/OpenGL-Refpages/gl4/html/{}",
+                "https://registry.khronos.org/OpenGL-Refpages/{}/html/{}",
+                refpage.name,
                 filename.replace("xml", "xhtml")
             );
             let resp = std::fs::read_to_string(path).expect("Failed to read cached file");
@@ -95,9 +153,11 @@ impl GlslIntrinsicParser {
                         link: Some(link.clone()),
                         data: ShaderSymbolData::Functions {
                             signatures: signature.1,
+                            workgroup_size: None,
                         },
                         range: None,
                         scope_stack: None,
+                        snippet: None,
                     });
                 }
             } else {
@@ -168,9 +228,14 @@ impl GlslIntrinsicParser {
                     version: "".to_string(),
                     stages: Vec::new(),
                     link: Some(link.clone()),
-                    data: ShaderSymbolData::Variables { ty: ty.into() },
+                    data: ShaderSymbolData::Variables {
+                        ty: ty.into(),
+                        qualifier: "".into(),
+                        register: None,
+                    },
                     range: None,
                     scope_stack: None,
+                    snippet: None,
                 });
             }
             let refsect = get_childs(&elements, "refsect1");
@@ -272,7 +337,11 @@ impl GlslIntrinsicParser {
                                         .replace("[4]", "");
                                     if compare_symbol == key {
                                         found = true;
-                                        symbol.version = glsl_version.to_string();
+                                        symbol.version = if refpage.is_es {
+                                            format!("{} es", glsl_version)
+                                        } else {
+                                            glsl_version.to_string()
+                                        };
                                     }
                                 }
                                 if !found {
@@ -297,11 +366,11 @@ impl GlslIntrinsicParser {
             // TODO: retrieve stage aswell. might need to do this manually, with a list of all func for all stages.
             for symbol in link_symbol {
                 if is_function {
-                    symbols.functions.push(symbol);
+                    merge_symbol(&mut symbols.functions, symbol);
                 } else if is_variable {
-                    symbols.variables.push(symbol);
+                    merge_symbol(&mut symbols.variables, symbol);
                 } else {
-                    symbols.constants.push(symbol);
+                    merge_symbol(&mut symbols.constants, symbol);
                 }
             }
         }
@@ -7,7 +7,10 @@ use regex::Regex;
 use scraper::{Html, Selector};
 use xmltree::XMLNode;
 
+mod atomics;
+mod builtins;
 mod extensions;
+mod int64;
 mod keywords;
 mod methods;
 mod types;
@@ -83,22 +86,56 @@ pub fn merge_text(element: &xmltree::Element) -> String {
         full_text
     }
 }
+struct GlslRefpage {
+    name: &'static str,
+    is_es: bool,
+}
+
+// Desktop & ES reference pages. Intrinsics found in several pages keep the earliest version of each.
+const GLSL_REFPAGES: [GlslRefpage; 4] = [
+    GlslRefpage {
+        name: "gl4",
+        is_es: false,
+    },
+    GlslRefpage {
+        name: "gl2.1",
+        is_es: false,
+    },
+    GlslRefpage {
+        name: "es3",
+        is_es: true,
+    },
+    GlslRefpage {
+        name: "es2.0",
+        is_es: true,
+    },
+];
+
+fn get_refpage_cache_path(cache_path: &str, refpage: &GlslRefpage) -> String {
+    format!("{}{}/", cache_path, refpage.name)
+}
+
 pub struct GlslIntrinsicParser {}
 
-impl IntrinsicParser for GlslIntrinsicParser {
-    fn cache(&self, cache_path: &str) {
-        // TODO: dont parse only gl4, missing gl2.1, es3 (3.2) / es3.1 / es3.0 / es2.0 / es1.1
-        let unique_links =
-            get_links("https://registry.khronos.org/OpenGL-Refpages/gl4/html/indexflat.php");
-        std::fs::create_dir_all(cache_path).expect("Failed to create dir.");
+impl GlslIntrinsicParser {
+    fn cache_refpage(&self, cache_path: &str, refpage: &GlslRefpage) {
+        let unique_links = get_links(
+            format!(
+                "https://registry.khronos.org/OpenGL-Refpages/{}/html/indexflat.php",
+                refpage.name
+            )
+            .as_str(),
+        );
+        let cache_path = get_refpage_cache_path(cache_path, refpage);
+        std::fs::create_dir_all(&cache_path).expect("Failed to create dir.");
         for link in unique_links {
             let filename = link.replace("xhtml", "xml");
             if filename == "removedTypes.xml" {
                 continue; // Unvalid file.
             }
             let url = format!(
-                "https://registry.khronos.org/OpenGL-Refpages/gl4/{}",
-                filename
+                "https://registry.khronos.org/OpenGL-Refpages/{}/{}",
+                refpage.name, filename
             );
             println!("Caching file from {} to {}{}", url, cache_path, filename);
             let mut asset = download_file(url.as_str());
@@ -117,6 +154,14 @@ impl IntrinsicParser for GlslIntrinsicParser {
                 .expect("Failed to write file");
         }
     }
+}
+
+impl IntrinsicParser for GlslIntrinsicParser {
+    fn cache(&self, cache_path: &str) {
+        for refpage in &GLSL_REFPAGES {
+            self.cache_refpage(cache_path, refpage);
+        }
+    }
     fn parse(&self, cache_path: &str) -> ShaderSymbolList {
         let mut symbols = ShaderSymbolList {
             types: Vec::new(),
@@ -128,8 +173,11 @@ impl IntrinsicParser for GlslIntrinsicParser {
         };
 
         self.add_methods(&mut symbols, cache_path);
+        self.add_atomic_counters(&mut symbols);
         self.add_types(&mut symbols);
+        self.add_int64(&mut symbols);
         self.add_keywords(&mut symbols);
+        self.add_builtins(&mut symbols);
 
         symbols
     }
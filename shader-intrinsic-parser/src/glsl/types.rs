@@ -1,4 +1,6 @@
-use shader_sense::symbols::symbols::{ShaderSymbol, ShaderSymbolData, ShaderSymbolList};
+use shader_sense::symbols::symbols::{
+    ShaderSymbol, ShaderSymbolData, ShaderSymbolList, ShaderVectorType,
+};
 
 use super::GlslIntrinsicParser;
 
@@ -11,53 +13,93 @@ impl GlslIntrinsicParser {
                 version: version.to_string(),
                 stages: vec![],
                 link: None,
-                data: ShaderSymbolData::Types { ty: label.into() },
+                data: ShaderSymbolData::Types {
+                    ty: label.into(),
+                    vector: None,
+                },
                 range: None,
                 scope_stack: None,
+                snippet: None,
+            }
+        }
+        pub fn new_glsl_vector(
+            label: &str,
+            description: &str,
+            version: &str,
+            scalar: &str,
+            dimension: u32,
+        ) -> ShaderSymbol {
+            ShaderSymbol {
+                data: ShaderSymbolData::Types {
+                    ty: label.into(),
+                    vector: Some(ShaderVectorType {
+                        scalar: scalar.into(),
+                        dimension,
+                    }),
+                },
+                ..new_glsl_type(label, description, version)
             }
         }
         // Manually push types as they are not in documentation
-        symbols.types.push(new_glsl_type(
+        symbols.types.push(new_glsl_vector(
             "bool",
             "conditional type, values may be either true or false",
             "110",
+            "bool",
+            1,
         ));
-        symbols.types.push(new_glsl_type(
+        symbols.types.push(new_glsl_vector(
             "int",
             " a signed, two's complement, 32-bit integer",
             "110",
+            "int",
+            1,
         ));
-        symbols
-            .types
-            .push(new_glsl_type("uint", " an unsigned 32-bit integer", "110"));
-        symbols.types.push(new_glsl_type(
+        symbols.types.push(new_glsl_vector(
+            "uint",
+            " an unsigned 32-bit integer",
+            "110",
+            "uint",
+            1,
+        ));
+        symbols.types.push(new_glsl_vector(
             "float",
             "an IEEE-754 single-precision floating point number",
             "110",
+            "float",
+            1,
         ));
-        symbols.types.push(new_glsl_type(
+        symbols.types.push(new_glsl_vector(
             "double",
             "an IEEE-754 double-precision floating-point number",
-            "110",
+            "400",
+            "double",
+            1,
         ));
         for component in 2..=4 {
             // Vectors
-            symbols.types.push(new_glsl_type(
+            symbols.types.push(new_glsl_vector(
                 format!("bvec{}", component).as_str(),
                 format!("Vector with {} components of booleans", component).as_str(),
                 "110",
+                "bool",
+                component,
             ));
-            symbols.types.push(new_glsl_type(
+            symbols.types.push(new_glsl_vector(
                 format!("ivec{}", component).as_str(),
                 format!("Vector with {} components of signed integers", component).as_str(),
                 "110",
+                "int",
+                component,
             ));
-            symbols.types.push(new_glsl_type(
+            symbols.types.push(new_glsl_vector(
                 format!("uvec{}", component).as_str(),
                 format!("Vector with {} components of unsigned integers", component).as_str(),
                 "110",
+                "uint",
+                component,
             ));
-            symbols.types.push(new_glsl_type(
+            symbols.types.push(new_glsl_vector(
                 format!("vec{}", component).as_str(),
                 format!(
                     "Vector with {} components of single-precision floating-point numbers",
@@ -65,15 +107,19 @@ impl GlslIntrinsicParser {
                 )
                 .as_str(),
                 "110",
+                "float",
+                component,
             ));
-            symbols.types.push(new_glsl_type(
+            symbols.types.push(new_glsl_vector(
                 format!("dvec{}", component).as_str(),
                 format!(
                     "Vector with {} components of double-precision floating-point numbers",
                     component
                 )
                 .as_str(),
-                "110",
+                "400",
+                "double",
+                component,
             ));
             // Matrices
             symbols.types.push(new_glsl_type(
@@ -92,11 +138,11 @@ impl GlslIntrinsicParser {
                     component
                 )
                 .as_str(),
-                "110",
+                "400",
             ));
             for component_row in 2..=4 {
                 symbols.types.push(new_glsl_type(format!("mat{}x{}", component, component_row).as_str(), format!("Matrice with {} columns and {} rows of single-precision floating-point numbers", component, component_row).as_str(), "110"));
-                symbols.types.push(new_glsl_type(format!("dmat{}x{}", component, component_row).as_str(), format!("Matrice with {} columns and {} rows of double-precision floating-point numbers", component, component_row).as_str(), "110"));
+                symbols.types.push(new_glsl_type(format!("dmat{}x{}", component, component_row).as_str(), format!("Matrice with {} columns and {} rows of double-precision floating-point numbers", component, component_row).as_str(), "400"));
             }
         }
         // Samplers
@@ -148,6 +194,6 @@ impl GlslIntrinsicParser {
             ));
         }
         // Atomic counters
-        symbols.types.push(new_glsl_type("atomic_uint", "An Atomic Counter is a GLSL variable type whose storage comes from a Buffer Object. Atomic counters, as the name suggests, can have atomic memory operations performed on them. They can be thought of as a very limited form of buffer image variable.", "460"));
+        symbols.types.push(new_glsl_type("atomic_uint", "An Atomic Counter is a GLSL variable type whose storage comes from a Buffer Object. Atomic counters, as the name suggests, can have atomic memory operations performed on them. They can be thought of as a very limited form of buffer image variable.", "420"));
     }
 }
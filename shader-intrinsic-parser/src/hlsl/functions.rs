@@ -19,9 +19,10 @@ impl HlslIntrinsicParser {
                 returnType: "void".into(),
                 description: "".into(),
                 parameters: vec![],
-            }]},
+            }], workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "abs".into(),
@@ -45,9 +46,11 @@ impl HlslIntrinsicParser {
                         }],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "acos".into(),
@@ -63,9 +66,10 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value. Each component should be a floating-point value within the range of -1 to 1.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "all".into(),
@@ -81,9 +85,10 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "any".into(),
@@ -99,9 +104,10 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "AllMemoryBarrier".into(),
@@ -113,9 +119,10 @@ impl HlslIntrinsicParser {
                 returnType: "void".into(),
                 description: "".into(),
                 parameters: vec![],
-            }]},
+            }], workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "AllMemoryBarrierWithGroupSync".into(),
@@ -127,9 +134,10 @@ impl HlslIntrinsicParser {
                 returnType: "void".into(),
                 description: "".into(),
                 parameters: vec![],
-            }]},
+            }], workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "asdouble".into(),
@@ -156,9 +164,11 @@ impl HlslIntrinsicParser {
                         },
                     ],
                 }],
+                workgroup_size: None,
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "asfloat".into(),
@@ -174,9 +184,10 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The input value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "asint".into(),
@@ -192,9 +203,10 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The input value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "asuint".into(),
@@ -210,9 +222,10 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The input value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "asin".into(),
@@ -228,9 +241,10 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "atan".into(),
@@ -246,9 +260,10 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "atan2".into(),
@@ -269,9 +284,10 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The x value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ceil".into(),
@@ -285,11 +301,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "CheckAccessFullyMapped".into(),
@@ -303,11 +320,12 @@ impl HlslIntrinsicParser {
                     label: "status".into(),
                     description: "The status value that is returned from a Sample, Gather, or Load operation. Because you can't access this status value directly, you need to pass it to CheckAccessFullyMapped.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::Fragment, ShaderStage::Compute],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "clamp".into(),
@@ -331,11 +349,12 @@ impl HlslIntrinsicParser {
                     label: "max".into(),
                     description: " The specified maximum range.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "clip".into(),
@@ -349,11 +368,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm1".into(),
             stages: vec![ShaderStage::Fragment],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "cos".into(),
@@ -375,11 +395,13 @@ impl HlslIntrinsicParser {
                         }],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "cosh".into(),
@@ -401,11 +423,13 @@ impl HlslIntrinsicParser {
                         }],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "countbits".into(),
@@ -427,11 +451,13 @@ impl HlslIntrinsicParser {
                         }],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "cross".into(),
@@ -450,11 +476,12 @@ impl HlslIntrinsicParser {
                     label: "y".into(),
                     description: "The second floating-point, 3D vector.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ddx".into(),
@@ -468,11 +495,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm2".into(),
             stages: vec![ShaderStage::Fragment],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ddx_coarse".into(),
@@ -486,11 +514,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::Fragment],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ddx_fine".into(),
@@ -504,11 +533,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::Fragment],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ddy".into(),
@@ -522,11 +552,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm2".into(),
             stages: vec![ShaderStage::Fragment],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ddy_coarse".into(),
@@ -540,11 +571,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::Fragment],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ddy_fine".into(),
@@ -558,11 +590,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::Fragment],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "degrees".into(),
@@ -576,11 +609,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "determinant".into(),
@@ -594,11 +628,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "DeviceMemoryBarrier".into(),
@@ -608,11 +643,12 @@ impl HlslIntrinsicParser {
                 returnType: "void".into(),
                 description: "".into(),
                 parameters: vec![],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "DeviceMemoryBarrierWithGroupSync".into(),
@@ -622,11 +658,12 @@ impl HlslIntrinsicParser {
                 returnType: "void".into(),
                 description: "".into(),
                 parameters: vec![],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "distance".into(),
@@ -645,11 +682,12 @@ impl HlslIntrinsicParser {
                     label: "y".into(),
                     description: "The second floating-point vector to compare.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "dot".into(),
@@ -679,11 +717,13 @@ impl HlslIntrinsicParser {
                         ],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "dst".into(),
@@ -712,11 +752,13 @@ impl HlslIntrinsicParser {
                         ],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "errorf".into(),
@@ -741,11 +783,13 @@ impl HlslIntrinsicParser {
                         },
                     ],
                 }],
+                workgroup_size: None,
             },
             version: "sm4".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "EvaluateAttributeCentroid".into(),
@@ -759,11 +803,12 @@ impl HlslIntrinsicParser {
                     label: "value".into(),
                     description: "The input value.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::Fragment],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "EvaluateAttributeAtSample".into(),
@@ -782,11 +827,12 @@ impl HlslIntrinsicParser {
                     label: "sampleindex".into(),
                     description: "The sample location.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::Fragment],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "EvaluateAttributeSnapped".into(),
@@ -805,11 +851,12 @@ impl HlslIntrinsicParser {
                     label: "offset".into(),
                     description: "A 2D offset from the pixel center using a 16x16 grid.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::Fragment],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "exp".into(),
@@ -831,11 +878,13 @@ impl HlslIntrinsicParser {
                         }],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "exp2".into(),
@@ -849,11 +898,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The input value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "f16tof32".into(),
@@ -875,11 +925,13 @@ impl HlslIntrinsicParser {
                         }],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm4".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "f32tof16".into(),
@@ -900,11 +952,13 @@ impl HlslIntrinsicParser {
                         }],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm4".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "faceforward".into(),
@@ -926,11 +980,12 @@ impl HlslIntrinsicParser {
                     label: "ng".into(),
                     description: "A floating-point surface-normal vector.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "firstbithigh".into(),
@@ -944,11 +999,12 @@ impl HlslIntrinsicParser {
                     label: "value".into(),
                     description: "The input value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "firstbitlow".into(),
@@ -962,11 +1018,12 @@ impl HlslIntrinsicParser {
                     label: "value".into(),
                     description: "The input value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "floor".into(),
@@ -980,11 +1037,12 @@ impl HlslIntrinsicParser {
                     label: "value".into(),
                     description: "The input value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "fma".into(),
@@ -1008,11 +1066,12 @@ impl HlslIntrinsicParser {
                     label: "c".into(),
                     description: "The third value in the fused multiply-addition.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "fmod".into(),
@@ -1031,11 +1090,12 @@ impl HlslIntrinsicParser {
                     label: "y".into(),
                     description: "The floating-point divisor.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "frac".into(),
@@ -1049,11 +1109,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "frexp".into(),
@@ -1073,11 +1134,12 @@ impl HlslIntrinsicParser {
                     label: "exp".into(),
                     description: "The returned exponent of the x parameter.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "fwidth".into(),
@@ -1091,11 +1153,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm2".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "GetRenderTargetSampleCount".into(),
@@ -1105,11 +1168,12 @@ impl HlslIntrinsicParser {
                 returnType: "uint".into(),
                 description: "The number of samples.".into(),
                 parameters: vec![],
-            }]},
+            }], workgroup_size: None },
             version: "sm4".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "GetRenderTargetSamplePosition".into(),
@@ -1123,11 +1187,12 @@ impl HlslIntrinsicParser {
                     label: "index".into(),
                     description: "".into() 
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm4".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "GroupMemoryBarrier".into(),
@@ -1137,11 +1202,12 @@ impl HlslIntrinsicParser {
                 returnType: "void".into(),
                 description: "".into(),
                 parameters: vec![],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "GroupMemoryBarrierWithGroupSync".into(),
@@ -1151,11 +1217,12 @@ impl HlslIntrinsicParser {
                 returnType: "void".into(),
                 description: "".into(),
                 parameters: vec![],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "InterlockedAdd".into(),
@@ -1187,11 +1254,13 @@ impl HlslIntrinsicParser {
                         },
                     ],
                 }],
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "InterlockedAnd".into(),
@@ -1222,11 +1291,13 @@ impl HlslIntrinsicParser {
                         },
                     ],
                 }],
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "InterlockedCompareExchange".into(),
@@ -1255,11 +1326,12 @@ impl HlslIntrinsicParser {
                     label: "original_value".into(),
                     description: "Optional. The original input value.".into() 
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "InterlockedCompareStore".into(),
@@ -1283,11 +1355,12 @@ impl HlslIntrinsicParser {
                     label: "original_value".into(),
                     description: "Optional. The original input value.".into() 
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "InterlockedExchange".into(),
@@ -1318,11 +1391,13 @@ impl HlslIntrinsicParser {
                         },
                     ],
                 }],
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "InterlockedMax".into(),
@@ -1353,11 +1428,13 @@ impl HlslIntrinsicParser {
                         },
                     ],
                 }],
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "InterlockedMin".into(),
@@ -1388,11 +1465,13 @@ impl HlslIntrinsicParser {
                         },
                     ],
                 }],
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "InterlockedOr".into(),
@@ -1422,11 +1501,13 @@ impl HlslIntrinsicParser {
                         },
                     ],
                 }],
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "InterlockedXor".into(),
@@ -1457,11 +1538,13 @@ impl HlslIntrinsicParser {
                         },
                     ],
                 }],
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "isfinite".into(),
@@ -1475,11 +1558,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "isinf".into(),
@@ -1493,11 +1577,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "isnan".into(),
@@ -1511,11 +1596,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ldexp".into(),
@@ -1534,11 +1620,12 @@ impl HlslIntrinsicParser {
                     label: "exp".into(),
                     description: "The specified exponent.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "length".into(),
@@ -1552,11 +1639,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "lerp".into(),
@@ -1580,11 +1668,12 @@ impl HlslIntrinsicParser {
                     label: "s".into(),
                     description: "A value that linearly interpolates between the x parameter and the y parameter.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "lit".into(),
@@ -1608,11 +1697,12 @@ impl HlslIntrinsicParser {
                     label: "m".into(),
                     description: "A specular exponent.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "log".into(),
@@ -1626,11 +1716,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "log10".into(),
@@ -1644,11 +1735,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "log2".into(),
@@ -1662,11 +1754,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "mad".into(),
@@ -1697,11 +1790,13 @@ impl HlslIntrinsicParser {
                         ],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "max".into(),
@@ -1730,11 +1825,13 @@ impl HlslIntrinsicParser {
                         ],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "min".into(),
@@ -1764,11 +1861,13 @@ impl HlslIntrinsicParser {
                         ],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "modf".into(),
@@ -1787,11 +1886,12 @@ impl HlslIntrinsicParser {
                     label: "ip".into(),
                     description: "The integer portion of x.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "msad4".into(),
@@ -1815,11 +1915,12 @@ impl HlslIntrinsicParser {
                     label: "accum".into(),
                     description: "A vector of 4 values. msad4 adds this vector to the masked sum of absolute differences of the different byte alignments between the reference value and the source value.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "mul".into(),
@@ -1839,11 +1940,12 @@ impl HlslIntrinsicParser {
                     label: "y".into(),
                     description: " The y input value. If y is a vector, it treated as a column vector.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: Some("mul(${1:mat}, ${2:vec})".into()),
         });
         symbols.functions.push(ShaderSymbol {
             label: "noise".into(),
@@ -1857,11 +1959,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "A floating-point vector from which to generate Perlin noise.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "normalize".into(),
@@ -1875,11 +1978,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified floating-point vector.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "pow".into(),
@@ -1909,11 +2013,13 @@ impl HlslIntrinsicParser {
                         ],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "printf".into(),
@@ -1938,11 +2044,13 @@ impl HlslIntrinsicParser {
                         },
                     ],
                 }],
+                workgroup_size: None,
             },
             version: "sm4".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "Process2DQuadTessFactorsAvg".into(),
@@ -1976,11 +2084,12 @@ impl HlslIntrinsicParser {
                     label: "UnroundedInsideTessFactors".into(),
                     description: "The tessellation factors calculated by the tessellator stage for inside edges.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::TesselationControl],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "Process2DQuadTessFactorsMax".into(),
@@ -2014,11 +2123,12 @@ impl HlslIntrinsicParser {
                     label: "UnroundedInsideTessFactors".into(),
                     description: "The tessellation factors calculated by the tessellator stage for inside edges.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::TesselationControl],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "Process2DQuadTessFactorsMin".into(),
@@ -2052,11 +2162,12 @@ impl HlslIntrinsicParser {
                     label: "UnroundedInsideTessFactors".into(),
                     description: "The tessellation factors calculated by the tessellator stage for inside edges.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::TesselationControl],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ProcessIsolineTessFactors".into(),
@@ -2085,11 +2196,12 @@ impl HlslIntrinsicParser {
                     label: "RoundedDensityFactor".into(),
                     description: "The rounded density factor clamped to a rangethat can be used by the tessellator.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::TesselationControl],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ProcessQuadTessFactorsAvg".into(),
@@ -2123,11 +2235,12 @@ impl HlslIntrinsicParser {
                     label: "UnroundedInsideTessFactors".into(),
                     description: "The tessellation factors calculated by the tessellator stage for inside edges.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::TesselationControl],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ProcessQuadTessFactorsMax".into(),
@@ -2161,11 +2274,12 @@ impl HlslIntrinsicParser {
                     label: "UnroundedInsideTessFactors".into(),
                     description: "The tessellation factors calculated by the tessellator stage for inside edges.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::TesselationControl],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ProcessQuadTessFactorsMin".into(),
@@ -2199,11 +2313,12 @@ impl HlslIntrinsicParser {
                     label: "UnroundedInsideTessFactors".into(),
                     description: "The tessellation factors calculated by the tessellator stage for inside edges.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::TesselationControl],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ProcessTriTessFactorsAvg".into(),
@@ -2237,11 +2352,12 @@ impl HlslIntrinsicParser {
                     label: "UnroundedInsideTessFactors".into(),
                     description: "The tessellation factors calculated by the tessellator stage for inside edges.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::TesselationControl],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ProcessTriTessFactorsMax".into(),
@@ -2275,11 +2391,12 @@ impl HlslIntrinsicParser {
                     label: "UnroundedInsideTessFactors".into(),
                     description: "The tessellation factors calculated by the tessellator stage for inside edges.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::TesselationControl],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "ProcessTriTessFactorsMin".into(),
@@ -2313,11 +2430,12 @@ impl HlslIntrinsicParser {
                     label: "UnroundedInsideTessFactors".into(),
                     description: "The tessellation factors calculated by the tessellator stage for inside edges.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm5".into(),
             stages: vec![ShaderStage::TesselationControl],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "radians".into(),
@@ -2331,11 +2449,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "rcp".into(),
@@ -2354,11 +2473,13 @@ impl HlslIntrinsicParser {
                         }],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "reflect".into(),
@@ -2377,11 +2498,12 @@ impl HlslIntrinsicParser {
                     label: "n".into(),
                     description: "A floating-point, normal vector.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "refract".into(),
@@ -2405,11 +2527,12 @@ impl HlslIntrinsicParser {
                     label: "f".into(),
                     description: "A floating-point, refraction index scalar.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "reversebits".into(),
@@ -2430,11 +2553,13 @@ impl HlslIntrinsicParser {
                         }],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm5".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "round".into(),
@@ -2448,11 +2573,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "rsqrt".into(),
@@ -2466,11 +2592,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "saturate".into(),
@@ -2484,11 +2611,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "sign".into(),
@@ -2502,11 +2630,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The input value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "sin".into(),
@@ -2528,11 +2657,13 @@ impl HlslIntrinsicParser {
                         }],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "sincos".into(),
@@ -2556,11 +2687,12 @@ impl HlslIntrinsicParser {
                     label: "c".into(),
                     description: "Returns the cosine of x.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "sinh".into(),
@@ -2574,11 +2706,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value, in radians.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "smoothstep".into(),
@@ -2602,11 +2735,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value to be interpolated.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "sqrt".into(),
@@ -2620,11 +2754,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified floating-point value.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "step".into(),
@@ -2643,11 +2778,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The second floating-point value to compare.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "tan".into(),
@@ -2669,11 +2805,13 @@ impl HlslIntrinsicParser {
                         }],
                     })
                     .collect(),
+                workgroup_size: None,
             },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "tanh".into(),
@@ -2687,11 +2825,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified value, in radians.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         for dim in 1..=4 {
             let dim_text = if dim == 4 {
@@ -2717,11 +2856,12 @@ impl HlslIntrinsicParser {
                         label: "t".into(),
                         description: "The texture coordinate.".into(),
                     }],
-                }]},
+                }], workgroup_size: None },
                 version: "sm1".into(),
                 stages: vec![ShaderStage::Fragment],
                 scope_stack: None,
                 range: None,
+                snippet: None,
             });
             symbols.functions.push(ShaderSymbol {
                 label: format!("tex{}", dim_text),
@@ -2750,11 +2890,12 @@ impl HlslIntrinsicParser {
                         label: "ddy".into(),
                         description: "Rate of change of the surface geometry in the y direction.".into(),
                     }],
-                }]},
+                }], workgroup_size: None },
                 version: "sm2".into(),
                 stages: vec![ShaderStage::Fragment],
                 scope_stack: None,
                 range: None,
+                snippet: None,
             });
             symbols.functions.push(ShaderSymbol {
                 label: format!("tex{}bias", dim_text),
@@ -2773,11 +2914,12 @@ impl HlslIntrinsicParser {
                         label: "t".into(),
                         description: "The texture coordinate.".into(),
                     }],
-                }]},
+                }], workgroup_size: None },
                 version: "sm2".into(),
                 stages: vec![ShaderStage::Fragment],
                 scope_stack: None,
                 range: None,
+                snippet: None,
             });
             symbols.functions.push(ShaderSymbol {
                 label: format!("tex{}grad", dim_text),
@@ -2806,11 +2948,12 @@ impl HlslIntrinsicParser {
                         label: "ddy".into(),
                         description: "Rate of change of the surface geometry in the y direction.".into(),
                     }],
-                }]},
+                }], workgroup_size: None },
                 version: "sm2".into(),
                 stages: vec![ShaderStage::Fragment],
                 scope_stack: None,
                 range: None,
+                snippet: None,
             });
             symbols.functions.push(ShaderSymbol {
                 label: format!("tex{}lod", dim_text),
@@ -2829,11 +2972,12 @@ impl HlslIntrinsicParser {
                         label: "t".into(),
                         description: "The texture coordinate.".into(),
                     }],
-                }]},
+                }], workgroup_size: None },
                 version: "sm3".into(),
                 stages: vec![ShaderStage::Fragment],
                 scope_stack: None,
                 range: None,
+                snippet: None,
             });
             symbols.functions.push(ShaderSymbol {
                 label: format!("tex{}proj", dim_text),
@@ -2852,11 +2996,12 @@ impl HlslIntrinsicParser {
                         label: "t".into(),
                         description: "The texture coordinate.".into(),
                     }],
-                }]},
+                }], workgroup_size: None },
                 version: "sm2".into(),
                 stages: vec![ShaderStage::Fragment],
                 scope_stack: None,
                 range: None,
+                snippet: None,
             });
         }
         symbols.functions.push(ShaderSymbol {
@@ -2871,11 +3016,12 @@ impl HlslIntrinsicParser {
                     label: "s".into(),
                     description: "The specified matrix.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "trunc".into(),
@@ -2889,11 +3035,12 @@ impl HlslIntrinsicParser {
                     label: "x".into(),
                     description: "The specified input.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm1".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         // sm 6.0
         symbols.functions.push(ShaderSymbol {
@@ -2908,11 +3055,12 @@ impl HlslIntrinsicParser {
                     label: "localValue".into(),
                     description: "The requested type.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![ShaderStage::Fragment, ShaderStage::Compute],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "QuadReadLaneAt".into(),
@@ -2930,11 +3078,12 @@ impl HlslIntrinsicParser {
                     label: "quadLaneID".into(),
                     description: "The lane ID; this will be a value from 0 to 3.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![ShaderStage::Fragment, ShaderStage::Compute],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "QuadReadAcrossX".into(),
@@ -2948,11 +3097,12 @@ impl HlslIntrinsicParser {
                     label: "localValue".into(),
                     description: "The requested type.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![ShaderStage::Fragment, ShaderStage::Compute],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "QuadReadAcrossY".into(),
@@ -2966,11 +3116,12 @@ impl HlslIntrinsicParser {
                     label: "localValue".into(),
                     description: "The requested type.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![ShaderStage::Fragment, ShaderStage::Compute],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveAllEqual".into(),
@@ -2984,11 +3135,12 @@ impl HlslIntrinsicParser {
                     label: "expr".into(),
                     description: "The expression to evaluate. type can be a basic scalar, vector, or matrix type.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveBitAnd".into(),
@@ -3002,11 +3154,12 @@ impl HlslIntrinsicParser {
                     label: "expr".into(),
                     description: "The expression to evaluate.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveBitOr".into(),
@@ -3020,11 +3173,12 @@ impl HlslIntrinsicParser {
                     label: "expr".into(),
                     description: "The expression to evaluate.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveBitXor".into(),
@@ -3038,11 +3192,12 @@ impl HlslIntrinsicParser {
                     label: "expr".into(),
                     description: "The expression to evaluate.".into(),
                 }],
-            }).collect()},
+            }).collect(), workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveCountBits".into(),
@@ -3056,11 +3211,12 @@ impl HlslIntrinsicParser {
                     label: "bBit".into(),
                     description: "The boolean variables to evaluate. Providing an explicit true Boolean value returns the number of active lanes.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveMax".into(),
@@ -3074,11 +3230,12 @@ impl HlslIntrinsicParser {
                     label: "expr".into(),
                     description: "The expression to evaluate.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveMin".into(),
@@ -3092,11 +3249,12 @@ impl HlslIntrinsicParser {
                     label: "expr".into(),
                     description: "The expression to evaluate.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveProduct".into(),
@@ -3110,11 +3268,12 @@ impl HlslIntrinsicParser {
                     label: "expr".into(),
                     description: "The expression to evaluate.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveSum".into(),
@@ -3128,11 +3287,12 @@ impl HlslIntrinsicParser {
                     label: "expr".into(),
                     description: "The expression to evaluate.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveAllTrue".into(),
@@ -3152,11 +3312,13 @@ impl HlslIntrinsicParser {
                         description: "The expression to evaluate.".into(),
                     }],
                 }],
+                workgroup_size: None,
             },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveAnyTrue".into(),
@@ -3170,11 +3332,12 @@ impl HlslIntrinsicParser {
                     label: "expr".into(),
                     description: "The expression to evaluate.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveActiveBallot".into(),
@@ -3188,11 +3351,12 @@ impl HlslIntrinsicParser {
                     label: "expr".into(),
                     description: "The boolean expression to evaluate.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveGetLaneCount".into(),
@@ -3202,11 +3366,12 @@ impl HlslIntrinsicParser {
                 returnType: "uint".into(),
                 description: "The result will be between 4 and 128, and includes all waves: active, inactive, and/or helper lanes. The result returned from this function may vary significantly depending on the driver implementation.".into(),
                 parameters: vec![],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveGetLaneIndex".into(),
@@ -3216,11 +3381,12 @@ impl HlslIntrinsicParser {
                 returnType: "uint".into(),
                 description: "The current lane index. The result will be between 0 and the result returned from WaveGetLaneCount.".into(),
                 parameters: vec![],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveIsFirstLane".into(),
@@ -3239,11 +3405,13 @@ impl HlslIntrinsicParser {
                             .into(),
                     parameters: vec![],
                 }],
+                workgroup_size: None,
             },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WavePrefixCountBits".into(),
@@ -3257,11 +3425,12 @@ impl HlslIntrinsicParser {
                     label: "bBit".into(),
                     description: "The specified boolean variables.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WavePrefixProduct".into(),
@@ -3275,11 +3444,12 @@ impl HlslIntrinsicParser {
                     label: "value".into(),
                     description: "The value to multiply.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WavePrefixSum".into(),
@@ -3293,11 +3463,12 @@ impl HlslIntrinsicParser {
                     label: "value".into(),
                     description: "The value to sum up.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveReadLaneFirst".into(),
@@ -3311,11 +3482,12 @@ impl HlslIntrinsicParser {
                     label: "expr".into(),
                     description: "The expression to evaluate.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.functions.push(ShaderSymbol {
             label: "WaveReadLaneAt".into(),
@@ -3334,11 +3506,68 @@ impl HlslIntrinsicParser {
                     label: "laneIndex".into(),
                     description: "The index of the lane for which the expr result will be returned.".into(),
                 }],
-            }]},
+            }], workgroup_size: None },
             version: "sm6".into(),
             stages: vec![],
             scope_stack: None,
             range: None,
+            snippet: None,
+        });
+        // HLSL 2021 logical intrinsics, as && and || do not short-circuit on vectors anymore.
+        for (label, operation) in [("and", "AND"), ("or", "OR")] {
+            symbols.functions.push(ShaderSymbol {
+                label: label.into(),
+                description: format!("Performs a component-wise logical {} of two boolean values. Introduced with HLSL 2021.", operation),
+                version: "sm6".into(),
+                stages: vec![],
+                link: Some("https://github.com/microsoft/DirectXShaderCompiler/wiki/HLSL-2021".into()),
+                data: ShaderSymbolData::Functions { signatures: type_size_iter(&["bool"], true, true, false).iter().map(|v| ShaderSignature {
+                    returnType: v.format(),
+                    description: format!("The component-wise logical {} of x and y.", operation),
+                    parameters: vec![ShaderParameter {
+                        ty: v.format(),
+                        label: "x".into(),
+                        description: "The first boolean value.".into(),
+                    },
+                    ShaderParameter {
+                        ty: v.format(),
+                        label: "y".into(),
+                        description: "The second boolean value.".into(),
+                    }],
+                }).collect(), workgroup_size: None },
+                scope_stack: None,
+                range: None,
+                snippet: None,
+            });
+        }
+        symbols.functions.push(ShaderSymbol {
+            label: "select".into(),
+            description: "Selects component-wise between two values depending on a condition. Introduced with HLSL 2021 as vector ternary operator does not short-circuit anymore.".into(),
+            version: "sm6".into(),
+            stages: vec![],
+            link: Some("https://github.com/microsoft/DirectXShaderCompiler/wiki/HLSL-2021".into()),
+            data: ShaderSymbolData::Functions { signatures: type_size_iter(&["float", "int", "uint", "bool"], true, true, false).iter().map(|v| ShaderSignature {
+                returnType: v.format(),
+                description: "The components of t where cond is true, the components of f otherwise.".into(),
+                parameters: vec![ShaderParameter {
+                    ty: v.format_with_type("bool"),
+                    label: "cond".into(),
+                    description: "The condition selecting each component.".into(),
+                },
+                ShaderParameter {
+                    ty: v.format(),
+                    label: "t".into(),
+                    description: "The value selected when the condition is true.".into(),
+                },
+                ShaderParameter {
+                    ty: v.format(),
+                    label: "f".into(),
+                    description: "The value selected when the condition is false.".into(),
+                }],
+            }).collect(), workgroup_size: None },
+            scope_stack: None,
+            range: None,
+            snippet: None,
         });
     }
 }
@@ -37,6 +37,10 @@ impl HlslIntrinsicParser {
                         .nth(2)
                         .map(|f| f.text().collect::<String>())
                         .unwrap();
+                    // Some tables repeat their header as a row.
+                    if ty == "Type" {
+                        continue;
+                    }
 
                     println!("Reading semantic {}", label);
 
@@ -46,9 +50,14 @@ impl HlslIntrinsicParser {
                         version: "".into(),
                         stages: vec![],
                         link: Some("https://learn.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-semantics".into()),
-                        data: ShaderSymbolData::Variables { ty },
+                        data: ShaderSymbolData::Variables {
+                            ty,
+                            qualifier: "".into(),
+                            register: None,
+                        },
                         range: None,
                         scope_stack:None,
+                        snippet: None,
                     });
                 }
             }
@@ -1,6 +1,9 @@
 use shader_sense::{
     shader::ShaderStage,
-    symbols::symbols::{ShaderSymbol, ShaderSymbolData, ShaderSymbolList},
+    symbols::symbols::{
+        ShaderMethod, ShaderParameter, ShaderSignature, ShaderSymbol, ShaderSymbolData,
+        ShaderSymbolList, ShaderVectorType,
+    },
 };
 
 use super::HlslIntrinsicParser;
@@ -15,9 +18,16 @@ pub fn new_hlsl_scalar(label: &str, description: &str, version: &str) -> ShaderS
             "https://learn.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-scalar"
                 .into(),
         ),
-        data: ShaderSymbolData::Types { ty: label.into() },
+        data: ShaderSymbolData::Types {
+            ty: label.into(),
+            vector: Some(ShaderVectorType {
+                scalar: label.into(),
+                dimension: 1,
+            }),
+        },
         range: None,
         scope_stack: None,
+        snippet: None,
     }
 }
 
@@ -77,6 +87,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "Texture1D".into(),
@@ -93,6 +104,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "Texture1DArray".into(),
@@ -106,6 +118,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "Texture2D".into(),
@@ -122,6 +135,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "Texture2DArray".into(),
@@ -135,6 +149,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "Texture3D".into(),
@@ -151,6 +166,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "TextureCube".into(),
@@ -164,6 +180,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "TextureCubeArray".into(),
@@ -177,6 +194,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "Texture2DMS".into(),
@@ -190,6 +208,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "Texture2DMSArray".into(),
@@ -203,6 +222,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         // sm 5.0 : Object<Type, Samples> name
         // https://learn.microsoft.com/en-us/windows/win32/direct3dhlsl/d3d11-graphics-reference-sm5-objects
@@ -222,6 +242,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "ByteAddressBuffer".into(),
@@ -235,6 +256,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "ByteAddressBuffer".into(),
@@ -248,6 +270,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "ConsumeStructuredBuffer".into(),
@@ -261,6 +284,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "InputPatch".into(),
@@ -274,6 +298,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "OutputPatch".into(),
@@ -287,6 +312,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RWBuffer".into(),
@@ -303,6 +329,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RWByteAddressBuffer".into(),
@@ -316,6 +343,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RWStructuredBuffer".into(),
@@ -329,6 +357,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RWTexture1D".into(),
@@ -342,6 +371,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RWTexture1DArray".into(),
@@ -355,6 +385,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RWTexture2D".into(),
@@ -368,6 +399,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RWTexture2DArray".into(),
@@ -381,6 +413,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RWTexture3D".into(),
@@ -394,6 +427,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "StructuredBuffer".into(),
@@ -407,6 +441,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         // sm 5.1
         symbols.types.push(ShaderSymbol {
@@ -421,6 +456,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RasterizerOrderedBuffer".into(),
@@ -434,6 +470,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RasterizerOrderedByteAddressBuffer".into(),
@@ -447,6 +484,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RasterizerOrderedStructuredBuffer".into(),
@@ -460,6 +498,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RasterizerOrderedTexture1D".into(),
@@ -473,6 +512,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RasterizerOrderedTexture1DArray".into(),
@@ -486,6 +526,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RasterizerOrderedTexture2D".into(),
@@ -499,6 +540,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RasterizerOrderedTexture2DArray".into(),
@@ -512,6 +554,7 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
         symbols.types.push(ShaderSymbol {
             label: "RasterizerOrderedTexture3D".into(),
@@ -525,7 +568,171 @@ impl HlslIntrinsicParser {
             },
             scope_stack: None,
             range: None,
+            snippet: None,
         });
+        // sm 6.8 : WaveMatrixLeft<Type, M, N> name, operated by the whole wave.
+        // https://microsoft.github.io/DirectX-Specs/d3d/HLSL_SM_6_8_WaveMatrix.html
+        let method = |label: &str,
+                      return_type: &str,
+                      description: &str,
+                      parameters: &[(&str, &str, &str)]| ShaderMethod {
+            label: label.into(),
+            signature: ShaderSignature {
+                returnType: return_type.into(),
+                description: description.into(),
+                parameters: parameters
+                    .iter()
+                    .map(|(ty, label, description)| ShaderParameter {
+                        ty: ty.to_string(),
+                        label: label.to_string(),
+                        description: description.to_string(),
+                    })
+                    .collect(),
+            },
+        };
+        let load_store_parameters = [
+            (
+                "ByteAddressBuffer",
+                "buffer",
+                "The buffer holding the matrix.",
+            ),
+            (
+                "uint",
+                "startOffset",
+                "The offset in bytes of the first element in the buffer.",
+            ),
+            (
+                "uint",
+                "stride",
+                "The stride in bytes between rows or columns.",
+            ),
+            (
+                "bool",
+                "colMajor",
+                "Whether the matrix is stored in column major order.",
+            ),
+        ];
+        let load = method(
+            "Load",
+            "void",
+            "Loads the matrix from a buffer.",
+            &load_store_parameters,
+        );
+        let store = method(
+            "Store",
+            "void",
+            "Stores the matrix to a buffer.",
+            &[
+                (
+                    "RWByteAddressBuffer",
+                    "buffer",
+                    "The buffer receiving the matrix.",
+                ),
+                load_store_parameters[1],
+                load_store_parameters[2],
+                load_store_parameters[3],
+            ],
+        );
+        let fill = method(
+            "Fill",
+            "void",
+            "Fills all the elements of the matrix with a value.",
+            &[("T", "value", "The value of all elements.")],
+        );
+        let matrix_depth = method(
+            "MatrixDepth",
+            "uint",
+            "Returns the K dimension of the matrix, which depends on the hardware.",
+            &[],
+        );
+        let scalar_methods = [
+            (
+                "ScalarMultiply",
+                "Multiplies each element of the matrix by a value.",
+            ),
+            (
+                "ScalarDivide",
+                "Divides each element of the matrix by a value.",
+            ),
+            ("ScalarAdd", "Adds a value to each element of the matrix."),
+            (
+                "ScalarSubtract",
+                "Subtracts a value from each element of the matrix.",
+            ),
+        ];
+        let wave_matrix_types = [
+            ("WaveMatrixLeft", "Left operand matrix of a wave matrix multiplication, of dimension MxK.", vec![load.clone(), store.clone(), fill.clone(), matrix_depth.clone()]),
+            ("WaveMatrixRight", "Right operand matrix of a wave matrix multiplication, of dimension KxN.", vec![load.clone(), store.clone(), fill.clone(), matrix_depth]),
+            ("WaveMatrixLeftColAcc", "Column accumulator of dimension Mx1, summing the rows of a left matrix.", vec![load.clone(), store.clone(), fill.clone(), method("SumAccumulate", "void", "Adds the sum of each row of the left matrix to the accumulator.", &[("WaveMatrixLeft", "left", "The matrix to sum.")])]),
+            ("WaveMatrixRightRowAcc", "Row accumulator of dimension 1xN, summing the columns of a right matrix.", vec![load.clone(), store.clone(), fill.clone(), method("SumAccumulate", "void", "Adds the sum of each column of the right matrix to the accumulator.", &[("WaveMatrixRight", "right", "The matrix to sum.")])]),
+            ("WaveMatrixAccumulator", "Accumulator matrix receiving the result of a wave matrix multiplication, of dimension MxN.", {
+                let mut methods = vec![load, store, fill];
+                methods.extend(scalar_methods.iter().map(|(label, description)| method(label, "void", description, &[("T", "value", "The scalar operand.")])));
+                methods.push(method("Add", "void", "Adds another accumulator to the matrix.", &[("WaveMatrixAccumulator", "matrix", "The matrix to add.")]));
+                methods.push(method("Multiply", "void", "Stores the product of the left & right matrices in the accumulator.", &[("WaveMatrixLeft", "left", "The left matrix."), ("WaveMatrixRight", "right", "The right matrix.")]));
+                methods.push(method("MultiplyAccumulate", "void", "Adds the product of the left & right matrices to the accumulator.", &[("WaveMatrixLeft", "left", "The left matrix."), ("WaveMatrixRight", "right", "The right matrix.")]));
+                methods
+            }),
+        ];
+        for (label, description, methods) in wave_matrix_types {
+            symbols.types.push(ShaderSymbol {
+                label: label.into(),
+                description: description.into(),
+                version: "sm6.8".into(),
+                stages: vec![],
+                link: Some(
+                    "https://microsoft.github.io/DirectX-Specs/d3d/HLSL_SM_6_8_WaveMatrix.html"
+                        .into(),
+                ),
+                data: ShaderSymbolData::Struct {
+                    members: vec![],
+                    methods,
+                },
+                scope_stack: None,
+                range: None,
+                snippet: None,
+            });
+        }
+        let sampler_types = [
+            (
+                "SamplerState",
+                "Sampler object used to sample textures.",
+                "sm4",
+            ),
+            (
+                "SamplerComparisonState",
+                "Sampler object used to sample textures with a comparison function.",
+                "sm4",
+            ),
+            ("sampler", "Legacy sampler object.", ""),
+            ("sampler1D", "Legacy sampler object for 1D textures.", ""),
+            ("sampler2D", "Legacy sampler object for 2D textures.", ""),
+            ("sampler3D", "Legacy sampler object for 3D textures.", ""),
+            (
+                "samplerCUBE",
+                "Legacy sampler object for cube textures.",
+                "",
+            ),
+        ];
+        for (label, description, version) in sampler_types {
+            symbols.types.push(ShaderSymbol {
+                label: label.into(),
+                description: description.into(),
+                version: version.into(),
+                stages: vec![],
+                link: Some(
+                    "https://learn.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-sampler"
+                        .into(),
+                ),
+                data: ShaderSymbolData::Types {
+                    ty: label.into(),
+                    vector: None,
+                },
+                scope_stack: None,
+                range: None,
+                snippet: None,
+            });
+        }
 
         // Manually push types as they are not in documentation
         let mut scalar_types = Vec::new();
@@ -589,11 +796,18 @@ impl HlslIntrinsicParser {
                     label: fmt.clone(),
                     description: format!("Vector with {} components of {}", component_col, scalar.label),
                     link: Some("https://learn.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-vector".into()),
-                    data: ShaderSymbolData::Types { ty:fmt.clone() },
+                    data: ShaderSymbolData::Types {
+                        ty: fmt.clone(),
+                        vector: Some(ShaderVectorType {
+                            scalar: scalar.label.clone(),
+                            dimension: component_col,
+                        }),
+                    },
                     version: "".into(),
                     stages: vec![],
                     range: None,
                     scope_stack:None,
+                    snippet: None,
                 });
                 /*symbols.types.push(ShaderSymbol {
                     label: format!("vector<{},{}>", scalar.label, component_col),
@@ -609,11 +823,15 @@ impl HlslIntrinsicParser {
                         label: fmt.clone(),
                         description: format!("Matrice with {} rows and {} columns of {}", component_row, component_col, scalar.label),
                         link: Some("https://learn.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-matrix".into()),
-                        data: ShaderSymbolData::Types { ty:fmt.clone() },
+                        data: ShaderSymbolData::Types {
+                            ty: fmt.clone(),
+                            vector: None,
+                        },
                         version: "".into(),
                         stages: vec![],
                         range: None,
                         scope_stack:None,
+                        snippet: None,
                     });
                     /*symbols.types.push(ShaderSymbol{
                         label: format!("matrix<{},{},{}>", scalar.label, component_row, component_col),
@@ -1,7 +1,7 @@
 use std::{collections::HashSet, path::Path};
 
 use common::get_intrinsic_parser;
-use shader_sense::shader::ShadingLanguage;
+use shader_sense::{shader::ShadingLanguage, symbols::symbols::ShaderSymbolList};
 
 mod common;
 mod glsl;
@@ -15,19 +15,37 @@ fn usage() {
     --parse-hlsl : Parse hlsl doc & generate hlsl-intrinsics.json file.
     --parse-wgsl : Parse wgsl doc & generate wgsl-intrinsics.json file.
     --all : Parse all docs.
+    --dry-run : Parse from cache & print a summary without writing json files.
     "
     );
 }
 
+fn print_summary(intrinsic_symbols: &ShaderSymbolList) {
+    let mut total = 0;
+    for (symbols, ty) in intrinsic_symbols.iter() {
+        let missing_description = symbols
+            .iter()
+            .filter(|symbol| symbol.description.is_empty())
+            .count();
+        for symbol in symbols.iter().filter(|symbol| symbol.label.is_empty()) {
+            println!("Warning: {:?} symbol without label: {:?}", ty, symbol);
+        }
+        if missing_description > 0 {
+            println!(
+                "Warning: {} {:?} symbols without description.",
+                missing_description, ty
+            );
+        }
+        println!("{:?}: {}", ty, symbols.len());
+        total += symbols.len();
+    }
+    println!("Total: {}", total);
+}
+
 fn main() {
     let args: Vec<String> = std::env::args().collect();
-    let language_to_parse = if args.len() == 1 {
-        vec![
-            ShadingLanguage::Glsl,
-            ShadingLanguage::Hlsl,
-            ShadingLanguage::Wgsl,
-        ] // All
-    } else {
+    let mut dry_run = false;
+    let language_to_parse = {
         let mut language_to_parse = HashSet::new();
         for arg in &args[1..] {
             // Skip first executable
@@ -46,6 +64,9 @@ fn main() {
                     language_to_parse.insert(ShadingLanguage::Hlsl);
                     language_to_parse.insert(ShadingLanguage::Wgsl);
                 }
+                "--dry-run" => {
+                    dry_run = true;
+                }
                 "--help" => {
                     usage();
                     return;
@@ -57,16 +78,38 @@ fn main() {
                 }
             };
         }
-        language_to_parse.into_iter().collect()
+        if language_to_parse.is_empty() {
+            vec![
+                ShadingLanguage::Glsl,
+                ShadingLanguage::Hlsl,
+                ShadingLanguage::Wgsl,
+            ] // All
+        } else {
+            language_to_parse
+                .into_iter()
+                .collect::<Vec<ShadingLanguage>>()
+        }
     };
     for shading_language in language_to_parse {
         println!("Parsing {}", shading_language.to_string());
         let parser = get_intrinsic_parser(shading_language);
         let cache_path = format!("./.cache/{}/", shading_language.to_string());
         if !Path::new(&cache_path).is_dir() {
+            if dry_run {
+                println!("No cache found at {}, skipping.", cache_path);
+                continue;
+            }
             parser.cache(&cache_path);
         }
         let intrinsic_symbols = parser.parse(&cache_path);
+        if dry_run {
+            print_summary(&intrinsic_symbols);
+            println!(
+                "Dry run, {} json not written.",
+                shading_language.to_string()
+            );
+            continue;
+        }
 
         println!("Saving result...");
         let json = serde_json::to_string(&intrinsic_symbols).expect("Failed to serialize JSON");
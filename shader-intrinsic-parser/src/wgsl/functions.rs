@@ -0,0 +1,619 @@
+use shader_sense::{
+    shader::ShaderStage,
+    symbols::symbols::{
+        ShaderParameter, ShaderSignature, ShaderSymbol, ShaderSymbolData, ShaderSymbolList,
+    },
+};
+
+use super::{get_wgsl_link, WgslIntrinsicParser};
+
+// Generic types follow the spec notation:
+// S is a scalar type, T is S or vecN<S>, F is a floating point T, I is an integer T.
+fn new_wgsl_signature(return_type: &str, parameters: &[(&str, &str)]) -> ShaderSignature {
+    ShaderSignature {
+        returnType: return_type.into(),
+        description: "".into(),
+        parameters: parameters
+            .iter()
+            .map(|(label, ty)| ShaderParameter {
+                ty: (*ty).into(),
+                label: (*label).into(),
+                description: "".into(),
+            })
+            .collect(),
+    }
+}
+
+fn new_wgsl_function(
+    label: &str,
+    description: &str,
+    anchor: &str,
+    stages: Vec<ShaderStage>,
+    signatures: Vec<ShaderSignature>,
+) -> ShaderSymbol {
+    ShaderSymbol {
+        label: label.into(),
+        description: description.into(),
+        version: "".into(),
+        stages,
+        link: get_wgsl_link(anchor),
+        data: ShaderSymbolData::Functions {
+            signatures,
+            workgroup_size: None,
+        },
+        range: None,
+        scope_stack: None,
+        snippet: None,
+    }
+}
+
+impl WgslIntrinsicParser {
+    pub fn add_functions(&self, symbols: &mut ShaderSymbolList) {
+        self.add_numeric_functions(symbols);
+        self.add_derivative_functions(symbols);
+        self.add_texture_functions(symbols);
+        self.add_atomic_functions(symbols);
+        self.add_packing_functions(symbols);
+        self.add_synchronization_functions(symbols);
+    }
+    fn add_numeric_functions(&self, symbols: &mut ShaderSymbolList) {
+        let anchor = "numeric-builtin-functions";
+        // Component-wise functions with one floating point parameter.
+        let unary_float = [
+            ("acos", "Returns the arc cosine of e."),
+            ("acosh", "Returns the inverse hyperbolic cosine of e."),
+            ("asin", "Returns the arc sine of e."),
+            ("asinh", "Returns the inverse hyperbolic sine of e."),
+            ("atan", "Returns the arc tangent of e."),
+            ("atanh", "Returns the inverse hyperbolic tangent of e."),
+            ("ceil", "Returns the ceiling of e."),
+            ("cos", "Returns the cosine of e, where e is in radians."),
+            ("cosh", "Returns the hyperbolic cosine of e."),
+            ("degrees", "Converts radians to degrees."),
+            ("exp", "Returns the natural exponentiation of e."),
+            ("exp2", "Returns 2 raised to the power e."),
+            ("floor", "Returns the floor of e."),
+            ("fract", "Returns the fractional part of e, computed as e - floor(e)."),
+            ("inverseSqrt", "Returns the reciprocal of sqrt(e)."),
+            ("log", "Returns the natural logarithm of e."),
+            ("log2", "Returns the base-2 logarithm of e."),
+            ("quantizeToF16", "Quantizes a 32-bit floating point value e as if e were converted to f16 and then back to f32."),
+            ("radians", "Converts degrees to radians."),
+            ("round", "Returns e rounded to the nearest integer, ties rounded to even."),
+            ("saturate", "Returns clamp(e, 0.0, 1.0)."),
+            ("sin", "Returns the sine of e, where e is in radians."),
+            ("sinh", "Returns the hyperbolic sine of e."),
+            ("sqrt", "Returns the square root of e."),
+            ("tan", "Returns the tangent of e, where e is in radians."),
+            ("tanh", "Returns the hyperbolic tangent of e."),
+            ("trunc", "Returns e truncated to the nearest whole number toward zero."),
+        ];
+        for (label, description) in unary_float {
+            symbols.functions.push(new_wgsl_function(
+                label,
+                description,
+                anchor,
+                vec![],
+                vec![new_wgsl_signature("F", &[("e", "F")])],
+            ));
+        }
+        // Component-wise functions with one integer parameter.
+        let unary_integer = [
+            ("countLeadingZeros", "Returns the number of consecutive 0 bits starting from the most significant bit of e."),
+            ("countOneBits", "Returns the number of 1 bits in the representation of e."),
+            ("countTrailingZeros", "Returns the number of consecutive 0 bits starting from the least significant bit of e."),
+            ("firstLeadingBit", "Returns the bit index of the most significant 1 bit of e, or -1 if there is none. For signed values, the most significant bit different from the sign bit."),
+            ("firstTrailingBit", "Returns the bit index of the least significant 1 bit of e, or -1 if there is none."),
+            ("reverseBits", "Reverses the bits in e."),
+        ];
+        for (label, description) in unary_integer {
+            symbols.functions.push(new_wgsl_function(
+                label,
+                description,
+                anchor,
+                vec![],
+                vec![new_wgsl_signature("I", &[("e", "I")])],
+            ));
+        }
+        let functions = [
+            ("abs", "Returns the absolute value of e.", new_wgsl_signature("T", &[("e", "T")])),
+            ("atan2", "Returns the arc tangent of y / x, in the range [-π, π].", new_wgsl_signature("F", &[("y", "F"), ("x", "F")])),
+            ("clamp", "Restricts the value of e within a range, computed as min(max(e, low), high).", new_wgsl_signature("T", &[("e", "T"), ("low", "T"), ("high", "T")])),
+            ("cross", "Returns the cross product of e1 and e2.", new_wgsl_signature("vec3<F>", &[("e1", "vec3<F>"), ("e2", "vec3<F>")])),
+            ("determinant", "Returns the determinant of e.", new_wgsl_signature("F", &[("e", "matCxC<F>")])),
+            ("distance", "Returns the distance between e1 and e2, computed as length(e1 - e2).", new_wgsl_signature("F", &[("e1", "vecN<F>"), ("e2", "vecN<F>")])),
+            ("dot", "Returns the dot product of e1 and e2.", new_wgsl_signature("S", &[("e1", "vecN<S>"), ("e2", "vecN<S>")])),
+            ("dot4I8Packed", "Returns the dot product of the four 8-bit signed integers packed into e1 and e2.", new_wgsl_signature("i32", &[("e1", "u32"), ("e2", "u32")])),
+            ("dot4U8Packed", "Returns the dot product of the four 8-bit unsigned integers packed into e1 and e2.", new_wgsl_signature("u32", &[("e1", "u32"), ("e2", "u32")])),
+            ("extractBits", "Reads count bits from e, starting at bit offset. Signed values are sign extended.", new_wgsl_signature("I", &[("e", "I"), ("offset", "u32"), ("count", "u32")])),
+            ("faceForward", "Returns e1 if dot(e2, e3) is negative, and -e1 otherwise.", new_wgsl_signature("vecN<F>", &[("e1", "vecN<F>"), ("e2", "vecN<F>"), ("e3", "vecN<F>")])),
+            ("fma", "Returns e1 * e2 + e3.", new_wgsl_signature("F", &[("e1", "F"), ("e2", "F"), ("e3", "F")])),
+            ("frexp", "Splits e into a fraction and an exponent, returned in a structure with fract & exp members.", new_wgsl_signature("__frexp_result", &[("e", "F")])),
+            ("insertBits", "Sets count bits of newbits in e, starting at bit offset.", new_wgsl_signature("I", &[("e", "I"), ("newbits", "I"), ("offset", "u32"), ("count", "u32")])),
+            ("ldexp", "Returns e1 * 2^e2.", new_wgsl_signature("F", &[("e1", "F"), ("e2", "I")])),
+            ("length", "Returns the length of e.", new_wgsl_signature("S", &[("e", "F")])),
+            ("max", "Returns e2 if e1 is less than e2, and e1 otherwise.", new_wgsl_signature("T", &[("e1", "T"), ("e2", "T")])),
+            ("min", "Returns e2 if e2 is less than e1, and e1 otherwise.", new_wgsl_signature("T", &[("e1", "T"), ("e2", "T")])),
+            ("mix", "Returns the linear blend of e1 and e2, computed as e1 * (1 - e3) + e2 * e3.", new_wgsl_signature("F", &[("e1", "F"), ("e2", "F"), ("e3", "F")])),
+            ("modf", "Splits e into fractional and whole number parts, returned in a structure with fract & whole members.", new_wgsl_signature("__modf_result", &[("e", "F")])),
+            ("normalize", "Returns a unit vector in the same direction as e.", new_wgsl_signature("vecN<F>", &[("e", "vecN<F>")])),
+            ("pow", "Returns e1 raised to the power e2.", new_wgsl_signature("F", &[("e1", "F"), ("e2", "F")])),
+            ("reflect", "For the incident vector e1 and surface orientation e2, returns the reflection direction e1 - 2 * dot(e2, e1) * e2.", new_wgsl_signature("vecN<F>", &[("e1", "vecN<F>"), ("e2", "vecN<F>")])),
+            ("refract", "For the incident vector e1, surface normal e2 and ratio of indices of refraction e3, returns the refraction vector.", new_wgsl_signature("vecN<F>", &[("e1", "vecN<F>"), ("e2", "vecN<F>"), ("e3", "S")])),
+            ("sign", "Returns the sign of e.", new_wgsl_signature("T", &[("e", "T")])),
+            ("smoothstep", "Returns the smooth Hermite interpolation between 0 and 1 of x relative to low and high.", new_wgsl_signature("F", &[("low", "F"), ("high", "F"), ("x", "F")])),
+            ("step", "Returns 1.0 if edge is less than or equal to x, and 0.0 otherwise.", new_wgsl_signature("F", &[("edge", "F"), ("x", "F")])),
+            ("transpose", "Returns the transpose of e.", new_wgsl_signature("matRxC<F>", &[("e", "matCxR<F>")])),
+        ];
+        for (label, description, signature) in functions {
+            symbols.functions.push(new_wgsl_function(
+                label,
+                description,
+                anchor,
+                vec![],
+                vec![signature],
+            ));
+        }
+        // Logical & array functions.
+        symbols.functions.push(new_wgsl_function(
+            "all",
+            "Returns true if each component of e is true.",
+            "logical-builtin-functions",
+            vec![],
+            vec![
+                new_wgsl_signature("bool", &[("e", "vecN<bool>")]),
+                new_wgsl_signature("bool", &[("e", "bool")]),
+            ],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "any",
+            "Returns true if any component of e is true.",
+            "logical-builtin-functions",
+            vec![],
+            vec![
+                new_wgsl_signature("bool", &[("e", "vecN<bool>")]),
+                new_wgsl_signature("bool", &[("e", "bool")]),
+            ],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "select",
+            "Returns t when cond is true, and f otherwise. Component-wise when cond is a vector.",
+            "logical-builtin-functions",
+            vec![],
+            vec![
+                new_wgsl_signature("T", &[("f", "T"), ("t", "T"), ("cond", "bool")]),
+                new_wgsl_signature(
+                    "vecN<S>",
+                    &[("f", "vecN<S>"), ("t", "vecN<S>"), ("cond", "vecN<bool>")],
+                ),
+            ],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "arrayLength",
+            "Returns the number of elements in the runtime-sized array.",
+            "array-builtin-functions",
+            vec![],
+            vec![new_wgsl_signature(
+                "u32",
+                &[("p", "ptr<storage, array<E>, AM>")],
+            )],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "bitcast",
+            "Reinterprets the bits of e as type T, used as bitcast<T>(e).",
+            "bitcast-builtin",
+            vec![],
+            vec![new_wgsl_signature("T", &[("e", "S")])],
+        ));
+    }
+    fn add_derivative_functions(&self, symbols: &mut ShaderSymbolList) {
+        let derivatives = [
+            ("dpdx", "Partial derivative of e with respect to window x coordinates. Same as either dpdxFine or dpdxCoarse."),
+            ("dpdxCoarse", "Partial derivative of e with respect to window x coordinates using local differences, which may result in fewer unique positions than dpdxFine."),
+            ("dpdxFine", "Partial derivative of e with respect to window x coordinates."),
+            ("dpdy", "Partial derivative of e with respect to window y coordinates. Same as either dpdyFine or dpdyCoarse."),
+            ("dpdyCoarse", "Partial derivative of e with respect to window y coordinates using local differences, which may result in fewer unique positions than dpdyFine."),
+            ("dpdyFine", "Partial derivative of e with respect to window y coordinates."),
+            ("fwidth", "Returns abs(dpdx(e)) + abs(dpdy(e))."),
+            ("fwidthCoarse", "Returns abs(dpdxCoarse(e)) + abs(dpdyCoarse(e))."),
+            ("fwidthFine", "Returns abs(dpdxFine(e)) + abs(dpdyFine(e))."),
+        ];
+        for (label, description) in derivatives {
+            symbols.functions.push(new_wgsl_function(
+                label,
+                description,
+                "derivative-builtin-functions",
+                vec![ShaderStage::Fragment],
+                vec![new_wgsl_signature("T", &[("e", "T")])],
+            ));
+        }
+    }
+    fn add_texture_functions(&self, symbols: &mut ShaderSymbolList) {
+        let anchor = "texture-builtin-functions";
+        symbols.functions.push(new_wgsl_function(
+            "textureDimensions",
+            "Returns the dimensions of a texture, or of one of its mip levels.",
+            anchor,
+            vec![],
+            vec![
+                new_wgsl_signature("vecN<u32>", &[("t", "T")]),
+                new_wgsl_signature("vecN<u32>", &[("t", "T"), ("level", "u32")]),
+            ],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureGather",
+            "Returns the four texels of the given component that would be used for bilinear filtering.",
+            anchor,
+            vec![],
+            vec![
+                new_wgsl_signature(
+                    "vec4<S>",
+                    &[
+                        ("component", "u32"),
+                        ("t", "texture_2d<S>"),
+                        ("s", "sampler"),
+                        ("coords", "vec2<f32>"),
+                    ],
+                ),
+                new_wgsl_signature(
+                    "vec4<f32>",
+                    &[
+                        ("t", "texture_depth_2d"),
+                        ("s", "sampler"),
+                        ("coords", "vec2<f32>"),
+                    ],
+                ),
+            ],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureGatherCompare",
+            "Performs a depth comparison on the four texels that would be used for bilinear filtering.",
+            anchor,
+            vec![],
+            vec![new_wgsl_signature(
+                "vec4<f32>",
+                &[
+                    ("t", "texture_depth_2d"),
+                    ("s", "sampler_comparison"),
+                    ("coords", "vec2<f32>"),
+                    ("depth_ref", "f32"),
+                ],
+            )],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureLoad",
+            "Reads a single texel from a texture without sampling or filtering.",
+            anchor,
+            vec![],
+            vec![
+                new_wgsl_signature(
+                    "vec4<S>",
+                    &[("t", "T"), ("coords", "vecN<C>"), ("level", "L")],
+                ),
+                new_wgsl_signature(
+                    "vec4<S>",
+                    &[
+                        ("t", "texture_multisampled_2d<S>"),
+                        ("coords", "vec2<C>"),
+                        ("sample_index", "I"),
+                    ],
+                ),
+                new_wgsl_signature(
+                    "vec4<S>",
+                    &[("t", "texture_storage_2d<F, AM>"), ("coords", "vec2<C>")],
+                ),
+            ],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureNumLayers",
+            "Returns the number of layers (elements) of an array texture.",
+            anchor,
+            vec![],
+            vec![new_wgsl_signature("u32", &[("t", "T")])],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureNumLevels",
+            "Returns the number of mip levels of a texture.",
+            anchor,
+            vec![],
+            vec![new_wgsl_signature("u32", &[("t", "T")])],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureNumSamples",
+            "Returns the number samples per texel in a multisampled texture.",
+            anchor,
+            vec![],
+            vec![new_wgsl_signature("u32", &[("t", "T")])],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureSample",
+            "Samples a texture. Must only be used in a fragment shader stage.",
+            anchor,
+            vec![ShaderStage::Fragment],
+            vec![
+                new_wgsl_signature(
+                    "vec4<f32>",
+                    &[
+                        ("t", "texture_2d<f32>"),
+                        ("s", "sampler"),
+                        ("coords", "vec2<f32>"),
+                    ],
+                ),
+                new_wgsl_signature(
+                    "vec4<f32>",
+                    &[
+                        ("t", "texture_2d<f32>"),
+                        ("s", "sampler"),
+                        ("coords", "vec2<f32>"),
+                        ("offset", "vec2<i32>"),
+                    ],
+                ),
+                new_wgsl_signature(
+                    "vec4<f32>",
+                    &[
+                        ("t", "texture_2d_array<f32>"),
+                        ("s", "sampler"),
+                        ("coords", "vec2<f32>"),
+                        ("array_index", "A"),
+                    ],
+                ),
+                new_wgsl_signature(
+                    "vec4<f32>",
+                    &[
+                        ("t", "texture_3d<f32>"),
+                        ("s", "sampler"),
+                        ("coords", "vec3<f32>"),
+                    ],
+                ),
+                new_wgsl_signature(
+                    "vec4<f32>",
+                    &[
+                        ("t", "texture_cube<f32>"),
+                        ("s", "sampler"),
+                        ("coords", "vec3<f32>"),
+                    ],
+                ),
+                new_wgsl_signature(
+                    "f32",
+                    &[
+                        ("t", "texture_depth_2d"),
+                        ("s", "sampler"),
+                        ("coords", "vec2<f32>"),
+                    ],
+                ),
+            ],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureSampleBias",
+            "Samples a texture with a bias to the mip level. Must only be used in a fragment shader stage.",
+            anchor,
+            vec![ShaderStage::Fragment],
+            vec![new_wgsl_signature(
+                "vec4<f32>",
+                &[
+                    ("t", "texture_2d<f32>"),
+                    ("s", "sampler"),
+                    ("coords", "vec2<f32>"),
+                    ("bias", "f32"),
+                ],
+            )],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureSampleCompare",
+            "Samples a depth texture and compares the sampled depth values against a reference value. Must only be used in a fragment shader stage.",
+            anchor,
+            vec![ShaderStage::Fragment],
+            vec![new_wgsl_signature(
+                "f32",
+                &[
+                    ("t", "texture_depth_2d"),
+                    ("s", "sampler_comparison"),
+                    ("coords", "vec2<f32>"),
+                    ("depth_ref", "f32"),
+                ],
+            )],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureSampleCompareLevel",
+            "Samples the mip level 0 of a depth texture and compares the sampled depth values against a reference value.",
+            anchor,
+            vec![],
+            vec![new_wgsl_signature(
+                "f32",
+                &[
+                    ("t", "texture_depth_2d"),
+                    ("s", "sampler_comparison"),
+                    ("coords", "vec2<f32>"),
+                    ("depth_ref", "f32"),
+                ],
+            )],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureSampleGrad",
+            "Samples a texture using explicit gradients.",
+            anchor,
+            vec![],
+            vec![new_wgsl_signature(
+                "vec4<f32>",
+                &[
+                    ("t", "texture_2d<f32>"),
+                    ("s", "sampler"),
+                    ("coords", "vec2<f32>"),
+                    ("ddx", "vec2<f32>"),
+                    ("ddy", "vec2<f32>"),
+                ],
+            )],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureSampleLevel",
+            "Samples a texture using an explicit mip level.",
+            anchor,
+            vec![],
+            vec![
+                new_wgsl_signature(
+                    "vec4<f32>",
+                    &[
+                        ("t", "texture_2d<f32>"),
+                        ("s", "sampler"),
+                        ("coords", "vec2<f32>"),
+                        ("level", "f32"),
+                    ],
+                ),
+                new_wgsl_signature(
+                    "f32",
+                    &[
+                        ("t", "texture_depth_2d"),
+                        ("s", "sampler"),
+                        ("coords", "vec2<f32>"),
+                        ("level", "L"),
+                    ],
+                ),
+            ],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureSampleBaseClampToEdge",
+            "Samples a texture view at its base level, with texture coordinates clamped to the edge.",
+            anchor,
+            vec![],
+            vec![new_wgsl_signature(
+                "vec4<f32>",
+                &[("t", "T"), ("s", "sampler"), ("coords", "vec2<f32>")],
+            )],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "textureStore",
+            "Writes a single texel to a storage texture.",
+            anchor,
+            vec![],
+            vec![new_wgsl_signature(
+                "void",
+                &[
+                    ("t", "texture_storage_2d<F, AM>"),
+                    ("coords", "vec2<C>"),
+                    ("value", "vec4<S>"),
+                ],
+            )],
+        ));
+    }
+    fn add_atomic_functions(&self, symbols: &mut ShaderSymbolList) {
+        let anchor = "atomic-builtin-functions";
+        symbols.functions.push(new_wgsl_function(
+            "atomicLoad",
+            "Atomically loads the value pointed to by atomic_ptr.",
+            anchor,
+            vec![],
+            vec![new_wgsl_signature(
+                "T",
+                &[("atomic_ptr", "ptr<AS, atomic<T>, read_write>")],
+            )],
+        ));
+        symbols.functions.push(new_wgsl_function(
+            "atomicStore",
+            "Atomically stores v in the atomic object pointed to by atomic_ptr.",
+            anchor,
+            vec![],
+            vec![new_wgsl_signature(
+                "void",
+                &[("atomic_ptr", "ptr<AS, atomic<T>, read_write>"), ("v", "T")],
+            )],
+        ));
+        let read_modify_write = [
+            ("atomicAdd", "Atomically adds v to the atomic object and returns its original value."),
+            ("atomicSub", "Atomically subtracts v from the atomic object and returns its original value."),
+            ("atomicMax", "Atomically stores the maximum of v & the atomic object and returns its original value."),
+            ("atomicMin", "Atomically stores the minimum of v & the atomic object and returns its original value."),
+            ("atomicAnd", "Atomically performs a bitwise and with v and returns the original value."),
+            ("atomicOr", "Atomically performs a bitwise or with v and returns the original value."),
+            ("atomicXor", "Atomically performs a bitwise xor with v and returns the original value."),
+            ("atomicExchange", "Atomically stores v in the atomic object and returns its original value."),
+        ];
+        for (label, description) in read_modify_write {
+            symbols.functions.push(new_wgsl_function(
+                label,
+                description,
+                anchor,
+                vec![],
+                vec![new_wgsl_signature(
+                    "T",
+                    &[("atomic_ptr", "ptr<AS, atomic<T>, read_write>"), ("v", "T")],
+                )],
+            ));
+        }
+        symbols.functions.push(new_wgsl_function(
+            "atomicCompareExchangeWeak",
+            "Atomically stores value if the atomic object is equal to cmp. Returns a structure with the original value in old_value and whether the exchange happened in exchanged.",
+            anchor,
+            vec![],
+            vec![new_wgsl_signature(
+                "__atomic_compare_exchange_result<T>",
+                &[
+                    ("atomic_ptr", "ptr<AS, atomic<T>, read_write>"),
+                    ("cmp", "T"),
+                    ("v", "T"),
+                ],
+            )],
+        ));
+    }
+    fn add_packing_functions(&self, symbols: &mut ShaderSymbolList) {
+        let packing = [
+            ("pack4x8snorm", "Converts four normalized floating point values to 8-bit signed integers, and combines them into one u32 value.", "vec4<f32>"),
+            ("pack4x8unorm", "Converts four normalized floating point values to 8-bit unsigned integers, and combines them into one u32 value.", "vec4<f32>"),
+            ("pack4xI8", "Packs the lower 8 bits of four signed integers into one u32 value.", "vec4<i32>"),
+            ("pack4xU8", "Packs the lower 8 bits of four unsigned integers into one u32 value.", "vec4<u32>"),
+            ("pack2x16snorm", "Converts two normalized floating point values to 16-bit signed integers, and combines them into one u32 value.", "vec2<f32>"),
+            ("pack2x16unorm", "Converts two normalized floating point values to 16-bit unsigned integers, and combines them into one u32 value.", "vec2<f32>"),
+            ("pack2x16float", "Converts two floating point values to half-precision floating point numbers, and combines them into one u32 value.", "vec2<f32>"),
+        ];
+        for (label, description, ty) in packing {
+            symbols.functions.push(new_wgsl_function(
+                label,
+                description,
+                "pack-builtin-functions",
+                vec![],
+                vec![new_wgsl_signature("u32", &[("e", ty)])],
+            ));
+        }
+        let unpacking = [
+            ("unpack4x8snorm", "Decomposes a u32 value into four 8-bit chunks, then reinterprets each chunk as a signed normalized floating point value.", "vec4<f32>"),
+            ("unpack4x8unorm", "Decomposes a u32 value into four 8-bit chunks, then reinterprets each chunk as an unsigned normalized floating point value.", "vec4<f32>"),
+            ("unpack4xI8", "Decomposes a u32 value into four 8-bit chunks, then sign extends each chunk to an i32.", "vec4<i32>"),
+            ("unpack4xU8", "Decomposes a u32 value into four 8-bit chunks, then zero extends each chunk to an u32.", "vec4<u32>"),
+            ("unpack2x16snorm", "Decomposes a u32 value into two 16-bit chunks, then reinterprets each chunk as a signed normalized floating point value.", "vec2<f32>"),
+            ("unpack2x16unorm", "Decomposes a u32 value into two 16-bit chunks, then reinterprets each chunk as an unsigned normalized floating point value.", "vec2<f32>"),
+            ("unpack2x16float", "Decomposes a u32 value into two 16-bit chunks, and reinterpets each chunk as a half-precision floating point value.", "vec2<f32>"),
+        ];
+        for (label, description, ty) in unpacking {
+            symbols.functions.push(new_wgsl_function(
+                label,
+                description,
+                "unpack-builtin-functions",
+                vec![],
+                vec![new_wgsl_signature(ty, &[("e", "u32")])],
+            ));
+        }
+    }
+    fn add_synchronization_functions(&self, symbols: &mut ShaderSymbolList) {
+        let anchor = "sync-builtin-functions";
+        let barriers = [
+            ("storageBarrier", "Executes a control barrier synchronization function that affects memory and atomic operations in the storage address space."),
+            ("textureBarrier", "Executes a control barrier synchronization function that affects memory operations in the handle address space."),
+            ("workgroupBarrier", "Executes a control barrier synchronization function that affects memory and atomic operations in the workgroup address space."),
+        ];
+        for (label, description) in barriers {
+            symbols.functions.push(new_wgsl_function(
+                label,
+                description,
+                anchor,
+                vec![ShaderStage::Compute],
+                vec![new_wgsl_signature("void", &[])],
+            ));
+        }
+        symbols.functions.push(new_wgsl_function(
+            "workgroupUniformLoad",
+            "Returns the value pointed to by p to all invocations in the workgroup. Executes a workgroup barrier before and after the load.",
+            anchor,
+            vec![ShaderStage::Compute],
+            vec![new_wgsl_signature("T", &[("p", "ptr<workgroup, T>")])],
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+use shader_sense::symbols::symbols::{ShaderSymbol, ShaderSymbolData, ShaderSymbolList};
+
+use super::{get_wgsl_link, WgslIntrinsicParser};
+
+impl WgslIntrinsicParser {
+    pub fn add_keywords(&self, symbols: &mut ShaderSymbolList) {
+        fn new_wgsl_keyword(label: &str, description: &str, anchor: &str) -> ShaderSymbol {
+            ShaderSymbol {
+                label: label.into(),
+                description: description.into(),
+                version: "".into(),
+                stages: vec![],
+                link: get_wgsl_link(anchor),
+                data: ShaderSymbolData::Keyword {},
+                range: None,
+                scope_stack: None,
+                snippet: None,
+            }
+        }
+        let keywords = [
+            ("alias", "Declare a type alias.", "type-aliases"),
+            (
+                "break",
+                "Exit the innermost loop or switch statement.",
+                "break-statement",
+            ),
+            (
+                "case",
+                "Declare a case clause of a switch statement.",
+                "switch-statement",
+            ),
+            (
+                "const",
+                "Declare a value known at shader-creation time.",
+                "const-decls",
+            ),
+            (
+                "const_assert",
+                "Assert a condition known at shader-creation time.",
+                "const-assert-statement",
+            ),
+            (
+                "continue",
+                "Skip to the next iteration of the innermost loop.",
+                "continue-statement",
+            ),
+            (
+                "continuing",
+                "Declare the statements executed at the end of each loop iteration.",
+                "continuing-statement",
+            ),
+            (
+                "default",
+                "Declare the default clause of a switch statement.",
+                "switch-statement",
+            ),
+            (
+                "diagnostic",
+                "Control the severity of a diagnostic.",
+                "diagnostics",
+            ),
+            (
+                "discard",
+                "Demote the fragment invocation to a helper invocation.",
+                "discard-statement",
+            ),
+            (
+                "else",
+                "Declare the alternative branch of an if statement.",
+                "if-statement",
+            ),
+            (
+                "enable",
+                "Enable an optional language extension.",
+                "enable-extensions-sec",
+            ),
+            ("false", "Boolean false value.", "bool-type"),
+            ("fn", "Declare a function.", "function-declaration-sec"),
+            ("for", "Declare a for loop.", "for-statement"),
+            ("if", "Declare a conditional statement.", "if-statement"),
+            (
+                "let",
+                "Declare an immutable value computed at runtime.",
+                "let-decls",
+            ),
+            ("loop", "Declare an infinite loop.", "loop-statement"),
+            (
+                "override",
+                "Declare a pipeline-overridable constant.",
+                "override-decls",
+            ),
+            (
+                "requires",
+                "Require a language feature.",
+                "language-extension",
+            ),
+            (
+                "return",
+                "Return from the current function.",
+                "return-statement",
+            ),
+            ("struct", "Declare a structure type.", "struct-types"),
+            ("switch", "Declare a switch statement.", "switch-statement"),
+            ("true", "Boolean true value.", "bool-type"),
+            ("var", "Declare a mutable variable.", "var-decls"),
+            ("while", "Declare a while loop.", "while-statement"),
+            // Address spaces & access modes are context-dependent.
+            (
+                "function",
+                "Address space of function local variables.",
+                "address-spaces",
+            ),
+            (
+                "private",
+                "Address space of module scope variables private to an invocation.",
+                "address-spaces",
+            ),
+            (
+                "workgroup",
+                "Address space of variables shared by invocations of a compute workgroup.",
+                "address-spaces",
+            ),
+            (
+                "uniform",
+                "Address space of read-only uniform buffer variables.",
+                "address-spaces",
+            ),
+            (
+                "storage",
+                "Address space of storage buffer variables.",
+                "address-spaces",
+            ),
+            ("read", "Read only access mode.", "memory-access-mode"),
+            ("write", "Write only access mode.", "memory-access-mode"),
+            (
+                "read_write",
+                "Read and write access mode.",
+                "memory-access-mode",
+            ),
+        ];
+        for (label, description, anchor) in keywords {
+            symbols
+                .keywords
+                .push(new_wgsl_keyword(label, description, anchor));
+        }
+    }
+}
@@ -2,11 +2,26 @@ use shader_sense::symbols::symbols::ShaderSymbolList;
 
 use crate::common::IntrinsicParser;
 
+mod functions;
+mod keywords;
+mod types;
+
+pub const WGSL_SPEC_URL: &str = "https://www.w3.org/TR/WGSL/";
+
+pub fn get_wgsl_link(anchor: &str) -> Option<String> {
+    Some(format!("{}#{}", WGSL_SPEC_URL, anchor))
+}
+
 pub struct WgslIntrinsicParser {}
 
 impl IntrinsicParser for WgslIntrinsicParser {
     fn cache(&self, _cache_path: &str) {}
     fn parse(&self, _cache_path: &str) -> ShaderSymbolList {
-        ShaderSymbolList::default() // TODO:
+        let mut symbols = ShaderSymbolList::default();
+        // Spec is a single huge page with generic signatures. Do it manually.
+        self.add_functions(&mut symbols);
+        self.add_types(&mut symbols);
+        self.add_keywords(&mut symbols);
+        symbols
     }
 }
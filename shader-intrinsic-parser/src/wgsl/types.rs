@@ -0,0 +1,132 @@
+use shader_sense::symbols::symbols::{
+    ShaderSymbol, ShaderSymbolData, ShaderSymbolList, ShaderVectorType,
+};
+
+use super::{get_wgsl_link, WgslIntrinsicParser};
+
+pub fn new_wgsl_type(label: &str, description: &str, anchor: &str) -> ShaderSymbol {
+    ShaderSymbol {
+        label: label.into(),
+        description: description.into(),
+        version: "".into(),
+        stages: vec![],
+        link: get_wgsl_link(anchor),
+        data: ShaderSymbolData::Types {
+            ty: label.into(),
+            vector: None,
+        },
+        range: None,
+        scope_stack: None,
+        snippet: None,
+    }
+}
+
+pub fn new_wgsl_vector(
+    label: &str,
+    description: &str,
+    anchor: &str,
+    scalar: &str,
+    dimension: u32,
+) -> ShaderSymbol {
+    ShaderSymbol {
+        data: ShaderSymbolData::Types {
+            ty: label.into(),
+            vector: Some(ShaderVectorType {
+                scalar: scalar.into(),
+                dimension,
+            }),
+        },
+        ..new_wgsl_type(label, description, anchor)
+    }
+}
+
+impl WgslIntrinsicParser {
+    pub fn add_types(&self, symbols: &mut ShaderSymbolList) {
+        let scalars = [
+            ("bool", "Boolean value, either true or false.", "bool-type"),
+            ("i32", "32-bit signed integer.", "integer-types"),
+            ("u32", "32-bit unsigned integer.", "integer-types"),
+            (
+                "f32",
+                "32-bit IEEE-754 floating point value.",
+                "floating-point-types",
+            ),
+            (
+                "f16",
+                "16-bit IEEE-754 floating point value. Require the f16 extension.",
+                "floating-point-types",
+            ),
+        ];
+        for (label, description, anchor) in scalars {
+            symbols
+                .types
+                .push(new_wgsl_vector(label, description, anchor, label, 1));
+        }
+        // Vector & matrix with their predeclared aliases.
+        let alias_components = [("i", "i32"), ("u", "u32"), ("f", "f32"), ("h", "f16")];
+        for size in 2..=4 {
+            symbols.types.push(new_wgsl_type(
+                &format!("vec{}", size),
+                &format!(
+                    "Vector of {} components. Used as vec{}<T> where T is a scalar type.",
+                    size, size
+                ),
+                "vector-types",
+            ));
+            for (suffix, component) in alias_components {
+                symbols.types.push(new_wgsl_vector(
+                    &format!("vec{}{}", size, suffix),
+                    &format!("Alias of vec{}<{}>.", size, component),
+                    "vector-types",
+                    component,
+                    size,
+                ));
+            }
+        }
+        for columns in 2..=4 {
+            for rows in 2..=4 {
+                symbols.types.push(new_wgsl_type(
+                    &format!("mat{}x{}", columns, rows),
+                    &format!("Matrix of {} columns and {} rows. Used as mat{}x{}<T> where T is a floating point type.", columns, rows, columns, rows),
+                    "matrix-types",
+                ));
+                for (suffix, component) in &alias_components[2..] {
+                    symbols.types.push(new_wgsl_type(
+                        &format!("mat{}x{}{}", columns, rows, suffix),
+                        &format!("Alias of mat{}x{}<{}>.", columns, rows, component),
+                        "matrix-types",
+                    ));
+                }
+            }
+        }
+        let types = [
+            ("atomic", "Atomic type used as atomic<T> where T is i32 or u32. Only accessible through atomic builtin functions.", "atomic-types"),
+            ("array", "Array type used as array<E, N> or as runtime-sized array<E>.", "array-types"),
+            ("ptr", "Pointer type used as ptr<AS, T, AM> with an address space, a store type & an access mode.", "ref-ptr-types"),
+            ("sampler", "Sampler used to sample sampled textures.", "sampler-type"),
+            ("sampler_comparison", "Comparison sampler used to sample depth textures.", "sampler-type"),
+            ("texture_1d", "One dimensional sampled texture used as texture_1d<T>.", "sampled-texture-type"),
+            ("texture_2d", "Two dimensional sampled texture used as texture_2d<T>.", "sampled-texture-type"),
+            ("texture_2d_array", "Array of two dimensional sampled textures used as texture_2d_array<T>.", "sampled-texture-type"),
+            ("texture_3d", "Three dimensional sampled texture used as texture_3d<T>.", "sampled-texture-type"),
+            ("texture_cube", "Cube sampled texture used as texture_cube<T>.", "sampled-texture-type"),
+            ("texture_cube_array", "Array of cube sampled textures used as texture_cube_array<T>.", "sampled-texture-type"),
+            ("texture_multisampled_2d", "Two dimensional multisampled texture used as texture_multisampled_2d<T>.", "multisampled-texture-type"),
+            ("texture_depth_multisampled_2d", "Two dimensional multisampled depth texture.", "multisampled-texture-type"),
+            ("texture_external", "External texture such as a video frame.", "external-texture-type"),
+            ("texture_storage_1d", "One dimensional storage texture used as texture_storage_1d<F, AM>.", "texture-storage"),
+            ("texture_storage_2d", "Two dimensional storage texture used as texture_storage_2d<F, AM>.", "texture-storage"),
+            ("texture_storage_2d_array", "Array of two dimensional storage textures used as texture_storage_2d_array<F, AM>.", "texture-storage"),
+            ("texture_storage_3d", "Three dimensional storage texture used as texture_storage_3d<F, AM>.", "texture-storage"),
+            ("texture_depth_2d", "Two dimensional depth texture.", "texture-depth"),
+            ("texture_depth_2d_array", "Array of two dimensional depth textures.", "texture-depth"),
+            ("texture_depth_cube", "Cube depth texture.", "texture-depth"),
+            ("texture_depth_cube_array", "Array of cube depth textures.", "texture-depth"),
+        ];
+        for (label, description, anchor) in types {
+            symbols
+                .types
+                .push(new_wgsl_type(label, description, anchor));
+        }
+    }
+}
@@ -21,7 +21,20 @@ fn run_server() {
     server::run();
 }
 
+// Validate a single file or export its symbols without running the server, for CI usage.
+fn run_validation(args: &[String]) {
+    env_logger::init();
+    std::process::exit(server::run_validation(args));
+}
+
 pub fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args
+        .iter()
+        .any(|arg| arg == "--validate" || arg == "--symbols" || arg == "--stdin")
+    {
+        return run_validation(&args);
+    }
     let last = std::env::args().last();
     match last {
         Some(last) => match last.as_str() {
@@ -1,56 +1,116 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::str::FromStr;
 
+mod code_action;
 mod common;
 mod completion;
 mod debug;
+mod dependencies;
 mod diagnostic;
+mod document_link;
+mod document_symbol;
+mod folding_range;
+mod formatting;
 mod goto;
 mod hover;
+mod inlay_hint;
+mod member;
+mod references;
+mod rename;
+mod semantic_tokens;
 mod signature;
+mod status;
+mod symbol_origin;
+mod validate;
+mod validation_pool;
+mod workspace_symbol;
 
 mod server_config;
 mod server_connection;
 mod server_file_cache;
 mod server_language_data;
+mod shader_config_file;
 
 use debug::{DumpAstParams, DumpAstRequest};
+use dependencies::{DependenciesParams, DependenciesRequest};
 use log::{debug, error, info, warn};
 use lsp_types::notification::{
-    DidChangeConfiguration, DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
-    DidSaveTextDocument, Notification,
+    DidChangeConfiguration, DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument,
+    DidOpenTextDocument, DidSaveTextDocument, Notification, SetTrace,
 };
 use lsp_types::request::{
-    Completion, DocumentDiagnosticRequest, GotoDefinition, HoverRequest, Request,
-    SignatureHelpRequest, WorkspaceConfiguration,
+    CodeActionRequest, Completion, DocumentDiagnosticRequest, DocumentLinkRequest,
+    DocumentSymbolRequest, FoldingRangeRequest, Formatting, GotoDefinition, HoverRequest,
+    InlayHintRequest, PrepareRenameRequest, RangeFormatting, References, RegisterCapability,
+    Rename, Request, SemanticTokensFullRequest, SignatureHelpRequest, WorkspaceConfiguration,
+    WorkspaceSymbolRequest,
 };
 use lsp_types::ServerCapabilities;
 use lsp_types::{
+    CodeActionParams, CodeActionProviderCapability, CodeActionResponse,
     CompletionOptionsCompletionItem, CompletionParams, CompletionResponse, ConfigurationParams,
-    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+    DidChangeWatchedFilesRegistrationOptions, DidCloseTextDocumentParams,
     DidOpenTextDocumentParams, DidSaveTextDocumentParams, DocumentDiagnosticParams,
     DocumentDiagnosticReport, DocumentDiagnosticReportKind, DocumentDiagnosticReportResult,
-    FullDocumentDiagnosticReport, GotoDefinitionParams, HoverParams, HoverProviderCapability,
-    RelatedFullDocumentDiagnosticReport, SignatureHelpOptions, SignatureHelpParams,
-    TextDocumentSyncKind, Url, WorkDoneProgressOptions,
+    DocumentFormattingParams, DocumentLinkOptions, DocumentLinkParams,
+    DocumentRangeFormattingParams, DocumentSymbolParams, DocumentSymbolResponse, FileChangeType,
+    FileSystemWatcher, FoldingRangeParams, FoldingRangeProviderCapability,
+    FullDocumentDiagnosticReport, GlobPattern, GotoDefinitionParams, HoverParams,
+    HoverProviderCapability, InitializeParams, InlayHintParams, ReferenceParams, Registration,
+    RegistrationParams, RelatedFullDocumentDiagnosticReport, RenameOptions, RenameParams,
+    SemanticTokensFullOptions, SemanticTokensOptions, SemanticTokensParams, SemanticTokensResult,
+    SemanticTokensServerCapabilities, SetTraceParams, SignatureHelpOptions, SignatureHelpParams,
+    TextDocumentPositionParams, TextDocumentSyncKind, Url, WorkDoneProgressOptions,
+    WorkspaceSymbolParams, WorkspaceSymbolResponse,
 };
+use shader_sense::include::IncludeHandler;
 use shader_sense::shader::ShadingLanguage;
 
 use lsp_server::{ErrorCode, Message};
 
+use semantic_tokens::get_semantic_tokens_legend;
 use serde_json::Value;
-use server_config::ServerConfig;
+use server_config::{
+    ServerConfig, EXPERIMENTAL_CODE_ACTIONS, EXPERIMENTAL_FORMATTING, EXPERIMENTAL_INLAY_HINTS,
+    EXPERIMENTAL_SEMANTIC_TOKENS,
+};
 use server_connection::ServerConnection;
 use server_file_cache::ServerFileCacheHandle;
 use server_language_data::ServerLanguageData;
+use shader_config_file::ShaderConfigFile;
+use status::{get_server_status, StatusRequest};
+use symbol_origin::{SymbolOriginParams, SymbolOriginRequest};
+pub use validate::run_validation;
+use validation_pool::{hash_content, ValidationPool, ValidationResult};
+
+// Shaders & headers that might be included, watched when the client support dynamic registration.
+const WATCHED_FILES_GLOB: &str = "**/*.{hlsl,hlsli,fx,fxh,h,inc,glsl,vert,frag,comp,task,mesh,tesc,tese,geom,rgen,rchit,rahit,rcall,rmiss,rint,wgsl}";
+const SHADER_CONFIG_FILES_GLOB: &str = "**/.shader-config.json";
 
 pub struct ServerLanguage {
     connection: ServerConnection,
     // Cache
     file_language: HashMap<Url, ShadingLanguage>,
     language_data: HashMap<ShadingLanguage, ServerLanguageData>,
+    warned_missing_includes: HashSet<String>, // Only warn once per missing include.
+    experimental: HashSet<String>,            // Features advertised on initialization.
+    validation_pool: ValidationPool,          // Re-validate open files on config change.
+}
+
+// First line pragma such as `// lang: hlsl` forcing the language of a file.
+fn get_language_pragma(content: &str) -> Option<ShadingLanguage> {
+    let pragma = content
+        .lines()
+        .next()?
+        .trim()
+        .strip_prefix("//")?
+        .trim_start();
+    let language = pragma.strip_prefix("lang:")?.trim();
+    ShadingLanguage::from_str(&language.to_lowercase()).ok()
 }
 
 fn clean_url(url: &Url) -> Url {
@@ -71,6 +131,73 @@ fn clean_url(url: &Url) -> Url {
     }
 }
 
+// Experimental features are only advertised when their flag is set.
+fn get_server_capabilities(experimental: &HashSet<String>) -> ServerCapabilities {
+    let has_formatting = experimental.contains(EXPERIMENTAL_FORMATTING);
+    let semantic_tokens_provider = experimental
+        .contains(EXPERIMENTAL_SEMANTIC_TOKENS)
+        .then(|| {
+            SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+                legend: get_semantic_tokens_legend(),
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+                range: None,
+                work_done_progress_options: WorkDoneProgressOptions {
+                    work_done_progress: None,
+                },
+            })
+        });
+    ServerCapabilities {
+        text_document_sync: Some(lsp_types::TextDocumentSyncCapability::Kind(
+            TextDocumentSyncKind::INCREMENTAL,
+        )),
+        completion_provider: Some(lsp_types::CompletionOptions {
+            resolve_provider: None, // For more detailed data
+            completion_item: Some(CompletionOptionsCompletionItem {
+                label_details_support: Some(true),
+            }),
+            // Colon for HLSL semantics, less than for WGSL address spaces.
+            trigger_characters: Some(vec![".".into(), ":".into(), "<".into()]),
+            ..Default::default()
+        }),
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".to_owned(), ",".to_owned()]),
+            retrigger_characters: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(lsp_types::OneOf::Left(true)),
+        type_definition_provider: Some(lsp_types::TypeDefinitionProviderCapability::Simple(false)), // Disable as definition_provider is doing it.
+        references_provider: Some(lsp_types::OneOf::Left(true)),
+        rename_provider: Some(lsp_types::OneOf::Right(RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        })),
+        document_formatting_provider: has_formatting.then_some(lsp_types::OneOf::Left(true)),
+        document_range_formatting_provider: has_formatting.then_some(lsp_types::OneOf::Left(true)),
+        document_symbol_provider: Some(lsp_types::OneOf::Left(true)),
+        document_link_provider: Some(DocumentLinkOptions {
+            resolve_provider: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        workspace_symbol_provider: Some(lsp_types::OneOf::Left(true)),
+        folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+        inlay_hint_provider: experimental
+            .contains(EXPERIMENTAL_INLAY_HINTS)
+            .then_some(lsp_types::OneOf::Left(true)),
+        code_action_provider: experimental
+            .contains(EXPERIMENTAL_CODE_ACTIONS)
+            .then_some(CodeActionProviderCapability::Simple(true)),
+        semantic_tokens_provider,
+        ..Default::default()
+    }
+}
+
 impl ServerLanguage {
     pub fn new() -> Self {
         // Run the server and wait for the two threads to end (typically by trigger LSP Exit event).
@@ -82,63 +209,86 @@ impl ServerLanguage {
                 (ShadingLanguage::Hlsl, ServerLanguageData::hlsl()),
                 (ShadingLanguage::Wgsl, ServerLanguageData::wgsl()),
             ]),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
         }
     }
     pub fn initialize(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
-        let server_capabilities = serde_json::to_value(&ServerCapabilities {
-            text_document_sync: Some(lsp_types::TextDocumentSyncCapability::Kind(
-                TextDocumentSyncKind::INCREMENTAL,
-            )),
-            completion_provider: Some(lsp_types::CompletionOptions {
-                resolve_provider: None, // For more detailed data
-                completion_item: Some(CompletionOptionsCompletionItem {
-                    label_details_support: Some(true),
-                }),
-                trigger_characters: Some(vec![".".into()]),
-                ..Default::default()
-            }),
-            signature_help_provider: Some(SignatureHelpOptions {
-                trigger_characters: Some(vec!["(".to_owned(), ",".to_owned()]),
-                retrigger_characters: None,
-                work_done_progress_options: WorkDoneProgressOptions {
-                    work_done_progress: None,
-                },
-            }),
-            hover_provider: Some(HoverProviderCapability::Simple(true)),
-            definition_provider: Some(lsp_types::OneOf::Left(true)),
-            type_definition_provider: Some(lsp_types::TypeDefinitionProviderCapability::Simple(
-                false,
-            )), // Disable as definition_provider is doing it.
-            ..Default::default()
-        })?;
-        let client_initialization_params = self.connection.initialize(server_capabilities);
+        let experimental = &mut self.experimental;
+        let client_initialization_params =
+            self.connection
+                .initialize(|client_initialization_params: &InitializeParams| {
+                    *experimental = ServerConfig::get_initialization_experimental(
+                        client_initialization_params.initialization_options.as_ref(),
+                    );
+                    get_server_capabilities(experimental)
+                });
         debug!(
             "Received client params: {:#?}",
             client_initialization_params
         );
+        // Handlers check the same flags as the advertised capabilities.
+        for language_data in self.language_data.values_mut() {
+            language_data.config.experimental = self.experimental.clone();
+        }
+        if let Ok(InitializeParams {
+            trace: Some(trace), ..
+        }) = &client_initialization_params
+        {
+            self.connection.set_trace(*trace);
+        }
 
         self.request_configuration();
+        // Includes edited outside the editor are only known through file events.
+        let can_watch_files = client_initialization_params.is_ok_and(|params| {
+            params
+                .capabilities
+                .workspace
+                .and_then(|workspace| workspace.did_change_watched_files)
+                .and_then(|capability| capability.dynamic_registration)
+                .unwrap_or(false)
+        });
+        if can_watch_files {
+            self.register_file_watcher();
+        }
 
         return Ok(());
     }
     pub fn run(&mut self) -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
         loop {
-            let msg_err = self.connection.connection.receiver.recv();
-            match msg_err {
-                Ok(msg) => match msg {
-                    Message::Request(req) => {
-                        if self.connection.connection.handle_shutdown(&req)? {
-                            return Ok(());
+            // Handle pending messages first, so that a config change cancel validation early.
+            let msg_err = match self.connection.connection.receiver.try_recv() {
+                Ok(msg) => Ok(msg),
+                Err(crossbeam_channel::TryRecvError::Disconnected) => return Ok(()),
+                Err(crossbeam_channel::TryRecvError::Empty) => crossbeam_channel::select! {
+                    recv(self.connection.connection.receiver) -> msg => msg,
+                    recv(self.validation_pool.get_receiver()) -> result => {
+                        if let Ok(result) = result {
+                            self.on_validation_result(result);
                         }
-                        self.on_request(req)?;
-                    }
-                    Message::Response(resp) => {
-                        self.on_response(resp)?;
-                    }
-                    Message::Notification(not) => {
-                        self.on_notification(not)?;
+                        continue;
                     }
                 },
+            };
+            match msg_err {
+                Ok(msg) => {
+                    self.connection.trace_message(&msg, true);
+                    match msg {
+                        Message::Request(req) => {
+                            if self.connection.connection.handle_shutdown(&req)? {
+                                return Ok(());
+                            }
+                            self.on_request(req)?;
+                        }
+                        Message::Response(resp) => {
+                            self.on_response(resp)?;
+                        }
+                        Message::Notification(not) => {
+                            self.on_notification(not)?;
+                        }
+                    }
+                }
                 Err(_) => {
                     // Recv error means disconnected.
                     return Ok(());
@@ -146,6 +296,33 @@ impl ServerLanguage {
             }
         }
     }
+    // Publish diagnostics validated in background, on the connection thread.
+    fn on_validation_result(&mut self, result: ValidationResult) {
+        if !self.validation_pool.accept(&result) {
+            return;
+        }
+        let language_data = match self.language_data.get_mut(&result.shading_language) {
+            Some(language_data) => language_data,
+            None => return,
+        };
+        // File might have been closed meanwhile.
+        let cached_file = match language_data.watched_files.get(&result.uri) {
+            Some(cached_file) => cached_file,
+            None => return,
+        };
+        // File was edited after submission, its diagnostics are already outdated.
+        if hash_content(&RefCell::borrow(&cached_file).symbol_tree.content) != result.content_hash {
+            debug!("Dropping outdated validation of {}", result.uri);
+            return;
+        }
+        language_data.publish_validation_result(
+            &self.connection,
+            &result.uri,
+            &cached_file,
+            result.result,
+        );
+        language_data.index_file(&result.uri, &cached_file);
+    }
     fn on_request(&mut self, req: lsp_server::Request) -> Result<(), serde_json::Error> {
         match req.method.as_str() {
             DocumentDiagnosticRequest::METHOD => {
@@ -161,7 +338,15 @@ impl ServerLanguage {
                           _shading_language: ShadingLanguage,
                           language_data: &mut ServerLanguageData,
                           cached_file: ServerFileCacheHandle| {
-                        match language_data.recolt_diagnostic(&uri, &cached_file) {
+                        // Diagnostics are pushed by the server, send an empty report.
+                        let diagnostics = if language_data.config.diagnosticMode.is_pull() {
+                            language_data.recolt_diagnostic(&uri, &cached_file)
+                        } else {
+                            Ok(HashMap::new())
+                        };
+                        // Validation might have found new dependencies.
+                        language_data.index_file(&uri, &cached_file);
+                        match diagnostics {
                             Ok(mut diagnostics) => {
                                 let main_diagnostic = match diagnostics.remove(&uri) {
                                     Some(diag) => diag,
@@ -234,6 +419,86 @@ impl ServerLanguage {
                     },
                 );
             }
+            References::METHOD => {
+                let params: ReferenceParams = serde_json::from_value(req.params)?;
+                debug!("Received references request #{}: {:#?}", req.id, params);
+                let uri = clean_url(&params.text_document_position.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        match language_data.recolt_references(
+                            &uri,
+                            Rc::clone(&cached_file),
+                            params.text_document_position.position,
+                            params.context.include_declaration,
+                        ) {
+                            Ok(value) => {
+                                connection.send_response::<References>(req.id.clone(), value)
+                            }
+                            Err(err) => connection.send_response_error(
+                                req.id.clone(),
+                                ErrorCode::InvalidParams,
+                                format!("Failed to recolt references : {:#?}", err),
+                            ),
+                        }
+                    },
+                );
+            }
+            PrepareRenameRequest::METHOD => {
+                let params: TextDocumentPositionParams = serde_json::from_value(req.params)?;
+                debug!("Received prepare rename request #{}: {:#?}", req.id, params);
+                let uri = clean_url(&params.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        match language_data.recolt_prepare_rename(
+                            &uri,
+                            Rc::clone(&cached_file),
+                            params.position,
+                        ) {
+                            Ok(value) => connection
+                                .send_response::<PrepareRenameRequest>(req.id.clone(), value),
+                            Err(err) => connection.send_response_error(
+                                req.id.clone(),
+                                ErrorCode::InvalidParams,
+                                format!("Failed to prepare rename : {}", err),
+                            ),
+                        }
+                    },
+                );
+            }
+            Rename::METHOD => {
+                let params: RenameParams = serde_json::from_value(req.params)?;
+                debug!("Received rename request #{}: {:#?}", req.id, params);
+                let uri = clean_url(&params.text_document_position.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        match language_data.recolt_rename(
+                            &uri,
+                            Rc::clone(&cached_file),
+                            params.text_document_position.position,
+                            params.new_name.clone(),
+                        ) {
+                            Ok(value) => connection.send_response::<Rename>(req.id.clone(), value),
+                            Err(err) => connection.send_response_error(
+                                req.id.clone(),
+                                ErrorCode::InvalidParams,
+                                format!("Failed to rename : {}", err),
+                            ),
+                        }
+                    },
+                );
+            }
             Completion::METHOD => {
                 let params: CompletionParams = serde_json::from_value(req.params)?;
                 debug!("Received completion request #{}: {:#?}", req.id, params);
@@ -255,7 +520,7 @@ impl ServerLanguage {
                         ) {
                             Ok(value) => connection.send_response::<Completion>(
                                 req.id.clone(),
-                                Some(CompletionResponse::Array(value)),
+                                Some(CompletionResponse::List(value)),
                             ),
                             Err(error) => connection.send_response_error(
                                 req.id.clone(),
@@ -316,6 +581,262 @@ impl ServerLanguage {
                     },
                 );
             }
+            Formatting::METHOD => {
+                let params: DocumentFormattingParams = serde_json::from_value(req.params)?;
+                debug!("Received formatting request #{}: {:#?}", req.id, params);
+                let uri = clean_url(&params.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        if !language_data
+                            .config
+                            .is_experimental_enabled(EXPERIMENTAL_FORMATTING)
+                        {
+                            return connection.send_response::<Formatting>(req.id.clone(), None);
+                        }
+                        match language_data.recolt_formatting(
+                            &uri,
+                            Rc::clone(&cached_file),
+                            params.options.clone(),
+                            None,
+                        ) {
+                            Ok(value) => {
+                                connection.send_response::<Formatting>(req.id.clone(), value)
+                            }
+                            Err(err) => connection.send_response_error(
+                                req.id.clone(),
+                                ErrorCode::InvalidParams,
+                                format!("Failed to recolt formatting : {:#?}", err),
+                            ),
+                        }
+                    },
+                );
+            }
+            RangeFormatting::METHOD => {
+                let params: DocumentRangeFormattingParams = serde_json::from_value(req.params)?;
+                debug!(
+                    "Received range formatting request #{}: {:#?}",
+                    req.id, params
+                );
+                let uri = clean_url(&params.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        if !language_data
+                            .config
+                            .is_experimental_enabled(EXPERIMENTAL_FORMATTING)
+                        {
+                            return connection
+                                .send_response::<RangeFormatting>(req.id.clone(), None);
+                        }
+                        match language_data.recolt_formatting(
+                            &uri,
+                            Rc::clone(&cached_file),
+                            params.options.clone(),
+                            Some(params.range),
+                        ) {
+                            Ok(value) => {
+                                connection.send_response::<RangeFormatting>(req.id.clone(), value)
+                            }
+                            Err(err) => connection.send_response_error(
+                                req.id.clone(),
+                                ErrorCode::InvalidParams,
+                                format!("Failed to recolt formatting : {:#?}", err),
+                            ),
+                        }
+                    },
+                );
+            }
+            DocumentSymbolRequest::METHOD => {
+                let params: DocumentSymbolParams = serde_json::from_value(req.params)?;
+                debug!(
+                    "Received document symbol request #{}: {:#?}",
+                    req.id, params
+                );
+                let uri = clean_url(&params.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        match language_data.recolt_document_symbol(&uri, Rc::clone(&cached_file)) {
+                            Ok(symbols) => connection.send_response::<DocumentSymbolRequest>(
+                                req.id.clone(),
+                                Some(DocumentSymbolResponse::Nested(symbols)),
+                            ),
+                            Err(err) => connection.send_response_error(
+                                req.id.clone(),
+                                ErrorCode::InvalidParams,
+                                format!("Failed to recolt document symbols : {:#?}", err),
+                            ),
+                        }
+                    },
+                );
+            }
+            DocumentLinkRequest::METHOD => {
+                let params: DocumentLinkParams = serde_json::from_value(req.params)?;
+                debug!("Received document link request #{}: {:#?}", req.id, params);
+                let uri = clean_url(&params.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        match language_data.recolt_document_link(&uri, Rc::clone(&cached_file)) {
+                            Ok(links) => connection
+                                .send_response::<DocumentLinkRequest>(req.id.clone(), Some(links)),
+                            Err(err) => connection.send_response_error(
+                                req.id.clone(),
+                                ErrorCode::InvalidParams,
+                                format!("Failed to recolt document links : {:#?}", err),
+                            ),
+                        }
+                    },
+                );
+            }
+            SemanticTokensFullRequest::METHOD => {
+                let params: SemanticTokensParams = serde_json::from_value(req.params)?;
+                debug!(
+                    "Received semantic tokens request #{}: {:#?}",
+                    req.id, params
+                );
+                let uri = clean_url(&params.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        if !language_data
+                            .config
+                            .is_experimental_enabled(EXPERIMENTAL_SEMANTIC_TOKENS)
+                        {
+                            return connection
+                                .send_response::<SemanticTokensFullRequest>(req.id.clone(), None);
+                        }
+                        match language_data.recolt_semantic_tokens(Rc::clone(&cached_file)) {
+                            Ok(tokens) => connection.send_response::<SemanticTokensFullRequest>(
+                                req.id.clone(),
+                                Some(SemanticTokensResult::Tokens(tokens)),
+                            ),
+                            Err(err) => connection.send_response_error(
+                                req.id.clone(),
+                                ErrorCode::InvalidParams,
+                                format!("Failed to recolt semantic tokens : {:#?}", err),
+                            ),
+                        }
+                    },
+                );
+            }
+            FoldingRangeRequest::METHOD => {
+                let params: FoldingRangeParams = serde_json::from_value(req.params)?;
+                debug!("Received folding range request #{}: {:#?}", req.id, params);
+                let uri = clean_url(&params.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        match language_data.recolt_folding_range(Rc::clone(&cached_file)) {
+                            Ok(ranges) => connection
+                                .send_response::<FoldingRangeRequest>(req.id.clone(), Some(ranges)),
+                            Err(err) => connection.send_response_error(
+                                req.id.clone(),
+                                ErrorCode::InvalidParams,
+                                format!("Failed to recolt folding ranges : {:#?}", err),
+                            ),
+                        }
+                    },
+                );
+            }
+            InlayHintRequest::METHOD => {
+                let params: InlayHintParams = serde_json::from_value(req.params)?;
+                debug!("Received inlay hint request #{}: {:#?}", req.id, params);
+                let uri = clean_url(&params.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        if !language_data
+                            .config
+                            .is_experimental_enabled(EXPERIMENTAL_INLAY_HINTS)
+                        {
+                            return connection
+                                .send_response::<InlayHintRequest>(req.id.clone(), None);
+                        }
+                        match language_data.recolt_inlay_hint(Rc::clone(&cached_file), params.range)
+                        {
+                            Ok(hints) => connection
+                                .send_response::<InlayHintRequest>(req.id.clone(), Some(hints)),
+                            Err(err) => connection.send_response_error(
+                                req.id.clone(),
+                                ErrorCode::InvalidParams,
+                                format!("Failed to recolt inlay hints : {:#?}", err),
+                            ),
+                        }
+                    },
+                );
+            }
+            WorkspaceSymbolRequest::METHOD => {
+                let params: WorkspaceSymbolParams = serde_json::from_value(req.params)?;
+                debug!(
+                    "Received workspace symbol request #{}: {:#?}",
+                    req.id, params
+                );
+                // Headers included from several languages are indexed once per language.
+                let mut visited = HashSet::new();
+                let symbols = self
+                    .language_data
+                    .values()
+                    .flat_map(|language_data| language_data.recolt_workspace_symbol(&params.query))
+                    .filter(|symbol| {
+                        visited.insert((
+                            symbol.location.uri.clone(),
+                            symbol.name.clone(),
+                            symbol.location.range.start,
+                        ))
+                    })
+                    .collect();
+                self.connection.send_response::<WorkspaceSymbolRequest>(
+                    req.id.clone(),
+                    Some(WorkspaceSymbolResponse::Flat(symbols)),
+                );
+            }
+            CodeActionRequest::METHOD => {
+                let params: CodeActionParams = serde_json::from_value(req.params)?;
+                debug!("Received code action request #{}: {:#?}", req.id, params);
+                let uri = clean_url(&params.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          _cached_file: ServerFileCacheHandle| {
+                        if !language_data
+                            .config
+                            .is_experimental_enabled(EXPERIMENTAL_CODE_ACTIONS)
+                        {
+                            return connection
+                                .send_response::<CodeActionRequest>(req.id.clone(), None);
+                        }
+                        let actions: CodeActionResponse =
+                            language_data.recolt_code_action(&uri, &params.context.diagnostics);
+                        connection
+                            .send_response::<CodeActionRequest>(req.id.clone(), Some(actions));
+                    },
+                );
+            }
             // Debug request
             DumpAstRequest::METHOD => {
                 let params: DumpAstParams = serde_json::from_value(req.params)?;
@@ -332,6 +853,54 @@ impl ServerLanguage {
                     },
                 );
             }
+            DependenciesRequest::METHOD => {
+                let params: DependenciesParams = serde_json::from_value(req.params)?;
+                debug!("Received dependencies request #{}: {:#?}", req.id, params);
+                let uri = clean_url(&params.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        let dependencies = language_data.recolt_dependencies(&cached_file);
+                        connection
+                            .send_response::<DependenciesRequest>(req.id.clone(), dependencies);
+                    },
+                );
+            }
+            SymbolOriginRequest::METHOD => {
+                let params: SymbolOriginParams = serde_json::from_value(req.params)?;
+                debug!("Received symbol origin request #{}: {:#?}", req.id, params);
+                let uri = clean_url(&params.text_document.uri);
+                self.visit_watched_file(
+                    &uri,
+                    &mut |connection: &mut ServerConnection,
+                          _shading_language: ShadingLanguage,
+                          language_data: &mut ServerLanguageData,
+                          cached_file: ServerFileCacheHandle| {
+                        match language_data.recolt_symbol_origin(&uri, cached_file, params.position)
+                        {
+                            Ok(origin) => connection
+                                .send_response::<SymbolOriginRequest>(req.id.clone(), origin),
+                            Err(err) => connection.send_response_error(
+                                req.id.clone(),
+                                ErrorCode::InvalidParams,
+                                format!("Failed to recolt symbol origin : {:#?}", err),
+                            ),
+                        }
+                    },
+                );
+            }
+            StatusRequest::METHOD => {
+                debug!("Received status request #{}", req.id);
+                let status = get_server_status(
+                    &self.language_data,
+                    self.validation_pool.get_pending_count(),
+                );
+                self.connection
+                    .send_response::<StatusRequest>(req.id.clone(), status);
+            }
             _ => warn!("Received unhandled request: {:#?}", req),
         }
         Ok(())
@@ -365,8 +934,12 @@ impl ServerLanguage {
                     ));
                     return Ok(());
                 }
-                match ShadingLanguage::from_str(params.text_document.language_id.as_str()) {
-                    Ok(shading_language) => match self.language_data.get_mut(&shading_language) {
+                match self.get_shading_language(
+                    &uri,
+                    &params.text_document.language_id,
+                    &params.text_document.text,
+                ) {
+                    Some(shading_language) => match self.language_data.get_mut(&shading_language) {
                         Some(language_data) => {
                             match language_data.watched_files.watch_file(
                                 &uri,
@@ -379,12 +952,19 @@ impl ServerLanguage {
                                     // Dont care if we replace file_language input.
                                     self.file_language
                                         .insert(uri.clone(), shading_language.clone());
-                                    language_data.publish_diagnostic(
-                                        &self.connection,
-                                        &uri,
-                                        &cached_file,
-                                        Some(params.text_document.version),
-                                    );
+                                    // Symbols are still parsed for completion & others.
+                                    if language_data.config.deferOpenValidation {
+                                        debug!("Deferring validation of {}", uri);
+                                        language_data.deferred_files.insert(uri.clone());
+                                    } else {
+                                        language_data.publish_diagnostic(
+                                            &self.connection,
+                                            &uri,
+                                            &cached_file,
+                                            Some(params.text_document.version),
+                                        );
+                                    }
+                                    language_data.index_file(&uri, &cached_file);
                                 }
                                 Err(_) => self.connection.send_notification_error(format!(
                                     "Failed to watch file {}",
@@ -397,7 +977,7 @@ impl ServerLanguage {
                             shading_language.to_string()
                         )),
                     },
-                    Err(_) => self.connection.send_notification_error(format!(
+                    None => self.connection.send_notification_error(format!(
                         "Failed to parse language id : {}",
                         params.text_document.language_id
                     )),
@@ -408,6 +988,14 @@ impl ServerLanguage {
                     serde_json::from_value(notification.params)?;
                 let uri = clean_url(&params.text_document.uri);
                 debug!("got did save text document: {:#?}", uri);
+                if let Some(config_path) = uri
+                    .to_file_path()
+                    .ok()
+                    .filter(|file_path| ShaderConfigFile::is_shader_config_file(file_path))
+                {
+                    self.reload_shader_config_file(&config_path);
+                    return Ok(());
+                }
                 // File content is updated through DidChangeTextDocument.
                 self.visit_watched_file(
                     &uri,
@@ -432,6 +1020,7 @@ impl ServerLanguage {
                             Err(err) => connection.send_notification_error(format!("{}", err)),
                         };
                         language_data.publish_diagnostic(connection, &uri, &cached_file, None);
+                        language_data.index_file(&uri, &cached_file);
                     },
                 );
             }
@@ -450,9 +1039,12 @@ impl ServerLanguage {
                         match language_data.watched_files.remove_file(&uri) {
                             Ok(was_removed) => {
                                 if was_removed {
+                                    language_data.deferred_files.remove(&uri);
+                                    language_data.remove_dependents(&uri);
                                     language_data.clear_diagnostic(connection, &uri);
                                     is_removed = true;
                                 }
+                                language_data.evict_unwatched_files();
                             }
                             Err(err) => connection.send_notification_error(format!("{}", err)),
                         }
@@ -491,9 +1083,79 @@ impl ServerLanguage {
                             &cached_file,
                             Some(params.text_document.version),
                         );
+                        language_data.index_file(&uri, &cached_file);
                     },
                 );
             }
+            DidChangeWatchedFiles::METHOD => {
+                let params: DidChangeWatchedFilesParams =
+                    serde_json::from_value(notification.params)?;
+                debug!("Received did change watched files: {:#?}", params);
+                // Parsed trees & symbols of changed files are stale.
+                for change in &params.changes {
+                    if let Ok(file_path) = change.uri.to_file_path() {
+                        for language_data in self.language_data.values_mut() {
+                            language_data
+                                .symbol_provider
+                                .invalidate_ast_cache(&file_path);
+                            language_data.refresh_dependency(
+                                &clean_url(&change.uri),
+                                change.typ == FileChangeType::DELETED,
+                            );
+                        }
+                    }
+                }
+                for change in &params.changes {
+                    if let Ok(file_path) = change.uri.to_file_path() {
+                        if ShaderConfigFile::is_shader_config_file(&file_path) {
+                            self.reload_shader_config_file(&file_path);
+                        }
+                    }
+                }
+                // Created or deleted files might change include resolution.
+                if params
+                    .changes
+                    .iter()
+                    .any(|change| change.typ != FileChangeType::CHANGED)
+                {
+                    IncludeHandler::clear_cache();
+                }
+                // Revalidate opened files including a changed file.
+                for language_data in self.language_data.values_mut() {
+                    let mut dependents: Vec<Url> = params
+                        .changes
+                        .iter()
+                        .filter_map(|change| change.uri.to_file_path().ok())
+                        .flat_map(|file_path| language_data.get_dependents(&file_path))
+                        .collect();
+                    dependents.sort();
+                    dependents.dedup();
+                    for uri in dependents {
+                        let cached_file = match language_data.watched_files.get(&uri) {
+                            Some(cached_file) => cached_file,
+                            None => continue,
+                        };
+                        info!("Revalidating {} as one of its includes changed", uri);
+                        match RefCell::borrow_mut(&cached_file).update(
+                            &uri,
+                            &mut language_data.symbol_provider,
+                            &language_data.config,
+                            None,
+                            None,
+                        ) {
+                            Ok(_) => {}
+                            Err(err) => self.connection.send_notification_error(format!("{}", err)),
+                        };
+                        language_data.publish_diagnostic(
+                            &self.connection,
+                            &uri,
+                            &cached_file,
+                            None,
+                        );
+                        language_data.index_file(&uri, &cached_file);
+                    }
+                }
+            }
             DidChangeConfiguration::METHOD => {
                 let params: DidChangeConfigurationParams =
                     serde_json::from_value(notification.params)?;
@@ -502,6 +1164,11 @@ impl ServerLanguage {
                 //let config : ServerConfig = serde_json::from_value(params.settings)?;
                 self.request_configuration();
             }
+            SetTrace::METHOD => {
+                let params: SetTraceParams = serde_json::from_value(notification.params)?;
+                debug!("Received set trace: {:#?}", params);
+                self.connection.set_trace(params.value);
+            }
             _ => info!("Received unhandled notification: {:#?}", notification),
         }
         Ok(())
@@ -543,6 +1210,101 @@ impl ServerLanguage {
             )),
         };
     }
+    // A language pragma take precedence over explicit file associations,
+    // which take precedence over the language id reported by the client.
+    // Content detection is used as a last resort.
+    fn get_shading_language(
+        &self,
+        uri: &Url,
+        language_id: &str,
+        content: &str,
+    ) -> Option<ShadingLanguage> {
+        if let Some(pragma_language) = get_language_pragma(content) {
+            return Some(pragma_language);
+        }
+        let reported_language = ShadingLanguage::from_str(language_id).ok();
+        // All languages share the same config.
+        let config = self
+            .language_data
+            .values()
+            .next()
+            .map(|language_data| &language_data.config);
+        let associated_language =
+            config.and_then(|config| config.get_file_association(&uri.to_file_path().ok()?));
+        match associated_language {
+            Some(associated_language) => {
+                if reported_language.is_some_and(|language| language != associated_language) {
+                    debug!(
+                        "File {} reported as {} but associated to {}",
+                        uri,
+                        language_id,
+                        associated_language.to_string()
+                    );
+                }
+                Some(associated_language)
+            }
+            None => reported_language.or_else(|| config?.detect_language(content)),
+        }
+    }
+    // Reload a shader config file & republish diagnostics of files it applies to.
+    fn reload_shader_config_file(&mut self, config_path: &Path) {
+        info!("Reloading shader config file {}", config_path.display());
+        let config_directory = config_path.parent().unwrap_or(Path::new("."));
+        for language_data in self.language_data.values_mut() {
+            language_data.shader_config_files.remove(config_path);
+            let affected_files: Vec<(Url, ServerFileCacheHandle)> = language_data
+                .watched_files
+                .files
+                .iter()
+                .filter(|(uri, _)| {
+                    uri.to_file_path()
+                        .is_ok_and(|file_path| file_path.starts_with(config_directory))
+                        && !language_data.deferred_files.contains(uri)
+                })
+                .map(|(uri, cached_file)| (uri.clone(), Rc::clone(cached_file)))
+                .collect();
+            for (uri, cached_file) in affected_files {
+                language_data.publish_diagnostic(&self.connection, &uri, &cached_file, None);
+                language_data.index_file(&uri, &cached_file);
+            }
+        }
+    }
+    fn warn_missing_includes(&mut self, config: &ServerConfig) {
+        let missing_includes: Vec<String> = config
+            .get_missing_includes()
+            .into_iter()
+            .filter(|include| self.warned_missing_includes.insert(include.clone()))
+            .collect();
+        if !missing_includes.is_empty() {
+            self.connection.send_notification_warning(format!(
+                "Include directories not found: {}",
+                missing_includes.join(", ")
+            ));
+        }
+    }
+    fn register_file_watcher(&mut self) {
+        let watchers = [WATCHED_FILES_GLOB, SHADER_CONFIG_FILES_GLOB]
+            .iter()
+            .map(|glob_pattern| FileSystemWatcher {
+                glob_pattern: GlobPattern::String(glob_pattern.to_string()),
+                kind: None,
+            })
+            .collect();
+        let registration = Registration {
+            id: "shader-validator-file-watcher".into(),
+            method: DidChangeWatchedFiles::METHOD.into(),
+            register_options: serde_json::to_value(DidChangeWatchedFilesRegistrationOptions {
+                watchers,
+            })
+            .ok(),
+        };
+        self.connection.send_request::<RegisterCapability>(
+            RegistrationParams {
+                registrations: vec![registration],
+            },
+            |_server: &mut ServerLanguage, _value: Value| {},
+        );
+    }
     fn request_configuration(&mut self) {
         let config = ConfigurationParams {
             items: vec![lsp_types::ConfigurationItem {
@@ -556,13 +1318,33 @@ impl ServerLanguage {
                 // Sent 1 item, received 1 in an array
                 let mut parsed_config: Vec<ServerConfig> =
                     serde_json::from_value(value).expect("Failed to parse received config");
-                let config = parsed_config.remove(0);
+                let mut config = parsed_config.remove(0);
+                // Capabilities can't change after initialization, so flags are only read from there.
+                if config.experimental != server.experimental {
+                    warn!("Experimental features are only read from the initialization options, restart the server to change them.");
+                }
+                config.experimental = server.experimental.clone();
                 info!("Updating server config: {:#?}", config);
-                for (_language, language_data) in &mut server.language_data {
+                server.warn_missing_includes(&config);
+                // Includes might have changed.
+                IncludeHandler::clear_cache();
+                let mut validation_jobs = Vec::new();
+                for (language, language_data) in &mut server.language_data {
+                    let is_symbol_roots_changed =
+                        language_data.config.workspaceSymbolRoots != config.workspaceSymbolRoots;
                     language_data.config = config.clone();
+                    if is_symbol_roots_changed {
+                        language_data.scan_workspace_symbol_roots(*language);
+                    }
+                    if *language == ShadingLanguage::Hlsl {
+                        language_data.set_hlsl_backend(config.hlsl.backend);
+                    }
+                    language_data
+                        .symbol_provider
+                        .set_ast_cache_capacity(config.get_ast_cache_capacity());
                     // Republish all diagnostics
                     for (url, cached_file) in &language_data.watched_files.files {
-                        // Clear diags
+                        // Clear diags. Required for stale pushed diags when switching to pull mode.
                         language_data.clear_diagnostic(&server.connection, &url);
                         // Update symbols & republish diags.
                         match RefCell::borrow_mut(&cached_file).update(
@@ -577,11 +1359,64 @@ impl ServerLanguage {
                                 .connection
                                 .send_notification_error(format!("{}", err)),
                         };
+                        language_data
+                            .symbol_index
+                            .update_file(url, &RefCell::borrow(cached_file));
+                    }
+                    if !language_data.config.diagnosticMode.is_push() {
+                        continue;
+                    }
+                    let files: Vec<(Url, ServerFileCacheHandle)> = language_data
+                        .watched_files
+                        .files
+                        .iter()
+                        .map(|(uri, cached_file)| (uri.clone(), Rc::clone(cached_file)))
+                        .collect();
+                    for (uri, cached_file) in files {
+                        // Files opened with deferred validation wait for their first edit.
+                        if language_data.config.deferOpenValidation
+                            && language_data.deferred_files.contains(&uri)
+                        {
+                            continue;
+                        }
+                        language_data.deferred_files.remove(&uri);
+                        match language_data.get_validation_job(&uri, &cached_file) {
+                            Some(validation_job) => validation_jobs.push(validation_job),
+                            None => language_data.publish_diagnostic(
+                                &server.connection,
+                                &uri,
+                                &cached_file,
+                                None,
+                            ),
+                        }
                     }
                 }
+                // Validate in background, cancelling the batch of a previous config change.
+                let contents = server.get_watched_contents();
+                server.validation_pool.submit(validation_jobs, contents);
             },
         );
     }
+    // Content of all watched files, opened ones being possibly unsaved.
+    fn get_watched_contents(&self) -> HashMap<PathBuf, String> {
+        self.language_data
+            .values()
+            .flat_map(|language_data| {
+                language_data
+                    .watched_files
+                    .dependencies
+                    .iter()
+                    .chain(language_data.watched_files.files.iter())
+            })
+            .filter_map(|(uri, cached_file)| {
+                let file_path = uri.to_file_path().ok()?;
+                Some((
+                    file_path,
+                    RefCell::borrow(cached_file).symbol_tree.content.clone(),
+                ))
+            })
+            .collect()
+    }
 }
 
 pub fn run() {
@@ -602,3 +1437,768 @@ pub fn run() {
         Err(value) => error!("Server failed to join threads: {:#?}", value),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use lsp_server::{Connection, Notification as LspNotification, Request as LspRequest};
+    use lsp_types::{
+        notification::{Initialized, LogTrace, PublishDiagnostics, ShowMessage},
+        request::Initialize,
+        Diagnostic, LogTraceParams, MessageType, PublishDiagnosticsParams, ShowMessageParams,
+        TraceValue,
+    };
+
+    use super::*;
+    use crate::server::server_config::ServerDiagnosticMode;
+
+    fn run_messages(messages: Vec<Message>) -> Vec<Message> {
+        let (connection, client) = ServerConnection::memory();
+        let mut server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::from([(ShadingLanguage::Glsl, ServerLanguageData::glsl())]),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
+        };
+        for message in messages {
+            client.sender.send(message).unwrap();
+        }
+        // Closing the client end stop the server loop once all messages are handled.
+        drop(client.sender);
+        server.run().unwrap();
+        client.receiver.try_iter().collect()
+    }
+
+    fn status_request(id: i32) -> Message {
+        LspRequest::new(id.into(), StatusRequest::METHOD.into(), ()).into()
+    }
+
+    #[test]
+    fn set_trace_log_messages() {
+        let messages = run_messages(vec![
+            status_request(0),
+            LspNotification::new(
+                SetTrace::METHOD.into(),
+                SetTraceParams {
+                    value: TraceValue::Verbose,
+                },
+            )
+            .into(),
+            status_request(1),
+        ]);
+        let traces: Vec<LogTraceParams> = messages
+            .into_iter()
+            .filter_map(|message| match message {
+                Message::Notification(not) if not.method == LogTrace::METHOD => {
+                    Some(serde_json::from_value(not.params).unwrap())
+                }
+                _ => None,
+            })
+            .collect();
+        // First request is sent before tracing is enabled.
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].message, "Received request 'shader/status - (1)'.");
+        assert_eq!(traces[1].message, "Sending response '(1)'.");
+        assert!(traces.iter().all(|trace| trace.verbose.is_some()));
+    }
+
+    #[test]
+    fn file_association_override_language_id() {
+        let (connection, _client) = ServerConnection::memory();
+        let mut language_data = ServerLanguageData::glsl();
+        language_data.config.fileAssociations =
+            HashMap::from([("fx".into(), ShadingLanguage::Hlsl)]);
+        let server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::from([(ShadingLanguage::Glsl, language_data)]),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
+        };
+        let effect_uri = Url::parse("file:///shaders/effect.fx").unwrap();
+        let shader_uri = Url::parse("file:///shaders/shader.frag").unwrap();
+        assert_eq!(
+            server.get_shading_language(&effect_uri, "glsl", ""),
+            Some(ShadingLanguage::Hlsl)
+        );
+        assert_eq!(
+            server.get_shading_language(&effect_uri, "plaintext", ""),
+            Some(ShadingLanguage::Hlsl)
+        );
+        assert_eq!(
+            server.get_shading_language(&shader_uri, "glsl", ""),
+            Some(ShadingLanguage::Glsl)
+        );
+        assert_eq!(
+            server.get_shading_language(&shader_uri, "plaintext", ""),
+            None
+        );
+    }
+
+    #[test]
+    fn language_pragma_override_extension() {
+        let (connection, _client) = ServerConnection::memory();
+        let mut language_data = ServerLanguageData::glsl();
+        language_data.config.fileAssociations =
+            HashMap::from([("shader".into(), ShadingLanguage::Glsl)]);
+        language_data.config.languageDetectionOrder = vec![ShadingLanguage::Wgsl];
+        let server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::from([(ShadingLanguage::Glsl, language_data)]),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
+        };
+        let uri = Url::parse("file:///shaders/effect.shader").unwrap();
+        let hlsl_content = "// lang: HLSL\nfloat4 main() : SV_TARGET { return 0; }\n";
+        assert_eq!(
+            server.get_shading_language(&uri, "glsl", hlsl_content),
+            Some(ShadingLanguage::Hlsl)
+        );
+        assert_eq!(
+            server.get_shading_language(&uri, "glsl", "void main() {}\n"),
+            Some(ShadingLanguage::Glsl)
+        );
+        // Pragma must be on first line.
+        assert_eq!(
+            server.get_shading_language(&uri, "glsl", "\n// lang: hlsl\n"),
+            Some(ShadingLanguage::Glsl)
+        );
+        let unknown_uri = Url::parse("file:///shaders/effect.txt").unwrap();
+        assert_eq!(
+            server.get_shading_language(&unknown_uri, "plaintext", "fn main() {}\n"),
+            Some(ShadingLanguage::Wgsl)
+        );
+    }
+
+    #[test]
+    fn missing_includes_warning() {
+        let (connection, client) = ServerConnection::memory();
+        let mut server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::new(),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
+        };
+        let config = ServerConfig {
+            includes: vec![".".into(), "./missing-include-dir".into()],
+            ..Default::default()
+        };
+        server.warn_missing_includes(&config);
+        // Already reported, no new warning.
+        server.warn_missing_includes(&config);
+        let warnings: Vec<ShowMessageParams> = client
+            .receiver
+            .try_iter()
+            .filter_map(|message| match message {
+                Message::Notification(not) if not.method == ShowMessage::METHOD => {
+                    Some(serde_json::from_value(not.params).unwrap())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].typ, MessageType::WARNING);
+        assert_eq!(
+            warnings[0].message,
+            "Include directories not found: ./missing-include-dir"
+        );
+    }
+
+    fn initialize_capabilities(initialization_options: Value) -> ServerCapabilities {
+        let (connection, client) = ServerConnection::memory();
+        let mut server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::new(),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
+        };
+        let params = serde_json::json!({
+            "capabilities": {},
+            "initializationOptions": initialization_options,
+        });
+        client
+            .sender
+            .send(LspRequest::new(1.into(), Initialize::METHOD.into(), params).into())
+            .unwrap();
+        client
+            .sender
+            .send(LspNotification::new(Initialized::METHOD.into(), serde_json::json!({})).into())
+            .unwrap();
+        server.initialize().unwrap();
+        let result = client
+            .receiver
+            .try_iter()
+            .find_map(|message| match message {
+                Message::Response(response) => response.result,
+                _ => None,
+            })
+            .unwrap();
+        serde_json::from_value(result["capabilities"].clone()).unwrap()
+    }
+
+    #[test]
+    fn experimental_capabilities() {
+        let capabilities = initialize_capabilities(serde_json::json!({}));
+        assert!(capabilities.semantic_tokens_provider.is_none());
+        assert!(capabilities.document_formatting_provider.is_none());
+        assert!(capabilities.hover_provider.is_some());
+        let capabilities = initialize_capabilities(serde_json::json!({
+            "experimental": [EXPERIMENTAL_SEMANTIC_TOKENS],
+        }));
+        assert!(capabilities.semantic_tokens_provider.is_some());
+        assert!(capabilities.document_formatting_provider.is_none());
+        assert!(capabilities.inlay_hint_provider.is_none());
+        assert!(capabilities.code_action_provider.is_none());
+        let capabilities = initialize_capabilities(serde_json::json!({
+            "experimental": [EXPERIMENTAL_INLAY_HINTS, EXPERIMENTAL_CODE_ACTIONS],
+        }));
+        assert!(capabilities.inlay_hint_provider.is_some());
+        assert!(capabilities.code_action_provider.is_some());
+        assert!(capabilities.semantic_tokens_provider.is_none());
+    }
+
+    #[test]
+    fn experimental_from_initialization() {
+        let (connection, _client) = ServerConnection::memory();
+        let mut server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::from([(ShadingLanguage::Hlsl, ServerLanguageData::hlsl())]),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::from([EXPERIMENTAL_FORMATTING.to_string()]),
+            validation_pool: ValidationPool::default(),
+        };
+        // Workspace setting differs from the advertised capabilities.
+        server.request_configuration();
+        let config = ServerConfig {
+            experimental: HashSet::from([EXPERIMENTAL_INLAY_HINTS.to_string()]),
+            ..Default::default()
+        };
+        server
+            .on_response(lsp_server::Response::new_ok(
+                0.into(),
+                serde_json::to_value(vec![config]).unwrap(),
+            ))
+            .unwrap();
+        let config = &server.language_data[&ShadingLanguage::Hlsl].config;
+        assert!(config.is_experimental_enabled(EXPERIMENTAL_FORMATTING));
+        assert!(!config.is_experimental_enabled(EXPERIMENTAL_INLAY_HINTS));
+    }
+
+    #[test]
+    fn config_change_cancel_validation() {
+        let (connection, client) = ServerConnection::memory();
+        let mut server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::from([(ShadingLanguage::Glsl, ServerLanguageData::glsl())]),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
+        };
+        let uri = Url::from_file_path(std::env::temp_dir().join("config.frag.glsl")).unwrap();
+        server
+            .on_notification(LspNotification::new(
+                DidOpenTextDocument::METHOD.into(),
+                DidOpenTextDocumentParams {
+                    text_document: lsp_types::TextDocumentItem {
+                        uri: uri.clone(),
+                        language_id: "glsl".into(),
+                        version: 0,
+                        text: "#version 450\nvoid main() { undeclared = 1.0; }\n".into(),
+                    },
+                },
+            ))
+            .unwrap();
+        // Second config change arrives before the first batch is validated.
+        server.request_configuration();
+        server.request_configuration();
+        let config = serde_json::to_value(vec![ServerConfig::default()]).unwrap();
+        for id in 0..2 {
+            server
+                .on_response(lsp_server::Response::new_ok(id.into(), config.clone()))
+                .unwrap();
+        }
+        assert_eq!(server.validation_pool.get_pending_count(), 1);
+        while server.validation_pool.get_pending_count() > 0 {
+            let result = server
+                .validation_pool
+                .get_receiver()
+                .recv_timeout(std::time::Duration::from_secs(30))
+                .unwrap();
+            server.on_validation_result(result);
+        }
+        let published: Vec<PublishDiagnosticsParams> = client
+            .receiver
+            .try_iter()
+            .filter_map(|message| match message {
+                Message::Notification(not) if not.method == PublishDiagnostics::METHOD => {
+                    Some(serde_json::from_value(not.params).unwrap())
+                }
+                _ => None,
+            })
+            .filter(|params: &PublishDiagnosticsParams| params.uri == uri)
+            .collect();
+        // Published on open, cleared by each config change, then published once by the last batch.
+        let is_empty: Vec<bool> = published
+            .iter()
+            .map(|params| params.diagnostics.is_empty())
+            .collect();
+        assert_eq!(is_empty, vec![false, true, true, false]);
+        assert_eq!(published[0].diagnostics, published[3].diagnostics);
+    }
+
+    #[test]
+    fn edit_drop_outdated_validation() {
+        let (mut server, client, uri) = create_diagnostic_mode_server(ServerDiagnosticMode::Push);
+        server.request_configuration();
+        server
+            .on_response(lsp_server::Response::new_ok(
+                0.into(),
+                serde_json::to_value(vec![ServerConfig::default()]).unwrap(),
+            ))
+            .unwrap();
+        assert_eq!(server.validation_pool.get_pending_count(), 1);
+        // Error is fixed before the batch is received.
+        server
+            .on_notification(LspNotification::new(
+                DidChangeTextDocument::METHOD.into(),
+                DidChangeTextDocumentParams {
+                    text_document: lsp_types::VersionedTextDocumentIdentifier {
+                        uri: uri.clone(),
+                        version: 1,
+                    },
+                    content_changes: vec![lsp_types::TextDocumentContentChangeEvent {
+                        range: None,
+                        range_length: None,
+                        text: "#version 450\nvoid main() { float declared = 1.0; }\n".into(),
+                    }],
+                },
+            ))
+            .unwrap();
+        while server.validation_pool.get_pending_count() > 0 {
+            let result = server
+                .validation_pool
+                .get_receiver()
+                .recv_timeout(std::time::Duration::from_secs(30))
+                .unwrap();
+            server.on_validation_result(result);
+        }
+        // Diagnostics of the previous content are not published over the new ones.
+        let published = get_published_diagnostics(&client, &uri);
+        assert!(!published.is_empty());
+        assert!(published.last().unwrap().diagnostics.is_empty());
+    }
+
+    #[test]
+    fn config_change_clear_include_cache() {
+        let (connection, client) = ServerConnection::memory();
+        let mut server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::from([(ShadingLanguage::Glsl, ServerLanguageData::glsl())]),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
+        };
+        let directory = std::env::temp_dir().join("shader-language-server-include-cache");
+        let (preferred, fallback) = (directory.join("preferred"), directory.join("fallback"));
+        std::fs::create_dir_all(&preferred).unwrap();
+        std::fs::create_dir_all(&fallback).unwrap();
+        let _ = std::fs::remove_file(preferred.join("cache-header.glsl"));
+        std::fs::write(
+            fallback.join("cache-header.glsl"),
+            "float getValue() { return 1.0; }\n",
+        )
+        .unwrap();
+        let uri = Url::from_file_path(directory.join("cache.frag.glsl")).unwrap();
+        let config = ServerConfig {
+            includes: vec![
+                preferred.to_string_lossy().into(),
+                fallback.to_string_lossy().into(),
+            ],
+            ..Default::default()
+        };
+        server
+            .language_data
+            .get_mut(&ShadingLanguage::Glsl)
+            .unwrap()
+            .config = config.clone();
+        server
+            .on_notification(LspNotification::new(
+                DidOpenTextDocument::METHOD.into(),
+                DidOpenTextDocumentParams {
+                    text_document: lsp_types::TextDocumentItem {
+                        uri: uri.clone(),
+                        language_id: "glsl".into(),
+                        version: 0,
+                        text: "#version 450\n#extension GL_GOOGLE_include_directive : require\n#include \"cache-header.glsl\"\nvoid main() { float value = getOtherValue(); }\n".into(),
+                    },
+                },
+            ))
+            .unwrap();
+        // Each config change revalidates the file on a validation thread.
+        let mut change_config = |id: i32| -> Vec<PublishDiagnosticsParams> {
+            server.request_configuration();
+            server
+                .on_response(lsp_server::Response::new_ok(
+                    id.into(),
+                    serde_json::to_value(vec![config.clone()]).unwrap(),
+                ))
+                .unwrap();
+            while server.validation_pool.get_pending_count() > 0 {
+                let result = server
+                    .validation_pool
+                    .get_receiver()
+                    .recv_timeout(std::time::Duration::from_secs(30))
+                    .unwrap();
+                server.on_validation_result(result);
+            }
+            client
+                .receiver
+                .try_iter()
+                .filter_map(|message| match message {
+                    Message::Notification(not) if not.method == PublishDiagnostics::METHOD => {
+                        Some(serde_json::from_value(not.params).unwrap())
+                    }
+                    _ => None,
+                })
+                .filter(|params: &PublishDiagnosticsParams| params.uri == uri)
+                .collect()
+        };
+        let published = change_config(0);
+        assert!(!published.last().unwrap().diagnostics.is_empty());
+        // A header shadowing the resolved one is created in a preferred include directory.
+        std::fs::write(
+            preferred.join("cache-header.glsl"),
+            "float getOtherValue() { return 1.0; }\n",
+        )
+        .unwrap();
+        let published = change_config(1);
+        assert!(
+            published.last().unwrap().diagnostics.is_empty(),
+            "{:#?}",
+            published
+        );
+    }
+
+    fn create_diagnostic_mode_server(
+        diagnostic_mode: ServerDiagnosticMode,
+    ) -> (ServerLanguage, Connection, Url) {
+        let (connection, client) = ServerConnection::memory();
+        let mut language_data = ServerLanguageData::glsl();
+        language_data.config.diagnosticMode = diagnostic_mode;
+        let mut server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::from([(ShadingLanguage::Glsl, language_data)]),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
+        };
+        let uri = Url::from_file_path(std::env::temp_dir().join("mode.frag.glsl")).unwrap();
+        server
+            .on_notification(LspNotification::new(
+                DidOpenTextDocument::METHOD.into(),
+                DidOpenTextDocumentParams {
+                    text_document: lsp_types::TextDocumentItem {
+                        uri: uri.clone(),
+                        language_id: "glsl".into(),
+                        version: 0,
+                        text: "#version 450\nvoid main() { undeclared = 1.0; }\n".into(),
+                    },
+                },
+            ))
+            .unwrap();
+        (server, client, uri)
+    }
+
+    fn get_published_diagnostics(client: &Connection, uri: &Url) -> Vec<PublishDiagnosticsParams> {
+        client
+            .receiver
+            .try_iter()
+            .filter_map(|message| match message {
+                Message::Notification(not) if not.method == PublishDiagnostics::METHOD => {
+                    Some(serde_json::from_value(not.params).unwrap())
+                }
+                _ => None,
+            })
+            .filter(|params: &PublishDiagnosticsParams| params.uri == *uri)
+            .collect()
+    }
+
+    fn pull_diagnostics(
+        server: &mut ServerLanguage,
+        client: &Connection,
+        uri: &Url,
+    ) -> Vec<Diagnostic> {
+        server
+            .on_request(LspRequest::new(
+                100.into(),
+                DocumentDiagnosticRequest::METHOD.into(),
+                DocumentDiagnosticParams {
+                    text_document: lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+                    identifier: None,
+                    previous_result_id: None,
+                    work_done_progress_params: Default::default(),
+                    partial_result_params: Default::default(),
+                },
+            ))
+            .unwrap();
+        let report = client
+            .receiver
+            .try_iter()
+            .find_map(|message| match message {
+                Message::Response(response) if response.id == 100.into() => response.result,
+                _ => None,
+            })
+            .unwrap();
+        match serde_json::from_value(report).unwrap() {
+            DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(report)) => {
+                report.full_document_diagnostic_report.items
+            }
+            report => panic!("Unexpected report {:#?}", report),
+        }
+    }
+
+    #[test]
+    fn pull_diagnostic_mode() {
+        let (mut server, client, uri) = create_diagnostic_mode_server(ServerDiagnosticMode::Pull);
+        // Nothing is pushed on open nor on change.
+        server
+            .on_notification(LspNotification::new(
+                DidChangeTextDocument::METHOD.into(),
+                DidChangeTextDocumentParams {
+                    text_document: lsp_types::VersionedTextDocumentIdentifier {
+                        uri: uri.clone(),
+                        version: 1,
+                    },
+                    content_changes: vec![lsp_types::TextDocumentContentChangeEvent {
+                        range: None,
+                        range_length: None,
+                        text: "#version 450\nvoid main() { undeclared = 2.0; }\n".into(),
+                    }],
+                },
+            ))
+            .unwrap();
+        assert!(get_published_diagnostics(&client, &uri).is_empty());
+        assert!(!pull_diagnostics(&mut server, &client, &uri).is_empty());
+    }
+
+    #[test]
+    fn push_diagnostic_mode() {
+        let (mut server, client, uri) = create_diagnostic_mode_server(ServerDiagnosticMode::Push);
+        let published = get_published_diagnostics(&client, &uri);
+        assert_eq!(published.len(), 1);
+        assert!(!published[0].diagnostics.is_empty());
+        // Pushed diagnostics are not reported twice.
+        assert!(pull_diagnostics(&mut server, &client, &uri).is_empty());
+    }
+
+    #[test]
+    fn switch_to_pull_diagnostic_mode() {
+        let (mut server, client, uri) = create_diagnostic_mode_server(ServerDiagnosticMode::Both);
+        let published = get_published_diagnostics(&client, &uri);
+        assert_eq!(published.len(), 1);
+        assert!(!published[0].diagnostics.is_empty());
+        server.request_configuration();
+        let config = ServerConfig {
+            diagnosticMode: ServerDiagnosticMode::Pull,
+            ..Default::default()
+        };
+        server
+            .on_response(lsp_server::Response::new_ok(
+                0.into(),
+                serde_json::to_value(vec![config]).unwrap(),
+            ))
+            .unwrap();
+        assert_eq!(server.validation_pool.get_pending_count(), 0);
+        // Stale pushed diagnostics are cleared and not published again.
+        let published = get_published_diagnostics(&client, &uri);
+        assert_eq!(published.len(), 1);
+        assert!(published[0].diagnostics.is_empty());
+        assert!(!pull_diagnostics(&mut server, &client, &uri).is_empty());
+    }
+
+    #[test]
+    fn register_file_watcher() {
+        let (connection, client) = ServerConnection::memory();
+        let mut server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::new(),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
+        };
+        let params = serde_json::json!({
+            "capabilities": {
+                "workspace": { "didChangeWatchedFiles": { "dynamicRegistration": true } }
+            },
+        });
+        client
+            .sender
+            .send(LspRequest::new(1.into(), Initialize::METHOD.into(), params).into())
+            .unwrap();
+        client
+            .sender
+            .send(LspNotification::new(Initialized::METHOD.into(), serde_json::json!({})).into())
+            .unwrap();
+        server.initialize().unwrap();
+        let registrations: Vec<RegistrationParams> = client
+            .receiver
+            .try_iter()
+            .filter_map(|message| match message {
+                Message::Request(req) if req.method == RegisterCapability::METHOD => {
+                    Some(serde_json::from_value(req.params).unwrap())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(registrations.len(), 1);
+        assert_eq!(
+            registrations[0].registrations[0].method,
+            DidChangeWatchedFiles::METHOD
+        );
+    }
+
+    #[test]
+    fn include_changed_on_disk() {
+        let (connection, client) = ServerConnection::memory();
+        let mut server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::from([(ShadingLanguage::Glsl, ServerLanguageData::glsl())]),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
+        };
+        let directory = std::env::temp_dir().join("shader-language-server-watched");
+        std::fs::create_dir_all(&directory).unwrap();
+        let header_path = directory.join("watched-header.glsl");
+        std::fs::write(&header_path, "float getValue() { return 1.0; }\n").unwrap();
+        let header_path = shader_sense::include::canonicalize(&header_path).unwrap();
+        let uri =
+            Url::from_file_path(header_path.parent().unwrap().join("watched.frag.glsl")).unwrap();
+        let get_published = || -> Vec<PublishDiagnosticsParams> {
+            client
+                .receiver
+                .try_iter()
+                .filter_map(|message| match message {
+                    Message::Notification(not) if not.method == PublishDiagnostics::METHOD => {
+                        Some(serde_json::from_value(not.params).unwrap())
+                    }
+                    _ => None,
+                })
+                .filter(|params: &PublishDiagnosticsParams| params.uri == uri)
+                .collect()
+        };
+        server
+            .on_notification(LspNotification::new(
+                DidOpenTextDocument::METHOD.into(),
+                DidOpenTextDocumentParams {
+                    text_document: lsp_types::TextDocumentItem {
+                        uri: uri.clone(),
+                        language_id: "glsl".into(),
+                        version: 0,
+                        text: "#version 450\n#extension GL_GOOGLE_include_directive : require\n#include \"watched-header.glsl\"\nvoid main() { float value = getValue(); }\n".into(),
+                    },
+                },
+            ))
+            .unwrap();
+        let published = get_published();
+        assert_eq!(published.len(), 1);
+        assert!(published[0].diagnostics.is_empty());
+        // Header is renamed outside the editor.
+        std::fs::write(&header_path, "float getOtherValue() { return 1.0; }\n").unwrap();
+        server
+            .on_notification(LspNotification::new(
+                DidChangeWatchedFiles::METHOD.into(),
+                DidChangeWatchedFilesParams {
+                    changes: vec![lsp_types::FileEvent {
+                        uri: Url::from_file_path(&header_path).unwrap(),
+                        typ: FileChangeType::CHANGED,
+                    }],
+                },
+            ))
+            .unwrap();
+        let published = get_published();
+        assert_eq!(published.len(), 1);
+        assert!(!published[0].diagnostics.is_empty());
+    }
+
+    #[test]
+    fn defer_open_validation() {
+        let (connection, client) = ServerConnection::memory();
+        let mut language_data = ServerLanguageData::glsl();
+        language_data.config.deferOpenValidation = true;
+        let mut server = ServerLanguage {
+            connection,
+            file_language: HashMap::new(),
+            language_data: HashMap::from([(ShadingLanguage::Glsl, language_data)]),
+            warned_missing_includes: HashSet::new(),
+            experimental: HashSet::new(),
+            validation_pool: ValidationPool::default(),
+        };
+        let uri = Url::from_file_path(std::env::temp_dir().join("deferred.frag.glsl")).unwrap();
+        let get_published = || -> Vec<PublishDiagnosticsParams> {
+            client
+                .receiver
+                .try_iter()
+                .filter_map(|message| match message {
+                    Message::Notification(not) if not.method == PublishDiagnostics::METHOD => {
+                        Some(serde_json::from_value(not.params).unwrap())
+                    }
+                    _ => None,
+                })
+                .filter(|params: &PublishDiagnosticsParams| params.uri == uri)
+                .collect()
+        };
+        server
+            .on_notification(LspNotification::new(
+                DidOpenTextDocument::METHOD.into(),
+                DidOpenTextDocumentParams {
+                    text_document: lsp_types::TextDocumentItem {
+                        uri: uri.clone(),
+                        language_id: "glsl".into(),
+                        version: 0,
+                        text: "#version 450\nfloat helper() { return 1.0; }\nvoid main() { undeclared = 1.0; }\n".into(),
+                    },
+                },
+            ))
+            .unwrap();
+        // Not validated on open, but symbols are available.
+        assert!(get_published().is_empty());
+        let language_data = server.language_data.get(&ShadingLanguage::Glsl).unwrap();
+        assert!(language_data.deferred_files.contains(&uri));
+        let cached_file = language_data.watched_files.get(&uri).unwrap();
+        assert!(RefCell::borrow(&cached_file)
+            .symbol_cache
+            .functions
+            .iter()
+            .any(|symbol| symbol.label == "helper"));
+        // Validated on first save.
+        server
+            .on_notification(LspNotification::new(
+                DidSaveTextDocument::METHOD.into(),
+                DidSaveTextDocumentParams {
+                    text_document: lsp_types::TextDocumentIdentifier { uri: uri.clone() },
+                    text: None,
+                },
+            ))
+            .unwrap();
+        let published = get_published();
+        assert_eq!(published.len(), 1);
+        assert!(!published[0].diagnostics.is_empty());
+        let language_data = server.language_data.get(&ShadingLanguage::Glsl).unwrap();
+        assert!(language_data.deferred_files.is_empty());
+    }
+}
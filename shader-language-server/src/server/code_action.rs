@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, TextEdit, Url, WorkspaceEdit,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use shader_sense::shader_error::ShaderDiagnosticFix;
+
+use super::ServerLanguageData;
+
+// Fix stored in the diagnostic data, which client send back with code action request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DiagnosticFixData {
+    title: String,
+    new_text: String,
+}
+
+pub fn get_diagnostic_fix_data(fix: &ShaderDiagnosticFix) -> Option<Value> {
+    serde_json::to_value(DiagnosticFixData {
+        title: fix.title.clone(),
+        new_text: fix.new_text.clone(),
+    })
+    .ok()
+}
+
+impl ServerLanguageData {
+    // Quick fixes replacing the range of diagnostics that have one.
+    pub fn recolt_code_action(
+        &self,
+        uri: &Url,
+        diagnostics: &[Diagnostic],
+    ) -> Vec<CodeActionOrCommand> {
+        diagnostics
+            .iter()
+            .filter_map(|diagnostic| {
+                let fix: DiagnosticFixData =
+                    serde_json::from_value(diagnostic.data.clone()?).ok()?;
+                Some(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: fix.title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(
+                            uri.clone(),
+                            vec![TextEdit {
+                                range: diagnostic.range,
+                                new_text: fix.new_text,
+                            }],
+                        )])),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(true),
+                    ..Default::default()
+                }))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Position;
+    use shader_sense::shader::ShadingLanguage;
+
+    use super::*;
+
+    #[test]
+    fn code_action_insert_semicolon() {
+        let uri = Url::parse("file:///shaders/missing-semicolon.frag.glsl").unwrap();
+        let content = "#version 450\n\
+                       void main() {\n\
+                       \x20   float value = 0.0\n\
+                       }\n";
+        let mut language_data = ServerLanguageData::glsl();
+        language_data.config.lint.syntaxFixes = true;
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let diagnostics = language_data
+            .recolt_diagnostic(&uri, &cached_file)
+            .unwrap()
+            .remove(&uri)
+            .unwrap();
+        let actions = language_data.recolt_code_action(&uri, &diagnostics);
+        assert_eq!(actions.len(), 1, "{:#?}", diagnostics);
+        match &actions[0] {
+            CodeActionOrCommand::CodeAction(action) => {
+                assert_eq!(action.title, "Insert ';'");
+                let edits = &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+                assert_eq!(edits[0].new_text, ";");
+                assert_eq!(edits[0].range.start, Position::new(2, 21));
+                assert_eq!(edits[0].range.end, Position::new(2, 21));
+            }
+            _ => panic!("Expected a code action"),
+        }
+    }
+}
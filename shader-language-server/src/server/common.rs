@@ -1,6 +1,9 @@
 use std::path::Path;
 
-use shader_sense::symbols::symbols::{ShaderPosition, ShaderRange};
+use shader_sense::{
+    shader::{ShaderStage, ShadingLanguage},
+    symbols::symbols::{ShaderPosition, ShaderRange, ShaderSymbolData, ShaderSymbolList},
+};
 
 pub fn shader_range_to_lsp_range(range: &ShaderRange) -> lsp_types::Range {
     lsp_types::Range {
@@ -30,6 +33,17 @@ pub fn lsp_range_to_shader_range(range: &lsp_types::Range, file_path: &Path) ->
     }
 }
 
+// Stage extensions such as .frag are GLSL.
+pub fn get_shading_language(file_path: &Path) -> Option<ShadingLanguage> {
+    let extension = file_path.extension()?.to_string_lossy().to_lowercase();
+    match extension.as_str() {
+        "hlsl" | "hlsli" | "fx" | "fxh" => Some(ShadingLanguage::Hlsl),
+        "glsl" => Some(ShadingLanguage::Glsl),
+        "wgsl" => Some(ShadingLanguage::Wgsl),
+        _ => ShaderStage::from_file_name(&extension).map(|_| ShadingLanguage::Glsl),
+    }
+}
+
 // Handle non-utf8 characters
 pub fn read_string_lossy(file_path: &Path) -> std::io::Result<String> {
     use std::io::Read;
@@ -51,3 +65,65 @@ pub fn read_string_lossy(file_path: &Path) -> std::io::Result<String> {
         },
     }
 }
+
+pub fn is_identifier(text: &str) -> bool {
+    text.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// Get the type expected by a declaration or an assignment ending the text, such as `float3 n = `.
+pub fn get_expected_type(text: &str, symbol_list: &ShaderSymbolList) -> Option<String> {
+    let lhs = text.trim_end().strip_suffix('=')?;
+    // Comparison operators do not give any type.
+    if lhs.ends_with(['=', '<', '>', '!']) {
+        return None;
+    }
+    let lhs = lhs.trim_end_matches(['+', '-', '*', '/', '%', '&', '|', '^']);
+    let statement = lhs.rsplit([';', '{', '}', '(', ',']).next()?;
+    let statement = match statement.find('[') {
+        Some(array_start) => &statement[..array_start],
+        None => statement,
+    };
+    let mut tokens = statement.split_whitespace().rev();
+    let variable = tokens.next().filter(|token| is_identifier(token))?;
+    match tokens.next() {
+        Some(ty) if is_identifier(ty) => Some(ty.into()),
+        Some(_) => None,
+        None => match symbol_list.find_symbol(&variable.into())?.data {
+            ShaderSymbolData::Variables { ty, .. } => Some(ty),
+            _ => None,
+        },
+    }
+}
+
+// Score how well a return type match the expected one.
+// Generic intrinsic types such as floatn or floatnxn match any dimension.
+pub fn get_return_type_score(return_type: &str, expected_type: &str) -> u32 {
+    fn match_dimensions(dimensions: &str, pattern: &str) -> bool {
+        dimensions.len() == pattern.len()
+            && dimensions
+                .chars()
+                .zip(pattern.chars())
+                .all(|(d, p)| match p {
+                    'n' => ('1'..='4').contains(&d),
+                    p => d == p,
+                })
+    }
+    if return_type == expected_type {
+        return 2;
+    }
+    let generic_match = ["nxn", "n"].iter().any(|pattern| {
+        return_type.strip_suffix(pattern).is_some_and(|base| {
+            expected_type
+                .strip_prefix(base)
+                .is_some_and(|dimensions| match_dimensions(dimensions, pattern))
+        })
+    });
+    if generic_match {
+        1
+    } else {
+        0
+    }
+}
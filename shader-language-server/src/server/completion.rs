@@ -1,42 +1,236 @@
-use std::{ffi::OsStr, rc::Rc};
+use std::{cmp::Reverse, collections::HashSet, ffi::OsStr, rc::Rc};
 
-use log::{error, warn};
+use log::warn;
 use lsp_types::{
-    CompletionItem, CompletionItemKind, CompletionItemLabelDetails, MarkupContent, Position, Url,
+    CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionList,
+    InsertTextFormat, MarkupContent, Position, Url,
 };
 
 use shader_sense::{
     shader::ShadingLanguage,
-    symbols::symbols::{
-        ShaderPosition, ShaderSymbol, ShaderSymbolData, ShaderSymbolType, SymbolError,
+    symbols::{
+        get_shader_pragmas,
+        symbols::{
+            ShaderPosition, ShaderSymbol, ShaderSymbolData, ShaderSymbolList, ShaderSymbolType,
+            ShaderVectorType, SymbolError,
+        },
+        SymbolTree,
     },
 };
 
-use super::{ServerFileCacheHandle, ServerLanguageData};
+use super::{
+    common::{get_expected_type, get_return_type_score},
+    member::{get_swizzle_component_sets, MemberOwner},
+    ServerFileCacheHandle, ServerLanguageData,
+};
 
-impl ServerLanguageData {
-    fn list_members_and_methods(&self, symbol: &ShaderSymbol) -> Vec<ShaderSymbol> {
-        if let ShaderSymbolData::Struct { members, methods } = &symbol.data {
-            let mut converted_members: Vec<ShaderSymbol> =
-                members.iter().map(|e| e.as_symbol()).collect();
-            let converted_methods: Vec<ShaderSymbol> =
-                methods.iter().map(|e| e.as_symbol()).collect();
-            converted_members.extend(converted_methods);
-            converted_members
-        } else {
-            Vec::new()
+// Each component & the whole vector for every component set.
+fn get_swizzle_completions(
+    shading_language: ShadingLanguage,
+    vector: &ShaderVectorType,
+) -> Vec<CompletionItem> {
+    let dimension = (vector.dimension as usize).min(4);
+    let mut swizzles = Vec::new();
+    for set in get_swizzle_component_sets(shading_language) {
+        let components = &set[..dimension];
+        swizzles.extend(components.chars().map(|c| c.to_string()));
+        if dimension > 1 {
+            swizzles.push(components.to_string());
         }
     }
+    swizzles
+        .into_iter()
+        .map(|swizzle| CompletionItem {
+            kind: Some(CompletionItemKind::FIELD),
+            detail: Some(format!("Swizzle of {} component(s)", swizzle.len())),
+            label: swizzle,
+            ..Default::default()
+        })
+        .collect()
+}
 
+fn get_pragma_completions(shading_language: ShadingLanguage) -> Vec<CompletionItem> {
+    get_shader_pragmas(shading_language)
+        .into_iter()
+        .map(|pragma| CompletionItem {
+            label: pragma.label.into(),
+            kind: Some(CompletionItemKind::KEYWORD),
+            detail: Some(pragma.usage.into()),
+            documentation: Some(lsp_types::Documentation::String(pragma.description.into())),
+            ..Default::default()
+        })
+        .collect()
+}
+
+impl ServerLanguageData {
+    // Builtins removed by the stage filter of the file, that might be wrongly detected.
+    fn get_other_stage_builtins(&self, symbol_list: &ShaderSymbolList) -> ShaderSymbolList {
+        fn filter(builtins: &[ShaderSymbol], listed: &[ShaderSymbol]) -> Vec<ShaderSymbol> {
+            builtins
+                .iter()
+                .filter(|builtin| {
+                    !builtin.stages.is_empty()
+                        && !listed.iter().any(|symbol| symbol.label == builtin.label)
+                })
+                .cloned()
+                .collect()
+        }
+        let builtins = self.symbol_provider.get_intrinsics_symbol();
+        ShaderSymbolList {
+            types: filter(&builtins.types, &symbol_list.types),
+            constants: filter(&builtins.constants, &symbol_list.constants),
+            variables: filter(&builtins.variables, &symbol_list.variables),
+            functions: filter(&builtins.functions, &symbol_list.functions),
+            keywords: filter(&builtins.keywords, &symbol_list.keywords),
+        }
+    }
+    // Builtin semantics, then the ones used in the file that are not builtins.
+    fn get_semantic_completions(&self, symbol_tree: &SymbolTree) -> Vec<CompletionItem> {
+        let mut labels = HashSet::new();
+        let mut items: Vec<CompletionItem> = self
+            .symbol_provider
+            .get_intrinsics_symbol()
+            .constants
+            .iter()
+            .filter(|symbol| matches!(symbol.data, ShaderSymbolData::Variables { .. }))
+            .filter(|symbol| labels.insert(symbol.label.to_uppercase()))
+            .map(|symbol| {
+                let item = convert_completion_item(
+                    ShadingLanguage::Hlsl,
+                    symbol.clone(),
+                    CompletionItemKind::CONSTANT,
+                );
+                // Indexed semantics such as TEXCOORD[n] insert a placeholder for the index.
+                match symbol.label.strip_suffix("[n]") {
+                    Some(name) => CompletionItem {
+                        filter_text: Some(name.into()),
+                        insert_text: Some(format!("{}${{1:0}}", name)),
+                        insert_text_format: Some(InsertTextFormat::SNIPPET),
+                        ..item
+                    },
+                    None => item,
+                }
+            })
+            .collect();
+        // Semantics are case insensitive.
+        for semantic in symbol_tree.get_semantics() {
+            let label = semantic.to_uppercase();
+            let indexed_label = format!(
+                "{}[N]",
+                label.trim_end_matches(|c: char| c.is_ascii_digit())
+            );
+            if labels.contains(&label) || labels.contains(&indexed_label) {
+                continue;
+            }
+            labels.insert(label);
+            items.push(CompletionItem {
+                label: semantic,
+                kind: Some(CompletionItemKind::CONSTANT),
+                detail: Some("User semantic".into()),
+                ..Default::default()
+            });
+        }
+        items
+    }
     pub fn recolt_completion(
         &mut self,
         uri: &Url,
         cached_file: ServerFileCacheHandle,
         position: Position,
         trigger_character: Option<String>,
-    ) -> Result<Vec<CompletionItem>, SymbolError> {
+    ) -> Result<CompletionList, SymbolError> {
         let file_path = uri.to_file_path().unwrap();
-        let symbol_list = self.get_all_symbols(Rc::clone(&cached_file));
+        {
+            let cached_file = cached_file.borrow();
+            let word_position = ShaderPosition {
+                file_path: file_path.clone(),
+                line: position.line,
+                pos: position.character,
+            };
+            if cached_file.symbol_tree.is_pragma_position(&word_position) {
+                return Ok(CompletionList {
+                    is_incomplete: false,
+                    items: get_pragma_completions(cached_file.shading_language),
+                });
+            }
+            // Only semantics are listed after the colon of an HLSL declaration.
+            if cached_file.shading_language == ShadingLanguage::Hlsl
+                && cached_file.symbol_tree.is_semantic_position(&word_position)
+            {
+                return Ok(CompletionList {
+                    is_incomplete: false,
+                    items: self.get_semantic_completions(&cached_file.symbol_tree),
+                });
+            }
+            // Only address spaces & access modes are listed in WGSL var & ptr templates.
+            if let (ShadingLanguage::Wgsl, Some(keywords)) = (
+                cached_file.shading_language,
+                cached_file
+                    .symbol_tree
+                    .get_address_space_keywords(&word_position),
+            ) {
+                return Ok(CompletionList {
+                    is_incomplete: false,
+                    items: self
+                        .symbol_provider
+                        .get_intrinsics_symbol()
+                        .keywords
+                        .iter()
+                        .filter(|symbol| keywords.contains(&symbol.label.as_str()))
+                        .map(|symbol| {
+                            convert_completion_item(
+                                ShadingLanguage::Wgsl,
+                                symbol.clone(),
+                                CompletionItemKind::KEYWORD,
+                            )
+                        })
+                        .collect(),
+                });
+            }
+        }
+        // Colon of a ternary operator or less than operator, or of another language.
+        if matches!(trigger_character.as_deref(), Some(":") | Some("<")) {
+            return Ok(CompletionList {
+                is_incomplete: false,
+                items: Vec::new(),
+            });
+        }
+        // Wait for a long enough word before listing symbols, except for member access.
+        let min_prefix_length = self.config.completionMinPrefixLength;
+        if min_prefix_length > 0 && trigger_character.as_deref() != Some(".") {
+            let cached_file = cached_file.borrow();
+            let line = cached_file
+                .symbol_tree
+                .content
+                .lines()
+                .nth(position.line as usize)
+                .unwrap_or("");
+            let line = match line.char_indices().nth(position.character as usize) {
+                Some((offset, _)) => &line[..offset],
+                None => line,
+            };
+            let word = line.trim_end_matches(|c: char| c.is_ascii_alphanumeric() || c == '_');
+            let prefix_length = line.len() - word.len();
+            if prefix_length < min_prefix_length && !word.ends_with('.') {
+                return Ok(CompletionList {
+                    is_incomplete: true,
+                    items: Vec::new(),
+                });
+            }
+        }
+        let mut symbol_list = self.get_all_symbols(Rc::clone(&cached_file));
+        // Builtins of other stages are listed with their stages, so they can be told apart.
+        let other_stage_builtins = if self.config.completionAllStages {
+            let other_stage_builtins = self.get_other_stage_builtins(&symbol_list);
+            let labels = other_stage_builtins
+                .iter()
+                .flat_map(|(symbols, _)| symbols.iter().map(|symbol| symbol.label.clone()))
+                .collect::<HashSet<String>>();
+            symbol_list.append(other_stage_builtins);
+            labels
+        } else {
+            HashSet::new()
+        };
         let cached_file = cached_file.borrow();
         let shader_position = ShaderPosition {
             file_path: file_path.clone(),
@@ -49,102 +243,104 @@ impl ServerLanguageData {
             },
         };
         let symbol_list = symbol_list.filter_scoped_symbol(shader_position.clone());
-        match trigger_character {
-            Some(_) => {
-                match self.symbol_provider.get_word_chain_range_at_position(
-                    &cached_file.symbol_tree,
-                    shader_position.clone(),
-                ) {
-                    Ok(chain) => {
-                        let mut chain_list = chain.iter().rev();
-                        let mut current_symbol = match chain_list.next() {
-                            Some(next_item) => match symbol_list.find_symbol(&next_item.0) {
-                                Some(symbol) => {
-                                    if let ShaderSymbolData::Variables { ty } = &symbol.data {
-                                        match symbol_list.find_type_symbol(ty) {
-                                            Some(ty_symbol) => ty_symbol,
-                                            None => {
-                                                warn!("Symbol type {} is not found.", ty);
-                                                return Ok(vec![]);
-                                            }
+        // Text before the word being typed might give the expected type.
+        let expected_type = cached_file
+            .symbol_tree
+            .content
+            .lines()
+            .nth(position.line as usize)
+            .and_then(|line| {
+                let line = line.get(..position.character as usize).unwrap_or(line);
+                let line = line.trim_end_matches(|c: char| c.is_ascii_alphanumeric() || c == '_');
+                get_expected_type(line, &symbol_list)
+            });
+        let commit_characters = Some(
+            self.config
+                .get_completion_commit_characters(cached_file.shading_language),
+        )
+        .filter(|commit_characters| !commit_characters.is_empty());
+        // Member access, list only members of the type before the dot.
+        if trigger_character.as_deref() == Some(".") {
+            match self
+                .symbol_provider
+                .get_word_chain_range_at_position(&cached_file.symbol_tree, shader_position.clone())
+            {
+                Ok(chain) => {
+                    match self.resolve_member_owner(
+                        cached_file.shading_language,
+                        &chain,
+                        &symbol_list,
+                    ) {
+                        Some(MemberOwner::Struct(owner)) => {
+                            return Ok(CompletionList {
+                                is_incomplete: false,
+                                items: self
+                                    .list_members_and_methods(&owner)
+                                    .into_iter()
+                                    .map(|s| {
+                                        let completion_kind =
+                                            if let ShaderSymbolData::Functions { .. } = &s.data {
+                                                CompletionItemKind::FUNCTION
+                                            } else {
+                                                CompletionItemKind::VARIABLE
+                                            };
+                                        CompletionItem {
+                                            commit_characters: commit_characters.clone(),
+                                            ..convert_completion_item(
+                                                cached_file.shading_language,
+                                                s,
+                                                completion_kind,
+                                            )
                                         }
-                                    } else {
-                                        error!("Not variable {:?}", symbol);
-                                        return Ok(vec![]); // Nothing valid under cursor
-                                    }
-                                }
-                                None => {
-                                    error!("No symbol found for {}", next_item.0);
-                                    return Ok(vec![]);
-                                } // Nothing valid under cursor
-                            },
-                            None => {
-                                error!("No symbol in list for {:?}", chain_list);
-                                return Ok(vec![]);
-                            } // Nothing under cursor
-                        };
-                        while let Some(next_item) = chain_list.next() {
-                            let members_and_methods =
-                                self.list_members_and_methods(&current_symbol);
-                            let symbol =
-                                match members_and_methods.iter().find(|e| e.label == next_item.0) {
-                                    Some(next_symbol) => next_symbol.clone(),
-                                    None => {
-                                        return Err(SymbolError::InternalErr(format!(
-                                            "Failed to find symbol {} for struct {}",
-                                            next_item.0, current_symbol.label
-                                        )))
-                                    }
-                                };
-                            // find next element
-                            if let ShaderSymbolData::Variables { ty } = &symbol.data {
-                                match symbol_list.find_type_symbol(ty) {
-                                    Some(ty_symbol) => current_symbol = ty_symbol,
-                                    None => {
-                                        return Ok(vec![]);
-                                    }
-                                }
-                            } else {
-                                error!("Not variable 2 {:?}", symbol);
-                                return Ok(vec![]); // Nothing valid under cursor
-                            }
+                                    })
+                                    .collect(),
+                            });
                         }
-                        let members_and_methods = self.list_members_and_methods(&current_symbol);
-                        return Ok(members_and_methods
-                            .into_iter()
-                            .map(|s| {
-                                let completion_kind = if let ShaderSymbolData::Functions {
-                                    signatures: _,
-                                } = &s.data
-                                {
-                                    CompletionItemKind::FUNCTION
-                                } else {
-                                    CompletionItemKind::VARIABLE
-                                };
-                                convert_completion_item(
+                        Some(MemberOwner::Vector(vector)) => {
+                            return Ok(CompletionList {
+                                is_incomplete: false,
+                                items: get_swizzle_completions(
                                     cached_file.shading_language,
-                                    s,
-                                    completion_kind,
-                                )
-                            })
-                            .collect());
-                    }
-                    Err(err) => {
-                        if let SymbolError::NoSymbol = err {
-                            Ok(vec![])
-                        } else {
-                            Err(err)
+                                    &vector,
+                                ),
+                            });
                         }
+                        None => warn!(
+                            "Failed to resolve type of {:?}, listing all symbols.",
+                            chain.iter().map(|item| &item.0).collect::<Vec<&String>>()
+                        ),
                     }
                 }
+                Err(SymbolError::NoSymbol) => {}
+                Err(err) => return Err(err),
             }
-            None => Ok(symbol_list
-                .into_iter()
-                .map(|(symbol_list, ty)| {
-                    symbol_list
-                        .into_iter()
-                        .map(|s| {
-                            convert_completion_item(
+        }
+        let items = symbol_list
+            .into_iter()
+            .map(|(symbol_list, ty)| {
+                symbol_list
+                    .into_iter()
+                    .map(|mut s| {
+                        // Rank functions returning the expected type first.
+                        let sort_text = match &expected_type {
+                            Some(expected_type)
+                                if sort_signatures_by_return_type(&mut s, expected_type) > 0 =>
+                            {
+                                Some(format!("0{}", s.label))
+                            }
+                            _ => None,
+                        };
+                        let stages = other_stage_builtins.contains(&s.label).then(|| {
+                            s.stages
+                                .iter()
+                                .map(|stage| stage.to_string())
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        });
+                        let item = CompletionItem {
+                            commit_characters: commit_characters.clone(),
+                            sort_text,
+                            ..convert_completion_item(
                                 cached_file.shading_language,
                                 s,
                                 match ty {
@@ -155,12 +351,75 @@ impl ServerLanguageData {
                                     ShaderSymbolType::Keyword => CompletionItemKind::KEYWORD,
                                 },
                             )
-                        })
-                        .collect()
+                        };
+                        match stages {
+                            Some(stages) => CompletionItem {
+                                label_details: Some(CompletionItemLabelDetails {
+                                    detail: Some(format!(" [{}]", stages)),
+                                    ..item.label_details.unwrap_or_default()
+                                }),
+                                ..item
+                            },
+                            None => item,
+                        }
+                    })
+                    .collect()
+            })
+            .collect::<Vec<Vec<CompletionItem>>>()
+            .concat();
+        Ok(CompletionList {
+            is_incomplete: false,
+            items,
+        })
+    }
+}
+
+// Move overloads returning the expected type first & return the best score.
+fn sort_signatures_by_return_type(shader_symbol: &mut ShaderSymbol, expected_type: &str) -> u32 {
+    match &mut shader_symbol.data {
+        ShaderSymbolData::Functions { signatures, .. } => {
+            signatures.sort_by_key(|signature| {
+                Reverse(get_return_type_score(&signature.returnType, expected_type))
+            });
+            signatures.first().map_or(0, |signature| {
+                get_return_type_score(&signature.returnType, expected_type)
+            })
+        }
+        _ => 0,
+    }
+}
+
+// Get the snippet to insert for a function, either curated or generated from its signature.
+fn get_completion_snippet(shader_symbol: &ShaderSymbol) -> Option<String> {
+    fn escape_placeholder(placeholder: &str) -> String {
+        placeholder
+            .replace('\\', "\\\\")
+            .replace('$', "\\$")
+            .replace('}', "\\}")
+    }
+    match (&shader_symbol.snippet, &shader_symbol.data) {
+        (Some(snippet), _) => Some(snippet.clone()),
+        (None, ShaderSymbolData::Functions { signatures, .. }) => {
+            let parameters = signatures
+                .first()?
+                .parameters
+                .iter()
+                .enumerate()
+                .map(|(index, parameter)| {
+                    format!(
+                        "${{{}:{}}}",
+                        index + 1,
+                        escape_placeholder(&parameter.label)
+                    )
                 })
-                .collect::<Vec<Vec<CompletionItem>>>()
-                .concat()),
+                .collect::<Vec<String>>();
+            Some(format!(
+                "{}({})",
+                shader_symbol.label,
+                parameters.join(", ")
+            ))
         }
+        (None, _) => None,
     }
 }
 
@@ -178,7 +437,8 @@ fn convert_completion_item(
     } else {
         "".to_string()
     };
-    let doc_signature = if let ShaderSymbolData::Functions { signatures } = &shader_symbol.data {
+    let doc_signature = if let ShaderSymbolData::Functions { signatures, .. } = &shader_symbol.data
+    {
         // TODO: should not hide variants
         let parameters = signatures[0]
             .parameters
@@ -224,23 +484,28 @@ fn convert_completion_item(
     };
 
     let signature = shader_symbol.format();
+    let snippet = get_completion_snippet(&shader_symbol);
     CompletionItem {
         kind: Some(completion_kind),
         label: shader_symbol.label.clone(),
         detail: None,
         label_details: Some(CompletionItemLabelDetails {
             detail: None,
-            description: if let ShaderSymbolData::Functions { signatures } = &shader_symbol.data {
+            description: if let ShaderSymbolData::Functions { signatures, .. } = &shader_symbol.data {
                 Some(if signatures.len() > 1 {
                     format!("{} (+ {})", signatures[0].format(shader_symbol.label.as_str()), signatures.len() - 1)
                 } else {
                     signatures[0].format(shader_symbol.label.as_str())
                 })
+            } else if let ShaderSymbolData::Constants { value, .. } = &shader_symbol.data {
+                (!value.is_empty()).then(|| format!("= {}", value))
             } else {
                 None
             },
         }),
         filter_text: Some(shader_symbol.label.clone()),
+        insert_text_format: snippet.as_ref().map(|_| InsertTextFormat::SNIPPET),
+        insert_text: snippet,
         documentation: Some(lsp_types::Documentation::MarkupContent(MarkupContent {
             kind: lsp_types::MarkupKind::Markdown,
             value: format!("```{shading_language}\n{signature}\n```\n{description}\n\n{doc_signature}\n\n{position}\n{doc_link}"),
@@ -248,3 +513,820 @@ fn convert_completion_item(
         ..Default::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use shader_sense::{
+        shader::{HlslShaderModel, ShaderStage},
+        symbols::symbols::{ShaderParameter, ShaderSignature},
+    };
+
+    use super::*;
+
+    fn create_function(label: &str, snippet: Option<String>) -> ShaderSymbol {
+        ShaderSymbol {
+            label: label.into(),
+            data: ShaderSymbolData::Functions {
+                signatures: vec![ShaderSignature {
+                    returnType: "float4".into(),
+                    description: "".into(),
+                    parameters: vec![
+                        ShaderParameter {
+                            ty: "float4x4".into(),
+                            label: "x".into(),
+                            description: "".into(),
+                        },
+                        ShaderParameter {
+                            ty: "float4".into(),
+                            label: "y".into(),
+                            description: "".into(),
+                        },
+                    ],
+                }],
+                workgroup_size: None,
+            },
+            snippet,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn completion_custom_snippet() {
+        let symbol = create_function("mul", Some("mul(${1:mat}, ${2:vec})".into()));
+        let item =
+            convert_completion_item(ShadingLanguage::Hlsl, symbol, CompletionItemKind::FUNCTION);
+        assert_eq!(item.insert_text, Some("mul(${1:mat}, ${2:vec})".into()));
+        assert_eq!(item.insert_text_format, Some(InsertTextFormat::SNIPPET));
+    }
+
+    #[test]
+    fn completion_generated_snippet() {
+        let symbol = create_function("mul", None);
+        let item =
+            convert_completion_item(ShadingLanguage::Hlsl, symbol, CompletionItemKind::FUNCTION);
+        assert_eq!(item.insert_text, Some("mul(${1:x}, ${2:y})".into()));
+        assert_eq!(item.insert_text_format, Some(InsertTextFormat::SNIPPET));
+    }
+
+    fn recolt_test_completion(
+        shading_language: ShadingLanguage,
+        file_name: &str,
+        content: &str,
+        position: Position,
+        trigger_character: Option<String>,
+    ) -> Vec<CompletionItem> {
+        let uri = Url::parse(&format!("file:///shaders/{}", file_name)).unwrap();
+        let mut language_data = match shading_language {
+            ShadingLanguage::Glsl => ServerLanguageData::glsl(),
+            ShadingLanguage::Hlsl => ServerLanguageData::hlsl(),
+            ShadingLanguage::Wgsl => ServerLanguageData::wgsl(),
+        };
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                shading_language,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        language_data
+            .recolt_completion(&uri, cached_file, position, trigger_character)
+            .unwrap()
+            .items
+    }
+
+    fn recolt_glsl_completion(
+        file_name: &str,
+        content: &str,
+        position: Position,
+        trigger_character: Option<String>,
+    ) -> Vec<String> {
+        recolt_test_completion(
+            ShadingLanguage::Glsl,
+            file_name,
+            content,
+            position,
+            trigger_character,
+        )
+        .into_iter()
+        .map(|item| item.label)
+        .collect()
+    }
+
+    #[test]
+    fn completion_glsl_buffer_runtime_array() {
+        let content = "#version 450\n\
+                       struct Particle {\n\
+                       \x20   vec4 position;\n\
+                       };\n\
+                       layout(std430, binding = 0) buffer Particles {\n\
+                       \x20   Particle particles[];\n\
+                       } particleBuffer;\n\
+                       void main() {\n\
+                       \x20   particleBuffer.particles[0].\n\
+                       }\n";
+        // Indexing the runtime array resolve to its element type.
+        let members = recolt_glsl_completion(
+            "buffer.comp.glsl",
+            content,
+            Position::new(8, 32),
+            Some(".".into()),
+        );
+        assert_eq!(members, vec!["position".to_string()]);
+    }
+
+    #[test]
+    fn completion_inactive_branch() {
+        let content = "#define USE_FAST_PATH 0\n\
+                       #if USE_FAST_PATH\n\
+                       float fastLighting() { return 1.0; }\n\
+                       #else\n\
+                       float slowLighting() { return 0.5; }\n\
+                       #endif\n\
+                       float4 main() : SV_Target {\n\
+                       \x20   return \n\
+                       }\n";
+        let items = recolt_test_completion(
+            ShadingLanguage::Hlsl,
+            "branch.hlsl",
+            content,
+            Position::new(7, 11),
+            None,
+        );
+        assert!(items.iter().any(|item| item.label == "slowLighting"));
+        assert!(!items.iter().any(|item| item.label == "fastLighting"));
+    }
+
+    #[test]
+    fn completion_glsl_64_bits_types() {
+        let content = "#version 450\n\
+                       void main() {\n\
+                       \x20   d\n\
+                       }\n";
+        let items = recolt_glsl_completion("double.comp.glsl", content, Position::new(2, 5), None);
+        assert!(items.contains(&"double".to_string()));
+        assert!(items.contains(&"dmat4".to_string()));
+        assert!(items.contains(&"packDouble2x32".to_string()));
+        // 64-bit integers require their extension.
+        assert!(!items.contains(&"int64_t".to_string()));
+        assert!(!items.contains(&"packUint2x32".to_string()));
+        let content = "#version 450\n\
+                       #extension GL_EXT_shader_explicit_arithmetic_types_int64 : require\n\
+                       void main() {\n\
+                       \x20   u\n\
+                       }\n";
+        let items = recolt_glsl_completion("int64.comp.glsl", content, Position::new(3, 5), None);
+        assert!(items.contains(&"int64_t".to_string()));
+        assert!(items.contains(&"u64vec3".to_string()));
+        assert!(items.contains(&"packUint2x32".to_string()));
+    }
+
+    #[test]
+    fn completion_per_vertex_builtins() {
+        let tesselation = "#version 450\n\
+                           layout(vertices = 3) out;\n\
+                           void main() {\n\
+                           \x20   gl_out[gl_InvocationID].gl_Position = gl_in[gl_InvocationID].\n\
+                           }\n";
+        let geometry = "#version 450\n\
+                        void main() {\n\
+                        \x20   vec4 position = gl_in[0].\n\
+                        }\n";
+        for (file_name, content, position) in [
+            ("builtins.tesc.glsl", tesselation, Position::new(3, 65)),
+            ("builtins.geom.glsl", geometry, Position::new(2, 29)),
+        ] {
+            let members = recolt_glsl_completion(file_name, content, position, Some(".".into()));
+            assert!(
+                members.contains(&"gl_Position".to_string()),
+                "{}",
+                file_name
+            );
+            assert!(
+                members.contains(&"gl_PointSize".to_string()),
+                "{}",
+                file_name
+            );
+        }
+        // Builtins are only available in their stages.
+        let tesselation_symbols =
+            recolt_glsl_completion("builtins.tesc.glsl", tesselation, Position::new(3, 4), None);
+        assert!(tesselation_symbols.contains(&"gl_in".to_string()));
+        assert!(tesselation_symbols.contains(&"gl_InvocationID".to_string()));
+        let fragment_symbols =
+            recolt_glsl_completion("builtins.frag.glsl", tesselation, Position::new(3, 4), None);
+        assert!(!fragment_symbols.contains(&"gl_in".to_string()));
+    }
+
+    #[test]
+    fn completion_entry_point_stage() {
+        let has_label =
+            |items: &Vec<CompletionItem>, label: &str| items.iter().any(|item| item.label == label);
+        // HLSL stage from the shader attribute.
+        let content = "[shader(\"vertex\")]\n\
+                       float4 main(float4 position : POSITION) : SV_Position {\n\
+                       \x20   \n\
+                       \x20   return position;\n\
+                       }\n";
+        let items = recolt_test_completion(
+            ShadingLanguage::Hlsl,
+            "entry.hlsl",
+            content,
+            Position::new(2, 4),
+            None,
+        );
+        assert!(!has_label(&items, "clip"));
+        assert!(!has_label(&items, "ddx"));
+        assert!(has_label(&items, "saturate"));
+        let items = recolt_test_completion(
+            ShadingLanguage::Hlsl,
+            "entry.hlsl",
+            &content.replace("vertex", "pixel"),
+            Position::new(2, 4),
+            None,
+        );
+        assert!(has_label(&items, "clip"));
+        // WGSL stage from the entry point attribute.
+        let content = "@compute @workgroup_size(64)\n\
+                       fn main() {\n\
+                       \x20   \n\
+                       }\n";
+        let items = recolt_test_completion(
+            ShadingLanguage::Wgsl,
+            "entry.wgsl",
+            content,
+            Position::new(2, 4),
+            None,
+        );
+        assert!(!has_label(&items, "dpdx"));
+        assert!(has_label(&items, "workgroupBarrier"));
+        // Entry points of several stages don't filter anything.
+        let content = format!("@fragment\nfn fragment() {{}}\n{}", content);
+        let items = recolt_test_completion(
+            ShadingLanguage::Wgsl,
+            "entry.wgsl",
+            &content,
+            Position::new(4, 4),
+            None,
+        );
+        assert!(has_label(&items, "dpdx"));
+    }
+
+    #[test]
+    fn completion_glsl_default_stage() {
+        let uri = Url::parse("file:///shaders/builtins.glsl").unwrap();
+        let content = "#version 450\n\
+                       void main() {\n\
+                       \x20   g\n\
+                       }\n";
+        let get_labels = |default_stage: Option<ShaderStage>| -> Vec<String> {
+            let mut language_data = ServerLanguageData::glsl();
+            language_data.config.glsl.defaultStage = default_stage;
+            let cached_file = language_data
+                .watched_files
+                .watch_file(
+                    &uri,
+                    ShadingLanguage::Glsl,
+                    &content.to_string(),
+                    &mut language_data.symbol_provider,
+                    &language_data.config,
+                )
+                .unwrap();
+            language_data
+                .recolt_completion(&uri, cached_file, Position::new(2, 5), None)
+                .unwrap()
+                .items
+                .into_iter()
+                .map(|item| item.label)
+                .collect()
+        };
+        // Files without stage extension list builtins of all stages, unless a default stage is set.
+        assert!(get_labels(None).contains(&"gl_in".to_string()));
+        assert!(!get_labels(Some(ShaderStage::Fragment)).contains(&"gl_in".to_string()));
+        assert!(get_labels(Some(ShaderStage::Geometry)).contains(&"gl_in".to_string()));
+    }
+
+    #[test]
+    fn completion_wave_matrix_shader_model() {
+        let uri = Url::parse("file:///shaders/wave-matrix.hlsl").unwrap();
+        let content = "void main() {\n\
+                       \x20   WaveM\n\
+                       }\n";
+        let get_labels = |shader_model: HlslShaderModel| -> Vec<String> {
+            let mut language_data = ServerLanguageData::hlsl();
+            language_data.config.hlsl.shaderModel = shader_model;
+            let cached_file = language_data
+                .watched_files
+                .watch_file(
+                    &uri,
+                    ShadingLanguage::Hlsl,
+                    &content.to_string(),
+                    &mut language_data.symbol_provider,
+                    &language_data.config,
+                )
+                .unwrap();
+            language_data
+                .recolt_completion(&uri, cached_file, Position::new(1, 9), None)
+                .unwrap()
+                .items
+                .into_iter()
+                .map(|item| item.label)
+                .filter(|label| label.starts_with("WaveMatrix"))
+                .collect()
+        };
+        let labels = get_labels(HlslShaderModel::ShaderModel6_8);
+        for label in [
+            "WaveMatrixLeft",
+            "WaveMatrixRight",
+            "WaveMatrixAccumulator",
+            "WaveMatrixLeftColAcc",
+            "WaveMatrixRightRowAcc",
+        ] {
+            assert!(labels.iter().any(|item| item == label), "Missing {}", label);
+        }
+        assert!(get_labels(HlslShaderModel::ShaderModel6_7).is_empty());
+    }
+
+    #[test]
+    fn completion_pragma() {
+        let content = "#version 450\n\
+                       #pragma o\n";
+        let items = recolt_test_completion(
+            ShadingLanguage::Glsl,
+            "pragma.frag.glsl",
+            content,
+            Position::new(1, 9),
+            None,
+        );
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(labels, vec!["once", "shader_stage", "warning"]);
+    }
+
+    #[test]
+    fn completion_math_constants() {
+        let uri = Url::parse("file:///shaders/constants.wgsl").unwrap();
+        let content = "fn main() {\n\
+                       \x20   let angle = P\n\
+                       }\n";
+        let mut language_data = ServerLanguageData::wgsl();
+        language_data.config.mathConstants = HashMap::from([
+            ("PI".into(), "3.14159265358979".into()),
+            ("TAU".into(), "6.28318530717958".into()),
+        ]);
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Wgsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let items = language_data
+            .recolt_completion(&uri, cached_file, Position::new(1, 17), None)
+            .unwrap()
+            .items;
+        let pi = items.iter().find(|item| item.label == "PI").unwrap();
+        assert_eq!(pi.kind, Some(CompletionItemKind::CONSTANT));
+        assert_eq!(
+            pi.label_details.as_ref().unwrap().description.as_deref(),
+            Some("= 3.14159265358979")
+        );
+        match &pi.documentation {
+            Some(lsp_types::Documentation::MarkupContent(content)) => {
+                assert!(content.value.contains("const f32 PI = 3.14159265358979;"))
+            }
+            _ => panic!("Expected markdown documentation"),
+        }
+        assert!(items.iter().any(|item| item.label == "TAU"));
+        // Not builtin, so only listed when configured.
+        let items = recolt_test_completion(
+            ShadingLanguage::Wgsl,
+            "constants.wgsl",
+            content,
+            Position::new(1, 17),
+            None,
+        );
+        assert!(!items.iter().any(|item| item.label == "PI"));
+    }
+
+    #[test]
+    fn completion_all_stages() {
+        let uri = Url::parse("file:///shaders/builtins.frag.glsl").unwrap();
+        let content = "#version 450\n\
+                       void main() {\n\
+                       \x20   g\n\
+                       }\n";
+        let mut language_data = ServerLanguageData::glsl();
+        language_data.config.completionAllStages = true;
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let items = language_data
+            .recolt_completion(&uri, cached_file, Position::new(2, 5), None)
+            .unwrap()
+            .items;
+        // Builtins filtered out by the stage are listed with their stages.
+        let item = items.iter().find(|item| item.label == "gl_in").unwrap();
+        assert!(item
+            .label_details
+            .as_ref()
+            .and_then(|label_details| label_details.detail.as_ref())
+            .is_some_and(|detail| detail.contains("geometry")));
+        let item = items
+            .iter()
+            .find(|item| item.label == "gl_FragCoord")
+            .unwrap();
+        assert!(item
+            .label_details
+            .as_ref()
+            .is_some_and(|label_details| label_details.detail.is_none()));
+    }
+
+    #[test]
+    fn completion_hlsl_2021_logical() {
+        let content = "float4 main(float4 color : COLOR0) : SV_Target {\n\
+                       \x20   bool2 mask = and(color.xy > 0.5, color.zw > 0.5);\n\
+                       \x20   float2 value = s\n\
+                       }\n";
+        let items = recolt_test_completion(
+            ShadingLanguage::Hlsl,
+            "logical.hlsl",
+            content,
+            Position::new(2, 20),
+            None,
+        );
+        let select = items.iter().find(|item| item.label == "select").unwrap();
+        assert_eq!(select.kind, Some(CompletionItemKind::FUNCTION));
+        assert_eq!(
+            select.insert_text.as_deref(),
+            Some("select(${1:cond}, ${2:t}, ${3:f})")
+        );
+        assert!(items.iter().any(|item| item.label == "and"));
+        assert!(items.iter().any(|item| item.label == "or"));
+    }
+
+    #[test]
+    fn completion_min_prefix_length() {
+        let uri = Url::parse("file:///shaders/prefix.hlsl").unwrap();
+        let content = "float4 main(float4 color : COLOR0) : SV_Target {\n\
+                       \x20   float value = sat\n\
+                       \x20   return color.\n\
+                       }\n";
+        let mut language_data = ServerLanguageData::hlsl();
+        language_data.config.completionMinPrefixLength = 3;
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let mut completion = |position: Position, trigger_character: Option<&str>| {
+            language_data
+                .recolt_completion(
+                    &uri,
+                    Rc::clone(&cached_file),
+                    position,
+                    trigger_character.map(|c| c.to_string()),
+                )
+                .unwrap()
+        };
+        // Below the threshold, client should request again while typing.
+        for character in [18, 19, 20] {
+            let list = completion(Position::new(1, character), None);
+            assert!(list.is_incomplete);
+            assert!(list.items.is_empty());
+        }
+        let list = completion(Position::new(1, 21), None);
+        assert!(!list.is_incomplete);
+        assert!(list.items.iter().any(|item| item.label == "saturate"));
+        // Member access is never delayed.
+        let list = completion(Position::new(2, 17), Some("."));
+        assert!(list.items.iter().any(|item| item.label == "xyzw"));
+        let list = completion(Position::new(2, 17), None);
+        assert!(!list.items.is_empty());
+    }
+
+    #[test]
+    fn completion_member_access() {
+        let declarations = "struct Light {\n\
+                            \x20   float3 position;\n\
+                            \x20   float intensity;\n\
+                            };\n\
+                            struct Scene {\n\
+                            \x20   Light light;\n\
+                            };\n\
+                            float4 main() : SV_Target {\n\
+                            \x20   Scene scene;\n\
+                            \x20   float4 color;\n";
+        let recolt_member_completion = |expression: &str| -> Vec<String> {
+            let content = format!("{}    float value = {}\n}}\n", declarations, expression);
+            recolt_test_completion(
+                ShadingLanguage::Hlsl,
+                "members.hlsl",
+                &content,
+                Position::new(10, 18 + expression.len() as u32),
+                Some(".".into()),
+            )
+            .into_iter()
+            .map(|item| item.label)
+            .collect()
+        };
+        assert_eq!(recolt_member_completion("scene."), vec!["light"]);
+        assert_eq!(
+            recolt_member_completion("scene.light."),
+            vec!["position", "intensity"]
+        );
+        assert_eq!(
+            recolt_member_completion("scene.light.position."),
+            vec!["x", "y", "z", "xyz", "r", "g", "b", "rgb"]
+        );
+        assert_eq!(
+            recolt_member_completion("color.xy."),
+            vec!["x", "y", "xy", "r", "g", "rg"]
+        );
+        // Unknown type fallback to all symbols.
+        let symbols = recolt_member_completion("unknown.");
+        assert!(symbols.contains(&"scene".to_string()));
+        assert!(symbols.contains(&"abs".to_string()));
+    }
+
+    #[test]
+    fn completion_interface_requirements() {
+        let content = "interface ILight {\n\
+                       \x20   float3 illuminate(float3 position);\n\
+                       \x20   float getIntensity();\n\
+                       };\n\
+                       float4 main() : SV_Target {\n\
+                       \x20   ILight light;\n\
+                       \x20   float value = light.\n\
+                       }\n";
+        let labels: Vec<String> = recolt_test_completion(
+            ShadingLanguage::Hlsl,
+            "interface.hlsl",
+            content,
+            Position::new(6, 24),
+            Some(".".into()),
+        )
+        .into_iter()
+        .map(|item| item.label)
+        .collect();
+        assert_eq!(labels, vec!["illuminate", "getIntensity"]);
+    }
+
+    #[test]
+    fn completion_generic_constraint() {
+        let content = "interface ILight {\n\
+                       \x20   associatedtype Falloff;\n\
+                       \x20   float getIntensity();\n\
+                       };\n\
+                       float shade<T : ILight>(T light) {\n\
+                       \x20   return light.\n\
+                       }\n";
+        let labels: Vec<String> = recolt_test_completion(
+            ShadingLanguage::Hlsl,
+            "generic.hlsl",
+            content,
+            Position::new(5, 17),
+            Some(".".into()),
+        )
+        .into_iter()
+        .map(|item| item.label)
+        .collect();
+        assert_eq!(labels, vec!["getIntensity"]);
+    }
+
+    #[test]
+    fn completion_atomic_counter() {
+        let content = "#version 460\n\
+                       layout(binding = 0, offset = 4) uniform atomic_uint counter;\n\
+                       void main() {\n\
+                       \x20   uint index = atomicCounterI\n\
+                       }\n";
+        let items = recolt_test_completion(
+            ShadingLanguage::Glsl,
+            "atomic.comp.glsl",
+            content,
+            Position::new(3, 31),
+            None,
+        );
+        let increment = items
+            .iter()
+            .find(|item| item.label == "atomicCounterIncrement")
+            .expect("Missing atomicCounterIncrement");
+        assert_eq!(
+            increment
+                .label_details
+                .as_ref()
+                .and_then(|details| details.description.clone()),
+            Some("uint atomicCounterIncrement(atomic_uint c)".into())
+        );
+        let counter = items
+            .iter()
+            .find(|item| item.label == "counter")
+            .expect("Missing counter");
+        match &counter.documentation {
+            Some(lsp_types::Documentation::MarkupContent(content)) => assert!(content
+                .value
+                .contains("layout(binding = 0, offset = 4) uniform atomic_uint counter")),
+            _ => panic!("Missing documentation for counter"),
+        }
+    }
+
+    #[test]
+    fn completion_hlsl_samplers() {
+        let content = "SamplerState linearSampler : register(s0);\n\
+                       SamplerComparisonState shadowSampler : register(s1, space1);\n\
+                       SamplerState pointSampler {\n\
+                       \x20   Filter = MIN_MAG_MIP_POINT;\n\
+                       };\n\
+                       Texture2D tex : register(t0);\n\
+                       float4 main(float2 uv : TEXCOORD0) : SV_Target {\n\
+                       \x20   return tex.Sample(\n\
+                       }\n";
+        let items = recolt_test_completion(
+            ShadingLanguage::Hlsl,
+            "samplers.hlsl",
+            content,
+            Position::new(7, 22),
+            None,
+        );
+        let find_documentation = |label: &str| -> String {
+            let item = items
+                .iter()
+                .find(|item| item.label == label)
+                .unwrap_or_else(|| panic!("Missing completion for {}", label));
+            match &item.documentation {
+                Some(lsp_types::Documentation::MarkupContent(content)) => content.value.clone(),
+                _ => panic!("Missing documentation for {}", label),
+            }
+        };
+        assert!(find_documentation("linearSampler")
+            .contains("SamplerState linearSampler : register(s0)"));
+        assert!(find_documentation("shadowSampler")
+            .contains("SamplerComparisonState shadowSampler : register(s1, space1)"));
+        assert!(find_documentation("pointSampler").contains("Filter = MIN_MAG_MIP_POINT"));
+        assert!(items
+            .iter()
+            .any(|item| item.label == "SamplerComparisonState"));
+    }
+
+    #[test]
+    fn completion_intrinsic_snippet() {
+        let intrinsics =
+            shader_sense::symbols::symbols::parse_default_shader_intrinsics(ShadingLanguage::Hlsl);
+        let mul = intrinsics.find_symbol(&"mul".into()).unwrap();
+        assert_eq!(mul.snippet, Some("mul(${1:mat}, ${2:vec})".into()));
+    }
+
+    #[test]
+    fn completion_commit_characters() {
+        let uri = Url::parse("file:///shaders/commit.frag.glsl").unwrap();
+        let content = "#version 450\n\
+                       void main() {\n\
+                       \x20   \n\
+                       }\n";
+        let mut language_data = ServerLanguageData::glsl();
+        language_data.config.completionCommitCharacters =
+            HashMap::from([(ShadingLanguage::Glsl, vec!["(".to_string()])]);
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let items = language_data
+            .recolt_completion(&uri, cached_file, Position::new(2, 4), None)
+            .unwrap()
+            .items;
+        let abs = items.iter().find(|item| item.label == "abs").unwrap();
+        assert_eq!(abs.commit_characters, Some(vec!["(".to_string()]));
+    }
+
+    #[test]
+    fn completion_expected_return_type() {
+        let content = "void main(float3 v) {\n\
+                       \x20   uint3 u = asu\n\
+                       }\n";
+        let items = recolt_test_completion(
+            ShadingLanguage::Hlsl,
+            "expected.hlsl",
+            content,
+            Position::new(1, 17),
+            None,
+        );
+        let asuint = items.iter().find(|item| item.label == "asuint").unwrap();
+        assert_eq!(asuint.sort_text, Some("0asuint".into()));
+        let description = asuint.label_details.as_ref().unwrap().description.as_ref();
+        assert!(description.unwrap().starts_with("uintn asuint("));
+        // No bias for functions not returning the type.
+        let abs = items.iter().find(|item| item.label == "abs").unwrap();
+        assert_eq!(abs.sort_text, None);
+    }
+
+    #[test]
+    fn completion_hlsl_semantics() {
+        let content = "struct VSOutput {\n\
+                       \x20   float4 pos : SV_Position;\n\
+                       \x20   float3 data : MY_DATA;\n\
+                       \x20   float2 uv : \n\
+                       };\n\
+                       float4 main(VSOutput input, uint id : S) : SV_Target {\n\
+                       \x20   float x = input.pos.x > 0 ? 1.0 : \n\
+                       \x20   return x;\n\
+                       }\n";
+        let get_labels = |position: Position| -> Vec<String> {
+            recolt_test_completion(
+                ShadingLanguage::Hlsl,
+                "semantics.hlsl",
+                content,
+                position,
+                Some(":".into()),
+            )
+            .into_iter()
+            .map(|item| item.label)
+            .collect()
+        };
+        // Struct member, with user semantics of the file.
+        let labels = get_labels(Position::new(3, 16));
+        assert!(labels.contains(&"SV_Position".into()));
+        assert!(labels.contains(&"MY_DATA".into()));
+        assert!(!labels.contains(&"main".into()));
+        assert_eq!(
+            labels.iter().filter(|label| *label == "COLOR[n]").count(),
+            1
+        );
+        // Parameter, while typing the semantic.
+        let items = recolt_test_completion(
+            ShadingLanguage::Hlsl,
+            "semantics.hlsl",
+            content,
+            Position::new(5, 39),
+            None,
+        );
+        let texcoord = items
+            .iter()
+            .find(|item| item.label == "TEXCOORD[n]")
+            .unwrap();
+        assert_eq!(texcoord.insert_text.as_deref(), Some("TEXCOORD${1:0}"));
+        assert!(items.iter().all(|item| item.label != "abs"));
+        // Ternary operator.
+        assert!(get_labels(Position::new(6, 38)).is_empty());
+    }
+
+    #[test]
+    fn completion_wgsl_address_spaces() {
+        let content = "var<\n\
+                       var<storage, r> data: array<f32>;\n\
+                       fn main(p: ptr<storage, array<f32>, \n\
+                       \x20   let x = 1 < \n\
+                       }\n";
+        let get_labels = |position: Position, trigger_character: Option<String>| -> Vec<String> {
+            recolt_test_completion(
+                ShadingLanguage::Wgsl,
+                "address_space.wgsl",
+                content,
+                position,
+                trigger_character,
+            )
+            .into_iter()
+            .map(|item| item.label)
+            .collect()
+        };
+        assert_eq!(
+            get_labels(Position::new(0, 4), Some("<".into())),
+            vec!["function", "private", "workgroup", "uniform", "storage"]
+        );
+        // Access mode after the address space, or after the pointee type for pointers.
+        assert_eq!(
+            get_labels(Position::new(1, 14), None),
+            vec!["read", "write", "read_write"]
+        );
+        assert_eq!(
+            get_labels(Position::new(2, 36), None),
+            vec!["read", "write", "read_write"]
+        );
+        // Less than operator.
+        assert!(get_labels(Position::new(3, 16), Some("<".into())).is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+use std::cell::RefCell;
+
+use lsp_types::{request::Request, TextDocumentIdentifier, Url};
+use serde::{Deserialize, Serialize};
+
+use super::{ServerFileCacheHandle, ServerLanguageData};
+
+#[derive(Debug)]
+pub enum DependenciesRequest {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependenciesParams {
+    #[serde(flatten)]
+    pub text_document: TextDocumentIdentifier,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShaderDependency {
+    pub uri: Url,
+    pub included_by: Url,
+    pub depth: u32, // 1 for includes of the requested file.
+}
+
+impl Request for DependenciesRequest {
+    type Params = DependenciesParams;
+    type Result = Vec<ShaderDependency>;
+    const METHOD: &'static str = "shader-validator/dependencies";
+}
+
+impl ServerLanguageData {
+    // Includes found during last validation of the file, in include order.
+    pub fn recolt_dependencies(
+        &self,
+        cached_file: &ServerFileCacheHandle,
+    ) -> Vec<ShaderDependency> {
+        RefCell::borrow(cached_file)
+            .includers
+            .iter()
+            .filter_map(|(path, includer, depth)| {
+                Some(ShaderDependency {
+                    uri: Url::from_file_path(path).ok()?,
+                    included_by: Url::from_file_path(includer).ok()?,
+                    depth: *depth,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, rc::Rc};
+
+    use shader_sense::{include::canonicalize, shader::ShadingLanguage};
+
+    use super::*;
+
+    #[test]
+    fn dependencies_include_level() {
+        let file_path = canonicalize(Path::new(
+            "../shader-sense/test/glsl/include-level.comp.glsl",
+        ))
+        .unwrap();
+        let uri = Url::from_file_path(&file_path).unwrap();
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &std::fs::read_to_string(&file_path).unwrap(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+        let file_name = |uri: &Url| {
+            let path = uri.to_file_path().unwrap();
+            path.file_name().unwrap().to_string_lossy().to_string()
+        };
+        let dependencies: Vec<(String, String, u32)> = language_data
+            .recolt_dependencies(&cached_file)
+            .iter()
+            .map(|dependency| {
+                (
+                    file_name(&dependency.uri),
+                    file_name(&dependency.included_by),
+                    dependency.depth,
+                )
+            })
+            .collect();
+        assert_eq!(
+            dependencies,
+            vec![
+                (
+                    "level0.glsl".to_string(),
+                    "include-level.comp.glsl".to_string(),
+                    1
+                ),
+                ("level1.glsl".to_string(), "level0.glsl".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn dependencies_include_defines() {
+        let file_path = canonicalize(Path::new(
+            "../shader-sense/test/glsl/include-umbrella.frag.glsl",
+        ))
+        .unwrap();
+        let uri = Url::from_file_path(&file_path).unwrap();
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &std::fs::read_to_string(&file_path).unwrap(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let diagnostics = language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+        assert!(diagnostics[&uri].is_empty(), "{:#?}", diagnostics);
+        assert_eq!(RefCell::borrow(&cached_file).dependencies.len(), 3);
+        // Define from features.glsl applies to lighting.glsl included after it.
+        let symbols = language_data.get_all_symbols(Rc::clone(&cached_file));
+        assert!(symbols.find_symbol(&"computeLighting".into()).is_some());
+        assert!(symbols.find_symbol(&"computeUnlit".into()).is_none());
+    }
+}
@@ -1,17 +1,188 @@
 use std::{
     cell::RefCell,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
+    rc::Rc,
 };
 
 use log::{debug, error, info};
-use lsp_types::{Diagnostic, PublishDiagnosticsParams, Url};
+use lsp_types::{
+    CodeDescription, Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, DiagnosticTag,
+    Location, NumberOrString, PublishDiagnosticsParams, Url,
+};
+
+use shader_sense::{
+    include::{canonicalize, Dependencies, IncludeHandler},
+    shader::ShadingLanguage,
+    shader_error::{ShaderDiagnostic, ShaderDiagnosticList, ShaderErrorSeverity, ValidatorError},
+    symbols::symbols::ShaderSymbolData,
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    code_action::get_diagnostic_fix_data, common::shader_range_to_lsp_range,
+    server_file_cache::ServerFileCache, validation_pool::ValidationJob, ServerConnection,
+    ServerFileCacheHandle, ServerLanguageData,
+};
 
-use shader_sense::shader_error::{ShaderErrorSeverity, ValidatorError};
+// Keep learned include roots bounded as each one is searched for every include.
+const MAX_INCLUDE_ROOTS: usize = 16;
 
-use super::{ServerConnection, ServerFileCacheHandle, ServerLanguageData};
+// Lints documented in the README, with their code as heading.
+const LINT_CODES: [&str; 14] = [
+    "largeArray",
+    "reservedIdentifier",
+    "unreachableCode",
+    "syntaxFixes",
+    "callArguments",
+    "blockLayout",
+    "nonUniformSampling",
+    "unassignedOutParameters",
+    "integerDivision",
+    "redefinition",
+    "resourceLimits",
+    "swizzle",
+    "arraySize",
+    "ambiguousEntryPoint",
+];
+const LINT_DOCUMENTATION_URL: &str =
+    "https://github.com/antaalt/shader-sense/tree/main/shader-language-server";
+const CLANG_DIAGNOSTICS_URL: &str = "https://clang.llvm.org/docs/DiagnosticsReference.html";
+
+// Compiler codes can be disabled by number, such as 3206 for X3206.
+fn is_warning_disabled(diagnostic: &ShaderDiagnostic, disabled_warnings: &[String]) -> bool {
+    // Errors can't be disabled, as with compilers.
+    if matches!(diagnostic.severity, ShaderErrorSeverity::Error) {
+        return false;
+    }
+    let Some(code) = &diagnostic.code else {
+        return false;
+    };
+    let number = code
+        .strip_prefix('X')
+        .filter(|number| number.chars().all(|c| c.is_ascii_digit()));
+    disabled_warnings
+        .iter()
+        .any(|warning| warning == code || Some(warning.as_str()) == number)
+}
+
+// Link to the explanation of a diagnostic code, for DXC warning flags & lints.
+fn get_code_description(code: &str) -> Option<CodeDescription> {
+    let href = if let Some(flag) = code.strip_prefix("-W") {
+        format!("{}#w{}", CLANG_DIAGNOSTICS_URL, flag.to_lowercase())
+    } else if LINT_CODES.contains(&code) {
+        // Github anchors are lowercase.
+        format!("{}#{}", LINT_DOCUMENTATION_URL, code.to_lowercase())
+    } else {
+        return None;
+    };
+    Url::parse(&href).ok().map(|href| CodeDescription { href })
+}
+
+// Find the range of the include directive in the file leading to the dependency, even through other headers.
+fn find_include_range(
+    cached_file: &ServerFileCache,
+    dependency_uri: &Url,
+    visited: &mut HashSet<PathBuf>,
+) -> Option<lsp_types::Range> {
+    for symbol in &cached_file.symbol_cache.functions {
+        if let (ShaderSymbolData::Link { target }, Some(range)) = (&symbol.data, &symbol.range) {
+            if !visited.insert(target.file_path.clone()) {
+                continue;
+            }
+            let is_dependency = Url::from_file_path(&target.file_path)
+                .is_ok_and(|target_uri| target_uri == *dependency_uri);
+            let is_including_dependency = is_dependency
+                || match cached_file.dependencies.get(&target.file_path) {
+                    Some(included_file) => {
+                        find_include_range(&RefCell::borrow(included_file), dependency_uri, visited)
+                            .is_some()
+                    }
+                    None => false,
+                };
+            if is_including_dependency {
+                return Some(shader_range_to_lsp_range(range));
+            }
+        }
+    }
+    None
+}
+
+// Add a summary diagnostic in the main file for each dependency with errors.
+fn add_include_error_summary(
+    diagnostics: &mut HashMap<Url, Vec<Diagnostic>>,
+    uri: &Url,
+    get_include_range: &dyn Fn(&Url) -> Option<lsp_types::Range>,
+) {
+    let mut dependencies: Vec<&Url> = diagnostics
+        .keys()
+        .filter(|dependency_uri| *dependency_uri != uri)
+        .collect();
+    dependencies.sort();
+    let mut summaries = Vec::new();
+    for dependency_uri in dependencies {
+        let errors: Vec<&Diagnostic> = diagnostics[dependency_uri]
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Some(DiagnosticSeverity::ERROR))
+            .collect();
+        if errors.is_empty() {
+            continue;
+        }
+        let file_name = dependency_uri
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .unwrap_or(dependency_uri.path());
+        let mut message = format!(
+            "Error in included header {}: {}",
+            file_name, errors[0].message
+        );
+        if errors.len() > 1 {
+            message.push_str(&format!(" (and {} more)", errors.len() - 1));
+        }
+        summaries.push(Diagnostic {
+            range: get_include_range(dependency_uri).unwrap_or_default(),
+            severity: Some(DiagnosticSeverity::ERROR),
+            message,
+            source: Some("shader-validator".to_string()),
+            related_information: Some(
+                errors
+                    .iter()
+                    .map(|error| DiagnosticRelatedInformation {
+                        location: Location::new(dependency_uri.clone(), error.range),
+                        message: error.message.clone(),
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        });
+    }
+    if !summaries.is_empty() {
+        diagnostics
+            .entry(uri.clone())
+            .or_default()
+            .extend(summaries);
+    }
+}
 
 impl ServerLanguageData {
+    // Remember include roots so that sibling files resolve the same headers.
+    fn learn_include_roots(&mut self, include_roots: &[PathBuf]) {
+        for include_root in include_roots {
+            let is_configured = self.config.includes.iter().any(|include| {
+                canonicalize(Path::new(include)).is_ok_and(|include| include == *include_root)
+            });
+            let include_root = include_root.to_string_lossy().to_string();
+            if is_configured || self.include_roots.contains(&include_root) {
+                continue;
+            }
+            info!("Learned include root {}", include_root);
+            // Oldest roots are dropped first.
+            if self.include_roots.len() >= MAX_INCLUDE_ROOTS {
+                self.include_roots.remove(0);
+            }
+            self.include_roots.push(include_root);
+        }
+    }
     pub fn publish_diagnostic(
         &mut self,
         connection: &ServerConnection,
@@ -19,33 +190,55 @@ impl ServerLanguageData {
         cached_file: &ServerFileCacheHandle,
         version: Option<i32>,
     ) {
-        if self.config.validate {
-            match self.recolt_diagnostic(uri, cached_file) {
-                Ok(diagnostics) => {
-                    info!(
-                        "Publishing diagnostic for file {} ({} diags)",
-                        uri.path(),
-                        diagnostics.len()
+        if self.config.diagnosticMode.is_push() {
+            let diagnostics = self.recolt_diagnostic(uri, cached_file);
+            Self::send_diagnostic(connection, uri, diagnostics, version);
+        } else {
+            debug!("Diagnostic push disabled. {:?}", self.config);
+        }
+    }
+    // Publish diagnostics of a file validated by the validation pool.
+    pub fn publish_validation_result(
+        &mut self,
+        connection: &ServerConnection,
+        uri: &Url,
+        cached_file: &ServerFileCacheHandle,
+        result: Result<(ShaderDiagnosticList, Dependencies), ValidatorError>,
+    ) {
+        let diagnostics = result.map(|(diagnostic_list, dependencies)| {
+            self.watch_validation_dependencies(cached_file, &dependencies);
+            self.process_validation_result(uri, cached_file, diagnostic_list, dependencies)
+        });
+        Self::send_diagnostic(connection, uri, diagnostics, None);
+    }
+    fn send_diagnostic(
+        connection: &ServerConnection,
+        uri: &Url,
+        diagnostics: Result<HashMap<Url, Vec<Diagnostic>>, ValidatorError>,
+        version: Option<i32>,
+    ) {
+        match diagnostics {
+            Ok(diagnostics) => {
+                info!(
+                    "Publishing diagnostic for file {} ({} diags)",
+                    uri.path(),
+                    diagnostics.len()
+                );
+                for diagnostic in diagnostics {
+                    let publish_diagnostics_params = PublishDiagnosticsParams {
+                        uri: diagnostic.0,
+                        diagnostics: diagnostic.1,
+                        version: version,
+                    };
+                    connection.send_notification::<lsp_types::notification::PublishDiagnostics>(
+                        publish_diagnostics_params,
                     );
-                    for diagnostic in diagnostics {
-                        let publish_diagnostics_params = PublishDiagnosticsParams {
-                            uri: diagnostic.0,
-                            diagnostics: diagnostic.1,
-                            version: version,
-                        };
-                        connection
-                            .send_notification::<lsp_types::notification::PublishDiagnostics>(
-                                publish_diagnostics_params,
-                            );
-                    }
                 }
-                Err(err) => connection.send_notification_error(format!(
-                    "Failed to compute diagnostic for file {}: {}",
-                    uri, err
-                )),
             }
-        } else {
-            debug!("Diagnostic disabled. {:?}", self.config);
+            Err(err) => connection.send_notification_error(format!(
+                "Failed to compute diagnostic for file {}: {}",
+                uri, err
+            )),
         }
     }
 
@@ -61,14 +254,122 @@ impl ServerLanguageData {
         );
     }
 
+    // Diagnostics reported instead of validating the file, when validation is disabled or unavailable.
+    fn get_unvalidated_diagnostic(
+        &self,
+        uri: &Url,
+        shading_language: ShadingLanguage,
+    ) -> Option<HashMap<Url, Vec<Diagnostic>>> {
+        if !self.config.is_validation_enabled(shading_language) {
+            // Clear diagnostics that might have been published before it was disabled.
+            info!("Validation is disabled for {:?}", shading_language);
+            return Some(HashMap::from([(uri.clone(), Vec::new())]));
+        }
+        if let (Some(validator_error), false) =
+            (&self.validator_error, self.config.hlsl.glslangFallback)
+        {
+            // Symbols features still work, only tell once per file why nothing is reported.
+            let diagnostic = Diagnostic {
+                range: lsp_types::Range::default(),
+                severity: Some(lsp_types::DiagnosticSeverity::INFORMATION),
+                message: format!(
+                    "Validation is disabled as the validator could not be loaded: {}. Set hlsl.glslangFallback to validate with glslang instead.",
+                    validator_error
+                ),
+                source: Some("shader-validator".to_string()),
+                ..Default::default()
+            };
+            return Some(HashMap::from([(uri.clone(), vec![diagnostic])]));
+        }
+        None
+    }
+    // Params validating a file, targeting the stage of its selected entry point if any.
+    fn get_file_shader_params(
+        &mut self,
+        file_path: &Path,
+        cached_file: &ServerFileCacheHandle,
+    ) -> ValidationParams {
+        let mut validation_params = self.get_file_include_params(file_path);
+        if let (Some(entry_point), None) = (
+            &validation_params.entry_point,
+            validation_params.shader_stage,
+        ) {
+            validation_params.shader_stage = RefCell::borrow(cached_file)
+                .symbol_tree
+                .get_entry_points()
+                .into_iter()
+                .find(|(name, _, _)| name == entry_point)
+                .map(|(_, stage, _)| stage);
+        }
+        validation_params
+    }
+    // Job validating the file in background, none if there is nothing to validate.
+    pub fn get_validation_job(
+        &mut self,
+        uri: &Url,
+        cached_file: &ServerFileCacheHandle,
+    ) -> Option<ValidationJob> {
+        let shading_language = RefCell::borrow(cached_file).shading_language;
+        if self
+            .get_unvalidated_diagnostic(uri, shading_language)
+            .is_some()
+        {
+            return None;
+        }
+        let file_path = uri.to_file_path().ok()?;
+        Some(ValidationJob {
+            uri: uri.clone(),
+            shading_language,
+            validator_name: self.validator_name,
+            content: RefCell::borrow(cached_file).symbol_tree.content.clone(),
+            params: self.get_file_shader_params(&file_path, cached_file),
+        })
+    }
+    // Watch dependencies found by a validation that did not go through the server cache.
+    fn watch_validation_dependencies(
+        &mut self,
+        cached_file: &ServerFileCacheHandle,
+        dependencies: &Dependencies,
+    ) {
+        let shading_language = RefCell::borrow(cached_file).shading_language;
+        dependencies.visit_dependencies(&mut |deps_path: &Path| {
+            let deps_uri = match Url::from_file_path(deps_path) {
+                Ok(deps_uri) => deps_uri,
+                Err(_) => return,
+            };
+            let deps_file = match self.watched_files.get_dependency(&deps_uri) {
+                Some(deps_file) => deps_file,
+                None => match self.watched_files.watch_dependency(
+                    &deps_uri,
+                    shading_language,
+                    &mut self.symbol_provider,
+                    &self.config,
+                ) {
+                    Ok(deps_file) => deps_file,
+                    Err(err) => {
+                        error!("Failed to watch file {} : {:?}", deps_path.display(), err);
+                        return;
+                    }
+                },
+            };
+            RefCell::borrow_mut(cached_file)
+                .dependencies
+                .insert(PathBuf::from(deps_path), deps_file);
+        });
+    }
+
     pub fn recolt_diagnostic(
         &mut self,
         uri: &Url,
         cached_file: &ServerFileCacheHandle,
     ) -> Result<HashMap<Url, Vec<Diagnostic>>, ValidatorError> {
-        let file_path = uri.to_file_path().unwrap();
-        let validation_params = self.config.into_validation_params();
+        self.deferred_files.remove(uri);
         let shading_language = RefCell::borrow(&cached_file).shading_language;
+        if let Some(diagnostics) = self.get_unvalidated_diagnostic(uri, shading_language) {
+            return Ok(diagnostics);
+        }
+        let file_path = uri.to_file_path().unwrap();
+        let validation_params = self.get_file_shader_params(&file_path, cached_file);
         let content = RefCell::borrow(&cached_file).symbol_tree.content.clone();
         debug!("Validating file {}", file_path.display());
         match self.validator.validate_shader(
@@ -103,64 +404,602 @@ impl ServerLanguageData {
             },
         ) {
             Ok((diagnostic_list, dependencies)) => {
-                let mut diagnostics: HashMap<Url, Vec<Diagnostic>> = HashMap::new();
-                for diagnostic in diagnostic_list.diagnostics {
-                    let uri = match diagnostic.file_path {
-                        Some(diagnostic_file_path) => {
-                            Url::from_file_path(&diagnostic_file_path).unwrap()
-                        }
-                        None => uri.clone(),
-                    };
-                    if diagnostic
-                        .severity
-                        .is_required(ShaderErrorSeverity::from(self.config.severity.clone()))
-                    {
-                        let diagnostic = Diagnostic {
-                            range: lsp_types::Range::new(
-                                lsp_types::Position::new(diagnostic.line - 1, diagnostic.pos),
-                                lsp_types::Position::new(diagnostic.line - 1, diagnostic.pos),
-                            ),
-                            severity: Some(match diagnostic.severity {
-                                ShaderErrorSeverity::Hint => lsp_types::DiagnosticSeverity::HINT,
-                                ShaderErrorSeverity::Information => {
-                                    lsp_types::DiagnosticSeverity::INFORMATION
-                                }
-                                ShaderErrorSeverity::Warning => {
-                                    lsp_types::DiagnosticSeverity::WARNING
-                                }
-                                ShaderErrorSeverity::Error => lsp_types::DiagnosticSeverity::ERROR,
-                            }),
-                            message: diagnostic.error,
-                            source: Some("shader-validator".to_string()),
-                            ..Default::default()
-                        };
-                        match diagnostics.get_mut(&uri) {
-                            Some(value) => value.push(diagnostic),
-                            None => {
-                                diagnostics.insert(uri, vec![diagnostic]);
-                            }
-                        };
-                    }
-                }
-                // Clear diagnostic if no errors.
-                if diagnostics.get(&uri).is_none() {
-                    info!(
-                        "No issue found for main file. Clearing previous diagnostic {}",
-                        uri
-                    );
-                    diagnostics.insert(uri.clone(), vec![]);
+                Ok(self.process_validation_result(uri, cached_file, diagnostic_list, dependencies))
+            }
+            Err(err) => Err(err),
+        }
+    }
+    // Add lints & include diagnostics to the validation result, grouped by file.
+    fn process_validation_result(
+        &mut self,
+        uri: &Url,
+        cached_file: &ServerFileCacheHandle,
+        mut diagnostic_list: ShaderDiagnosticList,
+        dependencies: Dependencies,
+    ) -> HashMap<Url, Vec<Diagnostic>> {
+        let file_path = uri.to_file_path().unwrap();
+        let validation_params = self.get_file_include_params(&file_path);
+        let includes = validation_params.includes;
+        let virtual_includes = validation_params.virtual_includes;
+        // Validators do not report circular includes clearly.
+        let mut include_handler = IncludeHandler::new(&file_path, includes, virtual_includes);
+        let (_, include_diagnostics) = include_handler.find_dependencies(
+            &RefCell::borrow(cached_file).symbol_tree.content,
+            &mut |deps_path: &Path| -> Option<String> {
+                let deps_uri = Url::from_file_path(deps_path).ok()?;
+                self.watched_files
+                    .get_dependency(&deps_uri)
+                    .map(|deps_file| RefCell::borrow(&deps_file).symbol_tree.content.clone())
+            },
+        );
+        diagnostic_list
+            .diagnostics
+            .extend(include_diagnostics.diagnostics);
+        RefCell::borrow_mut(cached_file).includers =
+            include_handler.get_dependencies().get_includers().clone();
+        // Keep track of includers, to revalidate them when an include change on disk.
+        let mut dependency_paths = HashSet::new();
+        for file_dependencies in [include_handler.get_dependencies(), &dependencies] {
+            file_dependencies.visit_dependencies(&mut |dependency_path: &Path| {
+                dependency_paths.insert(dependency_path.to_path_buf());
+            });
+        }
+        dependency_paths.remove(&file_path);
+        self.set_dependencies(uri, dependency_paths);
+        if self.config.autoIncludeRoots {
+            self.learn_include_roots(include_handler.get_include_roots());
+        }
+        // Add diagnostics from linting the AST.
+        let lint_params = self.get_file_validation_params(&file_path);
+        match self
+            .symbol_provider
+            .get_lint_diagnostics(&RefCell::borrow(cached_file).symbol_tree, &lint_params)
+        {
+            Ok(lint_diagnostics) => diagnostic_list
+                .diagnostics
+                .extend(lint_diagnostics.diagnostics),
+            Err(err) => error!("Failed to lint file {} : {}", file_path.display(), err),
+        }
+        let symbol_list = self.get_all_symbols(Rc::clone(cached_file));
+        diagnostic_list.diagnostics.extend(
+            self.symbol_provider
+                .get_call_diagnostics(
+                    &RefCell::borrow(cached_file).symbol_tree,
+                    &symbol_list,
+                    &lint_params,
+                )
+                .diagnostics,
+        );
+        diagnostic_list.diagnostics.extend(
+            self.symbol_provider
+                .get_redefinition_diagnostics(
+                    &RefCell::borrow(cached_file).symbol_tree,
+                    &symbol_list,
+                    &lint_params,
+                )
+                .diagnostics,
+        );
+        diagnostic_list.diagnostics.extend(
+            self.symbol_provider
+                .get_entry_point_diagnostics(
+                    &RefCell::borrow(cached_file).symbol_tree,
+                    &lint_params,
+                )
+                .diagnostics,
+        );
+        // Warnings disabled in the file with #pragma warning(disable: code).
+        if !self.config.ignoreWarningPragmas {
+            let disabled_warnings = self
+                .symbol_provider
+                .get_disabled_warnings(&RefCell::borrow(cached_file).symbol_tree, &lint_params);
+            diagnostic_list.diagnostics.retain(|diagnostic| {
+                let is_in_file = diagnostic
+                    .file_path
+                    .as_ref()
+                    .is_none_or(|diagnostic_path| *diagnostic_path == file_path);
+                !is_in_file || !is_warning_disabled(diagnostic, &disabled_warnings)
+            });
+        }
+        let mut diagnostics: HashMap<Url, Vec<Diagnostic>> = HashMap::new();
+        for mut diagnostic in diagnostic_list.diagnostics {
+            if let Some(severity_override) = self
+                .config
+                .get_severity_override(diagnostic.code.as_deref(), &diagnostic.error)
+            {
+                match severity_override.into_severity() {
+                    Some(severity) => diagnostic.severity = severity,
+                    None => continue,
                 }
-                // Add empty diagnostics to dependencies without errors to clear them.
-                dependencies.visit_dependencies(&mut |dep| {
-                    let uri = Url::from_file_path(&dep).unwrap();
-                    if diagnostics.get(&uri).is_none() {
-                        info!("Clearing diagnostic for deps file {}", uri);
-                        diagnostics.insert(uri.clone(), vec![]);
+            }
+            let uri = match diagnostic.file_path {
+                Some(diagnostic_file_path) => Url::from_file_path(&diagnostic_file_path).unwrap(),
+                None => uri.clone(),
+            };
+            if diagnostic
+                .severity
+                .is_required(ShaderErrorSeverity::from(self.config.severity.clone()))
+            {
+                let (end_line, end_pos) =
+                    diagnostic.end.unwrap_or((diagnostic.line, diagnostic.pos));
+                let diagnostic = Diagnostic {
+                    range: lsp_types::Range::new(
+                        lsp_types::Position::new(diagnostic.line - 1, diagnostic.pos),
+                        lsp_types::Position::new(end_line - 1, end_pos),
+                    ),
+                    severity: Some(match diagnostic.severity {
+                        ShaderErrorSeverity::Hint => lsp_types::DiagnosticSeverity::HINT,
+                        ShaderErrorSeverity::Information => {
+                            lsp_types::DiagnosticSeverity::INFORMATION
+                        }
+                        ShaderErrorSeverity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+                        ShaderErrorSeverity::Error => lsp_types::DiagnosticSeverity::ERROR,
+                    }),
+                    message: diagnostic.error,
+                    code_description: diagnostic.code.as_deref().and_then(get_code_description),
+                    code: diagnostic.code.map(NumberOrString::String),
+                    source: Some("shader-validator".to_string()),
+                    tags: diagnostic
+                        .unnecessary
+                        .then(|| vec![DiagnosticTag::UNNECESSARY]),
+                    data: diagnostic.fix.as_ref().and_then(get_diagnostic_fix_data),
+                    ..Default::default()
+                };
+                match diagnostics.get_mut(&uri) {
+                    Some(value) => value.push(diagnostic),
+                    None => {
+                        diagnostics.insert(uri, vec![diagnostic]);
                     }
-                });
-                Ok(diagnostics)
+                };
             }
-            Err(err) => Err(err),
         }
+        // Report header errors in the including file aswell.
+        if self.config.includeErrorSummary {
+            let cached_file = RefCell::borrow(cached_file);
+            add_include_error_summary(&mut diagnostics, uri, &|dependency_uri| {
+                find_include_range(&cached_file, dependency_uri, &mut HashSet::new())
+            });
+        }
+        // Clear diagnostic if no errors.
+        if diagnostics.get(&uri).is_none() {
+            info!(
+                "No issue found for main file. Clearing previous diagnostic {}",
+                uri
+            );
+            diagnostics.insert(uri.clone(), vec![]);
+        }
+        // Add empty diagnostics to dependencies without errors to clear them.
+        dependencies.visit_dependencies(&mut |dep| {
+            let uri = Url::from_file_path(&dep).unwrap();
+            if diagnostics.get(&uri).is_none() {
+                info!("Clearing diagnostic for deps file {}", uri);
+                diagnostics.insert(uri.clone(), vec![]);
+            }
+        });
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shader_sense::shader::{HlslBackend, ShadingLanguage};
+
+    use super::super::server_config::ServerSeverityOverride;
+    use super::*;
+
+    #[test]
+    fn include_error_summary() {
+        let main_uri = Url::parse("file:///shaders/main.hlsl").unwrap();
+        let header_uri = Url::parse("file:///shaders/common/header.hlsl").unwrap();
+        let header_error = Diagnostic {
+            range: lsp_types::Range::new(
+                lsp_types::Position::new(4, 2),
+                lsp_types::Position::new(4, 2),
+            ),
+            severity: Some(DiagnosticSeverity::ERROR),
+            message: "unknown type 'flaot'".into(),
+            ..Default::default()
+        };
+        let header_warning = Diagnostic {
+            severity: Some(DiagnosticSeverity::WARNING),
+            message: "unused variable".into(),
+            ..Default::default()
+        };
+        let include_range = lsp_types::Range::new(
+            lsp_types::Position::new(0, 9),
+            lsp_types::Position::new(0, 29),
+        );
+        let mut diagnostics = HashMap::from([
+            (main_uri.clone(), vec![]),
+            (
+                header_uri.clone(),
+                vec![header_error.clone(), header_warning],
+            ),
+        ]);
+        add_include_error_summary(&mut diagnostics, &main_uri, &|dependency_uri| {
+            assert_eq!(*dependency_uri, header_uri);
+            Some(include_range)
+        });
+        let main_diagnostics = &diagnostics[&main_uri];
+        assert_eq!(main_diagnostics.len(), 1);
+        assert_eq!(main_diagnostics[0].range, include_range);
+        assert_eq!(
+            main_diagnostics[0].message,
+            "Error in included header header.hlsl: unknown type 'flaot'"
+        );
+        let related_information = main_diagnostics[0].related_information.as_ref().unwrap();
+        assert_eq!(related_information.len(), 1);
+        assert_eq!(related_information[0].location.uri, header_uri);
+        assert_eq!(related_information[0].location.range, header_error.range);
+        // Header diagnostics are kept.
+        assert_eq!(diagnostics[&header_uri].len(), 2);
+    }
+
+    #[test]
+    fn include_error_summary_validation() {
+        let file_path = canonicalize(Path::new(
+            "../shader-sense/test/glsl/include-error.frag.glsl",
+        ))
+        .unwrap();
+        let header_path =
+            canonicalize(Path::new("../shader-sense/test/glsl/inc0/broken.glsl")).unwrap();
+        let uri = Url::from_file_path(&file_path).unwrap();
+        let header_uri = Url::from_file_path(&header_path).unwrap();
+        let mut language_data = ServerLanguageData::glsl();
+        language_data.config.includeErrorSummary = true;
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &std::fs::read_to_string(&file_path).unwrap(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let diagnostics = language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+        assert!(!diagnostics[&header_uri].is_empty(), "{:#?}", diagnostics);
+        let summary = diagnostics[&uri]
+            .iter()
+            .find(|diagnostic| diagnostic.message.starts_with("Error in included header"))
+            .unwrap_or_else(|| panic!("Missing summary: {:#?}", diagnostics));
+        // Summary is located on the include directive.
+        assert_eq!(summary.range.start.line, 4);
+        assert!(
+            summary.message.contains("broken.glsl"),
+            "{}",
+            summary.message
+        );
+        assert_eq!(
+            summary.related_information.as_ref().unwrap()[0]
+                .location
+                .uri,
+            header_uri
+        );
+    }
+
+    #[test]
+    fn include_error_summary_no_error() {
+        let main_uri = Url::parse("file:///shaders/main.hlsl").unwrap();
+        let header_uri = Url::parse("file:///shaders/header.hlsl").unwrap();
+        let mut diagnostics = HashMap::from([(
+            header_uri,
+            vec![Diagnostic {
+                severity: Some(DiagnosticSeverity::WARNING),
+                message: "unused variable".into(),
+                ..Default::default()
+            }],
+        )]);
+        add_include_error_summary(&mut diagnostics, &main_uri, &|_| None);
+        assert!(!diagnostics.contains_key(&main_uri));
+    }
+
+    #[test]
+    fn auto_include_roots() {
+        let directory = std::env::temp_dir().join("shader-language-server-auto-include-roots");
+        let first_directory = directory.join("first");
+        let second_directory = directory.join("second");
+        std::fs::create_dir_all(&first_directory).unwrap();
+        std::fs::create_dir_all(&second_directory).unwrap();
+        let header = "float common_value() { return 1.0; }\n";
+        let content = "#version 450\n\
+                       #extension GL_GOOGLE_include_directive : require\n\
+                       #include \"common.glsl\"\n\
+                       layout(location = 0) out vec4 color;\n\
+                       void main() {\n\
+                       \x20   color = vec4(common_value());\n\
+                       }\n";
+        std::fs::write(first_directory.join("common.glsl"), header).unwrap();
+        let first_path = first_directory.join("first.frag.glsl");
+        let second_path = second_directory.join("second.frag.glsl");
+        std::fs::write(&first_path, content).unwrap();
+        std::fs::write(&second_path, content).unwrap();
+
+        let mut language_data = ServerLanguageData::glsl();
+        language_data.config.autoIncludeRoots = true;
+        let validate = |language_data: &mut ServerLanguageData, path: &Path| {
+            let uri = Url::from_file_path(path).unwrap();
+            let cached_file = match language_data.watched_files.get(&uri) {
+                Some(cached_file) => cached_file,
+                None => language_data
+                    .watched_files
+                    .watch_file(
+                        &uri,
+                        ShadingLanguage::Glsl,
+                        &std::fs::read_to_string(path).unwrap(),
+                        &mut language_data.symbol_provider,
+                        &language_data.config,
+                    )
+                    .unwrap(),
+            };
+            let diagnostics = language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+            diagnostics.get(&uri).cloned().unwrap_or_default()
+        };
+        // Header is not reachable from second file until a root is learned.
+        assert!(!validate(&mut language_data, &second_path).is_empty());
+        assert!(validate(&mut language_data, &first_path).is_empty());
+        assert_eq!(
+            language_data.include_roots,
+            vec![canonicalize(&first_directory)
+                .unwrap()
+                .to_string_lossy()
+                .to_string()]
+        );
+        assert!(validate(&mut language_data, &second_path).is_empty());
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn severity_override() {
+        let uri = Url::parse("file:///shaders/override.frag.glsl").unwrap();
+        let content = "#version 450\n\
+                       void main() {\n\
+                       \x20   undeclared_value;\n\
+                       }\n";
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let mut get_severities = |severity_override: ServerSeverityOverride| {
+            language_data.config.severityOverrides =
+                HashMap::from([("UNDECLARED IDENTIFIER".into(), severity_override)]);
+            language_data.recolt_diagnostic(&uri, &cached_file).unwrap()[&uri]
+                .iter()
+                .map(|diagnostic| diagnostic.severity)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            get_severities(ServerSeverityOverride::Hint),
+            vec![Some(DiagnosticSeverity::HINT)]
+        );
+        assert!(get_severities(ServerSeverityOverride::None).is_empty());
+        // Overrides also match the diagnostic code.
+        language_data.config.severityOverrides =
+            HashMap::from([("undeclared-identifier".into(), ServerSeverityOverride::Info)]);
+        let severities: Vec<_> = language_data.recolt_diagnostic(&uri, &cached_file).unwrap()[&uri]
+            .iter()
+            .map(|diagnostic| (diagnostic.severity, diagnostic.code.clone()))
+            .collect();
+        assert_eq!(
+            severities,
+            vec![(
+                Some(DiagnosticSeverity::INFORMATION),
+                Some(NumberOrString::String("undeclared-identifier".into()))
+            )]
+        );
+    }
+
+    #[test]
+    fn validation_disabled_for_language() {
+        let uri = Url::parse("file:///shaders/invalid.hlsl").unwrap();
+        let content = "float4 main() : SV_Target {\n\
+                       \x20   return undeclared;\n\
+                       }\n";
+        let mut language_data = ServerLanguageData::hlsl();
+        language_data.config.validateLanguages = HashMap::from([(ShadingLanguage::Hlsl, false)]);
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        // An empty set is published to clear previous diagnostics.
+        let diagnostics = language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[&uri].is_empty());
+        // Symbols are still available.
+        assert!(RefCell::borrow(&cached_file)
+            .symbol_cache
+            .functions
+            .iter()
+            .any(|symbol| symbol.label == "main"));
+    }
+
+    #[test]
+    fn hlsl_validator_unavailable() {
+        let file_path = canonicalize(Path::new("../shader-sense/test/hlsl/ok.hlsl")).unwrap();
+        let uri = Url::from_file_path(&file_path).unwrap();
+        let mut language_data = ServerLanguageData::hlsl();
+        language_data.validator_error = Some("DXC library not found".into());
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &std::fs::read_to_string(&file_path).unwrap(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let diagnostics = language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+        assert_eq!(diagnostics[&uri].len(), 1);
+        assert_eq!(
+            diagnostics[&uri][0].severity,
+            Some(DiagnosticSeverity::INFORMATION)
+        );
+        assert!(diagnostics[&uri][0]
+            .message
+            .contains("DXC library not found"));
+        assert_eq!(language_data.get_validator_name(), "none");
+        // Symbols are still available.
+        assert!(!language_data
+            .get_all_symbols(Rc::clone(&cached_file))
+            .functions
+            .is_empty());
+        // Fallback validator is used when allowed.
+        language_data.config.hlsl.glslangFallback = true;
+        let diagnostics = language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+        assert!(diagnostics
+            .values()
+            .flatten()
+            .all(|diagnostic| !diagnostic.message.contains("DXC library not found")));
+        assert_ne!(language_data.get_validator_name(), "none");
+    }
+
+    #[test]
+    fn hlsl_glslang_backend() {
+        let file_path = canonicalize(Path::new("../shader-sense/test/hlsl/ok.hlsl")).unwrap();
+        let uri = Url::from_file_path(&file_path).unwrap();
+        let mut language_data = ServerLanguageData::hlsl();
+        // Selecting glslang does not depend on DXC availability.
+        language_data.set_hlsl_backend(HlslBackend::Glslang);
+        assert!(language_data.validator_error.is_none());
+        assert_eq!(language_data.get_validator_name(), "glslang");
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &std::fs::read_to_string(&file_path).unwrap(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let diagnostics = language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+        assert!(diagnostics
+            .values()
+            .all(|diagnostics| diagnostics.is_empty()));
+    }
+
+    #[test]
+    fn pragma_warning_disable() {
+        let uri = Url::parse("file:///shaders/pragma.frag.glsl").unwrap();
+        let content = "#version 450\n\
+                       #pragma warning(disable: 3206 unreachableCode)\n\
+                       void main() {\n\
+                       \x20   return;\n\
+                       \x20   float value = 1.0;\n\
+                       }\n";
+        let get_codes = |ignore_warning_pragmas: bool| -> Vec<NumberOrString> {
+            let mut language_data = ServerLanguageData::glsl();
+            language_data.config.lint.unreachableCode = true;
+            language_data.config.ignoreWarningPragmas = ignore_warning_pragmas;
+            let cached_file = language_data
+                .watched_files
+                .watch_file(
+                    &uri,
+                    ShadingLanguage::Glsl,
+                    &content.to_string(),
+                    &mut language_data.symbol_provider,
+                    &language_data.config,
+                )
+                .unwrap();
+            let diagnostics = language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+            diagnostics[&uri]
+                .iter()
+                .filter_map(|diagnostic| diagnostic.code.clone())
+                .collect()
+        };
+        let unreachable_code = NumberOrString::String("unreachableCode".into());
+        assert!(!get_codes(false).contains(&unreachable_code));
+        assert!(get_codes(true).contains(&unreachable_code));
+        // Errors are never disabled.
+        let error = ShaderDiagnostic {
+            file_path: None,
+            severity: ShaderErrorSeverity::Error,
+            error: "undeclared identifier".into(),
+            line: 1,
+            pos: 0,
+            end: None,
+            unnecessary: false,
+            code: Some("X3004".into()),
+            fix: None,
+        };
+        assert!(!is_warning_disabled(&error, &["3004".into()]));
+        let warning = ShaderDiagnostic {
+            severity: ShaderErrorSeverity::Warning,
+            code: Some("X3206".into()),
+            ..error
+        };
+        assert!(is_warning_disabled(&warning, &["3206".into()]));
+    }
+
+    #[test]
+    fn unreachable_code_unnecessary() {
+        let uri = Url::parse("file:///shaders/unreachable.frag.glsl").unwrap();
+        let content = "#version 450\n\
+                       void main() {\n\
+                       \x20   return;\n\
+                       \x20   float value = 1.0;\n\
+                       }\n";
+        let mut language_data = ServerLanguageData::glsl();
+        language_data.config.lint.unreachableCode = true;
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let diagnostics = language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+        let unreachable = diagnostics[&uri]
+            .iter()
+            .find(|diagnostic| diagnostic.message == "Unreachable code.")
+            .unwrap();
+        assert_eq!(
+            unreachable.range,
+            lsp_types::Range::new(
+                lsp_types::Position::new(3, 4),
+                lsp_types::Position::new(3, 22)
+            )
+        );
+        assert_eq!(unreachable.tags, Some(vec![DiagnosticTag::UNNECESSARY]));
+        // Lints link to their documentation.
+        assert_eq!(
+            unreachable.code,
+            Some(NumberOrString::String("unreachableCode".into()))
+        );
+        assert_eq!(
+            unreachable
+                .code_description
+                .as_ref()
+                .map(|code_description| code_description.href.as_str()),
+            Some("https://github.com/antaalt/shader-sense/tree/main/shader-language-server#unreachablecode")
+        );
+    }
+
+    #[test]
+    fn diagnostic_code_description() {
+        assert_eq!(
+            get_code_description("-Wconversion").map(|code_description| code_description.href),
+            Some(
+                Url::parse("https://clang.llvm.org/docs/DiagnosticsReference.html#wconversion")
+                    .unwrap()
+            )
+        );
+        assert!(get_code_description("unknownCode").is_none());
     }
 }
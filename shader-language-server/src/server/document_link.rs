@@ -0,0 +1,100 @@
+use std::{cell::RefCell, path::Path};
+
+use lsp_types::{DocumentLink, Url};
+
+use shader_sense::symbols::symbols::SymbolError;
+
+use super::{common::shader_range_to_lsp_range, ServerFileCacheHandle, ServerLanguageData};
+
+impl ServerLanguageData {
+    // Link include paths to the included files. Unresolved includes are omitted.
+    pub fn recolt_document_link(
+        &mut self,
+        uri: &Url,
+        cached_file: ServerFileCacheHandle,
+    ) -> Result<Vec<DocumentLink>, SymbolError> {
+        let file_path = uri.to_file_path().unwrap();
+        let includes = self
+            .symbol_provider
+            .get_includes(&RefCell::borrow(&cached_file).symbol_tree);
+        if includes.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut include_handler = self.get_include_handler(&file_path);
+        Ok(includes
+            .into_iter()
+            .filter_map(|(relative_path, range)| {
+                let absolute_path =
+                    include_handler.search_path_in_includes(Path::new(&relative_path))?;
+                Some(DocumentLink {
+                    range: shader_range_to_lsp_range(&range),
+                    target: Some(Url::from_file_path(&absolute_path).ok()?),
+                    tooltip: Some(absolute_path.display().to_string()),
+                    data: None,
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use lsp_types::{Position, Range};
+    use shader_sense::{include::canonicalize, shader::ShadingLanguage};
+
+    use super::*;
+
+    #[test]
+    fn document_link_includes() {
+        let file_path = canonicalize(Path::new(
+            "../shader-sense/test/glsl/document-link.frag.glsl",
+        ))
+        .unwrap();
+        let uri = Url::from_file_path(&file_path).unwrap();
+        let content = "#version 450\n\
+                       #extension GL_GOOGLE_include_directive : require\n\
+                       #include \"inc0/level0.glsl\"\n\
+                       #include \"inc0/missing.glsl\"\n\
+                       #ifdef USE_ONCE\n\
+                       #include <inc0/once.glsl>\n\
+                       #endif\n\
+                       void main() {}\n"
+            .to_string();
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &content,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let links = language_data
+            .recolt_document_link(&uri, Rc::clone(&cached_file))
+            .unwrap();
+        let directory = file_path.parent().unwrap().join("inc0");
+        assert_eq!(
+            links
+                .iter()
+                .map(|link| (
+                    link.range,
+                    link.target.as_ref().unwrap().to_file_path().unwrap()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    Range::new(Position::new(2, 9), Position::new(2, 27)),
+                    directory.join("level0.glsl")
+                ),
+                (
+                    Range::new(Position::new(5, 9), Position::new(5, 25)),
+                    directory.join("once.glsl")
+                ),
+            ]
+        );
+    }
+}
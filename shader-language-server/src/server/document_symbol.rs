@@ -0,0 +1,231 @@
+use std::{cell::RefCell, path::Path};
+
+use lsp_types::{DocumentSymbol, SymbolKind, Url};
+
+use shader_sense::symbols::symbols::{
+    ShaderRange, ShaderSymbol, ShaderSymbolData, ShaderSymbolList, SymbolError,
+};
+
+use super::{common::shader_range_to_lsp_range, ServerFileCacheHandle, ServerLanguageData};
+
+#[allow(deprecated)] // deprecated field is required.
+fn create_document_symbol(
+    symbol: &ShaderSymbol,
+    range: &ShaderRange,
+    kind: SymbolKind,
+    children: Vec<DocumentSymbol>,
+) -> DocumentSymbol {
+    let selection_range = shader_range_to_lsp_range(range);
+    // Range must contain children range.
+    let mut full_range = selection_range;
+    for child in &children {
+        if child.range.end > full_range.end {
+            full_range.end = child.range.end;
+        }
+    }
+    DocumentSymbol {
+        name: symbol.label.clone(),
+        detail: match &symbol.data {
+            ShaderSymbolData::Functions { signatures, .. } => {
+                Some(signatures[0].format(&symbol.label))
+            }
+            ShaderSymbolData::Variables { ty, .. } => Some(ty.clone()),
+            ShaderSymbolData::Constants { value, .. } | ShaderSymbolData::Macro { value, .. } => {
+                Some(value.clone())
+            }
+            _ => None,
+        },
+        kind,
+        tags: None,
+        deprecated: None,
+        range: full_range,
+        selection_range,
+        children: if children.is_empty() {
+            None
+        } else {
+            Some(children)
+        },
+    }
+}
+
+fn create_struct_children(symbol: &ShaderSymbol, range: &ShaderRange) -> Vec<DocumentSymbol> {
+    match &symbol.data {
+        // Members have no range, use the struct one.
+        ShaderSymbolData::Struct { members, methods } => members
+            .iter()
+            .map(|member| {
+                create_document_symbol(&member.as_symbol(), range, SymbolKind::FIELD, vec![])
+            })
+            .chain(methods.iter().map(|method| {
+                create_document_symbol(&method.as_symbol(), range, SymbolKind::METHOD, vec![])
+            }))
+            .collect(),
+        ShaderSymbolData::Interface { methods } => methods
+            .iter()
+            .map(|method| {
+                create_document_symbol(&method.as_symbol(), range, SymbolKind::METHOD, vec![])
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+// Build the outline of a file from its symbols. Locals are nested in their function.
+pub fn build_document_symbols(
+    symbol_list: &ShaderSymbolList,
+    file_path: &Path,
+) -> Vec<DocumentSymbol> {
+    let local_range = |symbol: &ShaderSymbol| -> Option<ShaderRange> {
+        match &symbol.range {
+            Some(range) if range.start.file_path == file_path => Some(range.clone()),
+            _ => None, // Intrinsics & symbols from other files.
+        }
+    };
+    // Function body scope is the first scope declared after its label.
+    let function_ranges: Vec<(&ShaderSymbol, ShaderRange)> = symbol_list
+        .functions
+        .iter()
+        .filter(|symbol| matches!(symbol.data, ShaderSymbolData::Functions { .. }))
+        .filter_map(|symbol| local_range(symbol).map(|range| (symbol, range)))
+        .collect();
+    let find_function = |scope: &ShaderRange| -> Option<usize> {
+        function_ranges
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, range))| range.start <= scope.start)
+            .max_by(|(_, (_, a)), (_, (_, b))| a.start.cmp(&b.start))
+            .map(|(index, _)| index)
+    };
+    let mut function_children: Vec<Vec<DocumentSymbol>> = vec![vec![]; function_ranges.len()];
+    let mut document_symbols = Vec::new();
+    for symbol in &symbol_list.variables {
+        let range = match local_range(symbol) {
+            Some(range) => range,
+            None => continue,
+        };
+        let mut document_symbol =
+            create_document_symbol(symbol, &range, SymbolKind::VARIABLE, vec![]);
+        let function_scope = match &symbol.scope_stack {
+            Some(scope_stack) => scope_stack
+                .first()
+                .and_then(|scope| find_function(scope).map(|index| (index, scope))),
+            None => None,
+        };
+        match function_scope {
+            Some((function_index, scope)) => {
+                // Extend to the end of the body so that the function range contains it.
+                document_symbol.range.end = shader_range_to_lsp_range(scope).end;
+                function_children[function_index].push(document_symbol)
+            }
+            None => document_symbols.push(document_symbol),
+        }
+    }
+    for ((symbol, range), children) in function_ranges.iter().zip(function_children) {
+        document_symbols.push(create_document_symbol(
+            symbol,
+            range,
+            SymbolKind::FUNCTION,
+            children,
+        ));
+    }
+    for symbol in &symbol_list.types {
+        if let Some(range) = local_range(symbol) {
+            let (kind, children) = match symbol.data {
+                ShaderSymbolData::Struct { .. } => {
+                    (SymbolKind::STRUCT, create_struct_children(symbol, &range))
+                }
+                ShaderSymbolData::Interface { .. } => (
+                    SymbolKind::INTERFACE,
+                    create_struct_children(symbol, &range),
+                ),
+                // Local to their function or interface.
+                ShaderSymbolData::TypeParameter { .. } => continue,
+                _ => (SymbolKind::CLASS, vec![]),
+            };
+            document_symbols.push(create_document_symbol(symbol, &range, kind, children));
+        }
+    }
+    // Macros are stored with functions.
+    for symbol in symbol_list
+        .constants
+        .iter()
+        .chain(symbol_list.functions.iter())
+    {
+        if let (ShaderSymbolData::Constants { .. } | ShaderSymbolData::Macro { .. }, Some(range)) =
+            (&symbol.data, local_range(symbol))
+        {
+            document_symbols.push(create_document_symbol(
+                symbol,
+                &range,
+                SymbolKind::CONSTANT,
+                vec![],
+            ));
+        }
+    }
+    document_symbols.sort_by_key(|symbol| symbol.selection_range.start);
+    document_symbols
+}
+
+impl ServerLanguageData {
+    pub fn recolt_document_symbol(
+        &mut self,
+        uri: &Url,
+        cached_file: ServerFileCacheHandle,
+    ) -> Result<Vec<DocumentSymbol>, SymbolError> {
+        let file_path = uri.to_file_path().unwrap();
+        let cached_file = RefCell::borrow(&cached_file);
+        Ok(build_document_symbols(
+            &cached_file.symbol_cache,
+            &file_path,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shader_sense::{symbols::symbols::SymbolProvider, validator::validator::ValidationParams};
+
+    use super::*;
+
+    #[test]
+    fn document_symbol_outline() {
+        let file_path = Path::new("/shaders/outline.hlsl");
+        let content = "#define COUNT 4\n\
+                       struct Light { float3 position; float intensity; };\n\
+                       float4 main(float2 uv : TEXCOORD0) : SV_TARGET {\n\
+                       \x20   float value = 0.0;\n\
+                       \x20   return float4(uv, value, 1.0);\n\
+                       }\n";
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider.create_ast(file_path, content).unwrap();
+        let symbols = symbol_provider
+            .get_all_symbols(&symbol_tree, &ValidationParams::default())
+            .unwrap();
+        let outline = build_document_symbols(&symbols, file_path);
+        let labels: Vec<(&str, SymbolKind)> = outline
+            .iter()
+            .map(|symbol| (symbol.name.as_str(), symbol.kind))
+            .collect();
+        // Intrinsics have no range and are not listed.
+        assert_eq!(
+            labels,
+            vec![
+                ("COUNT", SymbolKind::CONSTANT),
+                ("Light", SymbolKind::STRUCT),
+                ("main", SymbolKind::FUNCTION),
+            ]
+        );
+        let members = outline[1].children.as_ref().unwrap();
+        assert_eq!(members.len(), 2);
+        assert!(members.iter().all(|m| m.kind == SymbolKind::FIELD));
+        let locals: Vec<&str> = outline[2]
+            .children
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect();
+        assert!(locals.contains(&"value"));
+        assert!(outline[2].range.end.line >= 5);
+    }
+}
@@ -0,0 +1,152 @@
+use std::cell::RefCell;
+
+use lsp_types::{FoldingRange, FoldingRangeKind};
+
+use shader_sense::symbols::symbols::SymbolError;
+
+use super::{ServerFileCacheHandle, ServerLanguageData};
+
+// Fold #if/#ifdef/#ifndef blocks, splitting them on #elif/#else.
+// Unbalanced directives are ignored.
+pub fn get_preprocessor_folding_ranges(content: &str) -> Vec<FoldingRange> {
+    let mut ranges = Vec::new();
+    let mut stack: Vec<u32> = Vec::new();
+    let mut push_range = |start_line: u32, end_line: u32| {
+        if end_line > start_line {
+            ranges.push(FoldingRange {
+                start_line,
+                end_line,
+                kind: Some(FoldingRangeKind::Region),
+                ..Default::default()
+            });
+        }
+    };
+    for (line, text) in content.lines().enumerate() {
+        let line = line as u32;
+        let directive = match text.trim_start().strip_prefix('#') {
+            Some(directive) => directive.trim_start(),
+            None => continue,
+        };
+        let directive = directive
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .next()
+            .unwrap_or("");
+        match directive {
+            "if" | "ifdef" | "ifndef" => stack.push(line),
+            "elif" | "else" => {
+                if let Some(start_line) = stack.pop() {
+                    push_range(start_line, line - 1);
+                    stack.push(line);
+                }
+            }
+            "endif" => {
+                if let Some(start_line) = stack.pop() {
+                    push_range(start_line, line - 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    ranges
+}
+
+impl ServerLanguageData {
+    pub fn recolt_folding_range(
+        &mut self,
+        cached_file: ServerFileCacheHandle,
+    ) -> Result<Vec<FoldingRange>, SymbolError> {
+        let cached_file = RefCell::borrow(&cached_file);
+        let mut folding_ranges: Vec<FoldingRange> = cached_file
+            .symbol_tree
+            .get_block_ranges()
+            .iter()
+            .filter_map(|(range, is_comment)| {
+                // Keep closing brace visible.
+                let end_line = if *is_comment {
+                    range.end.line
+                } else {
+                    range.end.line - 1
+                };
+                if end_line > range.start.line {
+                    Some(FoldingRange {
+                        start_line: range.start.line,
+                        end_line,
+                        kind: Some(if *is_comment {
+                            FoldingRangeKind::Comment
+                        } else {
+                            FoldingRangeKind::Region
+                        }),
+                        ..Default::default()
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+        folding_ranges.extend(get_preprocessor_folding_ranges(
+            &cached_file.symbol_tree.content,
+        ));
+        folding_ranges.sort_by_key(|range| (range.start_line, range.end_line));
+        Ok(folding_ranges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Url;
+    use shader_sense::shader::ShadingLanguage;
+
+    use super::*;
+
+    #[test]
+    fn folding_range_scopes_and_preprocessor() {
+        let content = "/* Block\n\
+                       comment */\n\
+                       struct Light {\n\
+                       \x20   float3 position;\n\
+                       \x20   float intensity;\n\
+                       };\n\
+                       #ifdef USE_SHADOW\n\
+                       #if SHADOW_COUNT > 1\n\
+                       float shadow;\n\
+                       #endif\n\
+                       #else\n\
+                       float no_shadow;\n\
+                       #endif\n\
+                       #endif\n\
+                       float4 main() : SV_TARGET {\n\
+                       \x20   float value = 0.0;\n\
+                       \x20   return float4(value, 0.0, 0.0, 1.0);\n\
+                       }\n";
+        let uri = Url::parse("file:///shaders/folding.hlsl").unwrap();
+        let mut language_data = ServerLanguageData::hlsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let ranges: Vec<(u32, u32, FoldingRangeKind)> = language_data
+            .recolt_folding_range(cached_file)
+            .unwrap()
+            .into_iter()
+            .map(|range| (range.start_line, range.end_line, range.kind.unwrap()))
+            .collect();
+        // Unbalanced #endif on line 13 is ignored.
+        assert_eq!(
+            ranges,
+            vec![
+                (0, 1, FoldingRangeKind::Comment),
+                (2, 4, FoldingRangeKind::Region),
+                (6, 9, FoldingRangeKind::Region),
+                (7, 8, FoldingRangeKind::Region),
+                (10, 11, FoldingRangeKind::Region),
+                (14, 16, FoldingRangeKind::Region),
+            ]
+        );
+    }
+}
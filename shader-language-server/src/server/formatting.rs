@@ -0,0 +1,174 @@
+use std::cell::RefCell;
+
+use lsp_types::{FormattingOptions, Position, Range, TextEdit, Url};
+
+use shader_sense::symbols::{symbols::SymbolError, ShaderFormattingOptions};
+
+use super::{ServerFileCacheHandle, ServerLanguageData};
+
+// LSP columns are counted in UTF-16 code units.
+fn get_line_length(line: &str) -> u32 {
+    line.encode_utf16().count() as u32
+}
+
+// Unlike str::lines, keep a last empty line so that both contents split the same way.
+fn split_lines(content: &str) -> Vec<&str> {
+    content
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect()
+}
+
+fn get_end_position(content: &str) -> Position {
+    let line = content.split('\n').count() - 1;
+    let last_line = content.rsplit('\n').next().unwrap_or("");
+    Position::new(line as u32, get_line_length(last_line))
+}
+
+impl ServerLanguageData {
+    pub fn recolt_formatting(
+        &mut self,
+        _uri: &Url,
+        cached_file: ServerFileCacheHandle,
+        options: FormattingOptions,
+        range: Option<Range>,
+    ) -> Result<Option<Vec<TextEdit>>, SymbolError> {
+        let cached_file = RefCell::borrow(&cached_file);
+        let content = &cached_file.symbol_tree.content;
+        let formatting_options = ShaderFormattingOptions {
+            tab_size: options.tab_size,
+            insert_spaces: options.insert_spaces,
+            trim_trailing_whitespace: options.trim_trailing_whitespace.unwrap_or(true),
+            // Only relevant when formatting the whole document.
+            insert_final_newline: range.is_none() && options.insert_final_newline.unwrap_or(false),
+        };
+        let formatted_content = cached_file.symbol_tree.format(&formatting_options);
+        if formatted_content == *content {
+            return Ok(Some(vec![]));
+        }
+        match range {
+            Some(range) => {
+                // Formatting preserve line count, so we can replace the lines from the range.
+                let start_line = range.start.line as usize;
+                let end_line = if range.end.character == 0 && range.end.line > range.start.line {
+                    range.end.line as usize - 1
+                } else {
+                    range.end.line as usize
+                };
+                let lines = split_lines(content);
+                let formatted_lines = split_lines(&formatted_content);
+                let line_count = lines.len().min(formatted_lines.len());
+                if start_line >= line_count {
+                    return Ok(Some(vec![]));
+                }
+                let end_line = end_line.min(line_count - 1);
+                let line_ending = if content.contains("\r\n") {
+                    "\r\n"
+                } else {
+                    "\n"
+                };
+                let original = lines[start_line..=end_line].join(line_ending);
+                let formatted = formatted_lines[start_line..=end_line].join(line_ending);
+                if original == formatted {
+                    Ok(Some(vec![]))
+                } else {
+                    Ok(Some(vec![TextEdit {
+                        range: Range::new(
+                            Position::new(start_line as u32, 0),
+                            Position::new(end_line as u32, get_line_length(lines[end_line])),
+                        ),
+                        new_text: formatted,
+                    }]))
+                }
+            }
+            None => Ok(Some(vec![TextEdit {
+                range: Range::new(Position::new(0, 0), get_end_position(content)),
+                new_text: formatted_content,
+            }])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shader_sense::shader::ShadingLanguage;
+
+    use super::*;
+
+    #[test]
+    fn formatting_utf16_range() {
+        let uri = Url::parse("file:///shaders/emoji.frag.glsl").unwrap();
+        let content = "void main() {\n\
+                       float value = 1.0; // \u{1F600}\n\
+                       }";
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let options = FormattingOptions {
+            tab_size: 4,
+            insert_spaces: true,
+            ..Default::default()
+        };
+        // Emoji is a single char but two UTF-16 code units.
+        let edits = language_data
+            .recolt_formatting(
+                &uri,
+                cached_file,
+                options,
+                Some(Range::new(Position::new(1, 0), Position::new(1, 0))),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].range.end, Position::new(1, 24));
+        assert_eq!(edits[0].new_text, "    float value = 1.0; // \u{1F600}");
+        assert_eq!(get_end_position("a\n\u{1F600}b"), Position::new(1, 3));
+    }
+
+    #[test]
+    fn formatting_range_last_line() {
+        let uri = Url::parse("file:///shaders/last-line.frag.glsl").unwrap();
+        // Last line is whitespace only, without trailing newline.
+        let content = "void main() {\n\
+                       float value = 1.0;\n\
+                       }\n   ";
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let edits = language_data
+            .recolt_formatting(
+                &uri,
+                cached_file,
+                FormattingOptions {
+                    tab_size: 4,
+                    insert_spaces: true,
+                    ..Default::default()
+                },
+                Some(Range::new(Position::new(1, 0), Position::new(3, 3))),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(
+            edits[0].range,
+            Range::new(Position::new(1, 0), Position::new(3, 3))
+        );
+        assert_eq!(edits[0].new_text, "    float value = 1.0;\n}\n");
+    }
+}
@@ -1,12 +1,60 @@
-use std::rc::Rc;
+use std::{path::Path, rc::Rc};
 
+use regex::Regex;
 use shader_sense::symbols::symbols::{ShaderPosition, ShaderRange, ShaderSymbolData, SymbolError};
 
-use lsp_types::{GotoDefinitionResponse, Position, Url};
+use lsp_types::{GotoDefinitionResponse, Location, Position, Url};
 
 use super::{common::shader_range_to_lsp_range, ServerFileCacheHandle, ServerLanguageData};
 
+// Include path at position from the raw text, for directives the tree can't parse.
+fn get_text_include_at_position(content: &str, shader_position: &ShaderPosition) -> Option<String> {
+    let line = content.lines().nth(shader_position.line as usize)?;
+    let include_regex = Regex::new(r#"^\s*#\s*include\s*["<]([^">]*)[">]"#).unwrap();
+    let path = include_regex.captures(line)?.get(1)?;
+    let position = shader_position.pos as usize;
+    // Quotes are part of the path token.
+    if position + 1 >= path.start() && position <= path.end() + 1 {
+        Some(path.as_str().into())
+    } else {
+        None
+    }
+}
+
 impl ServerLanguageData {
+    // Open the included file when the position is on the path of an include directive.
+    // None when not on an include. Unresolved includes have no target, validators report them.
+    fn get_include_goto(
+        &mut self,
+        cached_file: ServerFileCacheHandle,
+        shader_position: &ShaderPosition,
+    ) -> Option<Option<GotoDefinitionResponse>> {
+        let relative_path = {
+            let cached_file = cached_file.borrow();
+            match self
+                .symbol_provider
+                .get_include_at_position(&cached_file.symbol_tree, shader_position)
+            {
+                Some((relative_path, _)) => relative_path,
+                None => {
+                    get_text_include_at_position(&cached_file.symbol_tree.content, shader_position)?
+                }
+            }
+        };
+        let mut include_handler = self.get_include_handler(&shader_position.file_path);
+        Some(
+            include_handler
+                .search_path_in_includes(Path::new(&relative_path))
+                .and_then(|absolute_path| Url::from_file_path(absolute_path).ok())
+                .map(|target_uri| {
+                    GotoDefinitionResponse::Scalar(Location::new(
+                        target_uri,
+                        lsp_types::Range::new(Position::new(0, 0), Position::new(0, 0)),
+                    ))
+                }),
+        )
+    }
+
     pub fn recolt_goto(
         &mut self,
         uri: &Url,
@@ -19,6 +67,10 @@ impl ServerLanguageData {
             line: position.line as u32,
             pos: position.character as u32,
         };
+        if let Some(include_goto) = self.get_include_goto(Rc::clone(&cached_file), &shader_position)
+        {
+            return Ok(include_goto);
+        }
         let all_symbol_list = self.get_all_symbols(Rc::clone(&cached_file));
         let cached_file = cached_file.borrow();
         match self
@@ -78,3 +130,114 @@ impl ServerLanguageData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use shader_sense::shader::ShadingLanguage;
+
+    use super::*;
+
+    #[test]
+    fn goto_include() {
+        let file_path = shader_sense::include::canonicalize(Path::new(
+            "../shader-sense/test/hlsl/goto-include.hlsl",
+        ))
+        .unwrap();
+        let uri = Url::from_file_path(&file_path).unwrap();
+        let content = "#include \"inc0/level0.hlsl\"\n\
+                       #include <inc0/level0.hlsl>\n\
+                       #include \"inc0/missing.hlsl\"\n\
+                       void main() {}\n"
+            .to_string();
+        let mut language_data = ServerLanguageData::hlsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &content,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let mut goto = |position: Position| {
+            language_data
+                .recolt_goto(&uri, Rc::clone(&cached_file), position)
+                .unwrap()
+        };
+        let target = file_path.parent().unwrap().join("inc0").join("level0.hlsl");
+        for position in [
+            Position::new(0, 9),
+            Position::new(0, 16),
+            Position::new(1, 26),
+        ] {
+            match goto(position) {
+                Some(GotoDefinitionResponse::Scalar(location)) => {
+                    assert_eq!(location.uri.to_file_path().unwrap(), target);
+                    assert_eq!(location.range.start, Position::new(0, 0));
+                }
+                response => panic!("Unexpected goto at {:?}: {:?}", position, response),
+            }
+        }
+        // Unresolved include & directive keyword.
+        assert_eq!(goto(Position::new(2, 16)), None);
+        assert_eq!(goto(Position::new(0, 3)), None);
+    }
+
+    #[test]
+    fn goto_text_include() {
+        let position = |pos| ShaderPosition::new("shader.hlsl".into(), 1, pos);
+        let content = "void main() {}\n  # include <common/utils.hlsl> // comment\n";
+        assert_eq!(
+            get_text_include_at_position(content, &position(12)),
+            Some("common/utils.hlsl".into())
+        );
+        assert_eq!(
+            get_text_include_at_position(content, &position(29)),
+            Some("common/utils.hlsl".into())
+        );
+        assert_eq!(get_text_include_at_position(content, &position(4)), None);
+        assert_eq!(get_text_include_at_position(content, &position(33)), None);
+    }
+
+    #[test]
+    fn goto_parameter_shadowing_global() {
+        let uri = Url::parse("file:///shaders/shadowing.frag.glsl").unwrap();
+        let content = "#version 450\n\
+                       float value = 1.0;\n\
+                       float scale(float value) {\n\
+                       \x20   return value * 2.0;\n\
+                       }\n\
+                       void main() {\n\
+                       \x20   float result = scale(value);\n\
+                       }\n"
+        .to_string();
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &content,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let mut target_line = |position: Position| -> Vec<u32> {
+            match language_data
+                .recolt_goto(&uri, Rc::clone(&cached_file), position)
+                .unwrap()
+            {
+                Some(GotoDefinitionResponse::Link(links)) => links
+                    .iter()
+                    .map(|link| link.target_range.start.line)
+                    .collect(),
+                _ => vec![],
+            }
+        };
+        // Inside the function, the parameter shadows the global.
+        assert_eq!(target_line(Position::new(3, 12)), vec![2]);
+        // Outside of it, the global is used.
+        assert_eq!(target_line(Position::new(6, 26)), vec![1]);
+    }
+}
@@ -2,11 +2,279 @@ use std::rc::Rc;
 
 use lsp_types::{Hover, HoverContents, MarkupContent, Position, Url};
 
-use shader_sense::symbols::symbols::{ShaderPosition, SymbolError};
+use shader_sense::{
+    shader::ShadingLanguage,
+    symbols::{
+        get_shader_pragmas,
+        symbols::{
+            ShaderPosition, ShaderRange, ShaderSymbol, ShaderSymbolData, ShaderSymbolList,
+            ShaderVectorType, SymbolError,
+        },
+        SymbolTree,
+    },
+};
 
-use super::{common::shader_range_to_lsp_range, ServerFileCacheHandle, ServerLanguageData};
+use super::{
+    common::{is_identifier, shader_range_to_lsp_range},
+    member::{get_swizzle_type, MemberOwner},
+    ServerFileCacheHandle, ServerLanguageData,
+};
+
+// Sections are only displayed when the symbol has data for them.
+fn format_hover(
+    symbol: &ShaderSymbol,
+    shading_language: ShadingLanguage,
+    other_symbol_count: usize,
+) -> String {
+    let mut sections = vec![format!(
+        "```{}\n{}\n```",
+        shading_language.to_string(),
+        symbol.format()
+    )];
+    if other_symbol_count > 0 {
+        sections.push(format!("(+{} symbol)", other_symbol_count));
+    }
+    if !symbol.description.is_empty() {
+        sections.push(symbol.description.clone());
+    }
+    if let ShaderSymbolData::Macro { external: true, .. } = &symbol.data {
+        sections.push("(defined externally)".into());
+    }
+    if let ShaderSymbolData::Functions {
+        workgroup_size: Some(workgroup_size),
+        ..
+    } = &symbol.data
+    {
+        sections.push(format!("Workgroup size: {}", workgroup_size.join("x")));
+    }
+    match symbol.stages.as_slice() {
+        [] => {}
+        [stage] => sections.push(format!("**Stages:** {} shader only", stage.to_string())),
+        stages => sections.push(format!(
+            "**Stages:** {}",
+            stages
+                .iter()
+                .map(|stage| stage.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        )),
+    }
+    if !symbol.version.is_empty() {
+        sections.push(format!("**Since:** {}", symbol.version));
+    }
+    if let Some(link) = &symbol.link {
+        sections.push(format!("[Online documentation]({})", link));
+    }
+    sections.join("\n\n")
+}
+
+// Identifier around the position from the raw text, for positions the tree can't resolve.
+fn get_text_word_at_position(
+    content: &str,
+    shader_position: &ShaderPosition,
+) -> Option<(String, ShaderRange)> {
+    let line: Vec<char> = content
+        .lines()
+        .nth(shader_position.line as usize)?
+        .chars()
+        .collect();
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let position = (shader_position.pos as usize).min(line.len());
+    let start = line[..position]
+        .iter()
+        .rposition(|c| !is_word(*c))
+        .map_or(0, |separator| separator + 1);
+    let end = line[position..]
+        .iter()
+        .position(|c| !is_word(*c))
+        .map_or(line.len(), |separator| position + separator);
+    let word: String = line[start..end].iter().collect();
+    if !is_identifier(&word) {
+        return None;
+    }
+    let file_path = shader_position.file_path.clone();
+    Some((
+        word,
+        ShaderRange::new(
+            ShaderPosition::new(file_path.clone(), shader_position.line, start as u32),
+            ShaderPosition::new(file_path, shader_position.line, end as u32),
+        ),
+    ))
+}
+
+fn get_edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut distances: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous = distances[0];
+        distances[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous + usize::from(a_char != *b_char);
+            previous = distances[j + 1];
+            distances[j + 1] = substitution.min(distances[j] + 1).min(distances[j + 1] + 1);
+        }
+    }
+    distances[b.len()]
+}
+
+// Same label out of scope first, then case insensitive label, then closest label with a few typos.
+fn find_fuzzy_symbol(symbol_list: &ShaderSymbolList, word: &str) -> Option<ShaderSymbol> {
+    let symbols: Vec<&ShaderSymbol> = symbol_list
+        .iter()
+        .flat_map(|(symbols, _)| symbols.iter())
+        .collect();
+    if let Some(symbol) = symbols.iter().find(|symbol| symbol.label == word) {
+        return Some((*symbol).clone());
+    }
+    if let Some(symbol) = symbols
+        .iter()
+        .find(|symbol| symbol.label.eq_ignore_ascii_case(word))
+    {
+        return Some((*symbol).clone());
+    }
+    let max_distance = (word.len() / 4).min(2);
+    symbols
+        .iter()
+        .map(|symbol| (get_edit_distance(&symbol.label, word), symbol))
+        .filter(|(distance, _)| *distance > 0 && *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, symbol)| (*symbol).clone())
+}
+
+// Builtin semantics matching a semantic, such as TEXCOORD[n] for TEXCOORD1. Semantics are case insensitive.
+fn find_semantic_symbols<'a>(symbols: &'a [ShaderSymbol], semantic: &str) -> Vec<&'a ShaderSymbol> {
+    let unindexed_semantic = semantic.trim_end_matches(|c: char| c.is_ascii_digit());
+    symbols
+        .iter()
+        .filter(|symbol| matches!(symbol.data, ShaderSymbolData::Variables { .. }))
+        .filter(|symbol| match symbol.label.strip_suffix("[n]") {
+            Some(name) => name.eq_ignore_ascii_case(unindexed_semantic),
+            None => symbol.label.eq_ignore_ascii_case(semantic),
+        })
+        .collect()
+}
 
 impl ServerLanguageData {
+    // Semantic after the colon of an HLSL declaration, such as SV_Position.
+    fn get_pragma_hover(
+        symbol_tree: &SymbolTree,
+        shading_language: ShadingLanguage,
+        shader_position: &ShaderPosition,
+    ) -> Option<Hover> {
+        let (name, range) = symbol_tree.get_pragma_at_position(shader_position)?;
+        let pragma = get_shader_pragmas(shading_language)
+            .into_iter()
+            .find(|pragma| pragma.label == name)?;
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: format!(
+                    "```{}\n{}\n```\n\n{}",
+                    shading_language.to_string(),
+                    pragma.usage,
+                    pragma.description
+                ),
+            }),
+            range: Some(shader_range_to_lsp_range(&range)),
+        })
+    }
+    fn get_semantic_hover(
+        &self,
+        symbol_tree: &SymbolTree,
+        shading_language: ShadingLanguage,
+        shader_position: &ShaderPosition,
+    ) -> Option<Hover> {
+        if shading_language != ShadingLanguage::Hlsl {
+            return None;
+        }
+        let (word, word_range) = get_text_word_at_position(&symbol_tree.content, shader_position)?;
+        if !symbol_tree.is_semantic_position(&word_range.start) {
+            return None;
+        }
+        let intrinsics = self.symbol_provider.get_intrinsics_symbol();
+        let symbols = find_semantic_symbols(&intrinsics.constants, &word);
+        let value = match symbols.first() {
+            Some(symbol) => format_hover(symbol, shading_language, symbols.len() - 1),
+            // Unknown system values might be typos, let other hovers handle them.
+            None if word.to_uppercase().starts_with("SV_") => return None,
+            None => format!("```hlsl\n{}\n```\n\n*Unknown semantic*", word),
+        };
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value,
+            }),
+            range: Some(shader_range_to_lsp_range(&word_range)),
+        })
+    }
+
+    // Approximate hover when the symbol under the cursor can't be resolved exactly.
+    fn get_fuzzy_hover(
+        &self,
+        word: &str,
+        word_range: &ShaderRange,
+        symbol_list: &ShaderSymbolList,
+        shading_language: ShadingLanguage,
+    ) -> Option<Hover> {
+        if !self.config.hoverFuzzyFallback {
+            return None;
+        }
+        let symbol = find_fuzzy_symbol(symbol_list, word)?;
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: format!(
+                    "*Approximate match for `{}`*\n\n{}",
+                    word,
+                    format_hover(&symbol, shading_language, 0)
+                ),
+            }),
+            range: Some(shader_range_to_lsp_range(word_range)),
+        })
+    }
+
+    // Swizzle of a vector, display the resulting vector type.
+    fn get_swizzle_hover(
+        &mut self,
+        cached_file: ServerFileCacheHandle,
+        shader_position: ShaderPosition,
+    ) -> Option<Hover> {
+        let symbol_list = self
+            .get_all_symbols(Rc::clone(&cached_file))
+            .filter_scoped_symbol(shader_position.clone());
+        let cached_file = cached_file.borrow();
+        let shading_language = cached_file.shading_language;
+        let chain = self
+            .symbol_provider
+            .get_word_chain_range_at_position(&cached_file.symbol_tree, shader_position)
+            .ok()?;
+        let (swizzle, swizzle_range) = chain.first()?;
+        let vector = match self.resolve_member_owner(shading_language, &chain[1..], &symbol_list)? {
+            MemberOwner::Vector(vector) => vector,
+            MemberOwner::Struct(_) => return None,
+        };
+        let swizzle_type = get_swizzle_type(shading_language, swizzle, &vector)?;
+        let format_type = |vector: &ShaderVectorType| match symbol_list
+            .find_vector_type_symbol(&vector.scalar, vector.dimension)
+        {
+            Some(symbol) => symbol.label,
+            None => format!("{}{}", vector.scalar, vector.dimension),
+        };
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: lsp_types::MarkupKind::Markdown,
+                value: format!(
+                    "```{}\n{} {}\n```\n\nSwizzle of {}",
+                    shading_language.to_string(),
+                    format_type(&swizzle_type),
+                    swizzle,
+                    format_type(&vector)
+                ),
+            }),
+            range: Some(shader_range_to_lsp_range(swizzle_range)),
+        })
+    }
+
     pub fn recolt_hover(
         &mut self,
         uri: &Url,
@@ -19,42 +287,56 @@ impl ServerLanguageData {
             line: position.line as u32,
             pos: position.character as u32,
         };
+        {
+            let cached_file = cached_file.borrow();
+            if let Some(hover) = Self::get_pragma_hover(
+                &cached_file.symbol_tree,
+                cached_file.shading_language,
+                &shader_position,
+            ) {
+                return Ok(Some(hover));
+            }
+            if let Some(hover) = self.get_semantic_hover(
+                &cached_file.symbol_tree,
+                cached_file.shading_language,
+                &shader_position,
+            ) {
+                return Ok(Some(hover));
+            }
+        }
+        if let Some(hover) =
+            self.get_swizzle_hover(Rc::clone(&cached_file), shader_position.clone())
+        {
+            return Ok(Some(hover));
+        }
         let cached_file = cached_file.borrow();
         match self
             .symbol_provider
             .get_word_range_at_position(&cached_file.symbol_tree, shader_position.clone())
         {
             // word_range should be the same as symbol range
-            Ok((word, _word_range)) => match self.watched_files.get(uri) {
+            Ok((word, word_range)) => match self.watched_files.get(uri) {
                 Some(target_cached_file) => {
                     let all_symbol_list = self.get_all_symbols(Rc::clone(&target_cached_file));
                     let target_cached_file = target_cached_file.borrow();
                     let symbol_list = all_symbol_list.filter_scoped_symbol(shader_position);
-                    let matching_symbols = symbol_list.find_symbols(word);
+                    let matching_symbols = symbol_list.find_symbols(word.clone());
                     if matching_symbols.len() == 0 {
-                        Ok(None)
+                        Ok(self.get_fuzzy_hover(
+                            &word,
+                            &word_range,
+                            &all_symbol_list,
+                            target_cached_file.shading_language,
+                        ))
                     } else {
                         let symbol = &matching_symbols[0];
-                        let label = symbol.format();
-                        let description = symbol.description.clone();
-                        let link = match &symbol.link {
-                            Some(link) => format!("[Online documentation]({})", link),
-                            None => "".into(),
-                        };
                         Ok(Some(Hover {
                             contents: HoverContents::Markup(MarkupContent {
                                 kind: lsp_types::MarkupKind::Markdown,
-                                value: format!(
-                                    "```{}\n{}\n```\n{}{}\n\n{}",
-                                    target_cached_file.shading_language.to_string(),
-                                    label,
-                                    if matching_symbols.len() > 1 {
-                                        format!("(+{} symbol)\n\n", matching_symbols.len() - 1)
-                                    } else {
-                                        "".into()
-                                    },
-                                    description,
-                                    link
+                                value: format_hover(
+                                    symbol,
+                                    target_cached_file.shading_language,
+                                    matching_symbols.len() - 1,
                                 ),
                             }),
                             range: match &symbol.range {
@@ -74,7 +356,27 @@ impl ServerLanguageData {
             },
             Err(err) => {
                 if let SymbolError::NoSymbol = err {
-                    Ok(None)
+                    // Parse gaps such as error nodes don't resolve to any identifier.
+                    if !self.config.hoverFuzzyFallback {
+                        return Ok(None);
+                    }
+                    let (word, word_range) = match get_text_word_at_position(
+                        &cached_file.symbol_tree.content,
+                        &shader_position,
+                    ) {
+                        Some(word) => word,
+                        None => return Ok(None),
+                    };
+                    let all_symbol_list = match self.watched_files.get(uri) {
+                        Some(target_cached_file) => self.get_all_symbols(target_cached_file),
+                        None => return Ok(None),
+                    };
+                    Ok(self.get_fuzzy_hover(
+                        &word,
+                        &word_range,
+                        &all_symbol_list,
+                        cached_file.shading_language,
+                    ))
                 } else {
                     Err(err)
                 }
@@ -82,3 +384,404 @@ impl ServerLanguageData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use shader_sense::shader::ShadingLanguage;
+
+    use super::*;
+
+    #[test]
+    fn hover_precise_qualifier() {
+        let uri = Url::parse("file:///shaders/precise.hlsl").unwrap();
+        let content = "precise float4 position = 1.0;\n\
+                       precise float compute(float a) {\n\
+                       \x20   precise float b = a * 2.0;\n\
+                       \x20   return b + position.x;\n\
+                       }\n"
+        .to_string();
+        let mut language_data = ServerLanguageData::hlsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &content,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let mut hover_label = |position: Position| -> String {
+            match language_data
+                .recolt_hover(&uri, Rc::clone(&cached_file), position)
+                .unwrap()
+            {
+                Some(Hover {
+                    contents: HoverContents::Markup(markup),
+                    ..
+                }) => markup.value.lines().nth(1).unwrap().to_string(),
+                _ => panic!("No hover at {:?}", position),
+            }
+        };
+        assert_eq!(hover_label(Position::new(3, 12)), "precise float b");
+        assert_eq!(hover_label(Position::new(3, 16)), "precise float4 position");
+        assert_eq!(
+            hover_label(Position::new(1, 16)),
+            "precise float compute(float a)"
+        );
+    }
+
+    fn hover_value(
+        mut language_data: ServerLanguageData,
+        shading_language: ShadingLanguage,
+        file_name: &str,
+        content: &str,
+        position: Position,
+    ) -> String {
+        let uri = Url::parse(&format!("file:///shaders/{}", file_name)).unwrap();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                shading_language,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        match language_data
+            .recolt_hover(&uri, cached_file, position)
+            .unwrap()
+        {
+            Some(Hover {
+                contents: HoverContents::Markup(markup),
+                ..
+            }) => markup.value,
+            _ => panic!("No hover at {:?}", position),
+        }
+    }
+
+    #[test]
+    fn hover_wgsl_pointer() {
+        let content = "var<storage, read_write> data: array<f32>;\n\
+                       fn increment(value: ptr<function, i32>) {\n\
+                       \x20   *value += 1;\n\
+                       }\n\
+                       fn main() {\n\
+                       \x20   var local: f32 = 1.0;\n\
+                       \x20   let p: ptr<function, f32> = &local;\n\
+                       \x20   *p = data[0];\n\
+                       }\n";
+        let get_hover = |position: Position| -> Vec<String> {
+            hover_value(
+                ServerLanguageData::wgsl(),
+                ShadingLanguage::Wgsl,
+                "pointer.wgsl",
+                content,
+                position,
+            )
+            .split("\n\n")
+            .map(|section| section.to_string())
+            .collect()
+        };
+        assert_eq!(
+            get_hover(Position::new(7, 5)),
+            [
+                "```wgsl\nlet ptr<function, f32> p\n```",
+                "Pointer to f32 in the function address space.",
+            ]
+        );
+        assert_eq!(
+            get_hover(Position::new(2, 6)),
+            [
+                "```wgsl\nptr<function, i32> value\n```",
+                "Pointer to i32 in the function address space.",
+            ]
+        );
+        assert_eq!(
+            get_hover(Position::new(7, 10)),
+            [
+                "```wgsl\nvar<storage, read_write> array<f32> data\n```",
+                "Variable in the storage address space with read_write access.",
+            ]
+        );
+    }
+
+    #[test]
+    fn hover_hlsl_semantics() {
+        let content = "struct VSOutput {\n\
+                       \x20   float4 pos : SV_Position;\n\
+                       \x20   float2 uv : TEXCOORD1;\n\
+                       \x20   float3 data : MY_DATA;\n\
+                       };\n\
+                       [numthreads(8, 1, 1)]\n\
+                       void main(uint3 id : SV_DispatchThreadID, uint index : SV_GroupIndx) {}\n";
+        let get_hover = |position: Position| -> String {
+            hover_value(
+                ServerLanguageData::hlsl(),
+                ShadingLanguage::Hlsl,
+                "semantics.hlsl",
+                content,
+                position,
+            )
+        };
+        let hover = get_hover(Position::new(6, 28));
+        assert!(
+            hover.starts_with(
+                "```hlsl\nuint3 SV_DispatchThreadID\n```\n\nDefines the global thread offset"
+            ),
+            "{}",
+            hover
+        );
+        // Indexed semantics.
+        let hover = get_hover(Position::new(2, 18));
+        assert!(hover.contains("TEXCOORD[n]"), "{}", hover);
+        assert_eq!(
+            get_hover(Position::new(3, 20)),
+            "```hlsl\nMY_DATA\n```\n\n*Unknown semantic*"
+        );
+        // Unknown system value is not reported as a user semantic, but might be a typo.
+        let mut language_data = ServerLanguageData::hlsl();
+        language_data.config.hoverFuzzyFallback = true;
+        let hover = hover_value(
+            language_data,
+            ShadingLanguage::Hlsl,
+            "semantics.hlsl",
+            content,
+            Position::new(6, 60),
+        );
+        assert!(
+            hover.starts_with(
+                "*Approximate match for `SV_GroupIndx`*\n\n```hlsl\nuint SV_GroupIndex"
+            ),
+            "{}",
+            hover
+        );
+        // Type before the colon is not a semantic.
+        assert!(!get_hover(Position::new(6, 12)).contains("semantic"));
+    }
+
+    #[test]
+    fn hover_intrinsic_sections() {
+        let content = "float4 main(float4 color : COLOR0) : SV_Target {\n\
+                       \x20   clip(color.a);\n\
+                       \x20   return color;\n\
+                       }\n";
+        let hover = hover_value(
+            ServerLanguageData::hlsl(),
+            ShadingLanguage::Hlsl,
+            "pixel.hlsl",
+            content,
+            Position::new(1, 6),
+        );
+        let sections: Vec<&str> = hover.split("\n\n").collect();
+        assert!(sections[0].starts_with("```hlsl\nvoid clip("), "{}", hover);
+        assert_eq!(
+            sections[1..],
+            [
+                "Discards the current pixel if the specified value is less than zero.",
+                "**Stages:** fragment shader only",
+                "**Since:** sm1",
+                "[Online documentation](https://learn.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-clip)",
+            ]
+        );
+    }
+
+    #[test]
+    fn hover_workgroup_size() {
+        let hlsl_content = "void helper() {}\n\
+                            [numthreads(8, 8, 1)]\n\
+                            void main(uint3 id : SV_DispatchThreadID) {\n\
+                            \x20   helper();\n\
+                            }\n";
+        let hover = hover_value(
+            ServerLanguageData::hlsl(),
+            ShadingLanguage::Hlsl,
+            "compute.hlsl",
+            hlsl_content,
+            Position::new(2, 6),
+        );
+        assert!(hover.contains("Workgroup size: 8x8x1"), "{}", hover);
+        let hover = hover_value(
+            ServerLanguageData::hlsl(),
+            ShadingLanguage::Hlsl,
+            "compute.hlsl",
+            hlsl_content,
+            Position::new(0, 6),
+        );
+        assert!(!hover.contains("Workgroup size"), "{}", hover);
+        // Omitted dimensions default to 1.
+        let wgsl_content = "@compute @workgroup_size(64, 2)\n\
+                            fn main(@builtin(global_invocation_id) id: vec3<u32>) {\n\
+                            }\n";
+        let hover = hover_value(
+            ServerLanguageData::wgsl(),
+            ShadingLanguage::Wgsl,
+            "compute.wgsl",
+            wgsl_content,
+            Position::new(1, 4),
+        );
+        assert!(hover.contains("Workgroup size: 64x2x1"), "{}", hover);
+    }
+
+    #[test]
+    fn hover_fuzzy_fallback() {
+        let uri = Url::parse("file:///shaders/fuzzy.hlsl").unwrap();
+        let content = "// Fuzzy hover\n\
+                       static const float lightIntensity = 1.0;\n\
+                       float helper() {\n\
+                       \x20   float localValue = 1.0;\n\
+                       \x20   return localValue;\n\
+                       }\n\
+                       #define LIGHT_SCALE (lightIntensity * 2.0)\n\
+                       float4 main() : SV_Target {\n\
+                       \x20   return localValue * lightIntensty;\n\
+                       }\n"
+        .to_string();
+        let mut language_data = ServerLanguageData::hlsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &content,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let hover = |language_data: &mut ServerLanguageData, position: Position| match language_data
+            .recolt_hover(&uri, Rc::clone(&cached_file), position)
+            .unwrap()
+        {
+            Some(Hover {
+                contents: HoverContents::Markup(markup),
+                ..
+            }) => Some(markup.value),
+            _ => None,
+        };
+        // Out of scope variable, misspelled variable & identifier in a macro body.
+        let positions = [
+            Position::new(8, 14),
+            Position::new(8, 28),
+            Position::new(6, 24),
+        ];
+        for position in positions {
+            assert_eq!(hover(&mut language_data, position), None);
+        }
+        language_data.config.hoverFuzzyFallback = true;
+        let hovers: Vec<String> = positions
+            .iter()
+            .map(|position| hover(&mut language_data, *position).unwrap())
+            .collect();
+        assert!(hovers
+            .iter()
+            .all(|hover| hover.starts_with("*Approximate match")));
+        assert!(hovers[0].contains("float localValue"), "{}", hovers[0]);
+        assert!(hovers[1].contains("float lightIntensity"), "{}", hovers[1]);
+        assert!(hovers[2].contains("float lightIntensity"), "{}", hovers[2]);
+        // Exact matches are not marked.
+        let exact = hover(&mut language_data, Position::new(4, 14)).unwrap();
+        assert!(!exact.starts_with("*Approximate match"), "{}", exact);
+    }
+
+    #[test]
+    fn hover_macro() {
+        let content = "#define SCALE 2.0\n\
+                       #define MAD(a, b, c) (a * b + c)\n\
+                       float4 main() : SV_Target {\n\
+                       \x20   return MAD(SCALE, EXTERNAL_SCALE, 1.0);\n\
+                       }\n";
+        let hover = hover_value(
+            ServerLanguageData::hlsl(),
+            ShadingLanguage::Hlsl,
+            "macro.hlsl",
+            content,
+            Position::new(3, 16),
+        );
+        assert_eq!(
+            hover,
+            "```hlsl\n#define SCALE\n```\n\nPreprocessor macro. Expanding to \n```hlsl\n2.0\n```"
+        );
+        let hover = hover_value(
+            ServerLanguageData::hlsl(),
+            ShadingLanguage::Hlsl,
+            "macro.hlsl",
+            content,
+            Position::new(3, 12),
+        );
+        assert!(
+            hover.starts_with("```hlsl\n#define MAD(a, b, c)\n```"),
+            "{}",
+            hover
+        );
+        assert!(hover.contains("(a * b + c)"), "{}", hover);
+        let mut language_data = ServerLanguageData::hlsl();
+        language_data
+            .config
+            .defines
+            .insert("EXTERNAL_SCALE".into(), "4.0".into());
+        let hover = hover_value(
+            language_data,
+            ShadingLanguage::Hlsl,
+            "macro.hlsl",
+            content,
+            Position::new(3, 24),
+        );
+        assert!(hover.contains("4.0"), "{}", hover);
+        assert!(hover.ends_with("(defined externally)"), "{}", hover);
+    }
+
+    #[test]
+    fn hover_pragma() {
+        let content = "#pragma pack_matrix(row_major)\n\
+                       float4 main() : SV_Target { return 0.0; }\n";
+        let hover = hover_value(
+            ServerLanguageData::hlsl(),
+            ShadingLanguage::Hlsl,
+            "pragma.hlsl",
+            content,
+            Position::new(0, 12),
+        );
+        assert!(
+            hover.starts_with("```hlsl\n#pragma pack_matrix(row_major)\n```"),
+            "{}",
+            hover
+        );
+    }
+
+    #[test]
+    fn hover_swizzle() {
+        let hlsl_content = "float4 main(float4 color : COLOR0) : SV_Target {\n\
+                            \x20   float2 uv = color.xy;\n\
+                            \x20   return color.rgb.x;\n\
+                            }\n";
+        let hover = hover_value(
+            ServerLanguageData::hlsl(),
+            ShadingLanguage::Hlsl,
+            "pixel.hlsl",
+            hlsl_content,
+            Position::new(1, 23),
+        );
+        assert_eq!(hover, "```hlsl\nfloat2 xy\n```\n\nSwizzle of float4");
+        let hover = hover_value(
+            ServerLanguageData::hlsl(),
+            ShadingLanguage::Hlsl,
+            "pixel.hlsl",
+            hlsl_content,
+            Position::new(2, 22),
+        );
+        assert_eq!(hover, "```hlsl\nfloat x\n```\n\nSwizzle of float3");
+        let glsl_content = "void main() {\n\
+                            \x20   ivec3 index = ivec3(0);\n\
+                            \x20   ivec2 st = index.st;\n\
+                            }\n";
+        let hover = hover_value(
+            ServerLanguageData::glsl(),
+            ShadingLanguage::Glsl,
+            "compute.comp.glsl",
+            glsl_content,
+            Position::new(2, 22),
+        );
+        assert_eq!(hover, "```glsl\nivec2 st\n```\n\nSwizzle of ivec3");
+    }
+}
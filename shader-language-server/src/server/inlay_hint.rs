@@ -0,0 +1,188 @@
+use std::{cell::RefCell, rc::Rc};
+
+use lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, Position, Range};
+
+use shader_sense::symbols::{symbols::SymbolError, ShaderInlayHintKind, ShaderInlayHintOptions};
+
+use super::{ServerFileCacheHandle, ServerLanguageData};
+
+impl ServerLanguageData {
+    pub fn recolt_inlay_hint(
+        &mut self,
+        cached_file: ServerFileCacheHandle,
+        range: Range,
+    ) -> Result<Vec<InlayHint>, SymbolError> {
+        let options = ShaderInlayHintOptions {
+            types: self.config.inlayHints.types,
+            parameters: self.config.inlayHints.parameters,
+        };
+        if !options.types && !options.parameters {
+            return Ok(Vec::new());
+        }
+        let all_symbol_list = self.get_all_symbols(Rc::clone(&cached_file));
+        let cached_file = RefCell::borrow(&cached_file);
+        Ok(cached_file
+            .symbol_tree
+            .get_inlay_hints(&all_symbol_list, &options)
+            .into_iter()
+            .map(|hint| {
+                let position = Position::new(hint.position.line, hint.position.pos);
+                let (kind, padding_left, padding_right) = match hint.kind {
+                    ShaderInlayHintKind::Type => (InlayHintKind::TYPE, false, false),
+                    ShaderInlayHintKind::Parameter => (InlayHintKind::PARAMETER, false, true),
+                };
+                InlayHint {
+                    position,
+                    label: InlayHintLabel::String(hint.label),
+                    kind: Some(kind),
+                    text_edits: None,
+                    tooltip: None,
+                    padding_left: Some(padding_left),
+                    padding_right: Some(padding_right),
+                    data: None,
+                }
+            })
+            .filter(|hint| hint.position >= range.start && hint.position <= range.end)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Url;
+    use shader_sense::shader::ShadingLanguage;
+
+    use super::*;
+
+    fn get_labels(hints: &[InlayHint]) -> Vec<(u32, u32, String)> {
+        hints
+            .iter()
+            .map(|hint| match &hint.label {
+                InlayHintLabel::String(label) => {
+                    (hint.position.line, hint.position.character, label.clone())
+                }
+                InlayHintLabel::LabelParts(_) => panic!("Expected a string label"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn inlay_hint_wgsl() {
+        let uri = Url::parse("file:///shaders/hints.wgsl").unwrap();
+        let content = "fn scale(value: f32, factor: f32) -> f32 {\n\
+                       \x20   return value * factor;\n\
+                       }\n\
+                       @fragment\n\
+                       fn main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {\n\
+                       \x20   let a = scale(uv.x, 2.0);\n\
+                       \x20   var b = vec3<f32>(1.0, 2.0, 3.0);\n\
+                       \x20   let c = 1.0;\n\
+                       \x20   let factor = a;\n\
+                       \x20   let d = scale(a, factor) < c;\n\
+                       \x20   let e = abs(uv);\n\
+                       \x20   return vec4<f32>(b, a);\n\
+                       }\n"
+        .to_string();
+        let mut language_data = ServerLanguageData::wgsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Wgsl,
+                &content,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let whole_file = Range::new(Position::new(0, 0), Position::new(13, 0));
+        let hints = language_data
+            .recolt_inlay_hint(Rc::clone(&cached_file), whole_file)
+            .unwrap();
+        // Literals & constructors have no type hint. Arguments named as parameters have none too.
+        assert_eq!(
+            get_labels(&hints),
+            vec![
+                (5, 9, ": f32".into()),
+                (5, 18, "value:".into()),
+                (5, 24, "factor:".into()),
+                (8, 14, ": f32".into()),
+                (9, 9, ": bool".into()),
+                (9, 18, "value:".into()),
+                (10, 9, ": vec2<f32>".into()),
+                (10, 16, "e:".into()),
+            ]
+        );
+        // Only hints in the requested range.
+        let hints = language_data
+            .recolt_inlay_hint(
+                Rc::clone(&cached_file),
+                Range::new(Position::new(8, 0), Position::new(9, 0)),
+            )
+            .unwrap();
+        assert_eq!(get_labels(&hints), vec![(8, 14, ": f32".into())]);
+        // Positions follow edits.
+        RefCell::borrow_mut(&cached_file)
+            .update(
+                &uri,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+                Some(Range::new(Position::new(5, 4), Position::new(5, 4))),
+                Some(&"let unused = 0;\n    ".to_string()),
+            )
+            .unwrap();
+        let hints = language_data
+            .recolt_inlay_hint(Rc::clone(&cached_file), whole_file)
+            .unwrap();
+        assert_eq!(
+            get_labels(&hints)[..3],
+            [
+                (6, 9, ": f32".into()),
+                (6, 18, "value:".into()),
+                (6, 24, "factor:".into()),
+            ]
+        );
+        // Disabled by config.
+        language_data.config.inlayHints.types = false;
+        let hints = language_data
+            .recolt_inlay_hint(Rc::clone(&cached_file), whole_file)
+            .unwrap();
+        assert!(hints
+            .iter()
+            .all(|hint| hint.kind == Some(InlayHintKind::PARAMETER)));
+        language_data.config.inlayHints.parameters = false;
+        let hints = language_data
+            .recolt_inlay_hint(Rc::clone(&cached_file), whole_file)
+            .unwrap();
+        assert!(hints.is_empty());
+    }
+
+    #[test]
+    fn inlay_hint_hlsl_parameters() {
+        let uri = Url::parse("file:///shaders/hints.hlsl").unwrap();
+        let content = "float attenuate(float distance, float range) { return distance / range; }\n\
+                       float main(float distance : TEXCOORD0) : SV_Target {\n\
+                       \x20   float value = attenuate(distance, 10.0);\n\
+                       \x20   return value;\n\
+                       }\n"
+        .to_string();
+        let mut language_data = ServerLanguageData::hlsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &content,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let hints = language_data
+            .recolt_inlay_hint(
+                Rc::clone(&cached_file),
+                Range::new(Position::new(0, 0), Position::new(5, 0)),
+            )
+            .unwrap();
+        assert_eq!(get_labels(&hints), vec![(2, 38, "range:".into())]);
+        assert_eq!(hints[0].padding_right, Some(true));
+    }
+}
@@ -0,0 +1,133 @@
+use shader_sense::{
+    shader::ShadingLanguage,
+    symbols::symbols::{
+        ShaderRange, ShaderSymbol, ShaderSymbolData, ShaderSymbolList, ShaderVectorType,
+    },
+};
+
+use super::ServerLanguageData;
+
+pub enum MemberOwner {
+    Struct(Box<ShaderSymbol>),
+    Vector(ShaderVectorType),
+}
+
+// Generic vectors such as vec4<f32> are not declared as types.
+fn get_generic_vector_type(
+    shading_language: ShadingLanguage,
+    ty: &str,
+) -> Option<ShaderVectorType> {
+    match shading_language {
+        ShadingLanguage::Wgsl => {
+            let (base, scalar) = ty.trim().strip_suffix('>')?.split_once('<')?;
+            let dimension = base.strip_prefix("vec")?.parse::<u32>().ok()?;
+            Some(ShaderVectorType {
+                scalar: scalar.trim().into(),
+                dimension,
+            })
+        }
+        ShadingLanguage::Hlsl | ShadingLanguage::Glsl => None,
+    }
+}
+
+pub fn get_swizzle_component_sets(shading_language: ShadingLanguage) -> &'static [&'static str] {
+    match shading_language {
+        ShadingLanguage::Glsl => &["xyzw", "rgba", "stpq"],
+        ShadingLanguage::Hlsl | ShadingLanguage::Wgsl => &["xyzw", "rgba"],
+    }
+}
+
+// Vector resulting from a swizzle. None if the swizzle is invalid.
+pub fn get_swizzle_type(
+    shading_language: ShadingLanguage,
+    swizzle: &str,
+    vector: &ShaderVectorType,
+) -> Option<ShaderVectorType> {
+    if swizzle.is_empty() || swizzle.len() > 4 {
+        return None;
+    }
+    let dimension = (vector.dimension as usize).min(4);
+    let is_valid = get_swizzle_component_sets(shading_language)
+        .iter()
+        .any(|set| swizzle.chars().all(|c| set[..dimension].contains(c)));
+    if is_valid {
+        Some(ShaderVectorType {
+            scalar: vector.scalar.clone(),
+            dimension: swizzle.len() as u32,
+        })
+    } else {
+        None
+    }
+}
+
+impl ServerLanguageData {
+    pub fn list_members_and_methods(&self, symbol: &ShaderSymbol) -> Vec<ShaderSymbol> {
+        match &symbol.data {
+            ShaderSymbolData::Struct { members, methods } => {
+                let mut converted_members: Vec<ShaderSymbol> =
+                    members.iter().map(|e| e.as_symbol()).collect();
+                let converted_methods: Vec<ShaderSymbol> =
+                    methods.iter().map(|e| e.as_symbol()).collect();
+                converted_members.extend(converted_methods);
+                converted_members
+            }
+            ShaderSymbolData::Interface { methods } => {
+                methods.iter().map(|e| e.as_symbol()).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    // Resolve the type owning the members of a chain such as a.b.c, from first to last item.
+    pub fn resolve_member_owner(
+        &self,
+        shading_language: ShadingLanguage,
+        chain: &[(String, ShaderRange)],
+        symbol_list: &ShaderSymbolList,
+    ) -> Option<MemberOwner> {
+        let resolve_type = |symbol: &ShaderSymbol| -> Option<MemberOwner> {
+            let ty = match &symbol.data {
+                ShaderSymbolData::Variables { ty, .. } => ty,
+                _ => return None,
+            };
+            // Generic parameters expose the members of their constraint.
+            let ty_symbol = match symbol_list.find_type_symbol(ty) {
+                Some(ShaderSymbol {
+                    data: ShaderSymbolData::TypeParameter { constraints },
+                    ..
+                }) => constraints
+                    .iter()
+                    .find_map(|constraint| symbol_list.find_type_symbol(constraint)),
+                ty_symbol => ty_symbol,
+            };
+            match ty_symbol {
+                Some(ty_symbol) => match &ty_symbol.data {
+                    ShaderSymbolData::Types {
+                        vector: Some(vector),
+                        ..
+                    } => Some(MemberOwner::Vector(vector.clone())),
+                    ShaderSymbolData::Struct { .. } | ShaderSymbolData::Interface { .. } => {
+                        Some(MemberOwner::Struct(Box::new(ty_symbol)))
+                    }
+                    _ => None,
+                },
+                None => get_generic_vector_type(shading_language, ty).map(MemberOwner::Vector),
+            }
+        };
+        let mut chain_list = chain.iter().rev();
+        let mut owner = resolve_type(&symbol_list.find_symbol(&chain_list.next()?.0)?)?;
+        for (label, _) in chain_list {
+            owner = match owner {
+                MemberOwner::Struct(owner) => resolve_type(
+                    self.list_members_and_methods(&owner)
+                        .iter()
+                        .find(|member| member.label == *label)?,
+                )?,
+                MemberOwner::Vector(vector) => {
+                    MemberOwner::Vector(get_swizzle_type(shading_language, label, &vector)?)
+                }
+            };
+        }
+        Some(owner)
+    }
+}
@@ -0,0 +1,185 @@
+use std::{cell::RefCell, rc::Rc};
+
+use lsp_types::{Location, Position, Url};
+
+use shader_sense::symbols::{
+    symbols::{ShaderPosition, ShaderRange, ShaderSymbol, ShaderSymbolList, SymbolError},
+    SymbolTree,
+};
+
+use super::{common::shader_range_to_lsp_range, ServerFileCacheHandle, ServerLanguageData};
+
+// Declarations are identified by their start position, None for intrinsics.
+type DeclarationKey = Option<ShaderPosition>;
+
+fn get_declaration_key(symbol: &ShaderSymbol) -> DeclarationKey {
+    symbol.range.as_ref().map(|range| range.start.clone())
+}
+
+// Find the declarations an occurence of a label resolve to.
+fn resolve_declarations(
+    label_symbols: &ShaderSymbolList,
+    occurence: &ShaderRange,
+) -> Vec<DeclarationKey> {
+    // Declaration themselves are not visible from their position.
+    let declarations: Vec<DeclarationKey> = label_symbols
+        .iter()
+        .flat_map(|(symbols, _)| symbols.iter())
+        .filter(|symbol| {
+            symbol
+                .range
+                .as_ref()
+                .is_some_and(|range| range.start == occurence.start)
+        })
+        .map(get_declaration_key)
+        .collect();
+    if declarations.is_empty() {
+        label_symbols
+            .filter_scoped_symbol(occurence.start.clone())
+            .iter()
+            .flat_map(|(symbols, _)| symbols.iter())
+            .map(get_declaration_key)
+            .collect()
+    } else {
+        declarations
+    }
+}
+
+pub struct SymbolReferences {
+    pub word_range: ShaderRange,
+    // Intrinsics have no declaration in sources.
+    pub is_intrinsic: bool,
+    pub locations: Vec<Location>,
+}
+
+impl ServerLanguageData {
+    pub fn recolt_references(
+        &mut self,
+        uri: &Url,
+        cached_file: ServerFileCacheHandle,
+        position: Position,
+        include_declaration: bool,
+    ) -> Result<Option<Vec<Location>>, SymbolError> {
+        Ok(self
+            .find_references(uri, cached_file, position, include_declaration)?
+            .map(|references| references.locations))
+    }
+    pub fn find_references(
+        &mut self,
+        uri: &Url,
+        cached_file: ServerFileCacheHandle,
+        position: Position,
+        include_declaration: bool,
+    ) -> Result<Option<SymbolReferences>, SymbolError> {
+        let file_path = uri.to_file_path().unwrap();
+        let shader_position = ShaderPosition {
+            file_path: file_path.clone(),
+            line: position.line,
+            pos: position.character,
+        };
+        let all_symbol_list = self.get_all_symbols(Rc::clone(&cached_file));
+        let cached_file = RefCell::borrow(&cached_file);
+        let (word, word_range) = match self
+            .symbol_provider
+            .get_word_range_at_position(&cached_file.symbol_tree, shader_position)
+        {
+            Ok(word) => word,
+            Err(SymbolError::NoSymbol) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        // Only keep symbols that could match to speed up resolution.
+        let filter_label = |symbols: &Vec<ShaderSymbol>| -> Vec<ShaderSymbol> {
+            symbols
+                .iter()
+                .filter(|symbol| symbol.label == word)
+                .cloned()
+                .collect()
+        };
+        let label_symbols = ShaderSymbolList {
+            types: filter_label(&all_symbol_list.types),
+            constants: filter_label(&all_symbol_list.constants),
+            variables: filter_label(&all_symbol_list.variables),
+            functions: filter_label(&all_symbol_list.functions),
+            keywords: filter_label(&all_symbol_list.keywords),
+        };
+        let targets = resolve_declarations(&label_symbols, &word_range);
+        if targets.is_empty() {
+            return Ok(None);
+        }
+        // Scan current file and all its includes.
+        let mut locations = Vec::new();
+        let mut scan_file = |symbol_tree: &SymbolTree| {
+            for occurence in self.symbol_provider.get_word_occurences(symbol_tree, &word) {
+                let is_declaration = targets
+                    .iter()
+                    .any(|target| target.as_ref() == Some(&occurence.start));
+                if is_declaration && !include_declaration {
+                    continue;
+                }
+                let declarations = resolve_declarations(&label_symbols, &occurence);
+                if is_declaration || declarations.iter().any(|d| targets.contains(d)) {
+                    locations.push(Location {
+                        uri: Url::from_file_path(&occurence.start.file_path).unwrap(),
+                        range: shader_range_to_lsp_range(&occurence),
+                    });
+                }
+            }
+        };
+        scan_file(&cached_file.symbol_tree);
+        for dependency in cached_file.dependencies.values() {
+            let dependency = RefCell::borrow(dependency);
+            scan_file(&dependency.symbol_tree);
+        }
+        Ok(Some(SymbolReferences {
+            word_range,
+            is_intrinsic: targets.contains(&None),
+            locations,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shader_sense::shader::ShadingLanguage;
+
+    use super::*;
+
+    #[test]
+    fn references_scoped_local() {
+        let uri = Url::parse("file:///shaders/references.frag.glsl").unwrap();
+        let content = "#version 450\n\
+                       float first() {\n\
+                       \x20   float value = 1.0;\n\
+                       \x20   return value * value;\n\
+                       }\n\
+                       float second() {\n\
+                       \x20   float value = 2.0;\n\
+                       \x20   return value;\n\
+                       }\n"
+        .to_string();
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &content,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let mut reference_lines = |position: Position, include_declaration: bool| -> Vec<u32> {
+            language_data
+                .recolt_references(&uri, Rc::clone(&cached_file), position, include_declaration)
+                .unwrap()
+                .unwrap()
+                .iter()
+                .map(|location| location.range.start.line)
+                .collect()
+        };
+        // Local in second function is not matched.
+        assert_eq!(reference_lines(Position::new(3, 12), true), vec![2, 3, 3]);
+        assert_eq!(reference_lines(Position::new(3, 12), false), vec![3, 3]);
+        assert_eq!(reference_lines(Position::new(6, 11), true), vec![6, 7]);
+    }
+}
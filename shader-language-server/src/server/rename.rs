@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use lsp_types::{Position, PrepareRenameResponse, TextEdit, Url, WorkspaceEdit};
+use regex::Regex;
+
+use shader_sense::{shader::ShadingLanguage, symbols::symbols::SymbolError};
+
+use super::{common::shader_range_to_lsp_range, ServerFileCacheHandle, ServerLanguageData};
+
+fn is_valid_identifier(shading_language: ShadingLanguage, name: &str) -> bool {
+    match shading_language {
+        // gl_ prefix & double underscores are reserved.
+        ShadingLanguage::Glsl => {
+            Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$")
+                .unwrap()
+                .is_match(name)
+                && !name.starts_with("gl_")
+                && !name.contains("__")
+        }
+        ShadingLanguage::Hlsl => Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$")
+            .unwrap()
+            .is_match(name),
+        // Single underscore & double underscore prefix are reserved.
+        ShadingLanguage::Wgsl => Regex::new(r"^([A-Za-z][A-Za-z0-9_]*|_[A-Za-z0-9][A-Za-z0-9_]*)$")
+            .unwrap()
+            .is_match(name),
+    }
+}
+
+impl ServerLanguageData {
+    pub fn recolt_prepare_rename(
+        &mut self,
+        uri: &Url,
+        cached_file: ServerFileCacheHandle,
+        position: Position,
+    ) -> Result<Option<PrepareRenameResponse>, SymbolError> {
+        match self.find_references(uri, cached_file, position, true)? {
+            Some(references) => {
+                if references.is_intrinsic {
+                    Err(SymbolError::InternalErr(
+                        "Cannot rename a builtin symbol".into(),
+                    ))
+                } else {
+                    Ok(Some(PrepareRenameResponse::Range(
+                        shader_range_to_lsp_range(&references.word_range),
+                    )))
+                }
+            }
+            None => Ok(None),
+        }
+    }
+    pub fn recolt_rename(
+        &mut self,
+        uri: &Url,
+        cached_file: ServerFileCacheHandle,
+        position: Position,
+        new_name: String,
+    ) -> Result<Option<WorkspaceEdit>, SymbolError> {
+        let shading_language = cached_file.borrow().shading_language;
+        // Builtin types & keywords can't be used as identifiers.
+        let intrinsics = self.symbol_provider.get_intrinsics_symbol();
+        let is_keyword = intrinsics
+            .keywords
+            .iter()
+            .chain(intrinsics.types.iter())
+            .any(|keyword| keyword.label == new_name);
+        if is_keyword || !is_valid_identifier(shading_language, &new_name) {
+            return Err(SymbolError::InternalErr(format!(
+                "{} is not a valid {} identifier",
+                new_name,
+                shading_language.to_string()
+            )));
+        }
+        match self.find_references(uri, cached_file, position, true)? {
+            Some(references) => {
+                if references.is_intrinsic {
+                    return Err(SymbolError::InternalErr(
+                        "Cannot rename a builtin symbol".into(),
+                    ));
+                }
+                let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+                for location in references.locations {
+                    changes
+                        .entry(location.uri)
+                        .or_default()
+                        .push(TextEdit::new(location.range, new_name.clone()));
+                }
+                Ok(Some(WorkspaceEdit {
+                    changes: Some(changes),
+                    ..Default::default()
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    fn watch_glsl(language_data: &mut ServerLanguageData, uri: &Url) -> ServerFileCacheHandle {
+        let content = "#version 450\n\
+                       float scale(float value) {\n\
+                       \x20   return value * 2.0;\n\
+                       }\n\
+                       void main() {\n\
+                       \x20   float result = scale(abs(1.0));\n\
+                       }\n"
+        .to_string();
+        language_data
+            .watched_files
+            .watch_file(
+                uri,
+                ShadingLanguage::Glsl,
+                &content,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn rename_function() {
+        let uri = Url::parse("file:///shaders/rename.frag.glsl").unwrap();
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = watch_glsl(&mut language_data, &uri);
+        let position = Position::new(5, 20);
+        match language_data
+            .recolt_prepare_rename(&uri, Rc::clone(&cached_file), position)
+            .unwrap()
+        {
+            Some(PrepareRenameResponse::Range(range)) => {
+                assert_eq!(range.start, Position::new(5, 19));
+                assert_eq!(range.end, Position::new(5, 24));
+            }
+            _ => panic!("Expected a range to rename"),
+        }
+        let edit = language_data
+            .recolt_rename(&uri, Rc::clone(&cached_file), position, "rescale".into())
+            .unwrap()
+            .unwrap();
+        let edits = &edit.changes.unwrap()[&uri];
+        assert_eq!(edits.len(), 2);
+        assert!(edits.iter().all(|edit| edit.new_text == "rescale"));
+    }
+
+    #[test]
+    fn rename_rejected() {
+        let uri = Url::parse("file:///shaders/rename.frag.glsl").unwrap();
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = watch_glsl(&mut language_data, &uri);
+        // Intrinsics can't be renamed.
+        assert!(language_data
+            .recolt_rename(
+                &uri,
+                Rc::clone(&cached_file),
+                Position::new(5, 26),
+                "abs2".into()
+            )
+            .is_err());
+        assert!(language_data
+            .recolt_prepare_rename(&uri, Rc::clone(&cached_file), Position::new(5, 26))
+            .is_err());
+        // Invalid or reserved identifiers.
+        for new_name in ["2scale", "gl_scale", "my scale", "float"] {
+            assert!(language_data
+                .recolt_rename(
+                    &uri,
+                    Rc::clone(&cached_file),
+                    Position::new(5, 20),
+                    new_name.into()
+                )
+                .is_err());
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use std::{collections::HashMap, rc::Rc};
+
+use lsp_types::{
+    SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokens, SemanticTokensLegend,
+};
+
+use shader_sense::symbols::symbols::{
+    ShaderRange, ShaderSymbol, ShaderSymbolData, ShaderSymbolList, ShaderSymbolType, SymbolError,
+};
+
+use super::{ServerFileCacheHandle, ServerLanguageData};
+
+// Index in these arrays are the token type & modifier bit sent to client.
+const TOKEN_TYPES: [SemanticTokenType; 6] = [
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::MACRO,
+    SemanticTokenType::KEYWORD,
+];
+const TOKEN_MODIFIERS: [SemanticTokenModifier; 2] = [
+    SemanticTokenModifier::DEFAULT_LIBRARY,
+    SemanticTokenModifier::READONLY,
+];
+
+fn get_token_type(token_type: SemanticTokenType) -> u32 {
+    TOKEN_TYPES.iter().position(|t| *t == token_type).unwrap() as u32
+}
+
+fn get_token_modifier(token_modifier: SemanticTokenModifier) -> u32 {
+    1 << TOKEN_MODIFIERS
+        .iter()
+        .position(|m| *m == token_modifier)
+        .unwrap()
+}
+
+pub fn get_semantic_tokens_legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: TOKEN_MODIFIERS.to_vec(),
+    }
+}
+
+// Parameters are declared outside of the function body they are scoped to.
+fn is_parameter(symbol: &ShaderSymbol) -> bool {
+    match (&symbol.range, &symbol.scope_stack) {
+        (Some(range), Some(scope_stack)) => scope_stack
+            .last()
+            .is_some_and(|scope| !scope.contain(&range.start)),
+        _ => false,
+    }
+}
+
+fn classify_symbol(symbol: &ShaderSymbol) -> Option<(u32, u32)> {
+    let (token_type, token_modifiers) = match &symbol.data {
+        ShaderSymbolData::Functions { .. } => (SemanticTokenType::FUNCTION, 0),
+        ShaderSymbolData::Types { .. }
+        | ShaderSymbolData::Struct { .. }
+        | ShaderSymbolData::Interface { .. }
+        | ShaderSymbolData::TypeParameter { .. } => (SemanticTokenType::TYPE, 0),
+        ShaderSymbolData::Variables { .. } => {
+            if is_parameter(symbol) {
+                (SemanticTokenType::PARAMETER, 0)
+            } else {
+                (SemanticTokenType::VARIABLE, 0)
+            }
+        }
+        ShaderSymbolData::Constants { .. } => (
+            SemanticTokenType::VARIABLE,
+            get_token_modifier(SemanticTokenModifier::READONLY),
+        ),
+        ShaderSymbolData::Macro { .. } => (SemanticTokenType::MACRO, 0),
+        ShaderSymbolData::Keyword {} => (SemanticTokenType::KEYWORD, 0),
+        ShaderSymbolData::Link { .. } | ShaderSymbolData::None => return None,
+    };
+    let library_modifier = if symbol.range.is_none() {
+        get_token_modifier(SemanticTokenModifier::DEFAULT_LIBRARY)
+    } else {
+        0
+    };
+    Some((
+        get_token_type(token_type),
+        token_modifiers | library_modifier,
+    ))
+}
+
+// Find the symbol an identifier refer to, if any.
+fn resolve_symbol(candidates: &ShaderSymbolList, word_range: &ShaderRange) -> Option<ShaderSymbol> {
+    // Declarations are not visible from their own position.
+    let declaration = candidates
+        .iter()
+        .flat_map(|(symbols, _)| symbols.iter())
+        .find(|symbol| {
+            symbol
+                .range
+                .as_ref()
+                .is_some_and(|range| range.start == word_range.start)
+        });
+    match declaration {
+        Some(declaration) => Some(declaration.clone()),
+        None => candidates
+            .filter_scoped_symbol(word_range.start.clone())
+            .iter()
+            .flat_map(|(symbols, _)| symbols.iter())
+            .find(|symbol| classify_symbol(symbol).is_some())
+            .cloned(),
+    }
+}
+
+// Encode tokens relative to the previous one as required by the spec.
+fn encode_semantic_tokens(tokens: Vec<(ShaderRange, u32, u32)>) -> Vec<SemanticToken> {
+    let mut previous_line = 0;
+    let mut previous_start = 0;
+    tokens
+        .into_iter()
+        .map(|(range, token_type, token_modifiers_bitset)| {
+            let delta_line = range.start.line - previous_line;
+            let delta_start = if delta_line == 0 {
+                range.start.pos - previous_start
+            } else {
+                range.start.pos
+            };
+            previous_line = range.start.line;
+            previous_start = range.start.pos;
+            SemanticToken {
+                delta_line,
+                delta_start,
+                length: range.end.pos - range.start.pos,
+                token_type,
+                token_modifiers_bitset,
+            }
+        })
+        .collect()
+}
+
+impl ServerLanguageData {
+    pub fn recolt_semantic_tokens(
+        &mut self,
+        cached_file: ServerFileCacheHandle,
+    ) -> Result<SemanticTokens, SymbolError> {
+        let all_symbol_list = self.get_all_symbols(Rc::clone(&cached_file));
+        let cached_file = cached_file.borrow();
+        // Group symbols per label to only resolve against candidates.
+        let mut candidates: HashMap<&str, ShaderSymbolList> = HashMap::new();
+        for (symbol_list, ty) in all_symbol_list.iter() {
+            for symbol in symbol_list {
+                let list = candidates.entry(symbol.label.as_str()).or_default();
+                match ty {
+                    ShaderSymbolType::Types => list.types.push(symbol.clone()),
+                    ShaderSymbolType::Constants => list.constants.push(symbol.clone()),
+                    ShaderSymbolType::Variables => list.variables.push(symbol.clone()),
+                    ShaderSymbolType::Functions => list.functions.push(symbol.clone()),
+                    ShaderSymbolType::Keyword => list.keywords.push(symbol.clone()),
+                }
+            }
+        }
+        // Only tokens of this file are in its tree, so included content is never highlighted here.
+        let tokens = self
+            .symbol_provider
+            .get_all_words(&cached_file.symbol_tree)
+            .into_iter()
+            .filter(|(_, range)| range.start.line == range.end.line)
+            .filter_map(|(word, range)| {
+                let symbol = resolve_symbol(candidates.get(word)?, &range)?;
+                let (token_type, token_modifiers) = classify_symbol(&symbol)?;
+                Some((range, token_type, token_modifiers))
+            })
+            .collect();
+        Ok(SemanticTokens {
+            result_id: None,
+            data: encode_semantic_tokens(tokens),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Url;
+    use shader_sense::shader::ShadingLanguage;
+
+    use super::*;
+
+    #[test]
+    fn semantic_tokens_classify() {
+        let uri = Url::parse("file:///shaders/tokens.frag.glsl").unwrap();
+        let content = "#version 450\n\
+                       #define SCALE 2.0\n\
+                       float scale(float value) {\n\
+                       \x20   float result = abs(value) * SCALE;\n\
+                       \x20   return result;\n\
+                       }\n"
+        .to_string();
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &content,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let tokens = language_data
+            .recolt_semantic_tokens(Rc::clone(&cached_file))
+            .unwrap();
+        // Decode delta to absolute positions.
+        let mut line = 0;
+        let mut start = 0;
+        let decoded: Vec<(u32, u32, SemanticTokenType, u32)> = tokens
+            .data
+            .iter()
+            .map(|token| {
+                if token.delta_line != 0 {
+                    start = 0;
+                }
+                line += token.delta_line;
+                start += token.delta_start;
+                (
+                    line,
+                    start,
+                    TOKEN_TYPES[token.token_type as usize].clone(),
+                    token.token_modifiers_bitset,
+                )
+            })
+            .collect();
+        let library = get_token_modifier(SemanticTokenModifier::DEFAULT_LIBRARY);
+        assert!(decoded.contains(&(1, 8, SemanticTokenType::MACRO, 0)));
+        assert!(decoded.contains(&(2, 6, SemanticTokenType::FUNCTION, 0)));
+        assert!(decoded.contains(&(2, 18, SemanticTokenType::PARAMETER, 0)));
+        assert!(decoded.contains(&(3, 10, SemanticTokenType::VARIABLE, 0)));
+        assert!(decoded.contains(&(3, 19, SemanticTokenType::FUNCTION, library)));
+        assert!(decoded.contains(&(3, 23, SemanticTokenType::PARAMETER, 0)));
+        assert!(decoded.contains(&(3, 32, SemanticTokenType::MACRO, 0)));
+        assert!(decoded.contains(&(4, 11, SemanticTokenType::VARIABLE, 0)));
+    }
+}
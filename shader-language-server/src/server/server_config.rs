@@ -1,11 +1,19 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use shader_sense::{
-    shader::{GlslSpirvVersion, GlslTargetClient, HlslShaderModel, HlslVersion},
+    shader::{
+        GlslProfile, GlslSpirvVersion, GlslTargetClient, HlslBackend, HlslShaderModel, HlslVersion,
+        ShaderStage, ShadingLanguage,
+    },
     shader_error::ShaderErrorSeverity,
-    validator::validator::ValidationParams,
+    symbols::DEFAULT_SYMBOL_TREE_CACHE_CAPACITY,
+    validator::validator::{ResourceLimits, ValidationParams},
 };
 
 #[allow(non_snake_case)]
@@ -14,36 +22,363 @@ pub struct ServerHlslConfig {
     pub shaderModel: HlslShaderModel,
     pub version: HlslVersion,
     pub enable16bitTypes: bool,
+    #[serde(default)]
+    pub glslangFallback: bool, // Validate with glslang when DXC can't be loaded.
+    #[serde(default)]
+    pub backend: HlslBackend,
 }
 #[allow(non_snake_case)]
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ServerGlslConfig {
     pub targetClient: GlslTargetClient,
     pub spirvVersion: GlslSpirvVersion,
+    #[serde(default)]
+    pub version: Option<u32>, // Used for files without #version directive.
+    #[serde(default)]
+    pub profile: Option<GlslProfile>,
+    #[serde(default)]
+    pub defaultStage: Option<ShaderStage>, // Stage of files without stage extension such as .frag.
+    #[serde(default)]
+    pub spirvCapabilities: Option<Vec<String>>, // Warn when SPIR-V use capabilities outside this list.
+    #[serde(default)]
+    pub spirvExtensions: Option<Vec<String>>,
+    #[serde(default)]
+    pub specializationConstants: HashMap<u32, String>, // Value by constant_id.
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServerWgslConfig {
+    #[serde(default)]
+    pub preprocessIncludes: bool, // Expand #include directives as naga does not support them.
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)] // Hints are enabled unless disabled explicitly.
+pub struct ServerInlayHintConfig {
+    pub types: bool,      // Inferred type of declarations without type, such as WGSL let.
+    pub parameters: bool, // Parameter name before call arguments.
+}
+
+impl Default for ServerInlayHintConfig {
+    fn default() -> Self {
+        Self {
+            types: true,
+            parameters: true,
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)] // Clients may only send the lints they change.
+pub struct ServerLintConfig {
+    pub largeArray: bool,
+    pub largeArrayThreshold: u32, // In bytes
+    pub reservedIdentifier: bool,
+    pub unreachableCode: bool,
+    pub syntaxFixes: bool,
+    pub callArguments: bool,
+    pub blockLayout: bool,
+    pub nonUniformSampling: bool,
+    pub unassignedOutParameters: bool,
+    pub integerDivision: bool,
+    pub redefinition: bool,
+    pub ambiguousEntryPoint: bool,
+    pub resourceLimits: bool,
+    // Slots by resource type. Default to D3D11 limits.
+    pub textureLimit: Option<u32>,
+    pub samplerLimit: Option<u32>,
+    pub uavLimit: Option<u32>,
+}
+
+impl Default for ServerLintConfig {
+    fn default() -> Self {
+        Self {
+            largeArray: false,
+            largeArrayThreshold: 8192,
+            reservedIdentifier: false,
+            unreachableCode: false,
+            syntaxFixes: false,
+            callArguments: false,
+            blockLayout: false,
+            nonUniformSampling: false,
+            unassignedOutParameters: false,
+            integerDivision: false,
+            redefinition: false,
+            ambiguousEntryPoint: false,
+            resourceLimits: false,
+            textureLimit: None,
+            samplerLimit: None,
+            uavLimit: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ServerDiagnosticMode {
+    // Diagnostics are published by the server on open, change & save.
+    Push,
+    // Diagnostics are only sent on client DocumentDiagnosticRequest.
+    Pull,
+    #[default]
+    Both,
+}
+
+impl ServerDiagnosticMode {
+    pub fn is_push(&self) -> bool {
+        matches!(
+            self,
+            ServerDiagnosticMode::Push | ServerDiagnosticMode::Both
+        )
+    }
+    pub fn is_pull(&self) -> bool {
+        matches!(
+            self,
+            ServerDiagnosticMode::Pull | ServerDiagnosticMode::Both
+        )
+    }
+}
+
+// Severity forced on matching diagnostics. Use the same names as the severity setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerSeverityOverride {
+    Error,
+    Warning,
+    Info,
+    Hint,
+    // Diagnostic is silenced.
+    None,
+}
+
+impl ServerSeverityOverride {
+    pub fn into_severity(self) -> Option<ShaderErrorSeverity> {
+        match self {
+            ServerSeverityOverride::Error => Some(ShaderErrorSeverity::Error),
+            ServerSeverityOverride::Warning => Some(ShaderErrorSeverity::Warning),
+            ServerSeverityOverride::Info => Some(ShaderErrorSeverity::Information),
+            ServerSeverityOverride::Hint => Some(ShaderErrorSeverity::Hint),
+            ServerSeverityOverride::None => None,
+        }
+    }
+}
+
+// D3D11 input slots, samplers & UAV slots.
+const D3D11_TEXTURE_LIMIT: u32 = 128;
+const D3D11_SAMPLER_LIMIT: u32 = 16;
+const D3D11_UAV_LIMIT: u32 = 8;
+
+// Features still being stabilized, only advertised when opted in through experimental.
+pub const EXPERIMENTAL_SEMANTIC_TOKENS: &str = "semanticTokens";
+pub const EXPERIMENTAL_FORMATTING: &str = "formatting";
+pub const EXPERIMENTAL_INLAY_HINTS: &str = "inlayHints";
+pub const EXPERIMENTAL_CODE_ACTIONS: &str = "codeActions";
+
+// Accept completion with call, member access or end of statement.
+const DEFAULT_COMPLETION_COMMIT_CHARACTERS: [&str; 3] = ["(", ".", ";"];
+
+// Syntax specific to a language, used to guess it from content.
+fn get_language_markers(shading_language: ShadingLanguage) -> &'static [&'static str] {
+    match shading_language {
+        ShadingLanguage::Wgsl => &["@vertex", "@fragment", "@compute", "@group(", "var<"],
+        ShadingLanguage::Hlsl => &["cbuffer", ": SV_", ":SV_", "[numthreads"],
+        ShadingLanguage::Glsl => &["#version", "gl_", "layout("],
+    }
 }
 
 #[allow(non_snake_case)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {
     pub includes: Vec<String>,
+    #[serde(default)]
+    pub virtualIncludes: HashMap<String, PathBuf>, // Virtual path prefix such as /Engine mapped to a directory.
+    #[serde(default)]
+    pub fileAssociations: HashMap<String, ShadingLanguage>, // Extension such as fx mapped to a language.
+    // Languages tried in order on content for files that can't be resolved otherwise. Empty disables it.
+    #[serde(default)]
+    pub languageDetectionOrder: Vec<ShadingLanguage>,
+    #[serde(default)]
+    pub autoIncludeRoots: bool, // Learn include roots from relative includes of opened files.
     pub defines: HashMap<String, String>,
     pub validate: bool,
+    // Languages missing from the map use validate. Symbol features are kept when disabled.
+    #[serde(default)]
+    pub validateLanguages: HashMap<ShadingLanguage, bool>,
+    #[serde(default)]
+    pub diagnosticMode: ServerDiagnosticMode,
+    #[serde(default)]
+    pub includeErrorSummary: bool, // Report errors of included headers in the including file.
+    #[serde(default)]
+    pub deferOpenValidation: bool, // Validate opened files on first edit, save or diagnostic request.
     pub symbols: bool,
     pub severity: String,
+    // Keys are matched against the diagnostic code, or against the message as a fallback.
+    #[serde(default)]
+    pub severityOverrides: HashMap<String, ServerSeverityOverride>,
+    #[serde(default)]
+    pub ignoreWarningPragmas: bool, // Keep diagnostics disabled with #pragma warning(disable: code).
     pub hlsl: ServerHlslConfig,
     pub glsl: ServerGlslConfig,
+    #[serde(default)]
+    pub wgsl: ServerWgslConfig,
+    #[serde(default)]
+    pub lint: ServerLintConfig,
+    // Languages missing from the map use the default set. An empty list disables them.
+    #[serde(default)]
+    pub completionCommitCharacters: HashMap<ShadingLanguage, Vec<String>>,
+    #[serde(default)]
+    pub completionMinPrefixLength: usize, // Characters to type before listing symbols. 0 disables it.
+    #[serde(default)]
+    pub completionAllStages: bool, // List builtins of all stages, when stage detection is wrong.
+    #[serde(default)]
+    pub mathConstants: HashMap<String, String>, // Constants such as PI completed as builtins.
+    #[serde(default)]
+    pub inlayHints: ServerInlayHintConfig,
+    #[serde(default)]
+    pub hoverFuzzyFallback: bool, // Hover closest symbol by name when the exact one can't be resolved.
+    #[serde(default)]
+    pub workspaceSymbolRoots: Vec<String>, // Directories scanned for workspace symbols of closed files.
+    #[serde(default)]
+    pub astCacheCapacity: Option<usize>, // Parsed files kept in cache. 0 disables it.
+    #[serde(default)]
+    pub experimental: HashSet<String>, // Opt-in features by name, see EXPERIMENTAL_*.
 }
 
 impl ServerConfig {
+    pub fn get_file_association(&self, file_path: &Path) -> Option<ShadingLanguage> {
+        let extension = file_path.extension()?.to_string_lossy().to_lowercase();
+        self.fileAssociations
+            .iter()
+            .find(|(association, _)| {
+                association.trim_start_matches('.').to_lowercase() == extension
+            })
+            .map(|(_, shading_language)| *shading_language)
+    }
+    // Pick the first language of the detection order whose syntax is found in the content.
+    // Fallback to the first one if none match.
+    pub fn detect_language(&self, content: &str) -> Option<ShadingLanguage> {
+        self.languageDetectionOrder
+            .iter()
+            .find(|shading_language| {
+                get_language_markers(**shading_language)
+                    .iter()
+                    .any(|marker| content.contains(marker))
+            })
+            .or(self.languageDetectionOrder.first())
+            .cloned()
+    }
+    // Case-insensitive match on the code, then substring match on the message.
+    // Longest key wins when several match the message.
+    pub fn get_severity_override(
+        &self,
+        code: Option<&str>,
+        message: &str,
+    ) -> Option<ServerSeverityOverride> {
+        if let Some(code) = code {
+            let code_override = self
+                .severityOverrides
+                .iter()
+                .find(|(pattern, _)| pattern.eq_ignore_ascii_case(code));
+            if let Some((_, severity)) = code_override {
+                return Some(*severity);
+            }
+        }
+        let message = message.to_lowercase();
+        self.severityOverrides
+            .iter()
+            .filter(|(pattern, _)| message.contains(&pattern.to_lowercase()))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, severity)| *severity)
+    }
+    pub fn is_validation_enabled(&self, shading_language: ShadingLanguage) -> bool {
+        self.validateLanguages
+            .get(&shading_language)
+            .cloned()
+            .unwrap_or(self.validate)
+    }
+    pub fn get_completion_commit_characters(
+        &self,
+        shading_language: ShadingLanguage,
+    ) -> Vec<String> {
+        match self.completionCommitCharacters.get(&shading_language) {
+            Some(commit_characters) => commit_characters.clone(),
+            None => DEFAULT_COMPLETION_COMMIT_CHARACTERS
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+        }
+    }
+    // Include directories that do not exist on disk.
+    pub fn get_missing_includes(&self) -> Vec<String> {
+        self.includes
+            .iter()
+            .filter(|include| !Path::new(include).is_dir())
+            .cloned()
+            .collect()
+    }
+    pub fn is_experimental_enabled(&self, feature: &str) -> bool {
+        self.experimental.contains(feature)
+    }
+    // Capabilities are sent before the configuration is received, so flags are read from the
+    // initialization options. Other settings might be missing there, so only parse this one.
+    pub fn get_initialization_experimental(
+        initialization_options: Option<&Value>,
+    ) -> HashSet<String> {
+        initialization_options
+            .and_then(|options| options.get("experimental"))
+            .and_then(|experimental| serde_json::from_value(experimental.clone()).ok())
+            .unwrap_or_default()
+    }
+    pub fn get_ast_cache_capacity(&self) -> usize {
+        self.astCacheCapacity
+            .unwrap_or(DEFAULT_SYMBOL_TREE_CACHE_CAPACITY)
+    }
     pub fn into_validation_params(&self) -> ValidationParams {
         ValidationParams {
             includes: self.includes.clone(),
+            virtual_includes: self.virtualIncludes.clone(),
             defines: self.defines.clone(),
+            math_constants: self.mathConstants.clone(),
             hlsl_shader_model: self.hlsl.shaderModel,
             hlsl_version: self.hlsl.version,
             hlsl_enable16bit_types: self.hlsl.enable16bitTypes,
+            entry_point: None,
+            shader_stage: None,
             glsl_client: self.glsl.targetClient,
             glsl_spirv: self.glsl.spirvVersion,
+            glsl_version: self.glsl.version,
+            glsl_profile: self.glsl.profile,
+            glsl_default_stage: self.glsl.defaultStage,
+            spirv_capabilities: self.glsl.spirvCapabilities.clone(),
+            spirv_extensions: self.glsl.spirvExtensions.clone(),
+            specialization_constants: self.glsl.specializationConstants.clone(),
+            wgsl_preprocess_includes: self.wgsl.preprocessIncludes,
+            large_array_threshold: if self.lint.largeArray {
+                Some(self.lint.largeArrayThreshold)
+            } else {
+                None
+            },
+            lint_reserved_identifiers: self.lint.reservedIdentifier,
+            lint_unreachable_code: self.lint.unreachableCode,
+            lint_syntax_fixes: self.lint.syntaxFixes,
+            lint_call_arguments: self.lint.callArguments,
+            lint_block_layout: self.lint.blockLayout,
+            lint_non_uniform_sampling: self.lint.nonUniformSampling,
+            lint_unassigned_out_parameters: self.lint.unassignedOutParameters,
+            lint_integer_division: self.lint.integerDivision,
+            lint_redefinitions: self.lint.redefinition,
+            lint_ambiguous_entry_points: self.lint.ambiguousEntryPoint,
+            resource_limits: if self.lint.resourceLimits {
+                Some(ResourceLimits {
+                    textures: self.lint.textureLimit.unwrap_or(D3D11_TEXTURE_LIMIT),
+                    samplers: self.lint.samplerLimit.unwrap_or(D3D11_SAMPLER_LIMIT),
+                    uavs: self.lint.uavLimit.unwrap_or(D3D11_UAV_LIMIT),
+                })
+            } else {
+                None
+            },
         }
     }
 }
@@ -52,12 +387,242 @@ impl Default for ServerConfig {
     fn default() -> Self {
         Self {
             includes: Vec::new(),
+            virtualIncludes: HashMap::new(),
+            fileAssociations: HashMap::new(),
+            languageDetectionOrder: Vec::new(),
+            autoIncludeRoots: false,
             defines: HashMap::new(),
             validate: true,
+            validateLanguages: HashMap::new(),
+            diagnosticMode: ServerDiagnosticMode::default(),
+            includeErrorSummary: false,
+            deferOpenValidation: false,
             symbols: true,
             severity: ShaderErrorSeverity::Hint.to_string(),
+            severityOverrides: HashMap::new(),
+            ignoreWarningPragmas: false,
             hlsl: ServerHlslConfig::default(),
             glsl: ServerGlslConfig::default(),
+            wgsl: ServerWgslConfig::default(),
+            lint: ServerLintConfig::default(),
+            completionCommitCharacters: HashMap::new(),
+            completionMinPrefixLength: 0,
+            completionAllStages: false,
+            mathConstants: HashMap::new(),
+            inlayHints: ServerInlayHintConfig::default(),
+            hoverFuzzyFallback: false,
+            workspaceSymbolRoots: Vec::new(),
+            astCacheCapacity: None,
+            experimental: HashSet::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnostic_mode_push() {
+        let mode: ServerDiagnosticMode = serde_json::from_str("\"Push\"").unwrap();
+        assert!(mode.is_push());
+        assert!(!mode.is_pull());
+    }
+
+    #[test]
+    fn diagnostic_mode_pull() {
+        let mode: ServerDiagnosticMode = serde_json::from_str("\"Pull\"").unwrap();
+        assert!(!mode.is_push());
+        assert!(mode.is_pull());
+    }
+
+    #[test]
+    fn diagnostic_mode_both() {
+        let mode: ServerDiagnosticMode = serde_json::from_str("\"Both\"").unwrap();
+        assert!(mode.is_push());
+        assert!(mode.is_pull());
+    }
+
+    #[test]
+    fn diagnostic_mode_default() {
+        // Older clients do not send the mode, both should be enabled.
+        let config: ServerConfig = serde_json::from_value(serde_json::json!({
+            "includes": [],
+            "defines": {},
+            "validate": true,
+            "symbols": true,
+            "severity": "hint",
+            "hlsl": serde_json::to_value(ServerHlslConfig::default()).unwrap(),
+            "glsl": serde_json::to_value(ServerGlslConfig::default()).unwrap(),
+        }))
+        .unwrap();
+        assert_eq!(config.diagnosticMode, ServerDiagnosticMode::Both);
+    }
+
+    #[test]
+    fn lint_config_partial() {
+        let lint: ServerLintConfig =
+            serde_json::from_value(serde_json::json!({ "redefinition": true })).unwrap();
+        assert!(lint.redefinition);
+        assert!(!lint.largeArray);
+        assert_eq!(lint.largeArrayThreshold, 8192);
+    }
+
+    #[test]
+    fn file_association_extension() {
+        let config = ServerConfig {
+            fileAssociations: HashMap::from([
+                ("fx".into(), ShadingLanguage::Hlsl),
+                (".ush".into(), ShadingLanguage::Hlsl),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.get_file_association(Path::new("/shaders/effect.FX")),
+            Some(ShadingLanguage::Hlsl)
+        );
+        assert_eq!(
+            config.get_file_association(Path::new("/shaders/Common.ush")),
+            Some(ShadingLanguage::Hlsl)
+        );
+        assert_eq!(
+            config.get_file_association(Path::new("/shaders/shader.frag")),
+            None
+        );
+    }
+
+    #[test]
+    fn language_detection_order() {
+        let config = ServerConfig {
+            languageDetectionOrder: vec![ShadingLanguage::Hlsl, ShadingLanguage::Glsl],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.detect_language("#version 450\nvoid main() {}"),
+            Some(ShadingLanguage::Glsl)
+        );
+        assert_eq!(
+            config.detect_language("float4 main() : SV_TARGET { return 0; }"),
+            Some(ShadingLanguage::Hlsl)
+        );
+        assert_eq!(
+            config.detect_language("float value;"),
+            Some(ShadingLanguage::Hlsl)
+        );
+        assert_eq!(
+            ServerConfig::default().detect_language("#version 450"),
+            None
+        );
+    }
+
+    #[test]
+    fn severity_override_match() {
+        let config: ServerConfig = serde_json::from_value(serde_json::json!({
+            "includes": [],
+            "defines": {},
+            "validate": true,
+            "symbols": true,
+            "severity": "hint",
+            "severityOverrides": {
+                "extension not supported": "hint",
+                "'GL_EXT_debug_printf' : extension not supported": "none",
+            },
+            "hlsl": serde_json::to_value(ServerHlslConfig::default()).unwrap(),
+            "glsl": serde_json::to_value(ServerGlslConfig::default()).unwrap(),
+        }))
+        .unwrap();
+        assert_eq!(
+            config.get_severity_override(None, "'GL_EXT_foo' : Extension not supported"),
+            Some(ServerSeverityOverride::Hint)
+        );
+        // More specific pattern is preferred.
+        assert_eq!(
+            config.get_severity_override(None, "'GL_EXT_debug_printf' : extension not supported"),
+            Some(ServerSeverityOverride::None)
+        );
+        assert_eq!(
+            config.get_severity_override(None, "undeclared identifier"),
+            None
+        );
+    }
+
+    #[test]
+    fn severity_override_code() {
+        let config = ServerConfig {
+            severityOverrides: HashMap::from([
+                ("X3206".into(), ServerSeverityOverride::None),
+                ("truncation".into(), ServerSeverityOverride::Hint),
+            ]),
+            ..Default::default()
+        };
+        // Code is matched before the message.
+        assert_eq!(
+            config.get_severity_override(Some("x3206"), "implicit truncation of vector type"),
+            Some(ServerSeverityOverride::None)
+        );
+        assert_eq!(
+            config.get_severity_override(Some("X3578"), "implicit truncation of vector type"),
+            Some(ServerSeverityOverride::Hint)
+        );
+        assert_eq!(
+            config
+                .get_severity_override(Some("X3578"), "output value is not completely initialized"),
+            None
+        );
+    }
+
+    #[test]
+    fn completion_commit_characters() {
+        let config: ServerConfig = serde_json::from_value(serde_json::json!({
+            "includes": [],
+            "defines": {},
+            "validate": true,
+            "symbols": true,
+            "severity": "hint",
+            "completionCommitCharacters": {
+                "Hlsl": ["("],
+                "Wgsl": [],
+            },
+            "hlsl": serde_json::to_value(ServerHlslConfig::default()).unwrap(),
+            "glsl": serde_json::to_value(ServerGlslConfig::default()).unwrap(),
+        }))
+        .unwrap();
+        assert_eq!(
+            config.get_completion_commit_characters(ShadingLanguage::Hlsl),
+            vec!["(".to_string()]
+        );
+        assert!(config
+            .get_completion_commit_characters(ShadingLanguage::Wgsl)
+            .is_empty());
+        assert_eq!(
+            config.get_completion_commit_characters(ShadingLanguage::Glsl),
+            vec!["(".to_string(), ".".to_string(), ";".to_string()]
+        );
+    }
+
+    #[test]
+    fn validate_languages() {
+        let config: ServerConfig = serde_json::from_value(serde_json::json!({
+            "includes": [],
+            "defines": {},
+            "validate": true,
+            "validateLanguages": {
+                "Hlsl": false,
+            },
+            "symbols": true,
+            "severity": "hint",
+            "hlsl": serde_json::to_value(ServerHlslConfig::default()).unwrap(),
+            "glsl": serde_json::to_value(ServerGlslConfig::default()).unwrap(),
+        }))
+        .unwrap();
+        assert!(!config.is_validation_enabled(ShadingLanguage::Hlsl));
+        assert!(config.is_validation_enabled(ShadingLanguage::Glsl));
+        let config = ServerConfig {
+            validate: false,
+            validateLanguages: HashMap::from([(ShadingLanguage::Wgsl, true)]),
+            ..Default::default()
+        };
+        assert!(!config.is_validation_enabled(ShadingLanguage::Glsl));
+        assert!(config.is_validation_enabled(ShadingLanguage::Wgsl));
+    }
+}
@@ -1,8 +1,12 @@
 use std::collections::HashMap;
 
-use log::error;
+use log::{error, warn};
 use lsp_server::{Connection, IoThreads, Message, RequestId, Response};
-use lsp_types::{InitializeParams, MessageType, ShowMessageParams};
+use lsp_types::{
+    notification::{LogTrace, Notification},
+    InitializeParams, LogTraceParams, MessageType, ServerCapabilities, ShowMessageParams,
+    TraceValue,
+};
 use serde_json::Value;
 
 use super::ServerLanguage;
@@ -12,6 +16,7 @@ pub struct ServerConnection {
     io_threads: Option<IoThreads>,
     request_id: i32,
     request_callbacks: HashMap<RequestId, fn(&mut ServerLanguage, Value)>,
+    trace: TraceValue,
 }
 
 impl ServerConnection {
@@ -24,25 +29,55 @@ impl ServerConnection {
             io_threads: Some(io_threads),
             request_id: 0,
             request_callbacks: HashMap::new(),
+            trace: TraceValue::Off,
         }
     }
-    pub fn initialize(
+    #[cfg(test)]
+    pub fn memory() -> (Self, Connection) {
+        let (connection, client) = Connection::memory();
+        (
+            Self {
+                connection,
+                io_threads: None,
+                request_id: 0,
+                request_callbacks: HashMap::new(),
+                trace: TraceValue::Off,
+            },
+            client,
+        )
+    }
+    // Capabilities depend on the client initialization options.
+    pub fn initialize<F: FnOnce(&InitializeParams) -> ServerCapabilities>(
         &mut self,
-        server_capabilities: Value,
+        get_server_capabilities: F,
     ) -> Result<InitializeParams, Box<dyn std::error::Error + Sync + Send>> {
-        match self.connection.initialize(server_capabilities) {
-            Ok(initialization_params) => {
-                let client_initialization_params: InitializeParams =
-                    serde_json::from_value(initialization_params)?;
-                Ok(client_initialization_params)
-            }
-            Err(e) => {
-                if e.channel_is_disconnected() {
-                    self.io_threads.take().unwrap().join()?;
+        let (id, initialization_params) = match self.connection.initialize_start() {
+            Ok(value) => value,
+            Err(e) => return Err(self.on_initialize_error(e)),
+        };
+        let client_initialization_params: InitializeParams =
+            serde_json::from_value(initialization_params)?;
+        let server_capabilities = get_server_capabilities(&client_initialization_params);
+        let initialize_result = serde_json::json!({
+            "capabilities": server_capabilities,
+        });
+        match self.connection.initialize_finish(id, initialize_result) {
+            Ok(_) => Ok(client_initialization_params),
+            Err(e) => Err(self.on_initialize_error(e)),
+        }
+    }
+    fn on_initialize_error(
+        &mut self,
+        e: lsp_server::ProtocolError,
+    ) -> Box<dyn std::error::Error + Sync + Send> {
+        if e.channel_is_disconnected() {
+            if let Some(io_threads) = self.io_threads.take() {
+                if let Err(err) = io_threads.join() {
+                    return err.into();
                 }
-                Err(e.into())
             }
         }
+        e.into()
     }
     pub fn remove_callback(
         &mut self,
@@ -78,6 +113,13 @@ impl ServerConnection {
             message: message,
         })
     }
+    pub fn send_notification_warning(&self, message: String) {
+        warn!("NOTIFICATION: {}", message);
+        self.send_notification::<lsp_types::notification::ShowMessage>(ShowMessageParams {
+            typ: MessageType::WARNING,
+            message,
+        })
+    }
     pub fn send_request<R: lsp_types::request::Request>(
         &mut self,
         params: R::Params,
@@ -89,7 +131,33 @@ impl ServerConnection {
         let req = lsp_server::Request::new(request_id, R::METHOD.to_owned(), params);
         self.send(req.into());
     }
+    pub fn set_trace(&mut self, trace: TraceValue) {
+        self.trace = trace;
+    }
+    pub fn trace_message(&self, message: &Message, received: bool) {
+        let description = match message {
+            Message::Request(req) => format!("request '{} - ({})'", req.method, req.id),
+            Message::Response(resp) => format!("response '({})'", resp.id),
+            // Do not trace traces.
+            Message::Notification(not) if not.method == LogTrace::METHOD => return,
+            Message::Notification(not) => format!("notification '{}'", not.method),
+        };
+        let verbose = match self.trace {
+            TraceValue::Off => return,
+            TraceValue::Messages => None,
+            TraceValue::Verbose => serde_json::to_string_pretty(message).ok(),
+        };
+        self.send_notification::<LogTrace>(LogTraceParams {
+            message: format!(
+                "{} {}.",
+                if received { "Received" } else { "Sending" },
+                description
+            ),
+            verbose,
+        });
+    }
     fn send(&self, message: Message) {
+        self.trace_message(&message, false);
         self.connection
             .sender
             .send(message)
@@ -24,6 +24,7 @@ pub struct ServerFileCache {
     pub symbol_tree: SymbolTree, // Store content on change as its not on disk.
     pub symbol_cache: ShaderSymbolList, // Store symbol to avoid computing them at every change.
     pub dependencies: HashMap<PathBuf, ServerFileCacheHandle>, // Store all dependencies of this file.
+    pub includers: Vec<(PathBuf, PathBuf, u32)>, // Include graph found during validation.
 }
 
 pub struct ServerLanguageFileCache {
@@ -133,6 +134,7 @@ impl ServerLanguageFileCache {
                         ShaderSymbolList::default()
                     },
                     dependencies: HashMap::new(), // Will be filled by validator.
+                    includers: Vec::new(),
                 }));
                 let none = self.files.insert(uri.clone(), Rc::clone(&cached_file));
                 assert!(none.is_none());
@@ -147,6 +149,8 @@ impl ServerLanguageFileCache {
         );
         Ok(cached_file)
     }
+    // Dependencies are parsed with the language of the including file rather than their extension,
+    // so a header shared between languages is watched once per language.
     pub fn watch_dependency(
         &mut self,
         uri: &Url,
@@ -196,6 +200,7 @@ impl ServerLanguageFileCache {
                             ShaderSymbolList::default()
                         },
                         dependencies: HashMap::new(), // Will be filled by validator.
+                        includers: Vec::new(),
                     }));
                     let none = self
                         .dependencies
@@ -1,23 +1,139 @@
-use std::cell::RefCell;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 
+use log::{error, warn};
+use lsp_types::Url;
 use shader_sense::{
-    symbols::symbols::{ShaderSymbolList, SymbolProvider},
-    validator::{glslang::Glslang, naga::Naga, validator::Validator},
+    include::canonicalize,
+    shader::{HlslBackend, ShadingLanguage},
+    symbols::symbols::{
+        ShaderSymbol, ShaderSymbolData, ShaderSymbolList, ShaderSymbolType, SymbolProvider,
+    },
+    validator::{glslang::Glslang, naga::Naga, registry::ValidatorRegistry, validator::Validator},
 };
 
-#[cfg(not(target_os = "wasi"))]
-use shader_sense::validator::dxc::Dxc;
-
 use super::{
+    common::{get_shading_language, read_string_lossy},
     server_config::ServerConfig,
-    server_file_cache::{ServerFileCacheHandle, ServerLanguageFileCache},
+    server_file_cache::{ServerFileCache, ServerFileCacheHandle, ServerLanguageFileCache},
+    shader_config_file::ShaderConfigFile,
 };
 
+// Files scanned from workspaceSymbolRoots are capped, as roots might be large.
+const MAX_SCANNED_FILES: usize = 10000;
+
+type IndexedSymbols = Vec<(ShaderSymbol, ShaderSymbolType)>;
+
+// Global symbols declared by a file, the ones of its includes are indexed with them.
+fn get_indexed_symbols(file_path: &Path, symbol_list: &ShaderSymbolList) -> IndexedSymbols {
+    symbol_list
+        .iter()
+        .filter(|(_, ty)| !matches!(ty, ShaderSymbolType::Keyword))
+        .flat_map(|(symbols, ty)| symbols.iter().map(move |symbol| (symbol, ty.clone())))
+        .filter(|(symbol, _)| {
+            let is_declared_here = symbol
+                .range
+                .as_ref()
+                .is_some_and(|range| range.start.file_path == *file_path);
+            let is_global = match &symbol.scope_stack {
+                Some(scope_stack) => scope_stack.is_empty(),
+                None => true,
+            };
+            let is_include = matches!(symbol.data, ShaderSymbolData::Link { .. });
+            is_declared_here && is_global && !is_include
+        })
+        .map(|(symbol, ty)| (symbol.clone(), ty))
+        .collect()
+}
+
+// Case insensitive score of the query within a label, lower is better. Labels starting with the
+// query come first, then the ones containing it or its characters in order, such as cmpLgt for computeLighting.
+fn get_match_score(label: &str, query: &str) -> Option<u32> {
+    let label = label.to_lowercase();
+    if label.starts_with(query) {
+        Some(0)
+    } else if label.contains(query) {
+        Some(1)
+    } else {
+        let mut label_chars = label.chars();
+        query
+            .chars()
+            .all(|query_char| label_chars.any(|label_char| label_char == query_char))
+            .then_some(2)
+    }
+}
+
+// Global symbols declared by each watched file, including dependencies.
+// Updated when files change so that workspace queries do not parse anything.
+// Files of workspaceSymbolRoots are scanned once, watched files take precedence over them.
+#[derive(Debug, Default)]
+pub struct ServerSymbolIndex {
+    files: HashMap<Url, IndexedSymbols>,
+    scanned_files: HashMap<Url, IndexedSymbols>,
+}
+
+impl ServerSymbolIndex {
+    pub fn update_file(&mut self, uri: &Url, cached_file: &ServerFileCache) {
+        self.files.insert(
+            uri.clone(),
+            get_indexed_symbols(
+                &cached_file.symbol_tree.file_path,
+                &cached_file.symbol_cache,
+            ),
+        );
+    }
+    pub fn remove_file(&mut self, uri: &Url) {
+        self.files.remove(uri);
+    }
+    pub fn get_files(&self) -> Vec<&Url> {
+        self.files.keys().collect()
+    }
+    pub fn set_scanned_files(&mut self, scanned_files: HashMap<Url, IndexedSymbols>) {
+        self.scanned_files = scanned_files;
+    }
+    // Fuzzy match of the query within symbol labels, best matches first. Empty query match all symbols.
+    pub fn find_symbols(&self, query: &str) -> Vec<(&Url, &ShaderSymbol, ShaderSymbolType)> {
+        let query = query.to_lowercase();
+        let mut symbols: Vec<(u32, &Url, &ShaderSymbol, ShaderSymbolType)> = self
+            .files
+            .iter()
+            .chain(
+                self.scanned_files
+                    .iter()
+                    .filter(|(uri, _)| !self.files.contains_key(*uri)),
+            )
+            .flat_map(|(uri, symbols)| {
+                symbols
+                    .iter()
+                    .map(move |(symbol, ty)| (uri, symbol, ty.clone()))
+            })
+            .filter_map(|(uri, symbol, ty)| {
+                get_match_score(&symbol.label, &query).map(|score| (score, uri, symbol, ty))
+            })
+            .collect();
+        symbols.sort_by(|lhs, rhs| (lhs.0, &lhs.2.label).cmp(&(rhs.0, &rhs.2.label)));
+        symbols
+            .into_iter()
+            .map(|(_, uri, symbol, ty)| (uri, symbol, ty))
+            .collect()
+    }
+}
+
 pub struct ServerLanguageData {
     pub watched_files: ServerLanguageFileCache,
     pub validator: Box<dyn Validator>,
+    pub validator_name: &'static str,
+    pub validator_error: Option<String>, // Set when the preferred validator could not be loaded.
     pub symbol_provider: SymbolProvider,
     pub config: ServerConfig,
+    pub include_roots: Vec<String>, // Roots learned this session when autoIncludeRoots is set.
+    pub shader_config_files: HashMap<PathBuf, ShaderConfigFile>, // Loaded on first use by path.
+    pub symbol_index: ServerSymbolIndex,
+    pub deferred_files: HashSet<Url>, // Opened files not validated yet, with deferOpenValidation.
+    pub dependents: HashMap<PathBuf, HashSet<Url>>, // Watched files including a path, found on validation.
 }
 
 impl ServerLanguageData {
@@ -25,39 +141,279 @@ impl ServerLanguageData {
         Self {
             watched_files: ServerLanguageFileCache::new(),
             validator: Box::new(Glslang::glsl()),
+            validator_name: "glslang",
+            validator_error: None,
             symbol_provider: SymbolProvider::glsl(),
             config: ServerConfig::default(),
+            include_roots: Vec::new(),
+            shader_config_files: HashMap::new(),
+            symbol_index: ServerSymbolIndex::default(),
+            deferred_files: HashSet::new(),
+            dependents: HashMap::new(),
         }
     }
     pub fn hlsl() -> Self {
+        let (validator, validator_name, validator_error) =
+            Self::create_hlsl_validator(HlslBackend::default());
         Self {
             watched_files: ServerLanguageFileCache::new(),
-            #[cfg(target_os = "wasi")]
-            validator: Box::new(Glslang::hlsl()),
-            #[cfg(not(target_os = "wasi"))]
-            validator: Box::new(Dxc::new().unwrap()),
+            validator,
+            validator_name,
+            validator_error,
             symbol_provider: SymbolProvider::hlsl(),
             config: ServerConfig::default(),
+            include_roots: Vec::new(),
+            shader_config_files: HashMap::new(),
+            symbol_index: ServerSymbolIndex::default(),
+            deferred_files: HashSet::new(),
+            dependents: HashMap::new(),
         }
     }
     pub fn wgsl() -> Self {
         Self {
             watched_files: ServerLanguageFileCache::new(),
             validator: Box::new(Naga::new()),
+            validator_name: "naga",
+            validator_error: None,
             symbol_provider: SymbolProvider::wgsl(),
             config: ServerConfig::default(),
+            include_roots: Vec::new(),
+            shader_config_files: HashMap::new(),
+            symbol_index: ServerSymbolIndex::default(),
+            deferred_files: HashSet::new(),
+            dependents: HashMap::new(),
+        }
+    }
+    // Glslang is used on WASI, or if DXC library is missing & hlsl.glslangFallback is set.
+    fn create_hlsl_validator(
+        hlsl_backend: HlslBackend,
+    ) -> (Box<dyn Validator>, &'static str, Option<String>) {
+        let registry = ValidatorRegistry::default();
+        let backend = registry
+            .resolve_backend(ShadingLanguage::Hlsl, hlsl_backend.get_name())
+            .unwrap_or("glslang");
+        match registry.create(ShadingLanguage::Hlsl, backend) {
+            Ok(validator) => (validator, backend, None),
+            Err(err) => {
+                warn!("HLSL validation is disabled: {}", err);
+                (Box::new(Glslang::hlsl()), "glslang", Some(err.to_string()))
+            }
+        }
+    }
+    // Recreate the HLSL validator when the configured backend changed.
+    pub fn set_hlsl_backend(&mut self, hlsl_backend: HlslBackend) {
+        let is_glslang_used = self.validator_name == "glslang" && self.validator_error.is_none();
+        if is_glslang_used != (hlsl_backend == HlslBackend::Glslang) {
+            (self.validator, self.validator_name, self.validator_error) =
+                Self::create_hlsl_validator(hlsl_backend);
+        }
+    }
+    // Validator actually used, none if it could not be loaded & no fallback is allowed.
+    pub fn get_validator_name(&self) -> &'static str {
+        if self.validator_error.is_some() && !self.config.hlsl.glslangFallback {
+            "none"
+        } else {
+            self.validator_name
+        }
+    }
+    // Index a file & the dependencies found during its validation.
+    pub fn index_file(&mut self, uri: &Url, cached_file: &ServerFileCacheHandle) {
+        let cached_file = RefCell::borrow(cached_file);
+        self.symbol_index.update_file(uri, &cached_file);
+        for (dependency_path, dependency_file) in &cached_file.dependencies {
+            if let Ok(dependency_uri) = Url::from_file_path(dependency_path) {
+                self.index_file(&dependency_uri, dependency_file);
+            }
+        }
+    }
+    // Index files of workspaceSymbolRoots, so that workspace symbols include closed files.
+    // Their includes are not resolved, included files being scanned on their own.
+    pub fn scan_workspace_symbol_roots(&mut self, shading_language: ShadingLanguage) {
+        fn collect_files(directory: &Path, file_paths: &mut Vec<PathBuf>) {
+            let entries = match std::fs::read_dir(directory) {
+                Ok(entries) => entries,
+                Err(err) => {
+                    warn!("Failed to scan {}: {}", directory.display(), err);
+                    return;
+                }
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+                if is_hidden {
+                    continue; // Such as .git
+                } else if path.is_dir() {
+                    collect_files(&path, file_paths);
+                } else {
+                    file_paths.push(path);
+                }
+            }
+        }
+        let mut file_paths = Vec::new();
+        for root in &self.config.workspaceSymbolRoots {
+            match canonicalize(Path::new(root)) {
+                Ok(root) => collect_files(&root, &mut file_paths),
+                Err(err) => warn!("Invalid workspace symbol root {}: {}", root, err),
+            }
+        }
+        file_paths.retain(|file_path| {
+            self.config
+                .get_file_association(file_path)
+                .or_else(|| get_shading_language(file_path))
+                == Some(shading_language)
+        });
+        if file_paths.len() > MAX_SCANNED_FILES {
+            warn!(
+                "Only the first {} of {} files of workspace symbol roots are scanned.",
+                MAX_SCANNED_FILES,
+                file_paths.len()
+            );
+            file_paths.truncate(MAX_SCANNED_FILES);
+        }
+        let validation_params = self.config.into_validation_params();
+        let mut scanned_files = HashMap::new();
+        for file_path in file_paths {
+            let Ok(uri) = Url::from_file_path(&file_path) else {
+                continue;
+            };
+            let symbol_list = read_string_lossy(&file_path)
+                .map_err(|err| err.to_string())
+                .and_then(|content| {
+                    let symbol_tree = self
+                        .symbol_provider
+                        .create_ast(&file_path, &content)
+                        .map_err(|err| err.to_string())?;
+                    self.symbol_provider
+                        .get_all_symbols(&symbol_tree, &validation_params)
+                        .map_err(|err| err.to_string())
+                });
+            match symbol_list {
+                Ok(symbol_list) => {
+                    scanned_files.insert(uri, get_indexed_symbols(&file_path, &symbol_list));
+                }
+                Err(err) => error!("Failed to scan {}: {}", file_path.display(), err),
+            }
+        }
+        self.symbol_index.set_scanned_files(scanned_files);
+    }
+    // Dependencies not opened in the editor are refreshed from disk.
+    pub fn refresh_dependency(&mut self, uri: &Url, is_deleted: bool) {
+        if self.watched_files.get(uri).is_some() {
+            return; // Content is managed by the client.
+        }
+        let cached_file = match self.watched_files.get_dependency(uri) {
+            Some(cached_file) => cached_file,
+            None => return,
+        };
+        if is_deleted {
+            self.symbol_index.remove_file(uri);
+            return;
+        }
+        let file_path = uri.to_file_path().unwrap();
+        let content = match read_string_lossy(&file_path) {
+            Ok(content) => content,
+            Err(err) => {
+                error!("Failed to read dependency {}: {}", file_path.display(), err);
+                return;
+            }
+        };
+        let result = RefCell::borrow_mut(&cached_file).update(
+            uri,
+            &mut self.symbol_provider,
+            &self.config,
+            None,
+            Some(&content),
+        );
+        match result {
+            Ok(()) => self
+                .symbol_index
+                .update_file(uri, &RefCell::borrow(&cached_file)),
+            Err(err) => error!(
+                "Failed to update dependency {}: {}",
+                file_path.display(),
+                err
+            ),
+        }
+    }
+    // Replace the includes of a watched file in the reverse map.
+    pub fn set_dependencies(&mut self, uri: &Url, dependencies: HashSet<PathBuf>) {
+        self.remove_dependents(uri);
+        for dependency in dependencies {
+            self.dependents
+                .entry(dependency)
+                .or_default()
+                .insert(uri.clone());
+        }
+    }
+    pub fn remove_dependents(&mut self, uri: &Url) {
+        self.dependents.retain(|_, dependents| {
+            dependents.remove(uri);
+            !dependents.is_empty()
+        });
+    }
+    // Watched files including the path, directly or not.
+    pub fn get_dependents(&self, file_path: &Path) -> Vec<Url> {
+        let mut dependents: Vec<Url> = self
+            .dependents
+            .get(file_path)
+            .map(|dependents| dependents.iter().cloned().collect())
+            .unwrap_or_default();
+        dependents.sort();
+        dependents
+    }
+    // Evict files that are not watched anymore, either as main file or dependency.
+    pub fn evict_unwatched_files(&mut self) {
+        let unwatched_files: Vec<Url> = self
+            .symbol_index
+            .get_files()
+            .into_iter()
+            .filter(|uri| {
+                self.watched_files.get(uri).is_none()
+                    && self.watched_files.get_dependency(uri).is_none()
+            })
+            .cloned()
+            .collect();
+        for uri in unwatched_files {
+            self.symbol_index.remove_file(&uri);
         }
     }
     pub fn get_all_symbols(&self, cached_file: ServerFileCacheHandle) -> ShaderSymbolList {
         let cached_file = RefCell::borrow(&cached_file);
-        // Add current symbols
-        let mut symbol_cache = cached_file.symbol_cache.clone();
+        let validation_params = self.config.into_validation_params();
+        // Add current & deps symbols. Deps are processed in include order so that their
+        // defines apply to the following ones.
+        let mut processed_dependencies = HashSet::new();
+        let mut symbol_cache = if self.config.symbols && !cached_file.dependencies.is_empty() {
+            match self.symbol_provider.get_all_symbols_with_includes(
+                &cached_file.symbol_tree,
+                &validation_params,
+                &mut |include_path: &Path| {
+                    let deps_cached_file = cached_file.dependencies.get(include_path)?;
+                    processed_dependencies.insert(PathBuf::from(include_path));
+                    Some(RefCell::borrow(deps_cached_file).symbol_tree.clone())
+                },
+            ) {
+                Ok(symbol_list) => symbol_list,
+                Err(err) => {
+                    error!("Failed to get symbols with includes: {}", err);
+                    processed_dependencies.clear();
+                    cached_file.symbol_cache.clone()
+                }
+            }
+        } else {
+            cached_file.symbol_cache.clone()
+        };
         // Add intrinsics symbols
-        symbol_cache.append(self.symbol_provider.get_intrinsics_symbol().clone());
-        // Add deps symbols
-        for (_, deps_cached_file) in &cached_file.dependencies {
-            let deps_cached_file = RefCell::borrow(&deps_cached_file);
-            symbol_cache.append(deps_cached_file.symbol_cache.clone());
+        symbol_cache.append(
+            self.symbol_provider
+                .get_file_intrinsics_symbol(&cached_file.symbol_tree, &validation_params),
+        );
+        // Add deps symbols that could not be resolved in order.
+        for (deps_path, deps_cached_file) in &cached_file.dependencies {
+            if !processed_dependencies.contains(deps_path) {
+                let deps_cached_file = RefCell::borrow(&deps_cached_file);
+                symbol_cache.append(deps_cached_file.symbol_cache.clone());
+            }
         }
         symbol_cache
     }
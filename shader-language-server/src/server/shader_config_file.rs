@@ -0,0 +1,213 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use shader_sense::{include::IncludeHandler, validator::validator::ValidationParams};
+
+use super::ServerLanguageData;
+
+pub const SHADER_CONFIG_FILE_NAME: &str = ".shader-config.json";
+
+// Workspace config applied to every shader in its directory & sub directories.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+#[allow(non_snake_case)]
+pub struct ShaderConfigFile {
+    pub defines: HashMap<String, String>,
+    pub includes: Vec<String>, // Relative to the config file directory.
+    pub entryPoints: HashMap<String, String>, // Entry point by file path relative to the config file directory.
+}
+
+impl ShaderConfigFile {
+    pub fn is_shader_config_file(file_path: &Path) -> bool {
+        file_path
+            .file_name()
+            .is_some_and(|file_name| file_name == SHADER_CONFIG_FILE_NAME)
+    }
+    // Closest config file walking up from the shader directory.
+    pub fn find(file_path: &Path) -> Option<PathBuf> {
+        file_path
+            .ancestors()
+            .skip(1)
+            .map(|directory| directory.join(SHADER_CONFIG_FILE_NAME))
+            .find(|config_path| config_path.is_file())
+    }
+    pub fn load(config_path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(config_path).map_err(|err| err.to_string())?;
+        serde_json::from_str(&content).map_err(|err| err.to_string())
+    }
+    // Local defines override global ones, local includes are searched first.
+    pub fn merge(
+        &self,
+        config_path: &Path,
+        file_path: &Path,
+        validation_params: &mut ValidationParams,
+    ) {
+        let config_directory = config_path.parent().unwrap_or(Path::new("."));
+        if let Ok(relative_path) = file_path.strip_prefix(config_directory) {
+            if let Some((_, entry_point)) = self
+                .entryPoints
+                .iter()
+                .find(|(entry_path, _)| Path::new(entry_path) == relative_path)
+            {
+                validation_params.entry_point = Some(entry_point.clone());
+            }
+        }
+        validation_params.defines.extend(self.defines.clone());
+        let includes: Vec<String> = self
+            .includes
+            .iter()
+            .map(|include| config_directory.join(include).to_string_lossy().to_string())
+            .collect();
+        validation_params.includes.splice(0..0, includes);
+    }
+}
+
+impl ServerLanguageData {
+    // Validation params for a file, with its shader config file applied.
+    pub fn get_file_validation_params(&mut self, file_path: &Path) -> ValidationParams {
+        let mut validation_params = self.config.into_validation_params();
+        if let Some(config_path) = ShaderConfigFile::find(file_path) {
+            let shader_config_file = self
+                .shader_config_files
+                .entry(config_path.clone())
+                .or_insert_with(|| match ShaderConfigFile::load(&config_path) {
+                    Ok(shader_config_file) => {
+                        info!("Loaded shader config file {}", config_path.display());
+                        shader_config_file
+                    }
+                    Err(err) => {
+                        error!(
+                            "Failed to load shader config file {}: {}",
+                            config_path.display(),
+                            err
+                        );
+                        ShaderConfigFile::default()
+                    }
+                });
+            shader_config_file.merge(&config_path, file_path, &mut validation_params);
+        }
+        validation_params
+    }
+    // Params used to search includes, with roots learned this session.
+    pub fn get_file_include_params(&mut self, file_path: &Path) -> ValidationParams {
+        let mut validation_params = self.get_file_validation_params(file_path);
+        if self.config.autoIncludeRoots {
+            validation_params
+                .includes
+                .extend(self.include_roots.iter().cloned());
+        }
+        validation_params
+    }
+    // Resolve includes of a file with the same search paths as validation.
+    pub fn get_include_handler(&mut self, file_path: &Path) -> IncludeHandler {
+        let validation_params = self.get_file_include_params(file_path);
+        IncludeHandler::new(
+            file_path,
+            validation_params.includes,
+            validation_params.virtual_includes,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Url;
+    use shader_sense::{
+        include::canonicalize,
+        shader::{HlslBackend, ShaderStage, ShadingLanguage},
+    };
+
+    use super::*;
+
+    #[test]
+    fn shader_config_file_defines_and_includes() {
+        let file_path = canonicalize(Path::new(
+            "../shader-sense/test/glsl/shader-config/shader-config.frag.glsl",
+        ))
+        .unwrap();
+        let uri = Url::from_file_path(&file_path).unwrap();
+        let mut language_data = ServerLanguageData::glsl();
+        language_data.config.defines = HashMap::from([
+            ("CONFIG_VALUE".into(), "1".into()),
+            ("GLOBAL_VALUE".into(), "1".into()),
+        ]);
+        let validation_params = language_data.get_file_validation_params(&file_path);
+        assert_eq!(validation_params.defines["CONFIG_VALUE"], "2");
+        assert_eq!(validation_params.defines["GLOBAL_VALUE"], "1");
+        assert!(validation_params.includes[0].ends_with("../inc0"));
+        // Shader requires the include directory & macro of the config file.
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &std::fs::read_to_string(&file_path).unwrap(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let diagnostics = language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+        assert!(
+            diagnostics
+                .values()
+                .all(|diagnostics| diagnostics.is_empty()),
+            "{:#?}",
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn shader_config_file_entry_point() {
+        let file_path = canonicalize(Path::new(
+            "../shader-sense/test/hlsl/shader-config/entry-point.hlsl",
+        ))
+        .unwrap();
+        let uri = Url::from_file_path(&file_path).unwrap();
+        let mut language_data = ServerLanguageData::hlsl();
+        language_data.config.lint.ambiguousEntryPoint = true;
+        let validation_params = language_data.get_file_validation_params(&file_path);
+        assert_eq!(validation_params.entry_point.as_deref(), Some("vsMain"));
+        // Selecting an entry point lifts the ambiguity between vertex entry points.
+        let symbol_tree = language_data
+            .symbol_provider
+            .create_ast(&file_path, &std::fs::read_to_string(&file_path).unwrap())
+            .unwrap();
+        assert!(language_data
+            .symbol_provider
+            .get_entry_point_diagnostics(&symbol_tree, &validation_params)
+            .diagnostics
+            .is_empty());
+        let ambiguous_file_path = canonicalize(Path::new(
+            "../shader-sense/test/hlsl/ambiguous-entry-point.hlsl",
+        ))
+        .unwrap();
+        assert!(language_data
+            .get_file_validation_params(&ambiguous_file_path)
+            .entry_point
+            .is_none());
+        // Validation targets the stage of the selected entry point.
+        language_data.set_hlsl_backend(HlslBackend::Glslang);
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &std::fs::read_to_string(&file_path).unwrap(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let validation_job = language_data
+            .get_validation_job(&uri, &cached_file)
+            .unwrap();
+        assert_eq!(validation_job.params.entry_point.as_deref(), Some("vsMain"));
+        assert_eq!(
+            validation_job.params.shader_stage,
+            Some(ShaderStage::Vertex)
+        );
+    }
+}
@@ -1,21 +1,20 @@
-use std::{
-    io::{BufRead, BufReader},
-    rc::Rc,
-};
+use std::{cmp::Reverse, rc::Rc};
 
 use log::debug;
 use lsp_types::{
     MarkupContent, ParameterInformation, ParameterLabel, Position, SignatureHelp,
     SignatureInformation, Url,
 };
-use regex::Regex;
 
 use shader_sense::{
     shader_error::ValidatorError,
     symbols::symbols::{ShaderPosition, ShaderSymbol, ShaderSymbolData},
 };
 
-use super::{ServerFileCacheHandle, ServerLanguageData};
+use super::{
+    common::{get_expected_type, get_return_type_score, is_identifier},
+    ServerFileCacheHandle, ServerLanguageData,
+};
 
 impl ServerLanguageData {
     pub fn recolt_signature(
@@ -38,6 +37,17 @@ impl ServerLanguageData {
             line: position.line as u32,
             pos: position.character as u32,
         });
+        // Assignment or declaration before the call give the expected return type.
+        let expected_type = function_parameter.0.as_ref().and_then(|function| {
+            let line = cached_file
+                .symbol_tree
+                .content
+                .lines()
+                .nth(position.line as usize)?;
+            let line = line.get(..position.character as usize).unwrap_or(line);
+            let call_start = line.rfind(format!("{}(", function).as_str())?;
+            get_expected_type(&line[..call_start], &completion)
+        });
         let (shader_symbols, parameter_index): (Vec<&ShaderSymbol>, u32) =
             if let (Some(function), Some(parameter_index)) = function_parameter {
                 (
@@ -54,7 +64,7 @@ impl ServerLanguageData {
         let signatures: Vec<SignatureInformation> = shader_symbols
             .iter()
             .filter_map(|shader_symbol| {
-                if let ShaderSymbolData::Functions { signatures } = &shader_symbol.data {
+                if let ShaderSymbolData::Functions { signatures, .. } = &shader_symbol.data {
                     Some(
                         signatures
                             .iter()
@@ -93,63 +103,195 @@ impl ServerLanguageData {
             })
             .collect::<Vec<Vec<SignatureInformation>>>()
             .concat();
+        // Best overload accepts the parameter being typed, then match the expected type.
+        let active_signature = shader_symbols
+            .iter()
+            .filter_map(|shader_symbol| match &shader_symbol.data {
+                ShaderSymbolData::Functions { signatures, .. } => Some(signatures),
+                _ => None,
+            })
+            .flatten()
+            .map(|signature| {
+                let is_variadic = signature
+                    .parameters
+                    .last()
+                    .is_some_and(|parameter| parameter.is_variadic());
+                let accept_parameter =
+                    is_variadic || signature.parameters.len() > parameter_index as usize;
+                let score = expected_type.as_ref().map_or(0, |expected_type| {
+                    get_return_type_score(&signature.returnType, expected_type)
+                });
+                (accept_parameter, score, Reverse(signature.parameters.len()))
+            })
+            .enumerate()
+            .min_by_key(|(_, key)| Reverse(*key))
+            .and_then(|(index, (accept_parameter, score, _))| {
+                (accept_parameter || score > 0).then_some(index as u32)
+            });
         if signatures.is_empty() {
             debug!("No signature for symbol {:?} found", shader_symbols);
             Ok(None)
         } else {
             Ok(Some(SignatureHelp {
                 signatures: signatures,
-                active_signature: None,
+                active_signature,
                 active_parameter: Some(parameter_index), // TODO: check out of bounds.
             }))
         }
     }
 }
+// Function called at position & index of the parameter being typed.
+// Commas are only counted at the depth of the call, so nested calls & arrays are skipped.
 fn get_function_parameter_at_position(
-    shader: &String,
+    shader: &str,
     position: Position,
 ) -> (Option<String>, Option<u32>) {
-    let reader = BufReader::new(shader.as_bytes());
-    let line = reader
-        .lines()
-        .nth(position.line as usize)
-        .expect("Text position is out of bounds")
-        .expect("Could not read line");
-    // Check this regex is working for all lang.
-    let regex =
-        Regex::new("\\b([a-zA-Z_][a-zA-Z0-9_]*)(\\(.*?)(\\))").expect("Failed to init regex");
-    for capture in regex.captures_iter(line.as_str()) {
-        let file_name = capture.get(1).expect("Failed to get function name");
-        let parenthesis = capture.get(2).expect("Failed to get paranthesis");
-        let parameter_index = if position.character >= parenthesis.start() as u32
-            && position.character <= parenthesis.end() as u32
-        {
-            let parameters = line[parenthesis.start()..parenthesis.end()].to_string();
-            let parameters = parameters.split(',');
-            let pos_in_parameters = position.character as usize - parenthesis.start();
-            // Compute parameter index
-            let mut parameter_index = 0;
-            let mut parameter_offset = 0;
-            for parameter in parameters {
-                parameter_offset += parameter.len() + 1; // Add 1 for removed comma
-                if parameter_offset > pos_in_parameters {
-                    break;
+    // Calls might span multiple lines, so read everything before the cursor.
+    let mut offset = 0;
+    for (index, line) in shader.split('\n').enumerate() {
+        if index == position.line as usize {
+            offset += line
+                .char_indices()
+                .nth(position.character as usize)
+                .map_or(line.len(), |(offset, _)| offset);
+            break;
+        }
+        offset += line.len() + 1;
+    }
+    let text = &shader[..offset.min(shader.len())];
+    let mut depth = 0;
+    let mut parameter_index = 0;
+    for (index, c) in text.char_indices().rev() {
+        match c {
+            ')' | ']' | '}' => depth += 1,
+            '(' | '[' | '{' if depth > 0 => depth -= 1,
+            '(' => {
+                let label = text[..index].trim_end();
+                let label_start = label
+                    .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .map_or(0, |label_start| label_start + 1);
+                let label = &label[label_start..];
+                if is_identifier(label) {
+                    return (Some(label.into()), Some(parameter_index));
                 }
-                parameter_index += 1;
+                // Parenthesized expression, the call is further.
+                parameter_index = 0;
             }
-            Some(parameter_index)
-        } else {
-            None
-        };
-        if position.character >= file_name.start() as u32
-            && position.character <= parenthesis.end() as u32
-        {
-            return (
-                Some(line[file_name.start()..file_name.end()].to_string()),
-                parameter_index,
-            );
+            '[' => parameter_index = 0,
+            // Reached the start of the block, not in a call.
+            '{' | ';' => break,
+            ',' if depth == 0 => parameter_index += 1,
+            _ => {}
         }
     }
     // No signature
     (None, None)
 }
+
+#[cfg(test)]
+mod tests {
+    use shader_sense::shader::ShadingLanguage;
+
+    use super::*;
+
+    #[test]
+    fn signature_expected_return_type() {
+        let uri = Url::parse("file:///shaders/signature.hlsl").unwrap();
+        let content = "float3 transform(float3x3 m, float3 v) {\n\
+                       \x20   float3 result = mul(m, v);\n\
+                       \x20   float scale = mul(1.0, 2.0);\n\
+                       \x20   result = mul(m, v);\n\
+                       \x20   return result * scale;\n\
+                       }\n";
+        let mut language_data = ServerLanguageData::hlsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let mut get_active_signature = |position: Position| {
+            let signature_help = language_data
+                .recolt_signature(&uri, Rc::clone(&cached_file), position)
+                .unwrap()
+                .unwrap();
+            signature_help
+                .active_signature
+                .map(|index| signature_help.signatures[index as usize].label.clone())
+        };
+        assert_eq!(
+            get_active_signature(Position::new(1, 25)),
+            Some("floatn mul(floatn x, floatn y)".into())
+        );
+        assert_eq!(
+            get_active_signature(Position::new(2, 24)),
+            Some("float mul(float x, float y)".into())
+        );
+        // Type of assigned variable is used when there is no declaration.
+        assert_eq!(
+            get_active_signature(Position::new(3, 18)),
+            Some("floatn mul(floatn x, floatn y)".into())
+        );
+    }
+
+    #[test]
+    fn signature_active_parameter() {
+        let uri = Url::parse("file:///shaders/parameter.hlsl").unwrap();
+        let content = "float4 main(float2 uv : TEXCOORD0, sampler2D s) : SV_Target {\n\
+                       \x20   float a = max(dot(uv, uv), uv[min(0, 1)]);\n\
+                       \x20   return tex2D(s, uv, float2(0.0, 1.0),\n\
+                       \x20       uv);\n\
+                       }\n";
+        let mut language_data = ServerLanguageData::hlsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &content.to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        let mut get_signature = |position: Position| {
+            let signature_help = language_data
+                .recolt_signature(&uri, Rc::clone(&cached_file), position)
+                .unwrap()
+                .unwrap();
+            (
+                signature_help.signatures[signature_help.active_signature.unwrap() as usize]
+                    .label
+                    .clone(),
+                signature_help.active_parameter.unwrap(),
+            )
+        };
+        // Nested call has its own parameters.
+        assert_eq!(
+            get_signature(Position::new(1, 22)),
+            ("float dot(floatn x, floatn y)".into(), 0)
+        );
+        assert_eq!(
+            get_signature(Position::new(1, 26)),
+            ("float dot(floatn x, floatn y)".into(), 1)
+        );
+        // Commas of nested calls & arrays are skipped.
+        assert_eq!(get_signature(Position::new(1, 32)).1, 1);
+        assert_eq!(get_signature(Position::new(1, 43)).1, 1);
+        // Overload is chosen by arity, across lines.
+        assert_eq!(
+            get_signature(Position::new(2, 17)),
+            ("float4 tex2D(sampler2D s, float2 t)".into(), 0)
+        );
+        assert_eq!(
+            get_signature(Position::new(3, 8)),
+            (
+                "float4 tex2D(sampler2D s, float2 t, float2 ddx, float2 ddy)".into(),
+                3
+            )
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use lsp_types::request::Request;
+use serde::{Deserialize, Serialize};
+use shader_sense::shader::ShadingLanguage;
+
+use super::{server_config::ServerConfig, ServerLanguageData};
+
+#[derive(Debug)]
+pub enum StatusRequest {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorStatus {
+    pub name: String,
+    pub available: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LanguageStatus {
+    pub language: ShadingLanguage,
+    pub validator: String,
+    pub intrinsics: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStatus {
+    pub version: String,
+    pub platform: String,
+    pub validators: Vec<ValidatorStatus>,
+    pub languages: Vec<LanguageStatus>,
+    pub pending_validations: usize, // Files of the current background validation batch.
+    pub config: ServerConfig,
+}
+
+impl Request for StatusRequest {
+    type Params = ();
+    type Result = ServerStatus;
+    const METHOD: &'static str = "shader/status";
+}
+
+pub fn get_server_status(
+    language_data: &HashMap<ShadingLanguage, ServerLanguageData>,
+    pending_validations: usize,
+) -> ServerStatus {
+    let mut languages: Vec<LanguageStatus> = language_data
+        .iter()
+        .map(|(language, data)| {
+            let intrinsics = data.symbol_provider.get_intrinsics_symbol();
+            LanguageStatus {
+                language: *language,
+                validator: data.get_validator_name().into(),
+                intrinsics: intrinsics.functions.len()
+                    + intrinsics.types.len()
+                    + intrinsics.constants.len()
+                    + intrinsics.variables.len()
+                    + intrinsics.keywords.len(),
+            }
+        })
+        .collect();
+    languages.sort_by_key(|status| status.language.to_string());
+    // DXC is only loaded if available on the platform, others are builtin.
+    let is_used = |name: &str| languages.iter().any(|status| status.validator == name);
+    let validators = vec![
+        ValidatorStatus {
+            name: "dxc".into(),
+            available: is_used("dxc"),
+        },
+        ValidatorStatus {
+            name: "glslang".into(),
+            available: true,
+        },
+        ValidatorStatus {
+            name: "naga".into(),
+            available: true,
+        },
+    ];
+    ServerStatus {
+        version: crate::get_version().into(),
+        platform: std::env::consts::OS.into(),
+        validators,
+        languages,
+        pending_validations,
+        config: language_data
+            .values()
+            .next()
+            .map(|data| data.config.clone())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_validators() {
+        let language_data = HashMap::from([
+            (ShadingLanguage::Glsl, ServerLanguageData::glsl()),
+            (ShadingLanguage::Hlsl, ServerLanguageData::hlsl()),
+            (ShadingLanguage::Wgsl, ServerLanguageData::wgsl()),
+        ]);
+        let status = get_server_status(&language_data, 0);
+        #[cfg(not(target_os = "wasi"))]
+        let dxc_available = shader_sense::validator::dxc::Dxc::new().is_ok();
+        #[cfg(target_os = "wasi")]
+        let dxc_available = false;
+        let dxc = status.validators.iter().find(|v| v.name == "dxc").unwrap();
+        assert_eq!(dxc.available, dxc_available);
+        let hlsl = status
+            .languages
+            .iter()
+            .find(|l| l.language == ShadingLanguage::Hlsl)
+            .unwrap();
+        // Glslang fallback for HLSL is opt-in.
+        assert_eq!(hlsl.validator, if dxc_available { "dxc" } else { "none" });
+        assert!(hlsl.intrinsics > 0);
+        assert_eq!(status.version, crate::get_version());
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use lsp_types::{request::Request, Position, TextDocumentIdentifier, Url};
+use serde::{Deserialize, Serialize};
+use shader_sense::symbols::symbols::{ShaderPosition, ShaderSymbolData, SymbolError};
+
+use super::{common::shader_range_to_lsp_range, ServerFileCacheHandle, ServerLanguageData};
+
+#[derive(Debug)]
+pub enum SymbolOriginRequest {}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolOriginParams {
+    pub text_document: TextDocumentIdentifier,
+    pub position: Position,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolOrigin {
+    pub label: String,
+    pub uri: Option<Url>, // None for builtins & configured defines.
+    pub range: Option<lsp_types::Range>,
+    pub include_chain: Vec<Url>, // Includers of the declaring file, up to the requested file.
+    pub description: String,     // Such as "from common.hlsl, included by math.hlsl".
+}
+
+impl Request for SymbolOriginRequest {
+    type Params = SymbolOriginParams;
+    type Result = Option<SymbolOrigin>;
+    const METHOD: &'static str = "shader/symbolOrigin";
+}
+
+fn get_file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+// Walk the include graph up from the declaring file, following the shallowest includer.
+fn get_include_chain(
+    includers: &[(PathBuf, PathBuf, u32)],
+    declaring_path: &Path,
+    file_path: &Path,
+) -> Vec<PathBuf> {
+    let mut chain = Vec::new();
+    let mut path = declaring_path;
+    while path != file_path {
+        let includer = includers
+            .iter()
+            .filter(|(dependency, includer, _)| dependency == path && !chain.contains(includer))
+            .min_by_key(|(_, _, depth)| *depth);
+        match includer {
+            Some((_, includer, _)) => {
+                chain.push(includer.clone());
+                path = includer;
+            }
+            None => break, // Graph is not known without validation.
+        }
+    }
+    chain
+}
+
+impl ServerLanguageData {
+    pub fn recolt_symbol_origin(
+        &mut self,
+        uri: &Url,
+        cached_file: ServerFileCacheHandle,
+        position: Position,
+    ) -> Result<Option<SymbolOrigin>, SymbolError> {
+        let file_path = uri.to_file_path().unwrap();
+        let shader_position = ShaderPosition {
+            file_path: file_path.clone(),
+            line: position.line,
+            pos: position.character,
+        };
+        let all_symbol_list = self.get_all_symbols(Rc::clone(&cached_file));
+        let cached_file = RefCell::borrow(&cached_file);
+        let word = match self
+            .symbol_provider
+            .get_word_range_at_position(&cached_file.symbol_tree, shader_position.clone())
+        {
+            Ok((word, _)) => word,
+            Err(SymbolError::NoSymbol) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let symbol_list = all_symbol_list.filter_scoped_symbol(shader_position);
+        // Declarations first, include directives are not symbols of their own.
+        let symbol = symbol_list
+            .find_symbols(word)
+            .into_iter()
+            .filter(|symbol| !matches!(symbol.data, ShaderSymbolData::Link { .. }))
+            .min_by_key(|symbol| symbol.range.is_none());
+        let Some(symbol) = symbol else {
+            return Ok(None);
+        };
+        let Some(range) = &symbol.range else {
+            let description = match symbol.data {
+                ShaderSymbolData::Macro { external: true, .. } => "from configuration",
+                _ => "builtin",
+            };
+            return Ok(Some(SymbolOrigin {
+                label: symbol.label.clone(),
+                uri: None,
+                range: None,
+                include_chain: Vec::new(),
+                description: description.into(),
+            }));
+        };
+        let declaring_path = &range.start.file_path;
+        let include_chain = get_include_chain(&cached_file.includers, declaring_path, &file_path);
+        // The requested file is implied.
+        let includers: Vec<String> = include_chain
+            .iter()
+            .filter(|includer| **includer != file_path)
+            .map(|includer| get_file_name(includer))
+            .collect();
+        let description = if includers.is_empty() {
+            format!("from {}", get_file_name(declaring_path))
+        } else {
+            format!(
+                "from {}, included by {}",
+                get_file_name(declaring_path),
+                includers.join(", included by ")
+            )
+        };
+        Ok(Some(SymbolOrigin {
+            label: symbol.label.clone(),
+            uri: Url::from_file_path(declaring_path).ok(),
+            range: Some(shader_range_to_lsp_range(range)),
+            include_chain: include_chain
+                .iter()
+                .filter_map(|includer| Url::from_file_path(includer).ok())
+                .collect(),
+            description,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shader_sense::{include::canonicalize, shader::ShadingLanguage};
+
+    use super::*;
+
+    #[test]
+    fn symbol_origin_include_chain() {
+        let file_path = canonicalize(Path::new(
+            "../shader-sense/test/glsl/include-level.comp.glsl",
+        ))
+        .unwrap();
+        let uri = Url::from_file_path(&file_path).unwrap();
+        let mut language_data = ServerLanguageData::glsl();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Glsl,
+                &std::fs::read_to_string(&file_path).unwrap(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        // Include graph is found by the validation.
+        language_data.recolt_diagnostic(&uri, &cached_file).unwrap();
+        let mut get_origin = |line: u32, character: u32| {
+            language_data
+                .recolt_symbol_origin(
+                    &uri,
+                    Rc::clone(&cached_file),
+                    Position::new(line, character),
+                )
+                .unwrap()
+                .unwrap()
+        };
+        let file_name = |uri: &Url| get_file_name(&uri.to_file_path().unwrap());
+        let origin = get_origin(24, 20);
+        assert_eq!(origin.label, "fibonacciLevel1");
+        assert_eq!(file_name(origin.uri.as_ref().unwrap()), "level1.glsl");
+        assert_eq!(
+            origin
+                .include_chain
+                .iter()
+                .map(file_name)
+                .collect::<Vec<String>>(),
+            vec!["level0.glsl", "include-level.comp.glsl"]
+        );
+        assert_eq!(
+            origin.description,
+            "from level1.glsl, included by level0.glsl"
+        );
+        let origin = get_origin(23, 20);
+        assert_eq!(origin.label, "fibonacciLevel0");
+        assert_eq!(origin.description, "from level0.glsl");
+        let origin = get_origin(22, 16);
+        assert_eq!(origin.label, "fibonacci");
+        assert!(origin.include_chain.is_empty());
+        assert_eq!(origin.description, "from include-level.comp.glsl");
+        let origin = get_origin(20, 16);
+        assert_eq!(origin.label, "gl_GlobalInvocationID");
+        assert_eq!(origin.uri, None);
+        assert_eq!(origin.description, "builtin");
+    }
+}
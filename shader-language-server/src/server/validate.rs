@@ -0,0 +1,662 @@
+use std::{
+    collections::HashMap,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::Serialize;
+use shader_sense::{
+    include::IncludeHandler,
+    shader::{HlslBackend, ShadingLanguage},
+    shader_error::{ShaderDiagnostic, ShaderErrorSeverity},
+    symbols::symbols::{ShaderSymbol, ShaderSymbolType, SymbolProvider},
+    validator::{create_validator, registry::ValidatorRegistry, validator::ValidationParams},
+};
+
+use super::{common::get_shading_language, server_config::ServerConfig};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ValidateFormat {
+    #[default]
+    Json,
+    Sarif, // SARIF 2.1.0 for code scanning tools.
+}
+
+// Arguments of the standalone validation mode.
+#[derive(Debug, Default)]
+pub struct ValidateArgs {
+    // With stdin, path is only used to resolve includes & report diagnostics.
+    pub file_path: PathBuf,
+    pub stdin: bool,
+    pub symbols: bool, // Print symbols instead of diagnostics.
+    pub shading_language: Option<ShadingLanguage>,
+    pub includes: Vec<String>,
+    pub defines: HashMap<String, String>,
+    pub format: ValidateFormat,
+    pub hlsl_backend: HlslBackend,
+}
+
+impl ValidateArgs {
+    // Parse --validate <file>, --symbols <file> or --stdin [--path <file>], --lang <hlsl|glsl|wgsl>,
+    // --include <dir>, --define K=V, --format <json|sarif> & --hlsl-backend <dxc|glslang>.
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let mut file_path = None;
+        let mut stdin_path = None;
+        let mut stdin = false;
+        let mut symbols = false;
+        let mut shading_language = None;
+        let mut includes = Vec::new();
+        let mut defines = HashMap::new();
+        let mut format = ValidateFormat::default();
+        let mut hlsl_backend = HlslBackend::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--validate" => {
+                    if symbols {
+                        return Err("--validate and --symbols are exclusive".into());
+                    }
+                    file_path = Some(PathBuf::from(
+                        args.next().ok_or("Missing file after --validate")?,
+                    ))
+                }
+                "--symbols" => {
+                    if file_path.is_some() {
+                        return Err("--validate and --symbols are exclusive".into());
+                    }
+                    file_path = Some(PathBuf::from(
+                        args.next().ok_or("Missing file after --symbols")?,
+                    ));
+                    symbols = true;
+                }
+                "--stdin" => stdin = true,
+                "--path" => {
+                    stdin_path = Some(PathBuf::from(
+                        args.next().ok_or("Missing file after --path")?,
+                    ))
+                }
+                "--lang" => {
+                    let lang = args.next().ok_or("Missing language after --lang")?;
+                    shading_language = Some(
+                        ShadingLanguage::from_str(lang)
+                            .map_err(|_| format!("Unknown language {}", lang))?,
+                    );
+                }
+                "--include" => includes.push(
+                    args.next()
+                        .ok_or("Missing directory after --include")?
+                        .clone(),
+                ),
+                "--define" => {
+                    let define = args.next().ok_or("Missing define after --define")?;
+                    let (key, value) = define.split_once('=').unwrap_or((define, ""));
+                    defines.insert(key.into(), value.into());
+                }
+                "--format" => {
+                    format = match args.next().map(|format| format.as_str()) {
+                        Some("json") => ValidateFormat::Json,
+                        Some("sarif") => ValidateFormat::Sarif,
+                        Some(format) => return Err(format!("Unknown format {}", format)),
+                        None => return Err("Missing format after --format".into()),
+                    }
+                }
+                "--hlsl-backend" => {
+                    hlsl_backend = match args.next().map(|backend| backend.as_str()) {
+                        Some("dxc") => HlslBackend::Dxc,
+                        Some("glslang") => HlslBackend::Glslang,
+                        Some(backend) => return Err(format!("Unknown HLSL backend {}", backend)),
+                        None => return Err("Missing backend after --hlsl-backend".into()),
+                    }
+                }
+                _ => return Err(format!("Unknown argument {}", arg)),
+            }
+        }
+        let file_path = match (file_path, stdin) {
+            (Some(_), true) if symbols => return Err("--symbols and --stdin are exclusive".into()),
+            (Some(_), true) => return Err("--validate and --stdin are exclusive".into()),
+            (Some(file_path), false) => file_path,
+            (None, true) => stdin_path.unwrap_or(PathBuf::from("stdin")),
+            (None, false) => {
+                return Err("Missing --validate <file>, --symbols <file> or --stdin".into())
+            }
+        };
+        Ok(Self {
+            file_path,
+            stdin,
+            symbols,
+            shading_language,
+            includes,
+            defines,
+            format,
+            hlsl_backend,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+struct ValidateDiagnostic {
+    file: String,
+    severity: String,
+    message: String,
+    line: u32,
+    column: u32,
+    endLine: Option<u32>,
+    endColumn: Option<u32>,
+}
+
+impl ValidateDiagnostic {
+    fn new(diagnostic: &ShaderDiagnostic, file_path: &Path) -> Self {
+        Self {
+            file: diagnostic
+                .file_path
+                .as_deref()
+                .unwrap_or(file_path)
+                .to_string_lossy()
+                .to_string(),
+            severity: diagnostic.severity.to_string(),
+            message: diagnostic.error.clone(),
+            line: diagnostic.line,
+            column: diagnostic.pos,
+            endLine: diagnostic.end.map(|(line, _)| line),
+            endColumn: diagnostic.end.map(|(_, column)| column),
+        }
+    }
+}
+
+// Symbol with its origin & range, which are not part of the symbol serialization.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+struct ExportedSymbol<'a> {
+    kind: ShaderSymbolType,
+    origin: &'static str, // intrinsic, file, include or define.
+    file: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    endLine: Option<u32>,
+    endColumn: Option<u32>,
+    #[serde(flatten)]
+    symbol: &'a ShaderSymbol,
+}
+
+impl<'a> ExportedSymbol<'a> {
+    fn new(
+        symbol: &'a ShaderSymbol,
+        kind: ShaderSymbolType,
+        is_intrinsic: bool,
+        file_path: &Path,
+    ) -> Self {
+        let origin = match &symbol.range {
+            _ if is_intrinsic => "intrinsic",
+            Some(range) if range.start.file_path == file_path => "file",
+            Some(_) => "include",
+            None => "define", // Defines from parameters.
+        };
+        let range = symbol.range.as_ref();
+        Self {
+            kind,
+            origin,
+            file: range.map(|range| range.start.file_path.to_string_lossy().to_string()),
+            line: range.map(|range| range.start.line),
+            column: range.map(|range| range.start.pos),
+            endLine: range.map(|range| range.end.line),
+            endColumn: range.map(|range| range.end.pos),
+            symbol,
+        }
+    }
+}
+
+fn get_validator_name(
+    shading_language: ShadingLanguage,
+    hlsl_backend: HlslBackend,
+) -> &'static str {
+    let registry = ValidatorRegistry::default();
+    let backend = match shading_language {
+        ShadingLanguage::Hlsl => hlsl_backend.get_name(),
+        _ => "",
+    };
+    registry
+        .resolve_backend(shading_language, backend)
+        .unwrap_or("none")
+}
+
+// Validators have no stable codes, so derive one from the message without its quoted names & numbers.
+fn get_rule_id(validator_name: &str, message: &str) -> String {
+    let mut normalized = String::new();
+    let mut quote = None;
+    for c in message.chars() {
+        match quote {
+            Some(end) if c == end => quote = None,
+            Some(_) => {}
+            None if ['\'', '"', '`'].contains(&c) => quote = Some(c),
+            None if c.is_ascii_alphabetic() => normalized.push(c.to_ascii_lowercase()),
+            None => {
+                if !normalized.is_empty() && !normalized.ends_with('-') {
+                    normalized.push('-');
+                }
+            }
+        }
+    }
+    let normalized: String = normalized.chars().take(64).collect();
+    let normalized = normalized.trim_end_matches('-');
+    if normalized.is_empty() {
+        format!("{}/diagnostic", validator_name)
+    } else {
+        format!("{}/{}", validator_name, normalized)
+    }
+}
+
+fn get_sarif_level(severity: &ShaderErrorSeverity) -> &'static str {
+    match severity {
+        ShaderErrorSeverity::Error => "error",
+        ShaderErrorSeverity::Warning => "warning",
+        ShaderErrorSeverity::Information | ShaderErrorSeverity::Hint => "note",
+    }
+}
+
+// SARIF lines & columns start at 1.
+fn get_sarif_log(
+    diagnostics: &[ShaderDiagnostic],
+    file_path: &Path,
+    shading_language: ShadingLanguage,
+    hlsl_backend: HlslBackend,
+) -> serde_json::Value {
+    let validator_name = get_validator_name(shading_language, hlsl_backend);
+    let mut rule_ids: Vec<String> = Vec::new();
+    let results: Vec<serde_json::Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let rule_id = get_rule_id(validator_name, &diagnostic.error);
+            if !rule_ids.contains(&rule_id) {
+                rule_ids.push(rule_id.clone());
+            }
+            let mut region = serde_json::json!({
+                "startLine": diagnostic.line.max(1),
+                "startColumn": diagnostic.pos + 1,
+            });
+            if let Some((end_line, end_column)) = diagnostic.end {
+                region["endLine"] = end_line.max(1).into();
+                region["endColumn"] = (end_column + 1).into();
+            }
+            serde_json::json!({
+                "ruleId": rule_id,
+                "level": get_sarif_level(&diagnostic.severity),
+                "message": { "text": diagnostic.error.trim() },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": {
+                            "uri": diagnostic
+                                .file_path
+                                .as_deref()
+                                .unwrap_or(file_path)
+                                .to_string_lossy()
+                                .replace('\\', "/"),
+                        },
+                        "region": region,
+                    }
+                }],
+            })
+        })
+        .collect();
+    let rules: Vec<serde_json::Value> = rule_ids
+        .iter()
+        .map(|rule_id| serde_json::json!({ "id": rule_id }))
+        .collect();
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "shader-language-server",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/antaalt/shader-sense",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+impl ValidateArgs {
+    // Explicit language takes precedence over the extension.
+    fn get_shading_language(&self) -> Option<ShadingLanguage> {
+        self.shading_language
+            .or_else(|| get_shading_language(&self.file_path))
+    }
+    fn get_validation_params(&self) -> ValidationParams {
+        let mut validation_params = ServerConfig::default().into_validation_params();
+        validation_params.includes = self.includes.clone();
+        validation_params.defines = self.defines.clone();
+        validation_params
+    }
+    // Main buffer come from stdin or disk, includes are always read from disk.
+    fn read_content(&self) -> Result<String, String> {
+        if self.stdin {
+            let mut content = String::new();
+            std::io::stdin()
+                .read_to_string(&mut content)
+                .map_err(|err| format!("Failed to read stdin: {}", err))?;
+            Ok(content)
+        } else {
+            std::fs::read_to_string(&self.file_path)
+                .map_err(|err| format!("Failed to read {}: {}", self.file_path.display(), err))
+        }
+    }
+}
+
+pub fn validate_file(args: &ValidateArgs) -> Result<Vec<ShaderDiagnostic>, String> {
+    validate_content(args, args.read_content()?)
+}
+
+pub fn validate_content(
+    args: &ValidateArgs,
+    content: String,
+) -> Result<Vec<ShaderDiagnostic>, String> {
+    let shading_language = args.get_shading_language().ok_or(format!(
+        "Unable to deduce shading language of {}, use --lang",
+        args.file_path.display()
+    ))?;
+    let validation_params = args.get_validation_params();
+    let mut validator = create_validator(shading_language, args.hlsl_backend);
+    let (diagnostic_list, _) = validator
+        .validate_shader(
+            content,
+            &args.file_path,
+            validation_params,
+            &mut |deps_path: &Path| std::fs::read_to_string(deps_path).ok(),
+        )
+        .map_err(|err| err.to_string())?;
+    Ok(diagnostic_list.diagnostics)
+}
+
+// Symbols of the file, its includes & intrinsics, as the server would resolve them.
+pub fn export_symbols(args: &ValidateArgs, content: String) -> Result<serde_json::Value, String> {
+    let shading_language = args.get_shading_language().ok_or(format!(
+        "Unable to deduce shading language of {}, use --lang",
+        args.file_path.display()
+    ))?;
+    let validation_params = args.get_validation_params();
+    let mut symbol_provider = SymbolProvider::from(shading_language);
+    let symbol_tree = symbol_provider
+        .create_ast(&args.file_path, &content)
+        .map_err(|err| err.to_string())?;
+    // Includes are parsed ahead, as the provider is borrowed while resolving them.
+    let mut include_handler = IncludeHandler::new(
+        &args.file_path,
+        validation_params.includes.clone(),
+        validation_params.virtual_includes.clone(),
+    );
+    let (dependencies, _) = include_handler.find_dependencies(&content, &mut |deps_path| {
+        std::fs::read_to_string(deps_path).ok()
+    });
+    let mut include_trees = HashMap::new();
+    for (deps_content, deps_path) in dependencies {
+        let deps_tree = symbol_provider
+            .create_ast(&deps_path, &deps_content)
+            .map_err(|err| err.to_string())?;
+        include_trees.insert(deps_path, deps_tree);
+    }
+    let symbol_list = symbol_provider
+        .get_all_symbols_with_includes(&symbol_tree, &validation_params, &mut |include_path| {
+            include_trees.get(include_path).cloned()
+        })
+        .map_err(|err| err.to_string())?;
+    let intrinsics = symbol_provider.get_file_intrinsics_symbol(&symbol_tree, &validation_params);
+    let mut exported_symbols = Vec::new();
+    for (list, is_intrinsic) in [(&symbol_list, false), (&intrinsics, true)] {
+        for (symbols, kind) in list.iter() {
+            exported_symbols.extend(symbols.iter().map(|symbol| {
+                ExportedSymbol::new(symbol, kind.clone(), is_intrinsic, &symbol_tree.file_path)
+            }));
+        }
+    }
+    Ok(serde_json::to_value(exported_symbols).unwrap())
+}
+
+// Print diagnostics as JSON. Exit code is 1 if an error is found, 2 if validation failed.
+pub fn run_validation(args: &[String]) -> i32 {
+    let args = match ValidateArgs::parse(args) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}", err);
+            return 2;
+        }
+    };
+    if args.symbols {
+        return match args
+            .read_content()
+            .and_then(|content| export_symbols(&args, content))
+        {
+            Ok(symbols) => {
+                println!("{}", serde_json::to_string_pretty(&symbols).unwrap());
+                0
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                2
+            }
+        };
+    }
+    match validate_file(&args) {
+        Ok(diagnostics) => {
+            let output = match args.format {
+                ValidateFormat::Json => serde_json::to_value(
+                    diagnostics
+                        .iter()
+                        .map(|diagnostic| ValidateDiagnostic::new(diagnostic, &args.file_path))
+                        .collect::<Vec<ValidateDiagnostic>>(),
+                )
+                .unwrap(),
+                // Language was already resolved by the validation.
+                ValidateFormat::Sarif => get_sarif_log(
+                    &diagnostics,
+                    &args.file_path,
+                    args.get_shading_language().unwrap(),
+                    args.hlsl_backend,
+                ),
+            };
+            println!("{}", serde_json::to_string_pretty(&output).unwrap());
+            if diagnostics
+                .iter()
+                .any(|diagnostic| matches!(diagnostic.severity, ShaderErrorSeverity::Error))
+            {
+                1
+            } else {
+                0
+            }
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            2
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn validate_parse_args() {
+        let args = ValidateArgs::parse(&to_args(&[
+            "--include",
+            "inc0",
+            "--validate",
+            "shader.frag",
+            "--define",
+            "KEY=VALUE",
+            "--define",
+            "FLAG",
+        ]))
+        .unwrap();
+        assert_eq!(args.file_path, PathBuf::from("shader.frag"));
+        assert_eq!(args.includes, vec!["inc0".to_string()]);
+        assert_eq!(args.defines["KEY"], "VALUE");
+        assert_eq!(args.defines["FLAG"], "");
+        assert!(ValidateArgs::parse(&to_args(&["--include", "inc0"])).is_err());
+        assert!(ValidateArgs::parse(&to_args(&["--validate"])).is_err());
+        assert!(ValidateArgs::parse(&to_args(&["--validate", "a.hlsl", "--unknown"])).is_err());
+        let args =
+            ValidateArgs::parse(&to_args(&["--validate", "a.hlsl", "--format", "sarif"])).unwrap();
+        assert_eq!(args.format, ValidateFormat::Sarif);
+        assert!(
+            ValidateArgs::parse(&to_args(&["--validate", "a.hlsl", "--format", "xml"])).is_err()
+        );
+        let args = ValidateArgs::parse(&to_args(&[
+            "--validate",
+            "a.hlsl",
+            "--hlsl-backend",
+            "glslang",
+        ]))
+        .unwrap();
+        assert_eq!(args.hlsl_backend, HlslBackend::Glslang);
+        assert_eq!(
+            get_shading_language(Path::new("shader.comp")),
+            Some(ShadingLanguage::Glsl)
+        );
+        assert_eq!(
+            get_shading_language(Path::new("shader.hlsli")),
+            Some(ShadingLanguage::Hlsl)
+        );
+        assert_eq!(get_shading_language(Path::new("shader.txt")), None);
+    }
+
+    #[test]
+    fn validate_parse_args_stdin() {
+        let args = ValidateArgs::parse(&to_args(&[
+            "--stdin",
+            "--lang",
+            "hlsl",
+            "--path",
+            "shaders/shader.txt",
+        ]))
+        .unwrap();
+        assert!(args.stdin);
+        assert_eq!(args.file_path, PathBuf::from("shaders/shader.txt"));
+        assert_eq!(args.get_shading_language(), Some(ShadingLanguage::Hlsl));
+        let args = ValidateArgs::parse(&to_args(&["--stdin"])).unwrap();
+        assert_eq!(args.file_path, PathBuf::from("stdin"));
+        assert_eq!(args.get_shading_language(), None);
+        assert!(ValidateArgs::parse(&to_args(&["--stdin", "--lang", "metal"])).is_err());
+        assert!(ValidateArgs::parse(&to_args(&["--stdin", "--validate", "a.hlsl"])).is_err());
+    }
+
+    #[test]
+    fn validate_content_includes() {
+        // Path does not exist on disk, but includes are resolved relative to it.
+        let args = ValidateArgs::parse(&to_args(&[
+            "--stdin",
+            "--path",
+            "../shader-sense/test/glsl/stdin.frag.glsl",
+        ]))
+        .unwrap();
+        let content = "#version 450\n#extension GL_GOOGLE_include_directive : require\n#include \"./inc0/level0.glsl\"\nvoid main() {}\n";
+        let diagnostics = validate_content(&args, content.into()).unwrap();
+        assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+        let content = "#version 450\nvoid main() { undeclared(); }\n";
+        let diagnostics = validate_content(&args, content.into()).unwrap();
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        let diagnostic = ValidateDiagnostic::new(&diagnostics[0], &args.file_path);
+        assert_eq!(diagnostic.file, "../shader-sense/test/glsl/stdin.frag.glsl");
+        assert_eq!(diagnostic.line, 2);
+    }
+
+    #[test]
+    fn validate_file_defines() {
+        let mut args = ValidateArgs::parse(&to_args(&[
+            "--validate",
+            "../shader-sense/test/glsl/macro.frag.glsl",
+        ]))
+        .unwrap();
+        let diagnostics = validate_file(&args).unwrap();
+        assert!(diagnostics
+            .iter()
+            .any(|diagnostic| matches!(diagnostic.severity, ShaderErrorSeverity::Error)));
+        args.defines.insert("CUSTOM_MACRO".into(), "1".into());
+        let diagnostics = validate_file(&args).unwrap();
+        assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+    }
+
+    #[test]
+    fn validate_sarif_output() {
+        let args = ValidateArgs::parse(&to_args(&[
+            "--validate",
+            "../shader-sense/test/glsl/macro.frag.glsl",
+            "--format",
+            "sarif",
+        ]))
+        .unwrap();
+        let diagnostics = validate_file(&args).unwrap();
+        let sarif = get_sarif_log(
+            &diagnostics,
+            &args.file_path,
+            ShadingLanguage::Glsl,
+            args.hlsl_backend,
+        );
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(
+            run["tool"]["driver"]["rules"][0]["id"],
+            "glslang/undeclared-identifier"
+        );
+        let result = &run["results"][0];
+        assert_eq!(result["ruleId"], "glslang/undeclared-identifier");
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["message"]["text"],
+            "'CUSTOM_MACRO' : undeclared identifier"
+        );
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(
+            location["artifactLocation"]["uri"],
+            "../shader-sense/test/glsl/macro.frag.glsl"
+        );
+        assert_eq!(location["region"]["startLine"], 4);
+        assert_eq!(location["region"]["startColumn"], 20);
+    }
+
+    #[test]
+    fn export_symbols_includes() {
+        let args = ValidateArgs::parse(&to_args(&[
+            "--symbols",
+            "../shader-sense/test/glsl/include-level.comp.glsl",
+        ]))
+        .unwrap();
+        assert!(args.symbols);
+        assert!(ValidateArgs::parse(&to_args(&["--symbols", "a.hlsl", "--stdin"])).is_err());
+        assert!(
+            ValidateArgs::parse(&to_args(&["--symbols", "a.hlsl", "--validate", "a.hlsl"]))
+                .is_err()
+        );
+        let symbols = export_symbols(&args, args.read_content().unwrap()).unwrap();
+        let symbols = symbols.as_array().unwrap();
+        let find_symbol = |label: &str| {
+            symbols
+                .iter()
+                .find(|symbol| symbol["label"] == label)
+                .unwrap_or_else(|| panic!("Missing symbol {}", label))
+        };
+        let fibonacci = find_symbol("fibonacci");
+        assert_eq!(fibonacci["origin"], "file");
+        assert_eq!(fibonacci["kind"], "Functions");
+        assert_eq!(fibonacci["line"], 6);
+        assert_eq!(fibonacci["column"], 5);
+        assert!(fibonacci["data"]["Functions"].is_object());
+        let level0 = find_symbol("fibonacciLevel0");
+        assert_eq!(level0["origin"], "include");
+        assert!(level0["file"].as_str().unwrap().ends_with("level0.glsl"));
+        // Nested includes are resolved too.
+        assert_eq!(find_symbol("fibonacciLevel1")["origin"], "include");
+        let intrinsic = find_symbol("gl_GlobalInvocationID");
+        assert_eq!(intrinsic["origin"], "intrinsic");
+        assert!(intrinsic["line"].is_null());
+        assert!(intrinsic["file"].is_null());
+    }
+}
@@ -0,0 +1,235 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use log::{debug, warn};
+use lsp_types::Url;
+use shader_sense::{
+    include::Dependencies,
+    shader::ShadingLanguage,
+    shader_error::{ShaderDiagnosticList, ValidatorError},
+    validator::{
+        registry::ValidatorRegistry,
+        validator::{ValidationParams, Validator},
+    },
+};
+
+use super::common::read_string_lossy;
+
+// Leave some cores to the client, validating files of a whole workspace can be heavy.
+const MAX_VALIDATION_THREADS: usize = 4;
+
+// File to validate with the validator of its language.
+pub struct ValidationJob {
+    pub uri: Url,
+    pub shading_language: ShadingLanguage,
+    pub validator_name: &'static str,
+    pub content: String,
+    pub params: ValidationParams,
+}
+
+pub struct ValidationResult {
+    pub generation: u64,
+    pub content_hash: u64, // Hash of the validated content, to detect edits made meanwhile.
+    pub uri: Url,
+    pub shading_language: ShadingLanguage,
+    pub result: Result<(ShaderDiagnosticList, Dependencies), ValidatorError>,
+}
+
+// Content of watched files at submission, as it might differ from disk.
+type ContentSnapshot = Arc<HashMap<PathBuf, String>>;
+
+struct QueuedJob {
+    generation: u64,
+    job: ValidationJob,
+    contents: ContentSnapshot,
+}
+
+// Validators are not Send, so each thread keeps its own validators.
+struct ValidationWorker {
+    registry: ValidatorRegistry,
+    validators: HashMap<(ShadingLanguage, &'static str), Box<dyn Validator>>,
+}
+
+impl ValidationWorker {
+    fn new() -> Self {
+        Self {
+            registry: ValidatorRegistry::default(),
+            validators: HashMap::new(),
+        }
+    }
+    fn validate(&mut self, queued_job: QueuedJob) -> ValidationResult {
+        let QueuedJob {
+            generation,
+            job,
+            contents,
+        } = queued_job;
+        let result = self.validate_job(&job, &contents);
+        ValidationResult {
+            generation,
+            content_hash: hash_content(&job.content),
+            uri: job.uri,
+            shading_language: job.shading_language,
+            result,
+        }
+    }
+    fn validate_job(
+        &mut self,
+        job: &ValidationJob,
+        contents: &ContentSnapshot,
+    ) -> Result<(ShaderDiagnosticList, Dependencies), ValidatorError> {
+        let file_path = job
+            .uri
+            .to_file_path()
+            .map_err(|_| ValidatorError::internal(format!("Invalid file path {}", job.uri)))?;
+        let validator = match self
+            .validators
+            .entry((job.shading_language, job.validator_name))
+        {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(
+                self.registry
+                    .create(job.shading_language, job.validator_name)?,
+            ),
+        };
+        debug!("Validating file {} in background", file_path.display());
+        validator.validate_shader(
+            job.content.clone(),
+            &file_path,
+            job.params.clone(),
+            &mut |deps_path: &Path| -> Option<String> {
+                match contents.get(deps_path) {
+                    Some(content) => Some(content.clone()),
+                    None => read_string_lossy(deps_path).ok(),
+                }
+            },
+        )
+    }
+}
+
+pub fn hash_content(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn run_worker(
+    jobs: Receiver<QueuedJob>,
+    results: Sender<ValidationResult>,
+    generation: Arc<AtomicU64>,
+) {
+    let mut worker = ValidationWorker::new();
+    for queued_job in jobs {
+        // Skip remaining jobs of cancelled batches.
+        if queued_job.generation != generation.load(Ordering::SeqCst) {
+            continue;
+        }
+        if results.send(worker.validate(queued_job)).is_err() {
+            break;
+        }
+    }
+}
+
+// Validate batches of files on a bounded set of threads.
+// Submitting a batch cancel the previous one, so that only the latest results are published.
+pub struct ValidationPool {
+    generation: Arc<AtomicU64>,
+    pending: usize, // Results of the current batch not received yet.
+    job_sender: Option<Sender<QueuedJob>>, // Workers are spawned with the first batch.
+    result_sender: Sender<ValidationResult>,
+    result_receiver: Receiver<ValidationResult>,
+}
+
+impl Default for ValidationPool {
+    fn default() -> Self {
+        let (result_sender, result_receiver) = crossbeam_channel::unbounded();
+        Self {
+            generation: Arc::new(AtomicU64::new(0)),
+            pending: 0,
+            job_sender: None,
+            result_sender,
+            result_receiver,
+        }
+    }
+}
+
+impl ValidationPool {
+    fn spawn_workers(&mut self) -> Option<Sender<QueuedJob>> {
+        if let Some(job_sender) = &self.job_sender {
+            return Some(job_sender.clone());
+        }
+        let thread_count = std::thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(1)
+            .min(MAX_VALIDATION_THREADS);
+        let (job_sender, job_receiver) = crossbeam_channel::unbounded();
+        for index in 0..thread_count {
+            let jobs = job_receiver.clone();
+            let results = self.result_sender.clone();
+            let generation = Arc::clone(&self.generation);
+            let spawned = std::thread::Builder::new()
+                .name(format!("validation-{}", index))
+                .spawn(move || run_worker(jobs, results, generation));
+            if let Err(err) = spawned {
+                // Threads are not supported on some targets such as WASI.
+                warn!("Failed to spawn validation thread: {}", err);
+                if index == 0 {
+                    return None;
+                }
+                break;
+            }
+        }
+        self.job_sender = Some(job_sender.clone());
+        Some(job_sender)
+    }
+    // Queue a batch of files, cancelling the running one. Returns the generation of the batch.
+    pub fn submit(&mut self, jobs: Vec<ValidationJob>, contents: HashMap<PathBuf, String>) -> u64 {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.pending = jobs.len();
+        let contents = Arc::new(contents);
+        let queued_jobs = jobs.into_iter().map(|job| QueuedJob {
+            generation,
+            job,
+            contents: Arc::clone(&contents),
+        });
+        match self.spawn_workers() {
+            Some(job_sender) => {
+                for queued_job in queued_jobs {
+                    // Workers only stop when the pool is dropped.
+                    let _ = job_sender.send(queued_job);
+                }
+            }
+            None => {
+                // Validate in place, results are still received from the channel.
+                let mut worker = ValidationWorker::new();
+                for queued_job in queued_jobs {
+                    let _ = self.result_sender.send(worker.validate(queued_job));
+                }
+            }
+        }
+        generation
+    }
+    // Results of the current batch are received through this channel.
+    pub fn get_receiver(&self) -> &Receiver<ValidationResult> {
+        &self.result_receiver
+    }
+    // Check if a received result belong to the current batch. Others were cancelled.
+    pub fn accept(&mut self, result: &ValidationResult) -> bool {
+        if result.generation != self.generation.load(Ordering::SeqCst) {
+            debug!("Dropping cancelled validation of {}", result.uri);
+            return false;
+        }
+        self.pending = self.pending.saturating_sub(1);
+        true
+    }
+    pub fn get_pending_count(&self) -> usize {
+        self.pending
+    }
+}
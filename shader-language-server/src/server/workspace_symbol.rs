@@ -0,0 +1,180 @@
+use lsp_types::{Location, SymbolInformation, SymbolKind};
+use shader_sense::symbols::symbols::{ShaderSymbolData, ShaderSymbolType};
+
+use super::{common::shader_range_to_lsp_range, ServerLanguageData};
+
+impl ServerLanguageData {
+    // Query the symbol index, no file is parsed here.
+    #[allow(deprecated)] // deprecated field is required.
+    pub fn recolt_workspace_symbol(&self, query: &str) -> Vec<SymbolInformation> {
+        self.symbol_index
+            .find_symbols(query)
+            .into_iter()
+            .filter_map(|(uri, symbol, ty)| {
+                let kind = match (ty, &symbol.data) {
+                    (_, ShaderSymbolData::Constants { .. }) => SymbolKind::CONSTANT,
+                    (_, ShaderSymbolData::Macro { .. }) => SymbolKind::CONSTANT, // Macros are stored with functions.
+                    (ShaderSymbolType::Functions, _) => SymbolKind::FUNCTION,
+                    (ShaderSymbolType::Types, ShaderSymbolData::Struct { .. }) => {
+                        SymbolKind::STRUCT
+                    }
+                    (ShaderSymbolType::Types, ShaderSymbolData::Interface { .. }) => {
+                        SymbolKind::INTERFACE
+                    }
+                    (ShaderSymbolType::Types, ShaderSymbolData::TypeParameter { .. }) => {
+                        SymbolKind::TYPE_PARAMETER
+                    }
+                    (ShaderSymbolType::Types, _) => SymbolKind::CLASS,
+                    (ShaderSymbolType::Variables, _) => SymbolKind::VARIABLE,
+                    _ => return None,
+                };
+                Some(SymbolInformation {
+                    name: symbol.label.clone(),
+                    kind,
+                    tags: None,
+                    deprecated: None,
+                    location: Location::new(
+                        uri.clone(),
+                        shader_range_to_lsp_range(symbol.range.as_ref()?),
+                    ),
+                    container_name: None,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use lsp_types::Url;
+    use shader_sense::shader::ShadingLanguage;
+
+    use super::*;
+
+    #[test]
+    fn workspace_symbol_index_update() {
+        let mut language_data = ServerLanguageData::hlsl();
+        let watch_file = |language_data: &mut ServerLanguageData, name: &str, content: &str| {
+            let uri = Url::parse(&format!("file:///shaders/{}", name)).unwrap();
+            let cached_file = language_data
+                .watched_files
+                .watch_file(
+                    &uri,
+                    ShadingLanguage::Hlsl,
+                    &content.to_string(),
+                    &mut language_data.symbol_provider,
+                    &language_data.config,
+                )
+                .unwrap();
+            language_data.index_file(&uri, &cached_file);
+            (uri, cached_file)
+        };
+        let query = |language_data: &ServerLanguageData, query: &str| -> Vec<String> {
+            let mut labels: Vec<String> = language_data
+                .recolt_workspace_symbol(query)
+                .into_iter()
+                .map(|symbol| symbol.name)
+                .collect();
+            labels.sort();
+            labels
+        };
+        let (lighting_uri, lighting_file) = watch_file(
+            &mut language_data,
+            "lighting.hlsl",
+            "struct Light { float3 position; };\n\
+             float computeLighting(Light light) {\n\
+             \x20   float attenuation = 1.0;\n\
+             \x20   return attenuation;\n\
+             }\n",
+        );
+        watch_file(
+            &mut language_data,
+            "shadow.hlsl",
+            "float computeShadow() { return 1.0; }\n",
+        );
+        // Locals & intrinsics are not indexed.
+        assert_eq!(
+            query(&language_data, ""),
+            vec!["Light", "computeLighting", "computeShadow"]
+        );
+        assert_eq!(
+            query(&language_data, "COMPUTE"),
+            vec!["computeLighting", "computeShadow"]
+        );
+        // Edit
+        RefCell::borrow_mut(&lighting_file)
+            .update(
+                &lighting_uri,
+                &mut language_data.symbol_provider,
+                &language_data.config,
+                None,
+                Some(&"float computeSpecular() { return 0.0; }\n".to_string()),
+            )
+            .unwrap();
+        language_data.index_file(&lighting_uri, &lighting_file);
+        assert_eq!(
+            query(&language_data, "compute"),
+            vec!["computeShadow", "computeSpecular"]
+        );
+        // Remove
+        language_data
+            .watched_files
+            .remove_file(&lighting_uri)
+            .unwrap();
+        language_data.evict_unwatched_files();
+        assert_eq!(query(&language_data, ""), vec!["computeShadow"]);
+    }
+
+    #[test]
+    fn workspace_symbol_fuzzy_scanned_roots() {
+        let mut language_data = ServerLanguageData::hlsl();
+        let uri = Url::parse("file:///shaders/lighting.hlsl").unwrap();
+        let cached_file = language_data
+            .watched_files
+            .watch_file(
+                &uri,
+                ShadingLanguage::Hlsl,
+                &"struct LightData { float3 position; };\n\
+                  float computeLighting() { return 1.0; }\n"
+                    .to_string(),
+                &mut language_data.symbol_provider,
+                &language_data.config,
+            )
+            .unwrap();
+        language_data.index_file(&uri, &cached_file);
+        let query = |language_data: &ServerLanguageData, query: &str| -> Vec<String> {
+            language_data
+                .recolt_workspace_symbol(query)
+                .into_iter()
+                .map(|symbol| symbol.name)
+                .collect()
+        };
+        // Labels starting with the query come first, then the ones containing its characters.
+        assert_eq!(
+            query(&language_data, "light"),
+            vec!["LightData", "computeLighting"]
+        );
+        assert_eq!(query(&language_data, "cmpLgt"), vec!["computeLighting"]);
+        assert!(query(&language_data, "shade").is_empty());
+        // Closed files are only found in scanned roots.
+        language_data.config.workspaceSymbolRoots = vec!["../shader-sense/test/hlsl/inc0".into()];
+        language_data.scan_workspace_symbol_roots(ShadingLanguage::Hlsl);
+        assert_eq!(query(&language_data, "shade"), vec!["shade"]);
+        let symbols = language_data.recolt_workspace_symbol("level");
+        let mut labels: Vec<&str> = symbols.iter().map(|symbol| symbol.name.as_str()).collect();
+        labels.sort();
+        assert_eq!(labels, vec!["level0", "level0", "level1"]); // level0-fail.hlsl declares it too.
+        assert!(symbols
+            .iter()
+            .all(|symbol| symbol.location.uri.path().contains("/inc0/")));
+        assert_eq!(
+            query(&language_data, "light"),
+            vec!["Light", "LightData", "computeLighting"]
+        );
+        // Other languages are not scanned.
+        language_data.scan_workspace_symbol_roots(ShadingLanguage::Glsl);
+        assert!(query(&language_data, "shade").is_empty());
+    }
+}
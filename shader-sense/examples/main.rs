@@ -1,14 +1,14 @@
 use std::path::Path;
 
 use shader_sense::{
-    shader::ShadingLanguage,
+    shader::{HlslBackend, ShadingLanguage},
     symbols::create_symbol_provider,
     validator::{create_validator, validator::ValidationParams},
 };
 
 fn validate_file(shading_language: ShadingLanguage, shader_path: &Path) {
     // Validator intended to validate a file using standard API.
-    let mut validator = create_validator(shading_language);
+    let mut validator = create_validator(shading_language, HlslBackend::default());
     let shader_content = std::fs::read_to_string(shader_path).unwrap();
     match validator.validate_shader(
         shader_content,
@@ -0,0 +1,55 @@
+use std::path::{Path, PathBuf};
+
+use shader_sense::{
+    shader::{HlslBackend, ShadingLanguage},
+    validator::{validate_shader_async, validator::ValidationParams},
+};
+
+// Validate files concurrently without blocking the runtime.
+// Run with `cargo run --example validate_async --features tokio -- <files...>`
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let tasks: Vec<_> = args
+        .into_iter()
+        .filter_map(|arg| {
+            let shader_path = PathBuf::from(arg);
+            let extension = shader_path.extension()?.to_string_lossy().to_string();
+            let shading_language = match extension.as_str() {
+                "hlsl" => ShadingLanguage::Hlsl,
+                "glsl" => ShadingLanguage::Glsl,
+                "wgsl" => ShadingLanguage::Wgsl,
+                _ => {
+                    println!("Unsupported file {}", shader_path.display());
+                    return None;
+                }
+            };
+            let shader_content = std::fs::read_to_string(&shader_path).ok()?;
+            Some(tokio::spawn(async move {
+                let result = validate_shader_async(
+                    shading_language,
+                    HlslBackend::default(),
+                    shader_content,
+                    shader_path.clone(),
+                    ValidationParams::default(),
+                    |path: &Path| std::fs::read_to_string(path).ok(),
+                )
+                .await;
+                (shader_path, result)
+            }))
+        })
+        .collect();
+    for task in tasks {
+        match task.await {
+            Ok((shader_path, Ok((diagnostic_list, _)))) => println!(
+                "Validated {} and return following diagnostics: {:#?}",
+                shader_path.display(),
+                diagnostic_list
+            ),
+            Ok((shader_path, Err(err))) => {
+                println!("Failed to validate {}: {:#?}", shader_path.display(), err)
+            }
+            Err(err) => println!("Failed to join validation task: {:#?}", err),
+        }
+    }
+}
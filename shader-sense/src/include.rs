@@ -1,18 +1,55 @@
 use std::{
-    collections::HashSet,
-    path::{Path, PathBuf},
+    collections::{HashMap, HashSet},
+    path::{Component, Path, PathBuf},
+    sync::{LazyLock, Mutex, MutexGuard},
 };
 
+use regex::Regex;
+
+use crate::shader_error::{ShaderDiagnostic, ShaderDiagnosticList, ShaderErrorSeverity};
+
 #[derive(Debug, Default, Clone)]
 pub struct Dependencies {
     dependencies: HashSet<PathBuf>,
+    // Dependency, file including it & include depth. Only filled by find_dependencies.
+    includers: Vec<(PathBuf, PathBuf, u32)>,
 }
 
 pub struct IncludeHandler {
     includes: Vec<String>,
-    directory_stack: Vec<PathBuf>, // Could be replace by deps.
-    dependencies: Dependencies,    // TODO: Remove
+    virtual_includes: HashMap<String, PathBuf>, // Virtual path prefix mapped to a filesystem root.
+    directory_stack: Vec<PathBuf>,              // Could be replace by deps.
+    dependencies: Dependencies,                 // TODO: Remove
+    visited_includes: HashSet<PathBuf>, // Canonical paths already resolved during this pass.
+    include_stack: Vec<PathBuf>,        // Chain of files being included, starting with main file.
+    include_roots: Vec<PathBuf>,        // Directories relative includes were resolved from.
+}
+
+pub enum IncludeVisit {
+    First,
+    // Header already included elsewhere, as with #pragma once or include guards.
+    AlreadyVisited,
+    // Header is including itself through the given chain.
+    Circular(Vec<PathBuf>),
 }
+// Key is the relative path, the directory of the includer & the include paths, in order.
+type IncludeCacheKey = (PathBuf, Option<PathBuf>, Vec<String>);
+
+// Cleared when full, so that a long session does not grow it forever.
+const MAX_INCLUDE_CACHE_SIZE: usize = 4096;
+
+// Resolved includes shared by all handlers, as the same headers are searched by many files.
+// Process wide so that clearing it also apply to handlers of validation threads.
+static INCLUDE_CACHE: LazyLock<Mutex<HashMap<IncludeCacheKey, PathBuf>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn get_include_cache() -> MutexGuard<'static, HashMap<IncludeCacheKey, PathBuf>> {
+    // Cache stay valid even if a thread panicked while holding it.
+    INCLUDE_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
 // std::fs::canonicalize not supported on wasi target... Emulate it.
 // On Windows, std::fs::canonicalize return a /? prefix that break hashmap.
 // https://stackoverflow.com/questions/50322817/how-do-i-remove-the-prefix-from-a-canonical-windows-path
@@ -48,6 +85,7 @@ impl Dependencies {
     pub fn new() -> Self {
         Self {
             dependencies: HashSet::new(),
+            includers: Vec::new(),
         }
     }
     pub fn add_dependency(&mut self, relative_path: PathBuf) {
@@ -55,6 +93,19 @@ impl Dependencies {
             canonicalize(&relative_path).expect("Failed to convert dependency path to absolute"),
         );
     }
+    pub fn add_includer(&mut self, path: &Path, includer: &Path, depth: u32) {
+        let path = canonicalize(path).expect("Failed to convert dependency path to absolute");
+        if !self
+            .includers
+            .iter()
+            .any(|(dependency, file, _)| *dependency == path && file == includer)
+        {
+            self.includers.push((path, PathBuf::from(includer), depth));
+        }
+    }
+    pub fn get_includers(&self) -> &Vec<(PathBuf, PathBuf, u32)> {
+        &self.includers
+    }
     pub fn visit_dependencies<F: FnMut(&Path)>(&self, callback: &mut F) {
         for dependency in &self.dependencies {
             callback(&dependency);
@@ -63,17 +114,123 @@ impl Dependencies {
 }
 
 impl IncludeHandler {
-    pub fn new(file: &Path, includes: Vec<String>) -> Self {
+    pub fn new(
+        file: &Path,
+        includes: Vec<String>,
+        virtual_includes: HashMap<String, PathBuf>,
+    ) -> Self {
         // Add local path to include path
         let mut includes_mut = includes;
         let cwd = file.parent().unwrap();
         let str = String::from(cwd.to_string_lossy());
         // TODO: push cwd in first. Or move it elsewhere
         includes_mut.push(str);
+        let file_path = canonicalize(file).expect("Failed to convert file path to absolute");
         Self {
             includes: includes_mut,
+            virtual_includes,
             directory_stack: Vec::new(),
             dependencies: Dependencies::new(),
+            visited_includes: HashSet::from([file_path.clone()]),
+            include_stack: vec![file_path],
+            include_roots: Vec::new(),
+        }
+    }
+    // Must be followed by a call to exit_include if returning First.
+    pub fn enter_include(&mut self, absolute_path: &Path) -> IncludeVisit {
+        if self.include_stack.iter().any(|path| path == absolute_path) {
+            let mut chain = self.include_stack.clone();
+            chain.push(PathBuf::from(absolute_path));
+            IncludeVisit::Circular(chain)
+        } else if self.visited_includes.contains(absolute_path) {
+            IncludeVisit::AlreadyVisited
+        } else {
+            self.visited_includes.insert(PathBuf::from(absolute_path));
+            self.include_stack.push(PathBuf::from(absolute_path));
+            IncludeVisit::First
+        }
+    }
+    pub fn exit_include(&mut self) {
+        self.include_stack.pop();
+    }
+    // Recursively find all dependencies of a file, each of them being read once.
+    pub fn find_dependencies(
+        &mut self,
+        shader_content: &str,
+        include_callback: &mut dyn FnMut(&Path) -> Option<String>,
+    ) -> (Vec<(String, PathBuf)>, ShaderDiagnosticList) {
+        let mut dependencies = Vec::new();
+        let mut diagnostics = ShaderDiagnosticList::empty();
+        self.find_dependencies_recursive(
+            shader_content,
+            include_callback,
+            &mut dependencies,
+            &mut diagnostics,
+        );
+        (dependencies, diagnostics)
+    }
+    fn find_dependencies_recursive(
+        &mut self,
+        shader_content: &str,
+        include_callback: &mut dyn FnMut(&Path) -> Option<String>,
+        dependencies: &mut Vec<(String, PathBuf)>,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        let include_regex = Regex::new("\\#include\\s+\"([\\w\\s\\\\/\\.\\-]+)\"").unwrap();
+        for capture in include_regex.captures_iter(shader_content) {
+            let relative_path = capture.get(1).unwrap().as_str();
+            let absolute_path = match self.search_path_in_includes(Path::new(relative_path)) {
+                Some(absolute_path) => absolute_path,
+                None => continue,
+            };
+            if let Some(includer) = self.include_stack.last() {
+                self.dependencies.add_includer(
+                    &absolute_path,
+                    includer,
+                    self.include_stack.len() as u32,
+                );
+            }
+            match self.enter_include(&absolute_path) {
+                IncludeVisit::First => {
+                    if let Some(content) = include_callback(&absolute_path) {
+                        self.find_dependencies_recursive(
+                            &content,
+                            include_callback,
+                            dependencies,
+                            diagnostics,
+                        );
+                        dependencies.push((content, absolute_path));
+                    }
+                    self.exit_include();
+                }
+                IncludeVisit::AlreadyVisited => {}
+                IncludeVisit::Circular(chain) => {
+                    let include_start = capture.get(0).unwrap().start();
+                    let line = shader_content[..include_start].matches('\n').count() as u32;
+                    diagnostics.push(ShaderDiagnostic {
+                        // Main file is reported without path.
+                        file_path: match self.include_stack.len() {
+                            1 => None,
+                            _ => self.include_stack.last().cloned(),
+                        },
+                        severity: ShaderErrorSeverity::Error,
+                        error: format!(
+                            "Circular include: {}",
+                            chain
+                                .iter()
+                                .map(|path| path.file_name().unwrap().to_string_lossy())
+                                .collect::<Vec<_>>()
+                                .join(" -> ")
+                        ),
+                        line: line + 1,
+                        pos: 0,
+                        end: None,
+                        unnecessary: false,
+                        code: None,
+                        fix: None,
+                    });
+                }
+            }
         }
     }
     pub fn search_in_includes(
@@ -90,38 +247,364 @@ impl IncludeHandler {
         self.search_path_in_includes_relative(relative_path)
             .map(|e| canonicalize(&e).expect("Failed to convert relative path to absolute"))
     }
+    // Clear resolved includes. Should be called when includes or filesystem changes.
+    pub fn clear_cache() {
+        get_include_cache().clear();
+    }
     pub fn search_path_in_includes_relative(&mut self, relative_path: &Path) -> Option<PathBuf> {
-        if relative_path.exists() {
+        if let Some(path) = self.search_path_in_virtual_includes(relative_path) {
+            self.on_include_found(&path);
+            Some(path)
+        } else if relative_path.exists() {
             Some(PathBuf::from(relative_path))
         } else {
-            // Check directory stack.
-            for directory_stack in &self.directory_stack {
-                let path = Path::new(directory_stack).join(&relative_path);
-                if path.exists() {
-                    if let Some(parent) = path.parent() {
-                        // TODO: should filter paths
-                        self.directory_stack.push(PathBuf::from(parent));
+            let key = (
+                PathBuf::from(relative_path),
+                self.get_includer_directory(),
+                self.includes.clone(),
+            );
+            let cached_path = get_include_cache().get(&key).cloned();
+            let path = match cached_path {
+                // File might have been removed since.
+                Some(path) if path.exists() => path,
+                _ => match self.search_path_in_includes_uncached(relative_path, &key.1) {
+                    Some(path) => {
+                        let mut include_cache = get_include_cache();
+                        if include_cache.len() >= MAX_INCLUDE_CACHE_SIZE {
+                            include_cache.clear();
+                        }
+                        include_cache.insert(key, path.clone());
+                        path
                     }
-                    self.dependencies.add_dependency(path.clone());
-                    return Some(path);
-                }
+                    // Depends on previously found headers, so it is not cached.
+                    None => self.search_path_in_directory_stack(relative_path)?,
+                },
+            };
+            self.on_include_found(&path);
+            self.add_include_root(relative_path, &path);
+            Some(path)
+        }
+    }
+    // Directory of the file being processed, if known.
+    fn get_includer_directory(&self) -> Option<PathBuf> {
+        // Main file directory is already part of the include paths.
+        match self.include_stack.len() {
+            0 | 1 => None,
+            _ => self
+                .include_stack
+                .last()
+                .and_then(|includer| includer.parent())
+                .map(PathBuf::from),
+        }
+    }
+    // Root is the directory the relative path was joined to.
+    fn add_include_root(&mut self, relative_path: &Path, path: &Path) {
+        if !relative_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return;
+        }
+        if let Some(root) = path.ancestors().nth(relative_path.components().count()) {
+            let root = canonicalize(root).expect("Failed to convert include root to absolute");
+            if !self.include_roots.contains(&root) {
+                self.include_roots.push(root);
             }
-            // Check include paths
-            for include_path in &self.includes {
-                let path = Path::new(include_path).join(&relative_path);
-                if path.exists() {
-                    if let Some(parent) = path.parent() {
-                        // TODO: should filter paths
-                        self.directory_stack.push(PathBuf::from(parent));
-                    }
-                    self.dependencies.add_dependency(path.clone());
-                    return Some(path);
+        }
+    }
+    pub fn get_include_roots(&self) -> &Vec<PathBuf> {
+        &self.include_roots
+    }
+    fn on_include_found(&mut self, path: &Path) {
+        if let Some(parent) = path.parent() {
+            // TODO: should filter paths
+            self.directory_stack.push(PathBuf::from(parent));
+        }
+        self.dependencies.add_dependency(PathBuf::from(path));
+    }
+    // Resolve paths such as /Engine/Private/Common.ush with the longest matching virtual prefix.
+    fn search_path_in_virtual_includes(&self, relative_path: &Path) -> Option<PathBuf> {
+        let virtual_path = relative_path.to_string_lossy().replace('\\', "/");
+        self.virtual_includes
+            .iter()
+            .filter_map(|(prefix, root)| {
+                let prefix = prefix.replace('\\', "/");
+                let prefix = prefix.trim_end_matches('/');
+                let remaining = virtual_path.strip_prefix(prefix)?;
+                // Prefix must match whole path components.
+                if !remaining.is_empty() && !remaining.starts_with('/') {
+                    return None;
                 }
+                Some((prefix.len(), root.join(remaining.trim_start_matches('/'))))
+            })
+            .max_by_key(|(prefix_length, _)| *prefix_length)
+            .map(|(_, path)| path)
+            .filter(|path| path.exists())
+    }
+    fn search_path_in_includes_uncached(
+        &self,
+        relative_path: &Path,
+        includer_directory: &Option<PathBuf>,
+    ) -> Option<PathBuf> {
+        // Check includer directory
+        if let Some(includer_directory) = includer_directory {
+            let path = includer_directory.join(relative_path);
+            if path.exists() {
+                return Some(path);
             }
-            return None;
         }
+        // Check include paths
+        for include_path in &self.includes {
+            let path = Path::new(include_path).join(&relative_path);
+            if path.exists() {
+                return Some(path);
+            }
+        }
+        None
+    }
+    // Validators not reporting the includer fall back on directories of headers found so far.
+    fn search_path_in_directory_stack(&self, relative_path: &Path) -> Option<PathBuf> {
+        self.directory_stack
+            .iter()
+            .map(|directory| directory.join(relative_path))
+            .find(|path| path.exists())
     }
     pub fn get_dependencies(&self) -> &Dependencies {
         return &self.dependencies;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tests clearing or inspecting the process wide cache must not run concurrently.
+    static CACHE_TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    fn lock_cache_test() -> MutexGuard<'static, ()> {
+        CACHE_TEST_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[test]
+    fn include_cache_hit() {
+        let _lock = lock_cache_test();
+        IncludeHandler::clear_cache();
+        let file_path = Path::new("./test/hlsl/include-config.hlsl");
+        let includes = vec!["./test/hlsl/inc0/".to_string()];
+        let key = (
+            PathBuf::from("level0.hlsl"),
+            None,
+            IncludeHandler::new(file_path, includes.clone(), HashMap::new()).includes,
+        );
+        let mut include_handler = IncludeHandler::new(file_path, includes.clone(), HashMap::new());
+        let path = include_handler.search_path_in_includes(Path::new("level0.hlsl"));
+        assert!(path.is_some());
+        assert!(get_include_cache().contains_key(&key));
+        // Redirect cached entry to check it is used by another handler with same config.
+        let cached_path = PathBuf::from("./test/hlsl/inc0/inc1/level1.hlsl");
+        get_include_cache().insert(key, cached_path.clone());
+        let mut include_handler = IncludeHandler::new(file_path, includes, HashMap::new());
+        assert_eq!(
+            include_handler.search_path_in_includes(Path::new("level0.hlsl")),
+            Some(canonicalize(&cached_path).unwrap())
+        );
+        IncludeHandler::clear_cache();
+    }
+    #[test]
+    fn include_cache_cleared_from_other_thread() {
+        let _lock = lock_cache_test();
+        let file_path = Path::new("./test/hlsl/include-config.hlsl");
+        // Search path only used by this test, so that other tests do not share its key.
+        let includes = vec!["./test/hlsl/inc0/inc1/../".to_string()];
+        let key = (
+            PathBuf::from("level0.hlsl"),
+            None,
+            IncludeHandler::new(file_path, includes.clone(), HashMap::new()).includes,
+        );
+        // Resolve on a worker thread, as validation does.
+        let resolve = {
+            let includes = includes.clone();
+            move || {
+                let mut include_handler = IncludeHandler::new(file_path, includes, HashMap::new());
+                include_handler.search_path_in_includes(Path::new("level0.hlsl"))
+            }
+        };
+        assert!(std::thread::spawn(resolve.clone())
+            .join()
+            .unwrap()
+            .is_some());
+        assert!(get_include_cache().contains_key(&key));
+        // Redirect the entry, then clear from this thread: the worker must not see it anymore.
+        get_include_cache().insert(
+            key.clone(),
+            PathBuf::from("./test/hlsl/inc0/inc1/level1.hlsl"),
+        );
+        IncludeHandler::clear_cache();
+        assert_eq!(
+            std::thread::spawn(resolve).join().unwrap(),
+            Some(canonicalize(Path::new("./test/hlsl/inc0/level0.hlsl")).unwrap())
+        );
+        IncludeHandler::clear_cache();
+    }
+    #[test]
+    fn include_cache_key_includer_directory() {
+        let _lock = lock_cache_test();
+        IncludeHandler::clear_cache();
+        let file_path = Path::new("./test/hlsl/include-config.hlsl");
+        let header_path = canonicalize(Path::new("./test/hlsl/inc0/inc1/level1.hlsl")).unwrap();
+        let mut include_handler = IncludeHandler::new(file_path, vec![], HashMap::new());
+        let includes = include_handler.includes.clone();
+        // Resolved relative to the header including it.
+        assert!(matches!(
+            include_handler.enter_include(&header_path),
+            IncludeVisit::First
+        ));
+        assert_eq!(
+            include_handler.search_path_in_includes(Path::new("level1.hlsl")),
+            Some(header_path.clone())
+        );
+        let includer_key = (
+            PathBuf::from("level1.hlsl"),
+            header_path.parent().map(PathBuf::from),
+            includes.clone(),
+        );
+        assert!(get_include_cache().contains_key(&includer_key));
+        // Found headers do not change the key.
+        assert_eq!(
+            include_handler.search_path_in_includes(Path::new("level1.hlsl")),
+            Some(header_path.clone())
+        );
+        assert_eq!(
+            get_include_cache()
+                .keys()
+                .filter(|key| key.0 == includer_key.0 && key.1 == includer_key.1)
+                .count(),
+            1
+        );
+        // Only found through previous headers from main file, so not cached.
+        include_handler.exit_include();
+        assert_eq!(
+            include_handler.search_path_in_includes(Path::new("level1.hlsl")),
+            Some(header_path)
+        );
+        assert!(!get_include_cache().contains_key(&(PathBuf::from("level1.hlsl"), None, includes)));
+        IncludeHandler::clear_cache();
+    }
+    #[test]
+    fn include_cache_size_limit() {
+        let _lock = lock_cache_test();
+        IncludeHandler::clear_cache();
+        get_include_cache().extend(
+            (0..MAX_INCLUDE_CACHE_SIZE)
+                .map(|index| {
+                    (
+                        PathBuf::from(format!("missing{}.hlsl", index)),
+                        None,
+                        Vec::new(),
+                    )
+                })
+                .map(|key| (key, PathBuf::new())),
+        );
+        let file_path = Path::new("./test/hlsl/include-config.hlsl");
+        let mut include_handler =
+            IncludeHandler::new(file_path, vec!["./test/hlsl/inc0/".into()], HashMap::new());
+        assert!(include_handler
+            .search_path_in_includes(Path::new("level0.hlsl"))
+            .is_some());
+        // Other tests might be filling it meanwhile.
+        assert!(get_include_cache().len() < MAX_INCLUDE_CACHE_SIZE / 2);
+        IncludeHandler::clear_cache();
+    }
+    #[test]
+    fn include_virtual_path() {
+        let _lock = lock_cache_test();
+        IncludeHandler::clear_cache();
+        let file_path = Path::new("./test/hlsl/include-config.hlsl");
+        let virtual_includes = HashMap::from([
+            ("/Engine".to_string(), PathBuf::from("./test/hlsl/inc0")),
+            (
+                "/Engine/Private/".to_string(),
+                PathBuf::from("./test/hlsl/inc0/inc1"),
+            ),
+        ]);
+        let mut include_handler = IncludeHandler::new(file_path, vec![], virtual_includes.clone());
+        assert_eq!(
+            include_handler.search_path_in_includes(Path::new("/Engine/level0.hlsl")),
+            Some(canonicalize(Path::new("./test/hlsl/inc0/level0.hlsl")).unwrap())
+        );
+        // Longest prefix wins & backslashes are normalized.
+        let mut include_handler = IncludeHandler::new(file_path, vec![], virtual_includes);
+        assert_eq!(
+            include_handler.search_path_in_includes(Path::new("\\Engine\\Private\\level1.hlsl")),
+            Some(canonicalize(Path::new("./test/hlsl/inc0/inc1/level1.hlsl")).unwrap())
+        );
+        // Prefix only match whole components.
+        assert_eq!(
+            include_handler.search_path_in_includes(Path::new("/EngineExtra/level0.hlsl")),
+            None
+        );
+    }
+    #[test]
+    fn include_roots_learned() {
+        let _lock = lock_cache_test();
+        IncludeHandler::clear_cache();
+        let file_path = Path::new("./test/hlsl/include-config.hlsl");
+        let mut include_handler = IncludeHandler::new(file_path, vec![], HashMap::new());
+        assert!(include_handler
+            .search_path_in_includes(Path::new("inc0/inc1/level1.hlsl"))
+            .is_some());
+        // Parent paths do not give a reliable root.
+        assert!(include_handler
+            .search_path_in_includes(Path::new("../hlsl/inc0/level0.hlsl"))
+            .is_some());
+        assert_eq!(
+            include_handler.get_include_roots(),
+            &vec![canonicalize(Path::new("./test/hlsl")).unwrap()]
+        );
+    }
+    fn find_test_dependencies(
+        file_path: &Path,
+    ) -> (IncludeHandler, Vec<(String, PathBuf)>, ShaderDiagnosticList) {
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut include_handler = IncludeHandler::new(file_path, vec![], HashMap::new());
+        let (dependencies, diagnostics) = include_handler
+            .find_dependencies(&shader_content, &mut |path| {
+                std::fs::read_to_string(path).ok()
+            });
+        (include_handler, dependencies, diagnostics)
+    }
+
+    #[test]
+    fn include_visited_once() {
+        let (include_handler, dependencies, diagnostics) =
+            find_test_dependencies(Path::new("./test/glsl/include-once.frag.glsl"));
+        assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+        let file_names: Vec<String> = dependencies
+            .iter()
+            .map(|(_, path)| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        // Shared header is only emitted once, before its first includer.
+        assert_eq!(file_names, vec!["once.glsl", "once0.glsl", "once1.glsl"]);
+        assert_eq!(include_handler.visited_includes.len(), 4);
+    }
+
+    #[test]
+    fn include_circular() {
+        let (_, dependencies, diagnostics) =
+            find_test_dependencies(Path::new("./test/glsl/include-circular.frag.glsl"));
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(diagnostics.diagnostics.len(), 1);
+        let diagnostic = &diagnostics.diagnostics[0];
+        assert_eq!(
+            diagnostic.file_path,
+            Some(canonicalize(Path::new("./test/glsl/inc0/circular.glsl")).unwrap())
+        );
+        assert_eq!(diagnostic.line, 1);
+        assert_eq!(
+            diagnostic.error,
+            "Circular include: include-circular.frag.glsl -> circular.glsl -> include-circular.frag.glsl"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, str::FromStr};
+use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
@@ -27,35 +27,33 @@ pub enum ShaderStage {
     Intersect,
 }
 
+// Extensions of GLSL files, used alone (.frag) or doubled (.frag.glsl).
+const STAGE_EXTENSIONS: [(&str, ShaderStage); 14] = [
+    ("vert", ShaderStage::Vertex),
+    ("frag", ShaderStage::Fragment),
+    ("comp", ShaderStage::Compute),
+    ("task", ShaderStage::Task),
+    ("mesh", ShaderStage::Mesh),
+    ("tesc", ShaderStage::TesselationControl),
+    ("tese", ShaderStage::TesselationEvaluation),
+    ("geom", ShaderStage::Geometry),
+    ("rgen", ShaderStage::RayGeneration),
+    ("rchit", ShaderStage::ClosestHit),
+    ("rahit", ShaderStage::AnyHit),
+    ("rcall", ShaderStage::Callable),
+    ("rmiss", ShaderStage::Miss),
+    ("rint", ShaderStage::Intersect),
+];
+
 impl ShaderStage {
-    pub fn from_file_name(file_name: &String) -> Option<ShaderStage> {
-        // TODO: add control for these
-        let paths = HashMap::from([
-            ("vert", ShaderStage::Vertex),
-            ("frag", ShaderStage::Fragment),
-            ("comp", ShaderStage::Compute),
-            ("task", ShaderStage::Task),
-            ("mesh", ShaderStage::Mesh),
-            ("tesc", ShaderStage::TesselationControl),
-            ("tese", ShaderStage::TesselationEvaluation),
-            ("geom", ShaderStage::Geometry),
-            ("rgen", ShaderStage::RayGeneration),
-            ("rchit", ShaderStage::ClosestHit),
-            ("rahit", ShaderStage::AnyHit),
-            ("rcall", ShaderStage::Callable),
-            ("rmiss", ShaderStage::Miss),
-            ("rint", ShaderStage::Intersect),
-        ]);
-        let extension_list = file_name.rsplit(".");
-        for extension in extension_list {
-            if let Some(stage) = paths.get(extension) {
-                return Some(stage.clone());
-            } else {
-                continue;
-            }
-        }
-        // For header files & undefined, will output issue with missing version...
-        None
+    pub fn from_file_name(file_name: &str) -> Option<ShaderStage> {
+        // Header files & others have no stage.
+        file_name.rsplit('.').find_map(|extension| {
+            STAGE_EXTENSIONS
+                .iter()
+                .find(|(stage_extension, _)| *stage_extension == extension)
+                .map(|(_, stage)| *stage)
+        })
     }
 }
 
@@ -103,7 +101,7 @@ impl ToString for ShadingLanguage {
 }
 
 // DXC only support shader model up to 6.0
-#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HlslShaderModel {
     ShaderModel6,
     ShaderModel6_1,
@@ -117,6 +115,23 @@ pub enum HlslShaderModel {
     ShaderModel6_8,
 }
 
+impl HlslShaderModel {
+    // Major & minor version of the model.
+    pub fn get_version(&self) -> (u32, u32) {
+        match self {
+            HlslShaderModel::ShaderModel6 => (6, 0),
+            HlslShaderModel::ShaderModel6_1 => (6, 1),
+            HlslShaderModel::ShaderModel6_2 => (6, 2),
+            HlslShaderModel::ShaderModel6_3 => (6, 3),
+            HlslShaderModel::ShaderModel6_4 => (6, 4),
+            HlslShaderModel::ShaderModel6_5 => (6, 5),
+            HlslShaderModel::ShaderModel6_6 => (6, 6),
+            HlslShaderModel::ShaderModel6_7 => (6, 7),
+            HlslShaderModel::ShaderModel6_8 => (6, 8),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub enum HlslVersion {
     V2016,
@@ -126,6 +141,24 @@ pub enum HlslVersion {
     V2021,
 }
 
+// Compiler used to validate HLSL. Glslang frontend lacks most SM6+ features.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HlslBackend {
+    #[default]
+    Dxc,
+    Glslang,
+}
+
+impl HlslBackend {
+    // Name of the backend in the validator registry.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            HlslBackend::Dxc => "dxc",
+            HlslBackend::Glslang => "glslang",
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub enum GlslTargetClient {
     Vulkan1_0,
@@ -145,6 +178,25 @@ impl GlslTargetClient {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GlslProfile {
+    Core,
+    Compatibility,
+    Es,
+}
+
+impl GlslProfile {
+    // Profile as written in #version directive.
+    pub fn from_directive(profile: &str) -> Option<Self> {
+        match profile {
+            "core" => Some(GlslProfile::Core),
+            "compatibility" => Some(GlslProfile::Compatibility),
+            "es" => Some(GlslProfile::Es),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub enum GlslSpirvVersion {
     SPIRV1_0,
@@ -52,6 +52,56 @@ pub struct ShaderDiagnostic {
     pub error: String,
     pub line: u32,
     pub pos: u32,
+    // End line & pos when the diagnostic cover a range instead of a position.
+    pub end: Option<(u32, u32)>,
+    // Code is unused or unreachable. Clients usually fade it out.
+    pub unnecessary: bool,
+    // Kind of diagnostic, such as a lint name or a compiler warning flag.
+    pub code: Option<String>,
+    // Edit of the diagnostic range that fix it.
+    pub fix: Option<ShaderDiagnosticFix>,
+}
+// Words kept in codes derived from messages, enough to tell most errors apart.
+const MAX_MESSAGE_CODE_WORDS: usize = 6;
+
+impl ShaderDiagnostic {
+    // Code of validators that do not emit any, from the message without quoted identifiers,
+    // numbers & paths, such as "undeclared-identifier". Stable across occurrences.
+    pub fn get_message_code(message: &str) -> Option<String> {
+        let message = message.lines().next()?.trim();
+        let message = ["error:", "warning:"]
+            .iter()
+            .find_map(|prefix| message.strip_prefix(prefix))
+            .unwrap_or(message);
+        let mut words = Vec::new();
+        let mut word = String::new();
+        let mut quote = None;
+        for c in message.chars().chain(std::iter::once(' ')) {
+            match quote {
+                Some(quote_char) if c == quote_char => quote = None,
+                Some(_) => {}
+                None if c.is_ascii_alphanumeric() || c == '_' || c == '.' => word.push(c),
+                None => {
+                    // Identifiers, numbers & file names change between occurrences.
+                    if !word.is_empty() && word.chars().all(|c| c.is_ascii_alphabetic()) {
+                        words.push(word.to_lowercase());
+                    }
+                    word.clear();
+                    if matches!(c, '\'' | '"' | '`') {
+                        quote = Some(c);
+                    }
+                }
+            }
+        }
+        words.truncate(MAX_MESSAGE_CODE_WORDS);
+        (!words.is_empty()).then(|| words.join("-"))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ShaderDiagnosticFix {
+    pub title: String,
+    pub new_text: String,
 }
 #[derive(Debug)]
 pub struct ShaderDiagnosticList {
@@ -0,0 +1,121 @@
+use std::path::Path;
+
+use tree_sitter::Node;
+
+use crate::{
+    shader_error::{ShaderDiagnosticList, ShaderErrorSeverity},
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    evaluator::{evaluate_integer_expression, get_expression_constness, ExpressionConstness},
+    linter::{node_diagnostic, SymbolTreeLinter},
+    parser::get_name,
+};
+
+// Runtime sized arrays are only allowed as the first dimension of the last member of a buffer block.
+fn is_runtime_array_allowed(declarator: Node) -> bool {
+    let is_first_dimension = declarator
+        .child_by_field_name("declarator")
+        .is_some_and(|identifier| identifier.kind() != "array_declarator");
+    let mut field = declarator;
+    while field.kind() == "array_declarator" {
+        field = match field.parent() {
+            Some(parent) => parent,
+            None => return true,
+        };
+    }
+    if field.kind() != "field_declaration" {
+        return true; // Sized by their initializer or unbounded resource arrays.
+    }
+    let is_last_member =
+        std::iter::successors(field.next_named_sibling(), |node| node.next_named_sibling())
+            .all(|node| node.kind() != "field_declaration");
+    let is_buffer_block = field
+        .parent()
+        .and_then(|field_list| field_list.parent())
+        .is_some_and(|declaration| {
+            declaration.kind() == "declaration"
+                && declaration
+                    .children(&mut declaration.walk())
+                    .any(|child| child.kind() == "buffer")
+        });
+    is_first_dimension && is_last_member && is_buffer_block
+}
+
+// Array sizes must be known at compile time & positive, wherever the array is declared.
+pub(super) struct ArraySizeLinter {}
+
+impl SymbolTreeLinter for ArraySizeLinter {
+    fn get_code(&self) -> &'static str {
+        "arraySize"
+    }
+    fn get_query(&self) -> String {
+        r#"(array_declarator) @array.declarator"#.into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        let declarator = matches.captures[0].node;
+        let size_node = match declarator.child_by_field_name("size") {
+            Some(size_node) => size_node,
+            None => {
+                if !is_runtime_array_allowed(declarator) {
+                    diagnostics.push(node_diagnostic(
+                        declarator,
+                        file_path,
+                        ShaderErrorSeverity::Error,
+                        format!(
+                            "Array '{}' has no size, only the last member of a buffer block can be a runtime sized array.",
+                            get_name(shader_content, declarator)
+                        ),
+                    ));
+                }
+                return;
+            }
+        };
+        let error = match get_expression_constness(shader_content, size_node, params) {
+            ExpressionConstness::Constant => {
+                match evaluate_integer_expression(shader_content, size_node, params) {
+                    Some(size) if size <= 0 => (
+                        size_node,
+                        format!("Array size must be greater than zero, got {}.", size),
+                    ),
+                    _ => return,
+                }
+            }
+            // Specialization constants are resolved when creating the pipeline with Vulkan.
+            ExpressionConstness::SpecializationConstant(node) => {
+                if !params.glsl_client.is_opengl() {
+                    return;
+                }
+                (
+                    node,
+                    format!(
+                        "Array size '{}' is a specialization constant, which can only size arrays when targeting Vulkan.",
+                        get_name(shader_content, node)
+                    ),
+                )
+            }
+            ExpressionConstness::NonConstant(node) => (
+                node,
+                format!(
+                    "Array size must be a compile time constant, '{}' is not a constant.",
+                    get_name(shader_content, node)
+                ),
+            ),
+        };
+        let (node, error) = error;
+        let mut diagnostic = node_diagnostic(node, file_path, ShaderErrorSeverity::Error, error);
+        diagnostic.end = Some((
+            node.end_position().row as u32 + 1,
+            node.end_position().column as u32,
+        ));
+        diagnostics.push(diagnostic);
+    }
+}
@@ -0,0 +1,449 @@
+use std::path::Path;
+
+use regex::Regex;
+use tree_sitter::Node;
+
+use crate::{
+    shader::ShadingLanguage,
+    shader_error::{ShaderDiagnosticList, ShaderErrorSeverity},
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    evaluator::evaluate_integer_expression,
+    linter::{node_diagnostic, SymbolTreeLinter},
+    parser::get_name,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockLayout {
+    Std140,
+    Std430,
+    Hlsl, // cbuffer packing, where members can't straddle a 16 bytes register.
+}
+
+impl BlockLayout {
+    fn get_name(&self) -> &'static str {
+        match self {
+            BlockLayout::Std140 => "std140",
+            BlockLayout::Std430 => "std430",
+            BlockLayout::Hlsl => "cbuffer",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TypeLayout {
+    size: u32,
+    align: u32,
+}
+
+fn round_up(value: u32, alignment: u32) -> u32 {
+    value.div_ceil(alignment.max(1)) * alignment.max(1)
+}
+
+// Name of a declarator without its array dimensions & array sizes from the outermost node.
+fn get_declarator_arrays<'a>(
+    shader_content: &str,
+    declarator: Node<'a>,
+    params: &ValidationParams,
+) -> (Node<'a>, Vec<Option<u32>>) {
+    let mut identifier = declarator;
+    let mut sizes = Vec::new();
+    while identifier.kind() == "array_declarator" {
+        sizes.push(
+            identifier
+                .child_by_field_name("size")
+                .and_then(|size| evaluate_integer_expression(shader_content, size, params))
+                .and_then(|size| u32::try_from(size).ok()),
+        );
+        identifier = match identifier.child_by_field_name("declarator") {
+            Some(identifier) => identifier,
+            None => break,
+        };
+    }
+    (identifier, sizes)
+}
+
+// Get value of a layout qualifier such as offset = 16, or an empty string for qualifiers without value.
+fn get_layout_qualifier<'a>(
+    shader_content: &'a str,
+    node: Node,
+    qualifier_name: &str,
+) -> Option<&'a str> {
+    let layout = node
+        .children(&mut node.walk())
+        .find(|child| child.kind() == "layout_specification")?;
+    let qualifiers = layout
+        .children(&mut layout.walk())
+        .find(|child| child.kind() == "layout_qualifiers")?;
+    let qualifier = qualifiers
+        .named_children(&mut qualifiers.walk())
+        .find(|qualifier| {
+            qualifier
+                .named_child(0)
+                .is_some_and(|name| get_name(shader_content, name) == qualifier_name)
+        })?;
+    Some(
+        qualifier
+            .named_child(1)
+            .map_or("", |value| get_name(shader_content, value)),
+    )
+}
+
+pub(super) struct BlockLayoutLinter {
+    shading_language: ShadingLanguage,
+    type_regex: Regex,
+}
+
+impl BlockLayoutLinter {
+    pub fn new(shading_language: ShadingLanguage) -> Self {
+        // Capture scalar type, then optional vector / matrix dimensions.
+        let type_regex = match shading_language {
+            ShadingLanguage::Hlsl => Regex::new(
+                r"^(bool|int|uint|dword|half|float|double|min16float|min10float|min16int|min12int|min16uint|int16_t|uint16_t|float16_t|int32_t|uint32_t|float32_t|int64_t|uint64_t|float64_t)(?:([1-4])(?:x([1-4]))?)?$",
+            ),
+            ShadingLanguage::Glsl | ShadingLanguage::Wgsl => Regex::new(
+                r"^(?:(bool|int|uint|float|double)|(b|i|u|d)?vec([2-4])|(d)?mat([2-4])(?:x([2-4]))?)$",
+            ),
+        }
+        .unwrap();
+        Self {
+            shading_language,
+            type_regex,
+        }
+    }
+    // Scalar size, columns & rows of a builtin type. Vectors are a single column.
+    fn get_builtin_type(&self, ty: &str) -> Option<(u32, u32, u32)> {
+        let capture = self.type_regex.captures(ty.trim())?;
+        let dimension = |index: usize| -> Option<u32> {
+            capture
+                .get(index)
+                .and_then(|dimension| dimension.as_str().parse::<u32>().ok())
+        };
+        let scalar_size = |scalar: &str| -> u32 {
+            match scalar {
+                "double" | "int64_t" | "uint64_t" | "float64_t" | "d" => 8,
+                "int16_t" | "uint16_t" | "float16_t" => 2,
+                _ => 4, // Min precision types are stored as 32 bits.
+            }
+        };
+        match self.shading_language {
+            // floatRxC has R rows & C columns.
+            ShadingLanguage::Hlsl => Some((
+                scalar_size(capture.get(1)?.as_str()),
+                dimension(3).unwrap_or(1),
+                dimension(2).unwrap_or(1),
+            )),
+            ShadingLanguage::Glsl | ShadingLanguage::Wgsl => {
+                if let Some(scalar) = capture.get(1) {
+                    Some((scalar_size(scalar.as_str()), 1, 1))
+                } else if let Some(rows) = dimension(3) {
+                    let scalar = capture.get(2).map_or("float", |scalar| scalar.as_str());
+                    Some((scalar_size(scalar), 1, rows))
+                } else {
+                    // matN is NxN, matCxR has C columns & R rows.
+                    let scalar = capture.get(4).map_or("float", |scalar| scalar.as_str());
+                    let columns = dimension(5)?;
+                    Some((
+                        scalar_size(scalar),
+                        columns,
+                        dimension(6).unwrap_or(columns),
+                    ))
+                }
+            }
+        }
+    }
+    fn get_vector_layout(layout: BlockLayout, scalar_size: u32, rows: u32) -> TypeLayout {
+        let size = scalar_size * rows;
+        let align = match layout {
+            BlockLayout::Hlsl => scalar_size,
+            BlockLayout::Std140 | BlockLayout::Std430 => match rows {
+                1 => scalar_size,
+                2 => 2 * scalar_size,
+                _ => 4 * scalar_size,
+            },
+        };
+        TypeLayout { size, align }
+    }
+    // Layout of an array & its element stride.
+    fn get_array_layout(layout: BlockLayout, element: TypeLayout, count: u32) -> (TypeLayout, u32) {
+        match layout {
+            BlockLayout::Std140 => {
+                let align = round_up(element.align, 16);
+                let stride = round_up(element.size, align);
+                (
+                    TypeLayout {
+                        size: stride * count,
+                        align,
+                    },
+                    stride,
+                )
+            }
+            BlockLayout::Std430 => {
+                let stride = round_up(element.size, element.align);
+                (
+                    TypeLayout {
+                        size: stride * count,
+                        align: element.align,
+                    },
+                    stride,
+                )
+            }
+            // Each element starts a register, last one is not padded.
+            BlockLayout::Hlsl => {
+                let stride = round_up(element.size, 16);
+                (
+                    TypeLayout {
+                        size: stride * count.saturating_sub(1) + element.size * count.min(1),
+                        align: 16,
+                    },
+                    stride,
+                )
+            }
+        }
+    }
+    // Offset of a member placed after the given offset.
+    fn place(layout: BlockLayout, offset: u32, member: TypeLayout) -> u32 {
+        let offset = round_up(offset, member.align);
+        if layout == BlockLayout::Hlsl && offset % 16 + member.size > 16 {
+            round_up(offset, 16)
+        } else {
+            offset
+        }
+    }
+    // Layout of the type of a field. Structs are searched before the field, so they can't recurse.
+    fn get_type_layout(
+        &self,
+        shader_content: &str,
+        field: Node,
+        layout: BlockLayout,
+        row_major: bool,
+        params: &ValidationParams,
+    ) -> Option<TypeLayout> {
+        let ty = get_name(shader_content, field.child_by_field_name("type")?);
+        if let Some((scalar_size, columns, rows)) = self.get_builtin_type(ty) {
+            let (columns, rows) = if row_major {
+                (rows, columns)
+            } else {
+                (columns, rows)
+            };
+            let column = Self::get_vector_layout(layout, scalar_size, rows);
+            if columns == 1 {
+                return Some(column);
+            }
+            // Matrices are arrays of columns.
+            return Some(Self::get_array_layout(layout, column, columns).0);
+        }
+        let root = {
+            let mut root = field;
+            while let Some(parent) = root.parent() {
+                root = parent;
+            }
+            root
+        };
+        let members = self.find_struct_members(shader_content, root, ty, field.start_byte())?;
+        let mut offset = 0;
+        let mut align = 1;
+        for field in members.named_children(&mut members.walk()) {
+            if field.kind() != "field_declaration" {
+                continue;
+            }
+            let member_layout =
+                self.get_type_layout(shader_content, field, layout, row_major, params)?;
+            for declarator in field.children_by_field_name("declarator", &mut field.walk()) {
+                let (_, sizes) = get_declarator_arrays(shader_content, declarator, params);
+                let mut declarator_layout = member_layout;
+                for size in sizes {
+                    declarator_layout = Self::get_array_layout(layout, declarator_layout, size?).0;
+                }
+                offset = Self::place(layout, offset, declarator_layout) + declarator_layout.size;
+                align = align.max(declarator_layout.align);
+            }
+        }
+        let align = match layout {
+            BlockLayout::Std140 | BlockLayout::Hlsl => round_up(align, 16),
+            BlockLayout::Std430 => align,
+        };
+        Some(TypeLayout {
+            size: round_up(offset, align),
+            align,
+        })
+    }
+    fn find_struct_members<'a>(
+        &self,
+        shader_content: &str,
+        node: Node<'a>,
+        ty: &str,
+        before_byte: usize,
+    ) -> Option<Node<'a>> {
+        if node.start_byte() >= before_byte {
+            return None;
+        }
+        if node.kind() == "struct_specifier"
+            && node.end_byte() <= before_byte
+            && node
+                .child_by_field_name("name")
+                .is_some_and(|name| get_name(shader_content, name) == ty)
+        {
+            if let Some(body) = node.child_by_field_name("body") {
+                return Some(body);
+            }
+        }
+        node.named_children(&mut node.walk())
+            .find_map(|child| self.find_struct_members(shader_content, child, ty, before_byte))
+    }
+    // Layout of a block declaration. None for layouts that are implementation defined.
+    fn get_block_layout(&self, shader_content: &str, block: Node) -> Option<BlockLayout> {
+        match self.shading_language {
+            ShadingLanguage::Hlsl => Some(BlockLayout::Hlsl),
+            ShadingLanguage::Glsl | ShadingLanguage::Wgsl => {
+                let has_qualifier = |qualifier: &str| {
+                    get_layout_qualifier(shader_content, block, qualifier).is_some()
+                };
+                // Interface blocks between stages have no memory layout.
+                let is_memory_block = block
+                    .children(&mut block.walk())
+                    .any(|child| ["uniform", "buffer"].contains(&child.kind()));
+                if !is_memory_block {
+                    None
+                } else if has_qualifier("std430") {
+                    Some(BlockLayout::Std430)
+                } else if has_qualifier("std140") {
+                    Some(BlockLayout::Std140)
+                } else if has_qualifier("shared")
+                    || has_qualifier("packed")
+                    || has_qualifier("scalar")
+                {
+                    None
+                } else if has_qualifier("push_constant") {
+                    Some(BlockLayout::Std430)
+                } else {
+                    Some(BlockLayout::Std140)
+                }
+            }
+        }
+    }
+}
+
+impl SymbolTreeLinter for BlockLayoutLinter {
+    fn get_code(&self) -> &'static str {
+        "blockLayout"
+    }
+    fn get_query(&self) -> String {
+        match self.shading_language {
+            ShadingLanguage::Hlsl => {
+                r#"(cbuffer_specifier body: (field_declaration_list) @block.members)"#.into()
+            }
+            ShadingLanguage::Glsl | ShadingLanguage::Wgsl => {
+                r#"(declaration (field_declaration_list) @block.members)"#.into()
+            }
+        }
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        if !params.lint_block_layout {
+            return;
+        }
+        let members = matches.captures[0].node;
+        let block = match members.parent() {
+            Some(block) => block,
+            None => return,
+        };
+        let layout = match self.get_block_layout(shader_content, block) {
+            Some(layout) => layout,
+            None => return,
+        };
+        let block_row_major = get_layout_qualifier(shader_content, block, "row_major").is_some();
+        let mut offset = 0;
+        for field in members.named_children(&mut members.walk()) {
+            if field.kind() != "field_declaration" {
+                continue;
+            }
+            let ty = match field.child_by_field_name("type") {
+                Some(ty) => get_name(shader_content, ty),
+                None => return,
+            };
+            let row_major = block_row_major
+                || get_layout_qualifier(shader_content, field, "row_major").is_some();
+            let member_layout =
+                match self.get_type_layout(shader_content, field, layout, row_major, params) {
+                    Some(member_layout) => member_layout,
+                    None => return, // Following offsets are unknown.
+                };
+            let explicit_offset = get_layout_qualifier(shader_content, field, "offset")
+                .and_then(|offset| offset.parse::<u32>().ok());
+            for declarator in field.children_by_field_name("declarator", &mut field.walk()) {
+                let (identifier, sizes) = get_declarator_arrays(shader_content, declarator, params);
+                let name = get_name(shader_content, identifier);
+                let mut declarator_layout = member_layout;
+                let mut stride = None;
+                for size in &sizes {
+                    // Runtime sized arrays have no size & end the block.
+                    let (array_layout, array_stride) =
+                        Self::get_array_layout(layout, declarator_layout, size.unwrap_or(0));
+                    stride = stride.or(Some(array_stride));
+                    declarator_layout = array_layout;
+                }
+                let computed_offset = Self::place(layout, offset, declarator_layout);
+                let mut warn = |error: String| {
+                    let mut diagnostic =
+                        node_diagnostic(identifier, file_path, ShaderErrorSeverity::Warning, error);
+                    diagnostic.end = Some((
+                        identifier.end_position().row as u32 + 1,
+                        identifier.end_position().column as u32,
+                    ));
+                    diagnostics.push(diagnostic);
+                };
+                let member_offset = match explicit_offset {
+                    // Explicit offsets can add padding, but not overlap or break alignment.
+                    Some(explicit_offset)
+                        if explicit_offset < computed_offset
+                            || explicit_offset % declarator_layout.align != 0 =>
+                    {
+                        warn(format!(
+                            "Explicit offset {} of '{}' conflicts with {} layout, which places it at offset {} with an alignment of {} bytes.",
+                            explicit_offset,
+                            name,
+                            layout.get_name(),
+                            computed_offset,
+                            declarator_layout.align
+                        ));
+                        computed_offset
+                    }
+                    Some(explicit_offset) => explicit_offset,
+                    None => {
+                        if computed_offset > offset && offset > 0 {
+                            warn(format!(
+                                "{} bytes of padding are inserted before '{}' to align it in {} layout.",
+                                computed_offset - offset,
+                                name,
+                                layout.get_name()
+                            ));
+                        }
+                        computed_offset
+                    }
+                };
+                if let Some(stride) = stride {
+                    if stride != member_layout.size && sizes.len() == 1 {
+                        warn(format!(
+                            "Array '{}' has a stride of {} bytes in {} layout, while {} is {} bytes.",
+                            name,
+                            stride,
+                            layout.get_name(),
+                            ty,
+                            member_layout.size
+                        ));
+                    }
+                }
+                offset = member_offset + declarator_layout.size;
+            }
+        }
+    }
+}
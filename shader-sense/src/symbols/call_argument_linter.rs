@@ -0,0 +1,133 @@
+use std::collections::BTreeSet;
+
+use tree_sitter::Node;
+
+use crate::shader_error::{ShaderDiagnosticList, ShaderErrorSeverity};
+
+use super::{
+    linter::node_diagnostic,
+    parser::{get_name, SymbolTree},
+    symbols::{ShaderSignature, ShaderSymbolData, ShaderSymbolList},
+};
+
+// Describe the accepted argument counts, such as "2 or 4" or "at least 1".
+fn format_argument_counts(signatures: &[&ShaderSignature]) -> String {
+    let mut counts = BTreeSet::new();
+    let mut variadic_count = None;
+    for signature in signatures {
+        let required_count = signature
+            .parameters
+            .iter()
+            .take_while(|parameter| !parameter.is_optional())
+            .count();
+        if signature
+            .parameters
+            .last()
+            .is_some_and(|parameter| parameter.is_variadic())
+        {
+            variadic_count = Some(
+                variadic_count.map_or(required_count, |count: usize| count.min(required_count)),
+            );
+        } else {
+            counts.extend(required_count..=signature.parameters.len());
+        }
+    }
+    // Counts covered by a variadic signature are merged into it.
+    if let Some(variadic_count) = variadic_count {
+        counts.retain(|count| *count < variadic_count);
+    }
+    let mut counts: Vec<String> = counts.into_iter().map(|count| count.to_string()).collect();
+    if let Some(variadic_count) = variadic_count {
+        counts.push(format!("at least {}", variadic_count));
+    }
+    match counts.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, others)) => format!("{} or {}", others.join(", "), last),
+        None => "0".into(),
+    }
+}
+
+// Calls whose argument count match none of the overloads of the called function.
+// Only functions found in the symbols are checked, so macros & unknown functions are skipped.
+pub(super) fn query_call_argument_diagnostics(
+    symbol_tree: &SymbolTree,
+    symbol_list: &ShaderSymbolList,
+    diagnostics: &mut ShaderDiagnosticList,
+) {
+    fn visit(
+        symbol_tree: &SymbolTree,
+        symbol_list: &ShaderSymbolList,
+        node: Node,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        for child in node.named_children(&mut node.walk()) {
+            visit(symbol_tree, symbol_list, child, diagnostics);
+        }
+        if node.kind() != "call_expression" {
+            return;
+        }
+        let (function, arguments) = match (
+            node.child_by_field_name("function"),
+            node.child_by_field_name("arguments"),
+        ) {
+            (Some(function), Some(arguments)) if function.kind() == "identifier" => {
+                (function, arguments)
+            }
+            _ => return, // Methods & constructors.
+        };
+        let label = get_name(&symbol_tree.content, function);
+        // Defines can shadow functions.
+        if symbol_list
+            .constants
+            .iter()
+            .any(|symbol| symbol.label == label)
+        {
+            return;
+        }
+        let signatures: Vec<&ShaderSignature> = symbol_list
+            .functions
+            .iter()
+            .filter(|symbol| symbol.label == label)
+            .filter_map(|symbol| match &symbol.data {
+                ShaderSymbolData::Functions { signatures, .. } => Some(signatures),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        if signatures.is_empty() {
+            return;
+        }
+        let argument_count = arguments
+            .named_children(&mut arguments.walk())
+            .filter(|argument| argument.kind() != "comment")
+            .count();
+        if signatures
+            .iter()
+            .any(|signature| signature.accept_argument_count(argument_count))
+        {
+            return;
+        }
+        let mut diagnostic = node_diagnostic(
+            function,
+            &symbol_tree.file_path,
+            ShaderErrorSeverity::Warning,
+            format!(
+                "'{}' expects {} argument(s) but {} were given.",
+                label,
+                format_argument_counts(&signatures),
+                argument_count
+            ),
+        );
+        diagnostic.end = Some((
+            node.end_position().row as u32 + 1,
+            node.end_position().column as u32,
+        ));
+        diagnostics.push(diagnostic);
+    }
+    visit(
+        symbol_tree,
+        symbol_list,
+        symbol_tree.tree.root_node(),
+        diagnostics,
+    );
+}
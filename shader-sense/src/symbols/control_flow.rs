@@ -0,0 +1,245 @@
+use tree_sitter::Node;
+
+use super::parser::get_name;
+
+// Whether the variable is assigned on all paths leaving a statement, by falling through or by
+// a break or continue. None when no path leaves this way.
+#[derive(Debug, Clone, Copy)]
+struct Flow {
+    normal: Option<bool>,
+    breaks: Option<bool>,
+}
+
+impl Flow {
+    fn normal(assigned: bool) -> Self {
+        Self {
+            normal: Some(assigned),
+            breaks: None,
+        }
+    }
+    fn exit() -> Self {
+        Self {
+            normal: None,
+            breaks: None,
+        }
+    }
+    fn merge(self, other: Flow) -> Self {
+        Self {
+            normal: merge_paths(self.normal, other.normal),
+            breaks: merge_paths(self.breaks, other.breaks),
+        }
+    }
+}
+
+// A value is assigned after paths joining only if it is assigned on each of them.
+fn merge_paths(lhs: Option<bool>, rhs: Option<bool>) -> Option<bool> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Some(lhs && rhs),
+        (lhs, None) => lhs,
+        (None, rhs) => rhs,
+    }
+}
+
+// Variable written by an expression, ignoring fields & indices that only write part of it.
+fn get_written_variable<'a>(shader_content: &'a str, node: Node) -> Option<&'a str> {
+    match node.kind() {
+        "identifier" => Some(get_name(shader_content, node)),
+        "field_expression" | "subscript_expression" => {
+            get_written_variable(shader_content, node.child_by_field_name("argument")?)
+        }
+        "parenthesized_expression" => get_written_variable(shader_content, node.named_child(0)?),
+        _ => None,
+    }
+}
+
+// Partial writes are considered as assigning the whole variable, as for arguments that might be
+// passed to an out parameter. This avoids false positives at the cost of missing some cases.
+fn is_assigning(shader_content: &str, node: Node, name: &str) -> bool {
+    let is_assigned = match node.kind() {
+        "assignment_expression" => {
+            node.child_by_field_name("left")
+                .and_then(|left| get_written_variable(shader_content, left))
+                == Some(name)
+        }
+        "argument_list" => node
+            .named_children(&mut node.walk())
+            .any(|argument| get_written_variable(shader_content, argument) == Some(name)),
+        _ => false,
+    };
+    is_assigned
+        || node
+            .named_children(&mut node.walk())
+            .any(|child| is_assigning(shader_content, child, name))
+}
+
+// Loops without condition or with a true one are only left with a break.
+fn is_infinite_loop(shader_content: &str, condition: Option<Node>) -> bool {
+    match condition {
+        Some(condition) => {
+            let condition = get_name(shader_content, condition)
+                .trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace());
+            condition == "true" || condition == "1"
+        }
+        None => true,
+    }
+}
+
+fn is_discard(shader_content: &str, statement: Node) -> bool {
+    // GLSL grammar parse discard as an identifier while HLSL has a discard_statement.
+    match statement.kind() {
+        "discard_statement" => true,
+        "expression_statement" => statement
+            .named_child(0)
+            .is_some_and(|child| get_name(shader_content, child) == "discard"),
+        _ => false,
+    }
+}
+
+struct AssignmentAnalysis<'a, 'b> {
+    shader_content: &'b str,
+    name: &'b str,
+    unassigned_exits: Vec<Node<'a>>,
+}
+
+impl<'a, 'b> AssignmentAnalysis<'a, 'b> {
+    fn is_assigning(&self, node: Option<Node>) -> bool {
+        node.is_some_and(|node| is_assigning(self.shader_content, node, self.name))
+    }
+    fn analyze_statements(&mut self, statements: &[Node<'a>], assigned: bool) -> Flow {
+        let mut flow = Flow::normal(assigned);
+        for statement in statements {
+            // Following statements are unreachable.
+            let Some(assigned) = flow.normal else {
+                break;
+            };
+            let statement_flow = self.analyze(*statement, assigned);
+            flow = Flow {
+                normal: statement_flow.normal,
+                breaks: merge_paths(flow.breaks, statement_flow.breaks),
+            };
+        }
+        flow
+    }
+    fn analyze(&mut self, statement: Node<'a>, assigned: bool) -> Flow {
+        match statement.kind() {
+            "compound_statement" => {
+                let statements: Vec<Node> = statement
+                    .named_children(&mut statement.walk())
+                    .filter(|child| child.kind() != "comment")
+                    .collect();
+                self.analyze_statements(&statements, assigned)
+            }
+            "return_statement" => {
+                if !assigned && !self.is_assigning(Some(statement)) {
+                    self.unassigned_exits.push(statement);
+                }
+                Flow::exit()
+            }
+            "break_statement" | "continue_statement" => Flow {
+                normal: None,
+                breaks: Some(assigned),
+            },
+            "if_statement" => {
+                let assigned =
+                    assigned || self.is_assigning(statement.child_by_field_name("condition"));
+                let consequence = match statement.child_by_field_name("consequence") {
+                    Some(consequence) => self.analyze(consequence, assigned),
+                    None => Flow::normal(assigned),
+                };
+                let alternative = match statement.child_by_field_name("alternative") {
+                    Some(alternative) => self.analyze(alternative, assigned),
+                    None => Flow::normal(assigned),
+                };
+                consequence.merge(alternative)
+            }
+            "while_statement" | "for_statement" => {
+                let condition = statement.child_by_field_name("condition");
+                let assigned = assigned
+                    || self.is_assigning(statement.child_by_field_name("initializer"))
+                    || self.is_assigning(condition);
+                let body = match statement.child_by_field_name("body") {
+                    Some(body) => self.analyze(body, assigned),
+                    None => Flow::normal(assigned),
+                };
+                if is_infinite_loop(self.shader_content, condition) {
+                    Flow {
+                        normal: body.breaks,
+                        breaks: None,
+                    }
+                } else {
+                    // Body might not be executed.
+                    Flow::normal(assigned)
+                }
+            }
+            "do_statement" => {
+                let body = match statement.child_by_field_name("body") {
+                    Some(body) => self.analyze(body, assigned),
+                    None => Flow::normal(assigned),
+                };
+                let is_condition_assigning =
+                    self.is_assigning(statement.child_by_field_name("condition"));
+                Flow {
+                    normal: merge_paths(body.normal, body.breaks)
+                        .map(|assigned| assigned || is_condition_assigning),
+                    breaks: None,
+                }
+            }
+            "switch_statement" => {
+                let assigned =
+                    assigned || self.is_assigning(statement.child_by_field_name("condition"));
+                let cases: Vec<Node> = match statement.child_by_field_name("body") {
+                    Some(body) => body
+                        .named_children(&mut body.walk())
+                        .filter(|child| child.kind() == "case_statement")
+                        .collect(),
+                    None => Vec::new(),
+                };
+                let mut has_default = false;
+                let mut normal = None;
+                for (index, case) in cases.iter().enumerate() {
+                    let value = case.child_by_field_name("value");
+                    has_default |= value.is_none();
+                    // Fall through is ignored, each case start with the state before the switch.
+                    let statements: Vec<Node> = case
+                        .named_children(&mut case.walk())
+                        .filter(|child| Some(*child) != value && child.kind() != "comment")
+                        .collect();
+                    let flow = self.analyze_statements(&statements, assigned);
+                    normal = merge_paths(normal, flow.breaks);
+                    if index + 1 == cases.len() {
+                        normal = merge_paths(normal, flow.normal);
+                    }
+                }
+                if !has_default {
+                    normal = merge_paths(normal, Some(assigned));
+                }
+                Flow {
+                    normal,
+                    breaks: None,
+                }
+            }
+            _ if is_discard(self.shader_content, statement) => Flow::exit(),
+            // Declarations, expressions & preprocessor blocks whose paths are not followed.
+            _ => Flow::normal(assigned || self.is_assigning(Some(statement))),
+        }
+    }
+}
+
+// Returns & end of the function body reached before the variable is assigned on all paths.
+// The body itself is returned when its end is reached without the variable being assigned.
+pub(super) fn find_unassigned_exits<'a>(
+    shader_content: &str,
+    body: Node<'a>,
+    name: &str,
+) -> Vec<Node<'a>> {
+    let mut analysis = AssignmentAnalysis {
+        shader_content,
+        name,
+        unassigned_exits: Vec::new(),
+    };
+    let flow = analysis.analyze(body, false);
+    if flow.normal == Some(false) {
+        analysis.unassigned_exits.push(body);
+    }
+    analysis.unassigned_exits
+}
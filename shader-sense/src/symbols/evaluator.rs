@@ -0,0 +1,217 @@
+use tree_sitter::Node;
+
+use crate::validator::validator::ValidationParams;
+
+use super::{
+    glsl_parser::get_specialization_constant_id, linter::find_variable_declaration,
+    parser::get_name,
+};
+
+// Avoid infinite recursion with macros or constants referencing each other.
+const MAX_EVALUATION_DEPTH: u32 = 16;
+
+// Parse an integer literal such as 12, 0x10 or 4u.
+pub(super) fn parse_integer_literal(literal: &str) -> Option<i64> {
+    let literal = literal.trim().trim_end_matches(['u', 'U', 'l', 'L']);
+    if let Some(hex) = literal
+        .strip_prefix("0x")
+        .or_else(|| literal.strip_prefix("0X"))
+    {
+        i64::from_str_radix(hex, 16).ok()
+    } else {
+        literal.parse::<i64>().ok()
+    }
+}
+
+// Evaluate a constant integer expression from the tree.
+// Identifiers are resolved from defines, #define in file & const variables.
+pub(super) fn evaluate_integer_expression(
+    shader_content: &str,
+    node: Node,
+    params: &ValidationParams,
+) -> Option<i64> {
+    evaluate(shader_content, node, params, 0)
+}
+
+fn find_define_value<'a>(shader_content: &'a str, node: Node, name: &str) -> Option<&'a str> {
+    let root = {
+        let mut root = node;
+        while let Some(parent) = root.parent() {
+            root = parent;
+        }
+        root
+    };
+    let mut value = None;
+    for child in root.named_children(&mut root.walk()) {
+        if child.start_byte() >= node.start_byte() {
+            break;
+        }
+        if child.kind() == "preproc_def" {
+            let define_name = child.child_by_field_name("name")?;
+            if get_name(shader_content, define_name) == name {
+                value = child
+                    .child_by_field_name("value")
+                    .map(|value| get_name(shader_content, value));
+            }
+        }
+    }
+    value
+}
+
+fn evaluate(
+    shader_content: &str,
+    node: Node,
+    params: &ValidationParams,
+    depth: u32,
+) -> Option<i64> {
+    if depth > MAX_EVALUATION_DEPTH {
+        return None;
+    }
+    match node.kind() {
+        "number_literal" | "int_literal" => parse_integer_literal(get_name(shader_content, node)),
+        "parenthesized_expression" => {
+            evaluate(shader_content, node.named_child(0)?, params, depth + 1)
+        }
+        "unary_expression" => {
+            let value = evaluate(
+                shader_content,
+                node.child_by_field_name("argument")?,
+                params,
+                depth + 1,
+            )?;
+            match get_name(shader_content, node.child_by_field_name("operator")?) {
+                "-" => Some(-value),
+                "+" => Some(value),
+                "~" => Some(!value),
+                _ => None,
+            }
+        }
+        "binary_expression" => {
+            let left = evaluate(
+                shader_content,
+                node.child_by_field_name("left")?,
+                params,
+                depth + 1,
+            )?;
+            let right = evaluate(
+                shader_content,
+                node.child_by_field_name("right")?,
+                params,
+                depth + 1,
+            )?;
+            match get_name(shader_content, node.child_by_field_name("operator")?) {
+                "+" => left.checked_add(right),
+                "-" => left.checked_sub(right),
+                "*" => left.checked_mul(right),
+                "/" => left.checked_div(right),
+                "%" => left.checked_rem(right),
+                "<<" => left.checked_shl(u32::try_from(right).ok()?),
+                ">>" => left.checked_shr(u32::try_from(right).ok()?),
+                "&" => Some(left & right),
+                "|" => Some(left | right),
+                "^" => Some(left ^ right),
+                _ => None,
+            }
+        }
+        "identifier" => {
+            let name = get_name(shader_content, node);
+            if let Some(value) = params.defines.get(name) {
+                return parse_integer_literal(value);
+            }
+            if let Some(value) = find_define_value(shader_content, node, name) {
+                return parse_integer_literal(value);
+            }
+            // Only const variables are compile time constants.
+            let (declaration, declarator) = find_variable_declaration(shader_content, node, name)?;
+            let is_const = declaration
+                .children(&mut declaration.walk())
+                .any(|child| get_name(shader_content, child) == "const");
+            let specialization_constant =
+                get_specialization_constant_id(shader_content, declaration)
+                    .and_then(|id| params.specialization_constants.get(&id));
+            if let (true, Some(value)) = (is_const, specialization_constant) {
+                parse_integer_literal(value)
+            } else if is_const && declarator.kind() == "init_declarator" {
+                evaluate(
+                    shader_content,
+                    declarator.child_by_field_name("value")?,
+                    params,
+                    depth + 1,
+                )
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+pub(super) enum ExpressionConstness<'a> {
+    Constant,
+    // Constant overridable at pipeline creation, referenced by this identifier.
+    SpecializationConstant(Node<'a>),
+    // Identifier of the first variable that is not a compile time constant.
+    NonConstant(Node<'a>),
+}
+
+// Check if an expression is known at compile time.
+// Identifiers that can't be resolved, such as macros from includes, are assumed constant.
+pub(super) fn get_expression_constness<'a>(
+    shader_content: &str,
+    node: Node<'a>,
+    params: &ValidationParams,
+) -> ExpressionConstness<'a> {
+    get_constness(shader_content, node, node, params, 0)
+}
+
+fn get_constness<'a>(
+    shader_content: &str,
+    node: Node<'a>,
+    reference: Node<'a>, // Identifier reported, in the expression being checked.
+    params: &ValidationParams,
+    depth: u32,
+) -> ExpressionConstness<'a> {
+    if depth > MAX_EVALUATION_DEPTH {
+        return ExpressionConstness::Constant;
+    }
+    if node.kind() != "identifier" {
+        let mut constness = ExpressionConstness::Constant;
+        for child in node.named_children(&mut node.walk()) {
+            // Reference the identifier of the checked expression, not the one of a declaration.
+            let child_reference = if depth == 0 { child } else { reference };
+            match get_constness(shader_content, child, child_reference, params, depth) {
+                ExpressionConstness::Constant => {}
+                ExpressionConstness::SpecializationConstant(node) => {
+                    constness = ExpressionConstness::SpecializationConstant(node)
+                }
+                non_constant => return non_constant,
+            }
+        }
+        return constness;
+    }
+    let name = get_name(shader_content, node);
+    if params.defines.contains_key(name) || find_define_value(shader_content, node, name).is_some()
+    {
+        return ExpressionConstness::Constant;
+    }
+    let (declaration, declarator) = match find_variable_declaration(shader_content, node, name) {
+        Some(declaration) => declaration,
+        None => return ExpressionConstness::Constant,
+    };
+    let is_const = declaration.kind() == "declaration"
+        && declaration
+            .children(&mut declaration.walk())
+            .any(|child| get_name(shader_content, child) == "const");
+    if !is_const {
+        return ExpressionConstness::NonConstant(reference);
+    }
+    if get_specialization_constant_id(shader_content, declaration).is_some() {
+        return ExpressionConstness::SpecializationConstant(reference);
+    }
+    match declarator.child_by_field_name("value") {
+        Some(value) if declarator.kind() == "init_declarator" => {
+            get_constness(shader_content, value, reference, params, depth + 1)
+        }
+        _ => ExpressionConstness::Constant,
+    }
+}
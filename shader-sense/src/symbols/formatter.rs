@@ -0,0 +1,226 @@
+use tree_sitter::Node;
+
+use super::parser::SymbolTree;
+
+#[derive(Debug, Clone)]
+pub struct ShaderFormattingOptions {
+    pub tab_size: u32,
+    pub insert_spaces: bool,
+    pub trim_trailing_whitespace: bool,
+    pub insert_final_newline: bool,
+}
+
+impl Default for ShaderFormattingOptions {
+    fn default() -> Self {
+        Self {
+            tab_size: 4,
+            insert_spaces: true,
+            trim_trailing_whitespace: true,
+            insert_final_newline: false,
+        }
+    }
+}
+
+// Operators that get a single space on each side.
+const SPACED_OPERATORS: &[&str] = &[
+    "=", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "<<=", ">>=", "+", "-", "*", "/", "%",
+    "==", "!=", "<", ">", "<=", ">=", "&&", "||", "&", "|", "^", "<<", ">>",
+];
+// Nodes whose operators are spaced.
+const SPACED_NODES: &[&str] = &[
+    "binary_expression",
+    "assignment_expression",
+    "init_declarator",
+    "assignment_statement",
+    "variable_statement",
+];
+// Nodes whose childs are indented statements even without braces.
+const BLOCK_NODES: &[&str] = &[
+    "preproc_if",
+    "preproc_ifdef",
+    "preproc_else",
+    "preproc_elif",
+];
+
+fn get_indent_width(indent: &str, tab_size: usize) -> usize {
+    indent.chars().fold(0, |width, c| {
+        if c == '\t' {
+            (width / tab_size + 1) * tab_size
+        } else {
+            width + 1
+        }
+    })
+}
+
+fn create_indent(width: usize, options: &ShaderFormattingOptions) -> String {
+    let tab_size = options.tab_size.max(1) as usize;
+    if options.insert_spaces {
+        " ".repeat(width)
+    } else {
+        format!(
+            "{}{}",
+            "\t".repeat(width / tab_size),
+            " ".repeat(width % tab_size)
+        )
+    }
+}
+
+fn is_block(node: Node) -> bool {
+    node.parent().is_none()
+        || node.is_error()
+        || BLOCK_NODES.contains(&node.kind())
+        || node
+            .children(&mut node.walk())
+            .any(|child| !child.is_named() && child.kind() == "{")
+}
+
+impl SymbolTree {
+    // Format the whole content. The line count is preserved, so that range can be extracted.
+    pub fn format(&self, options: &ShaderFormattingOptions) -> String {
+        let content = self.content.as_str();
+        let root = self.tree.root_node();
+        let tab_size = options.tab_size.max(1) as usize;
+
+        // Collect braces & operators spacing from tokens.
+        let mut opening_braces = Vec::new();
+        let mut closing_braces = Vec::new();
+        let mut spacing_edits: Vec<(usize, usize)> = Vec::new();
+        let mut cursor = root.walk();
+        loop {
+            let node = cursor.node();
+            if node.child_count() == 0 {
+                match node.kind() {
+                    "{" => opening_braces.push(node.start_byte()),
+                    "}" => closing_braces.push(node.start_byte()),
+                    _ => {}
+                }
+            } else if SPACED_NODES.contains(&node.kind()) && !node.has_error() {
+                let mut previous: Option<Node> = None;
+                let mut walker = node.walk();
+                let mut children = node.children(&mut walker).peekable();
+                while let Some(child) = children.next() {
+                    let operator = &content[child.start_byte()..child.end_byte()];
+                    let is_operator = (!child.is_named()
+                        || child.kind() == "compound_assignment_operator")
+                        && SPACED_OPERATORS.contains(&operator);
+                    if let (true, Some(previous), Some(next)) =
+                        (is_operator, previous, children.peek())
+                    {
+                        spacing_edits.push((previous.end_byte(), child.start_byte()));
+                        spacing_edits.push((child.end_byte(), next.start_byte()));
+                    }
+                    previous = Some(child);
+                }
+            }
+            if cursor.goto_first_child() {
+                continue;
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    break;
+                }
+            }
+            if cursor.node() == root {
+                break;
+            }
+        }
+        // Only keep gaps that are simple whitespaces on a single line.
+        spacing_edits.retain(|(start, end)| {
+            let gap = &content[*start..*end];
+            gap != " " && gap.chars().all(|c| c == ' ' || c == '\t')
+        });
+        spacing_edits.sort();
+
+        let mut formatted = String::with_capacity(content.len());
+        let mut line_start = 0;
+        let mut indent_delta: isize = 0;
+        for line in content.split_inclusive('\n') {
+            let line_end = line_start + line.len();
+            let line_ending = if line.ends_with("\r\n") {
+                "\r\n"
+            } else if line.ends_with('\n') {
+                "\n"
+            } else {
+                ""
+            };
+            let line_content = &line[..line.len() - line_ending.len()];
+            let body = line_content.trim_start_matches([' ', '\t']);
+            let indent = &line_content[..line_content.len() - body.len()];
+            let body_start = line_start + indent.len();
+            let body = if options.trim_trailing_whitespace {
+                body.trim_end_matches([' ', '\t'])
+            } else {
+                body
+            };
+            let first_node = root.descendant_for_byte_range(body_start, body_start);
+            let is_inside_token = match first_node {
+                Some(node) => node.child_count() == 0 && node.start_byte() < body_start,
+                None => false,
+            };
+            if body.is_empty() {
+                if !options.trim_trailing_whitespace {
+                    formatted.push_str(indent);
+                }
+            } else if is_inside_token || body.starts_with('#') {
+                // Keep multiline comments, macros & preprocessor directives untouched.
+                indent_delta = 0;
+                formatted.push_str(indent);
+                formatted.push_str(body);
+            } else {
+                // Line starting a statement get indented from its depth,
+                // others are continuation of a statement and keep their alignment.
+                let mut is_statement = false;
+                let mut node = first_node;
+                while let Some(current) = node {
+                    if current.start_byte() != body_start {
+                        break;
+                    }
+                    match current.parent() {
+                        Some(parent) => {
+                            if is_block(parent) {
+                                is_statement = true;
+                                break;
+                            }
+                            node = Some(parent);
+                        }
+                        None => {
+                            is_statement = true;
+                            break;
+                        }
+                    }
+                }
+                let old_width = get_indent_width(indent, tab_size);
+                let new_width = if is_statement {
+                    let opened = opening_braces.partition_point(|b| *b < body_start);
+                    let closed = closing_braces.partition_point(|b| *b < body_start);
+                    let closing = body.starts_with('}') as usize;
+                    let depth = opened.saturating_sub(closed).saturating_sub(closing);
+                    let new_width = depth * tab_size;
+                    indent_delta = new_width as isize - old_width as isize;
+                    new_width
+                } else {
+                    (old_width as isize + indent_delta).max(0) as usize
+                };
+                formatted.push_str(&create_indent(new_width, options));
+                // Apply operator spacing within the line.
+                let mut offset = body_start;
+                let first = spacing_edits.partition_point(|(start, _)| *start < body_start);
+                for (start, end) in &spacing_edits[first..] {
+                    if *end > body_start + body.len() {
+                        break;
+                    }
+                    formatted.push_str(&content[offset..*start]);
+                    formatted.push(' ');
+                    offset = *end;
+                }
+                formatted.push_str(&content[offset..body_start + body.len()]);
+            }
+            formatted.push_str(line_ending);
+            line_start = line_end;
+        }
+        if options.insert_final_newline && !formatted.is_empty() && !formatted.ends_with('\n') {
+            formatted.push('\n');
+        }
+        formatted
+    }
+}
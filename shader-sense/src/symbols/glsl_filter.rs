@@ -1,61 +1,132 @@
-use crate::shader::ShaderStage;
+use std::collections::HashSet;
 
-use super::symbols::{ShaderSymbolList, SymbolFilter};
+use tree_sitter::Node;
 
-pub struct GlslVersionFilter {}
+use crate::{shader::GlslProfile, validator::validator::ValidationParams};
 
-impl SymbolFilter for GlslVersionFilter {
-    fn filter_symbols(&self, _shader_symbols: &mut ShaderSymbolList, _file_name: &String) {
-        // TODO: filter version
-    }
+use super::{
+    parser::{get_name, SymbolTree},
+    symbols::{ShaderSymbol, ShaderSymbolList, SymbolFilter},
+};
+
+// Extensions exposing the symbols of another extension.
+const GLSL_EXTENSION_ALIASES: [(&str, &str); 2] = [
+    (
+        "GL_EXT_shader_explicit_arithmetic_types",
+        "GL_ARB_gpu_shader_int64",
+    ),
+    (
+        "GL_EXT_shader_explicit_arithmetic_types_int64",
+        "GL_ARB_gpu_shader_int64",
+    ),
+];
+
+// Intrinsics gated by an extension use its name as version.
+pub(super) fn is_glsl_extension(version: &str) -> bool {
+    version.starts_with("GL_")
 }
-pub struct GlslStageFilter {}
-
-impl SymbolFilter for GlslStageFilter {
-    fn filter_symbols(&self, shader_symbols: &mut ShaderSymbolList, file_name: &String) {
-        match ShaderStage::from_file_name(file_name) {
-            Some(shader_stage) => {
-                *shader_symbols = ShaderSymbolList {
-                    types: shader_symbols
-                        .types
-                        .drain(..)
-                        .filter(|value| {
-                            value.stages.contains(&shader_stage) || value.stages.is_empty()
-                        })
-                        .collect(),
-                    constants: shader_symbols
-                        .constants
-                        .drain(..)
-                        .filter(|value| {
-                            value.stages.contains(&shader_stage) || value.stages.is_empty()
-                        })
-                        .collect(),
-                    variables: shader_symbols
-                        .variables
-                        .drain(..)
-                        .filter(|value| {
-                            value.stages.contains(&shader_stage) || value.stages.is_empty()
-                        })
-                        .collect(),
-                    functions: shader_symbols
-                        .functions
-                        .drain(..)
-                        .filter(|value| {
-                            value.stages.contains(&shader_stage) || value.stages.is_empty()
-                        })
-                        .collect(),
-                    keywords: shader_symbols
-                        .keywords
-                        .drain(..)
-                        .filter(|value| {
-                            value.stages.contains(&shader_stage) || value.stages.is_empty()
-                        })
-                        .collect(),
+
+// Extensions enabled with #extension, including those inside preprocessor blocks.
+pub(super) fn get_glsl_enabled_extensions(symbol_tree: &SymbolTree) -> HashSet<String> {
+    fn collect_extensions(content: &str, node: Node, extensions: &mut HashSet<String>) {
+        for child in node.named_children(&mut node.walk()) {
+            if child.kind() == "preproc_call" {
+                let directive = child.child_by_field_name("directive");
+                let argument = child.child_by_field_name("argument");
+                if let (Some(directive), Some(argument)) = (directive, argument) {
+                    if get_name(content, directive) != "#extension" {
+                        continue;
+                    }
+                    if let Some((name, behavior)) = get_name(content, argument).split_once(':') {
+                        if behavior.trim() != "disable" {
+                            extensions.insert(name.trim().to_string());
+                        }
+                    }
                 }
+            } else if child.kind().starts_with("preproc") {
+                collect_extensions(content, child, extensions);
             }
-            None => {
-                // No filtering
-            }
         }
     }
+    let mut extensions = HashSet::new();
+    collect_extensions(
+        &symbol_tree.content,
+        symbol_tree.tree.root_node(),
+        &mut extensions,
+    );
+    for (extension, alias) in GLSL_EXTENSION_ALIASES {
+        if extensions.contains(extension) {
+            extensions.insert(alias.to_string());
+        }
+    }
+    extensions
+}
+
+// Version & profile declared with #version.
+pub(super) fn get_glsl_version_directive(
+    symbol_tree: &SymbolTree,
+) -> Option<(u32, Option<GlslProfile>)> {
+    let root_node = symbol_tree.tree.root_node();
+    let mut cursor = root_node.walk();
+    let version = root_node.named_children(&mut cursor).find_map(|child| {
+        if child.kind() != "preproc_call" {
+            return None;
+        }
+        let directive = child.child_by_field_name("directive")?;
+        if get_name(&symbol_tree.content, directive) != "#version" {
+            return None;
+        }
+        let argument = child.child_by_field_name("argument")?;
+        let mut tokens = get_name(&symbol_tree.content, argument).split_whitespace();
+        let version = tokens.next()?.parse().ok()?;
+        Some((version, tokens.next().and_then(GlslProfile::from_directive)))
+    });
+    version
+}
+
+// Intrinsics version holds the minimum desktop & ES versions, such as "130, 300 es".
+fn is_glsl_version_available(symbol_version: &str, version: u32, is_es: bool) -> bool {
+    if is_glsl_extension(symbol_version) {
+        return true;
+    }
+    let mut desktop_version = None;
+    let mut es_version = None;
+    for entry in symbol_version.split(',').map(|entry| entry.trim()) {
+        match entry.strip_suffix("es") {
+            Some(entry) => es_version = entry.trim().parse::<u32>().ok(),
+            None => desktop_version = entry.parse::<u32>().ok(),
+        }
+    }
+    match (desktop_version, es_version) {
+        (_, Some(es_version)) if is_es => es_version <= version,
+        // Intrinsics generated without ES pages only have desktop versions.
+        (_, None) if is_es => true,
+        (Some(desktop_version), _) => desktop_version <= version,
+        (None, Some(_)) => false,
+        (None, None) => true,
+    }
+}
+
+pub struct GlslVersionFilter {}
+
+impl SymbolFilter for GlslVersionFilter {
+    fn filter_symbols(
+        &self,
+        shader_symbols: &mut ShaderSymbolList,
+        _file_name: &String,
+        params: &ValidationParams,
+    ) {
+        let Some(version) = params.glsl_version else {
+            return;
+        };
+        // ESSL 1.00 directive has no profile.
+        let is_es = params.glsl_profile == Some(GlslProfile::Es) || version == 100;
+        let is_available =
+            |symbol: &ShaderSymbol| is_glsl_version_available(&symbol.version, version, is_es);
+        shader_symbols.types.retain(is_available);
+        shader_symbols.constants.retain(is_available);
+        shader_symbols.variables.retain(is_available);
+        shader_symbols.functions.retain(is_available);
+        shader_symbols.keywords.retain(is_available);
+    }
 }
@@ -1,15 +1,52 @@
 use std::path::Path;
 
-use crate::{include::IncludeHandler, shader::ShadingLanguage};
+use crate::{
+    include::IncludeHandler, shader::ShadingLanguage, validator::validator::ValidationParams,
+};
 
 use super::{
-    parser::{get_name, SymbolTreeParser},
+    parser::{get_name, get_qualifiers, SymbolTreeParser},
     symbols::{
         ShaderParameter, ShaderPosition, ShaderRange, ShaderScope, ShaderSignature, ShaderSymbol,
         ShaderSymbolData, ShaderSymbolList,
     },
 };
 
+// Get id of a specialization constant declared with layout(constant_id = N).
+pub(super) fn get_specialization_constant_id(
+    shader_content: &str,
+    declaration: tree_sitter::Node,
+) -> Option<u32> {
+    let layout = declaration
+        .children(&mut declaration.walk())
+        .find(|child| child.kind() == "layout_specification")?;
+    let qualifiers = layout
+        .children(&mut layout.walk())
+        .find(|child| child.kind() == "layout_qualifiers")?;
+    let qualifier = qualifiers
+        .named_children(&mut qualifiers.walk())
+        .find(|qualifier| {
+            qualifier
+                .named_child(0)
+                .is_some_and(|name| get_name(shader_content, name) == "constant_id")
+        })?;
+    get_name(shader_content, qualifier.named_child(1)?)
+        .parse::<u32>()
+        .ok()
+}
+
+// Identifier of a declarator, without its array dimensions.
+fn get_declarator_identifier(declarator: tree_sitter::Node) -> tree_sitter::Node {
+    let mut identifier = declarator;
+    while identifier.kind() == "array_declarator" {
+        identifier = match identifier.child_by_field_name("declarator") {
+            Some(identifier) => identifier,
+            None => break,
+        };
+    }
+    identifier
+}
+
 pub(super) struct GlslIncludeTreeParser {}
 
 impl SymbolTreeParser for GlslIncludeTreeParser {
@@ -33,11 +70,16 @@ impl SymbolTreeParser for GlslIncludeTreeParser {
         file_path: &Path,
         shader_content: &str,
         _scopes: &Vec<ShaderScope>,
+        params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     ) {
         let include_node = matches.captures[0].node;
         let range = ShaderRange::from_range(include_node.range(), file_path.into());
-        let mut include_handler = IncludeHandler::new(file_path, vec![]); // TODO: pass includes aswell ?
+        let mut include_handler = IncludeHandler::new(
+            file_path,
+            params.includes.clone(),
+            params.virtual_includes.clone(),
+        );
         let relative_path = get_name(shader_content, include_node);
         let relative_path = &relative_path[1..relative_path.len() - 1]; // TODO: use string_content instead
 
@@ -55,6 +97,7 @@ impl SymbolTreeParser for GlslIncludeTreeParser {
                     },
                     range: Some(range),
                     scope_stack: None, // No scope for include
+                    snippet: None,
                 });
             }
             None => {}
@@ -66,12 +109,11 @@ pub(super) struct GlslDefineTreeParser {}
 
 impl SymbolTreeParser for GlslDefineTreeParser {
     fn get_query(&self) -> String {
-        r#"(preproc_def
-            (#define)
-            name: (identifier) @define.label
-            value: (preproc_arg)? @define.value
-        )"#
-        .into()
+        r#"[
+            (preproc_def name: (identifier) @define.label)
+            (preproc_function_def name: (identifier) @define.label)
+        ] @define"#
+            .into()
     }
     fn process_match(
         &self,
@@ -79,15 +121,25 @@ impl SymbolTreeParser for GlslDefineTreeParser {
         file_path: &Path,
         shader_content: &str,
         _scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     ) {
-        let identifier_node = matches.captures[0].node;
+        let define_node = matches.captures[0].node;
+        let identifier_node = matches.captures[1].node;
         let range = ShaderRange::from_range(identifier_node.range(), file_path.into());
-        let value = if matches.captures.len() > 1 {
-            Some(get_name(shader_content, matches.captures[1].node).trim())
-        } else {
-            None
-        };
+        let value = define_node
+            .child_by_field_name("value")
+            .map(|value| get_name(shader_content, value).trim());
+        // Function-like macros only.
+        let parameters = define_node
+            .child_by_field_name("parameters")
+            .map(|parameters| {
+                let mut cursor = parameters.walk();
+                parameters
+                    .named_children(&mut cursor)
+                    .map(|parameter| get_name(shader_content, parameter).into())
+                    .collect::<Vec<String>>()
+            });
         symbols.functions.push(ShaderSymbol {
             label: get_name(shader_content, identifier_node).into(),
             description: match value {
@@ -101,16 +153,14 @@ impl SymbolTreeParser for GlslDefineTreeParser {
             version: "".into(),
             stages: vec![],
             link: None,
-            data: ShaderSymbolData::Constants {
-                ty: "#define".into(),
-                qualifier: "".into(),
-                value: match value {
-                    Some(value) => value.into(),
-                    None => "".into(),
-                },
+            data: ShaderSymbolData::Macro {
+                parameters,
+                value: value.unwrap_or("").into(),
+                external: false,
             },
             range: Some(range),
             scope_stack: None, // No scope for include
+            snippet: None,
         });
     }
 }
@@ -140,6 +190,7 @@ impl SymbolTreeParser for GlslFunctionTreeParser {
         file_path: &Path,
         shader_content: &str,
         scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     ) {
         let label_node = matches.captures[1].node;
@@ -165,7 +216,13 @@ impl SymbolTreeParser for GlslFunctionTreeParser {
             link: None,
             data: ShaderSymbolData::Functions {
                 signatures: vec![ShaderSignature {
-                    returnType: get_name(shader_content, matches.captures[0].node).into(),
+                    returnType: {
+                        let return_type = get_name(shader_content, matches.captures[0].node);
+                        match get_qualifiers(shader_content, matches.captures[0].node) {
+                            qualifiers if qualifiers.is_empty() => return_type.into(),
+                            qualifiers => format!("{} {}", qualifiers, return_type),
+                        }
+                    },
                     description: "".into(),
                     parameters: matches.captures[2..matches.captures.len() - 1]
                         .chunks(2)
@@ -176,10 +233,41 @@ impl SymbolTreeParser for GlslFunctionTreeParser {
                         })
                         .collect::<Vec<ShaderParameter>>(),
                 }],
+                workgroup_size: None,
             },
             range: Some(range),
-            scope_stack: Some(scope_stack), // In GLSL, all function are global scope.
+            scope_stack: Some(scope_stack.clone()), // In GLSL, all function are global scope.
+            snippet: None,
         });
+        // Parameters are variables scoped to the function body.
+        let body_scope = ShaderRange::from_range(
+            matches.captures[matches.captures.len() - 1].node.range(),
+            file_path.into(),
+        );
+        for parameter in matches.captures[2..matches.captures.len() - 1].chunks(2) {
+            symbols.variables.push(ShaderSymbol {
+                label: get_name(shader_content, parameter[1].node).into(),
+                description: "".into(),
+                version: "".into(),
+                stages: vec![],
+                link: None,
+                data: ShaderSymbolData::Variables {
+                    ty: get_name(shader_content, parameter[0].node).into(),
+                    qualifier: "".into(),
+                    register: None,
+                },
+                range: Some(ShaderRange::from_range(
+                    parameter[1].node.range(),
+                    file_path.into(),
+                )),
+                scope_stack: Some({
+                    let mut parameter_scope_stack = scope_stack.clone();
+                    parameter_scope_stack.push(body_scope.clone());
+                    parameter_scope_stack
+                }),
+                snippet: None,
+            });
+        }
     }
 }
 
@@ -204,6 +292,7 @@ impl SymbolTreeParser for GlslStructTreeParser {
         file_path: &Path,
         shader_content: &str,
         scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     ) {
         let label_node = matches.captures[0].node;
@@ -220,7 +309,8 @@ impl SymbolTreeParser for GlslStructTreeParser {
                     .chunks(2)
                     .map(|w| ShaderParameter {
                         ty: get_name(shader_content, w[0].node).into(),
-                        label: get_name(shader_content, w[1].node).into(),
+                        label: get_name(shader_content, get_declarator_identifier(w[1].node))
+                            .into(),
                         description: "".into(),
                     })
                     .collect::<Vec<ShaderParameter>>(),
@@ -228,9 +318,125 @@ impl SymbolTreeParser for GlslStructTreeParser {
             },
             range: Some(range),
             scope_stack: Some(scope_stack),
+            snippet: None,
         });
     }
 }
+// Interface blocks such as uniform & buffer blocks. Members of blocks without instance name are global.
+pub(super) struct GlslBlockTreeParser {}
+
+impl SymbolTreeParser for GlslBlockTreeParser {
+    fn get_query(&self) -> String {
+        r#"(declaration
+            (identifier) @block.type
+            (field_declaration_list
+                (field_declaration
+                    type: (_) @block.member.type
+                    declarator: (_) @block.member.decl
+                )+
+            )
+            (identifier)? @block.label
+        )"#
+        .into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
+        symbols: &mut ShaderSymbolList,
+    ) {
+        let type_node = matches.captures[0].node;
+        let (member_captures, instance_node) = match matches.captures.split_last() {
+            Some((last, captures)) if last.node.kind() == "identifier" => {
+                (&captures[1..], Some(last.node))
+            }
+            _ => (&matches.captures[1..], None),
+        };
+        let block_kind = type_node
+            .parent()
+            .and_then(|declaration| {
+                declaration
+                    .children(&mut declaration.walk())
+                    .map(|child| child.kind())
+                    .find(|kind| ["buffer", "uniform", "in", "out"].contains(kind))
+            })
+            .unwrap_or("");
+        let description = match block_kind {
+            "buffer" => "Shader storage block.",
+            "uniform" => "Uniform block.",
+            _ => "Interface block.",
+        };
+        let range = ShaderRange::from_range(type_node.range(), file_path.into());
+        let scope_stack = self.compute_scope_stack(scopes, &range);
+        let members: Vec<ShaderParameter> = member_captures
+            .chunks(2)
+            .map(|w| ShaderParameter {
+                ty: get_name(shader_content, w[0].node).into(),
+                label: get_name(shader_content, get_declarator_identifier(w[1].node)).into(),
+                description: "".into(),
+            })
+            .collect();
+        symbols.types.push(ShaderSymbol {
+            label: get_name(shader_content, type_node).into(),
+            description: description.into(),
+            version: "".into(),
+            stages: vec![],
+            link: None,
+            data: ShaderSymbolData::Struct {
+                members: members.clone(),
+                methods: vec![],
+            },
+            range: Some(range),
+            scope_stack: Some(scope_stack.clone()),
+            snippet: None,
+        });
+        match instance_node {
+            Some(instance_node) => symbols.variables.push(ShaderSymbol {
+                label: get_name(shader_content, instance_node).into(),
+                description: "".into(),
+                version: "".into(),
+                stages: vec![],
+                link: None,
+                data: ShaderSymbolData::Variables {
+                    ty: get_name(shader_content, type_node).into(),
+                    register: None,
+                    qualifier: block_kind.into(),
+                },
+                range: Some(ShaderRange::from_range(
+                    instance_node.range(),
+                    file_path.into(),
+                )),
+                scope_stack: Some(scope_stack),
+                snippet: None,
+            }),
+            None => {
+                for (member, w) in members.into_iter().zip(member_captures.chunks(2)) {
+                    symbols.variables.push(ShaderSymbol {
+                        label: member.label,
+                        description: "".into(),
+                        version: "".into(),
+                        stages: vec![],
+                        link: None,
+                        data: ShaderSymbolData::Variables {
+                            ty: member.ty,
+                            register: None,
+                            qualifier: block_kind.into(),
+                        },
+                        range: Some(ShaderRange::from_range(
+                            get_declarator_identifier(w[1].node).range(),
+                            file_path.into(),
+                        )),
+                        scope_stack: Some(scope_stack.clone()),
+                        snippet: None,
+                    });
+                }
+            }
+        }
+    }
+}
 pub(super) struct GlslVariableTreeParser {}
 
 impl SymbolTreeParser for GlslVariableTreeParser {
@@ -255,11 +461,42 @@ impl SymbolTreeParser for GlslVariableTreeParser {
         file_path: &Path,
         shader_content: &str,
         scopes: &Vec<ShaderScope>,
+        params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     ) {
         let label_node = matches.captures[1].node;
         let range = ShaderRange::from_range(label_node.range(), file_path.into());
         let scope_stack = self.compute_scope_stack(&scopes, &range);
+        let type_node = matches.captures[0].node;
+        let specialization_constant_id = type_node
+            .parent()
+            .and_then(|declaration| get_specialization_constant_id(shader_content, declaration));
+        if let Some(id) = specialization_constant_id {
+            let value = match params.specialization_constants.get(&id) {
+                Some(value) => value.clone(),
+                None => matches
+                    .captures
+                    .get(2)
+                    .map(|value| get_name(shader_content, value.node).to_string())
+                    .unwrap_or_default(),
+            };
+            symbols.constants.push(ShaderSymbol {
+                label: get_name(shader_content, label_node).into(),
+                description: format!("Specialization constant (id: {}).", id),
+                version: "".into(),
+                stages: vec![],
+                link: None,
+                data: ShaderSymbolData::Constants {
+                    ty: get_name(shader_content, type_node).into(),
+                    qualifier: get_qualifiers(shader_content, type_node),
+                    value,
+                },
+                range: Some(range),
+                scope_stack: Some(scope_stack),
+                snippet: None,
+            });
+            return;
+        }
         // Check if its parameter or struct element.
         let _type_qualifier = get_name(shader_content, matches.captures[0].node);
         // TODO: handle values & qualifiers..
@@ -272,9 +509,12 @@ impl SymbolTreeParser for GlslVariableTreeParser {
             link: None,
             data: ShaderSymbolData::Variables {
                 ty: get_name(shader_content, matches.captures[0].node).into(),
+                register: None,
+                qualifier: get_qualifiers(shader_content, matches.captures[0].node),
             },
             range: Some(range),
             scope_stack: Some(scope_stack),
+            snippet: None,
         });
     }
 }
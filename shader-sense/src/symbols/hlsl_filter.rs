@@ -1,54 +1,33 @@
-use crate::shader::ShaderStage;
+use crate::validator::validator::ValidationParams;
 
-use super::symbols::{ShaderSymbolList, SymbolFilter};
+use super::symbols::{ShaderSymbol, ShaderSymbolList, SymbolFilter};
 
-pub struct HlslStageFilter {}
+// Intrinsic versions are written as sm5 or sm5.1.
+fn parse_shader_model_version(version: &str) -> Option<(u32, u32)> {
+    let version = version.strip_prefix("sm")?;
+    let (major, minor) = version.split_once('.').unwrap_or((version, "0"));
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+pub struct HlslShaderModelFilter {}
 
-impl SymbolFilter for HlslStageFilter {
-    fn filter_symbols(&self, shader_symbols: &mut ShaderSymbolList, file_name: &String) {
-        match ShaderStage::from_file_name(file_name) {
-            Some(shader_stage) => {
-                *shader_symbols = ShaderSymbolList {
-                    types: shader_symbols
-                        .types
-                        .drain(..)
-                        .filter(|value| {
-                            value.stages.contains(&shader_stage) || value.stages.is_empty()
-                        })
-                        .collect(),
-                    constants: shader_symbols
-                        .constants
-                        .drain(..)
-                        .filter(|value| {
-                            value.stages.contains(&shader_stage) || value.stages.is_empty()
-                        })
-                        .collect(),
-                    variables: shader_symbols
-                        .variables
-                        .drain(..)
-                        .filter(|value| {
-                            value.stages.contains(&shader_stage) || value.stages.is_empty()
-                        })
-                        .collect(),
-                    functions: shader_symbols
-                        .functions
-                        .drain(..)
-                        .filter(|value| {
-                            value.stages.contains(&shader_stage) || value.stages.is_empty()
-                        })
-                        .collect(),
-                    keywords: shader_symbols
-                        .keywords
-                        .drain(..)
-                        .filter(|value| {
-                            value.stages.contains(&shader_stage) || value.stages.is_empty()
-                        })
-                        .collect(),
-                }
-            }
-            None => {
-                // No filtering
-            }
-        }
+impl SymbolFilter for HlslShaderModelFilter {
+    fn filter_symbols(
+        &self,
+        shader_symbols: &mut ShaderSymbolList,
+        _file_name: &String,
+        params: &ValidationParams,
+    ) {
+        let shader_model = params.hlsl_shader_model.get_version();
+        // Symbols without version are always available.
+        let is_available = |value: &ShaderSymbol| match parse_shader_model_version(&value.version) {
+            Some(version) => version <= shader_model,
+            None => true,
+        };
+        shader_symbols.types.retain(is_available);
+        shader_symbols.constants.retain(is_available);
+        shader_symbols.variables.retain(is_available);
+        shader_symbols.functions.retain(is_available);
+        shader_symbols.keywords.retain(is_available);
     }
 }
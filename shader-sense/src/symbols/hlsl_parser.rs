@@ -1,15 +1,72 @@
 use std::path::Path;
 
-use crate::{include::IncludeHandler, shader::ShadingLanguage, symbols::symbols::ShaderMember};
+use crate::{
+    include::IncludeHandler, shader::ShadingLanguage, symbols::symbols::ShaderMember,
+    validator::validator::ValidationParams,
+};
 
 use super::{
-    parser::{get_name, SymbolTreeParser},
+    parser::{get_name, get_qualifiers, SymbolTreeParser},
     symbols::{
-        ShaderMethod, ShaderParameter, ShaderPosition, ShaderRange, ShaderScope, ShaderSignature,
-        ShaderSymbol, ShaderSymbolData, ShaderSymbolList,
+        ShaderMethod, ShaderParameter, ShaderPosition, ShaderRange, ShaderRegister, ShaderScope,
+        ShaderSignature, ShaderSymbol, ShaderSymbolData, ShaderSymbolList,
     },
 };
 
+// Parse compute workgroup size of a function definition, such as [numthreads(8, 8, 1)].
+fn get_numthreads(shader_content: &str, definition: tree_sitter::Node) -> Option<[String; 3]> {
+    let numthreads_call = definition
+        .children(&mut definition.walk())
+        .filter(|child| child.kind() == "hlsl_attribute")
+        .filter_map(|attribute| attribute.named_child(0))
+        .find(|call| {
+            call.kind() == "call_expression"
+                && call
+                    .child_by_field_name("function")
+                    .is_some_and(|function| get_name(shader_content, function) == "numthreads")
+        })?;
+    let arguments = numthreads_call.child_by_field_name("arguments")?;
+    let dimensions: Vec<String> = arguments
+        .named_children(&mut arguments.walk())
+        .map(|argument| get_name(shader_content, argument).into())
+        .collect();
+    dimensions.try_into().ok()
+}
+
+// Parse register binding of a declaration, such as register(s0, space1).
+pub(super) fn get_register(
+    shader_content: &str,
+    declaration: tree_sitter::Node,
+) -> Option<ShaderRegister> {
+    let register_call = declaration
+        .children_by_field_name("declarator", &mut declaration.walk())
+        .filter(|declarator| declarator.kind() == "semantics")
+        .filter_map(|semantics| semantics.named_child(0))
+        .find(|call| {
+            call.kind() == "call_expression"
+                && call
+                    .child_by_field_name("function")
+                    .is_some_and(|function| get_name(shader_content, function) == "register")
+        })?;
+    let arguments = register_call.child_by_field_name("arguments")?;
+    let mut arguments = arguments
+        .named_children(&mut arguments.walk())
+        .map(|argument| get_name(shader_content, argument))
+        .collect::<Vec<&str>>()
+        .into_iter();
+    let slot = arguments.next()?;
+    let index_start = slot.find(|c: char| c.is_ascii_digit())?;
+    let space = match arguments.next() {
+        Some(space) => space.strip_prefix("space")?.parse::<u32>().ok()?,
+        None => 0,
+    };
+    Some(ShaderRegister {
+        ty: slot[..index_start].to_lowercase(),
+        index: slot[index_start..].parse::<u32>().ok()?,
+        space,
+    })
+}
+
 pub(super) struct HlslIncludeTreeParser {}
 
 impl SymbolTreeParser for HlslIncludeTreeParser {
@@ -33,11 +90,16 @@ impl SymbolTreeParser for HlslIncludeTreeParser {
         file_path: &Path,
         shader_content: &str,
         _scopes: &Vec<ShaderScope>,
+        params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     ) {
         let include_node = matches.captures[0].node;
         let range = ShaderRange::from_range(include_node.range(), file_path.into());
-        let mut include_handler = IncludeHandler::new(file_path, vec![]); // TODO: pass includes aswell ?
+        let mut include_handler = IncludeHandler::new(
+            file_path,
+            params.includes.clone(),
+            params.virtual_includes.clone(),
+        );
         let relative_path = get_name(shader_content, include_node);
         let relative_path = &relative_path[1..relative_path.len() - 1]; // TODO: use string_content instead
 
@@ -55,6 +117,7 @@ impl SymbolTreeParser for HlslIncludeTreeParser {
                     },
                     range: Some(range),
                     scope_stack: None, // No scope for include
+                    snippet: None,
                 });
             }
             None => {}
@@ -65,12 +128,11 @@ pub(super) struct HlslDefineTreeParser {}
 
 impl SymbolTreeParser for HlslDefineTreeParser {
     fn get_query(&self) -> String {
-        r#"(preproc_def
-            (#define)
-            name: (identifier) @define.label
-            value: (preproc_arg)? @define.value
-        )"#
-        .into()
+        r#"[
+            (preproc_def name: (identifier) @define.label)
+            (preproc_function_def name: (identifier) @define.label)
+        ] @define"#
+            .into()
     }
     fn process_match(
         &self,
@@ -78,15 +140,25 @@ impl SymbolTreeParser for HlslDefineTreeParser {
         file_path: &Path,
         shader_content: &str,
         _scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     ) {
-        let identifier_node = matches.captures[0].node;
+        let define_node = matches.captures[0].node;
+        let identifier_node = matches.captures[1].node;
         let range = ShaderRange::from_range(identifier_node.range(), file_path.into());
-        let value = if matches.captures.len() > 1 {
-            Some(get_name(shader_content, matches.captures[1].node).trim())
-        } else {
-            None
-        };
+        let value = define_node
+            .child_by_field_name("value")
+            .map(|value| get_name(shader_content, value).trim());
+        // Function-like macros only.
+        let parameters = define_node
+            .child_by_field_name("parameters")
+            .map(|parameters| {
+                let mut cursor = parameters.walk();
+                parameters
+                    .named_children(&mut cursor)
+                    .map(|parameter| get_name(shader_content, parameter).into())
+                    .collect::<Vec<String>>()
+            });
         symbols.functions.push(ShaderSymbol {
             label: get_name(shader_content, identifier_node).into(),
             description: match value {
@@ -100,16 +172,14 @@ impl SymbolTreeParser for HlslDefineTreeParser {
             version: "".into(),
             stages: vec![],
             link: None,
-            data: ShaderSymbolData::Constants {
-                ty: "#define".into(),
-                qualifier: "".into(),
-                value: match value {
-                    Some(value) => value.into(),
-                    None => "".into(),
-                },
+            data: ShaderSymbolData::Macro {
+                parameters,
+                value: value.unwrap_or("").into(),
+                external: false,
             },
             range: Some(range),
             scope_stack: None, // No scope for include
+            snippet: None,
         });
     }
 }
@@ -120,11 +190,19 @@ pub(super) struct HlslFunctionTreeParser {
 impl SymbolTreeParser for HlslFunctionTreeParser {
     fn get_query(&self) -> String {
         let field_prestring = if self.is_field { "field_" } else { "" };
+        let template = if self.is_field {
+            "template_method"
+        } else {
+            "template_function"
+        };
         format!(
             r#"(function_definition
             type: (_) @function.return
             declarator: (function_declarator
-                declarator: ({}identifier) @function.label
+                declarator: [
+                    ({0}identifier) @function.label
+                    ({1} name: ({0}identifier) @function.label)
+                ]
                 parameters: (parameter_list 
                     ((parameter_declaration
                         type: (_) @function.param.type
@@ -134,7 +212,7 @@ impl SymbolTreeParser for HlslFunctionTreeParser {
             )
             body: (compound_statement) @function.scope
         )"#,
-            field_prestring
+            field_prestring, template
         ) // compound_statement is function scope.
           /*(semantics
               (identifier) @function.param.semantic
@@ -146,6 +224,7 @@ impl SymbolTreeParser for HlslFunctionTreeParser {
         file_path: &Path,
         shader_content: &str,
         scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     ) {
         let label_node = matches.captures[1].node;
@@ -171,7 +250,13 @@ impl SymbolTreeParser for HlslFunctionTreeParser {
             link: None,
             data: ShaderSymbolData::Functions {
                 signatures: vec![ShaderSignature {
-                    returnType: get_name(shader_content, matches.captures[0].node).into(),
+                    returnType: {
+                        let return_type = get_name(shader_content, matches.captures[0].node);
+                        match get_qualifiers(shader_content, matches.captures[0].node) {
+                            qualifiers if qualifiers.is_empty() => return_type.into(),
+                            qualifiers => format!("{} {}", qualifiers, return_type),
+                        }
+                    },
                     description: "".into(),
                     parameters: matches.captures[2..matches.captures.len() - 1]
                         .chunks(2)
@@ -182,10 +267,114 @@ impl SymbolTreeParser for HlslFunctionTreeParser {
                         })
                         .collect::<Vec<ShaderParameter>>(),
                 }],
+                workgroup_size: matches.captures[0]
+                    .node
+                    .parent()
+                    .and_then(|definition| get_numthreads(shader_content, definition)),
             },
             range: Some(range),
-            scope_stack: Some(scope_stack), // In GLSL, all function are global scope.
+            scope_stack: Some(scope_stack.clone()), // In GLSL, all function are global scope.
+            snippet: None,
         });
+        // Parameters are variables scoped to the function body.
+        let body_scope = ShaderRange::from_range(
+            matches.captures[matches.captures.len() - 1].node.range(),
+            file_path.into(),
+        );
+        let body_scope_stack = {
+            let mut body_scope_stack = scope_stack.clone();
+            body_scope_stack.push(body_scope.clone());
+            body_scope_stack
+        };
+        // Generic parameters such as <T : IMaterial> are types scoped to the function body.
+        if let Some(arguments) = label_node
+            .parent()
+            .and_then(|template| template.child_by_field_name("arguments"))
+        {
+            for (label, constraints) in get_type_parameters(shader_content, arguments) {
+                symbols.types.push(create_type_parameter(
+                    shader_content,
+                    file_path,
+                    label,
+                    constraints,
+                    &body_scope_stack,
+                ));
+            }
+        }
+        for parameter in matches.captures[2..matches.captures.len() - 1].chunks(2) {
+            symbols.variables.push(ShaderSymbol {
+                label: get_name(shader_content, parameter[1].node).into(),
+                description: "".into(),
+                version: "".into(),
+                stages: vec![],
+                link: None,
+                data: ShaderSymbolData::Variables {
+                    ty: get_name(shader_content, parameter[0].node).into(),
+                    qualifier: "".into(),
+                    register: None,
+                },
+                range: Some(ShaderRange::from_range(
+                    parameter[1].node.range(),
+                    file_path.into(),
+                )),
+                scope_stack: Some(body_scope_stack.clone()),
+                snippet: None,
+            });
+        }
+    }
+}
+
+// The grammar has no generic constraints, <T : IMaterial> is recovered as an error before the constraint.
+// Multiple constrained parameters cannot be recovered.
+fn get_type_parameters<'a>(
+    shader_content: &str,
+    arguments: tree_sitter::Node<'a>,
+) -> Vec<(tree_sitter::Node<'a>, Vec<String>)> {
+    let mut type_parameters = Vec::new();
+    let mut constrained_label = None;
+    for argument in arguments.named_children(&mut arguments.walk()) {
+        match argument.kind() {
+            "ERROR" => {
+                constrained_label = argument
+                    .named_child(0)
+                    .filter(|label| label.kind() == "identifier");
+            }
+            "type_descriptor" => match constrained_label.take() {
+                Some(label) => {
+                    type_parameters.push((label, vec![get_name(shader_content, argument).into()]))
+                }
+                None => {
+                    if let Some(label) = argument
+                        .child_by_field_name("type")
+                        .filter(|label| label.kind() == "type_identifier")
+                    {
+                        type_parameters.push((label, vec![]));
+                    }
+                }
+            },
+            _ => constrained_label = None,
+        }
+    }
+    type_parameters
+}
+
+fn create_type_parameter(
+    shader_content: &str,
+    file_path: &Path,
+    label: tree_sitter::Node,
+    constraints: Vec<String>,
+    scope_stack: &[ShaderScope],
+) -> ShaderSymbol {
+    ShaderSymbol {
+        label: get_name(shader_content, label).into(),
+        description: "".into(),
+        version: "".into(),
+        stages: vec![],
+        link: None,
+        data: ShaderSymbolData::TypeParameter { constraints },
+        range: Some(ShaderRange::from_range(label.range(), file_path.into())),
+        scope_stack: Some(scope_stack.to_vec()),
+        snippet: None,
     }
 }
 
@@ -225,6 +414,7 @@ impl SymbolTreeParser for HlslStructTreeParser {
         file_path: &Path,
         shader_content: &str,
         scopes: &Vec<ShaderScope>,
+        params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     ) {
         let label_node = matches.captures[0].node;
@@ -246,6 +436,7 @@ impl SymbolTreeParser for HlslStructTreeParser {
                     file_path,
                     shader_content,
                     scopes,
+                    params,
                     &mut symbols,
                 );
                 symbols
@@ -253,7 +444,7 @@ impl SymbolTreeParser for HlslStructTreeParser {
                     .iter()
                     .map(|f| ShaderMethod {
                         label: f.label.clone(),
-                        signature: if let ShaderSymbolData::Functions { signatures } = &f.data {
+                        signature: if let ShaderSymbolData::Functions { signatures, .. } = &f.data {
                             signatures[0].clone()
                         } else {
                             panic!("Invalid function type");
@@ -279,6 +470,7 @@ impl SymbolTreeParser for HlslStructTreeParser {
                     file_path,
                     shader_content,
                     scopes,
+                    params,
                     &mut symbols,
                 );
                 symbols
@@ -286,7 +478,7 @@ impl SymbolTreeParser for HlslStructTreeParser {
                     .iter()
                     .map(|f| ShaderMember {
                         label: f.label.clone(),
-                        ty: if let ShaderSymbolData::Variables { ty } = &f.data {
+                        ty: if let ShaderSymbolData::Variables { ty, .. } = &f.data {
                             ty.clone()
                         } else {
                             panic!("Invalid variable type");
@@ -309,6 +501,129 @@ impl SymbolTreeParser for HlslStructTreeParser {
             },
             range: Some(range),
             scope_stack: Some(scope_stack),
+            snippet: None,
+        });
+    }
+}
+
+// Interfaces are parsed by the grammar as a definition with interface as type.
+pub(super) struct HlslInterfaceTreeParser {}
+
+impl SymbolTreeParser for HlslInterfaceTreeParser {
+    fn get_query(&self) -> String {
+        r#"(function_definition
+            type: (type_identifier) @interface.keyword
+            declarator: (identifier) @interface.label
+            body: (compound_statement) @interface.content
+        )"#
+        .into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
+        symbols: &mut ShaderSymbolList,
+    ) {
+        if get_name(shader_content, matches.captures[0].node) != "interface" {
+            return;
+        }
+        let label_node = matches.captures[1].node;
+        let range = ShaderRange::from_range(label_node.range(), file_path.into());
+        let scope_stack = self.compute_scope_stack(scopes, &range);
+        // Requirements are method declarations without body.
+        let content_node = matches.captures[2].node;
+        // Associated types are types scoped to the interface.
+        let content_scope_stack = {
+            let mut content_scope_stack = scope_stack.clone();
+            content_scope_stack.push(ShaderRange::from_range(
+                content_node.range(),
+                file_path.into(),
+            ));
+            content_scope_stack
+        };
+        for declaration in content_node.named_children(&mut content_node.walk()) {
+            let is_associated_type = declaration.kind() == "declaration"
+                && declaration
+                    .child_by_field_name("type")
+                    .is_some_and(|ty| get_name(shader_content, ty) == "associatedtype");
+            if !is_associated_type {
+                continue;
+            }
+            // Constraints are parsed as semantics, following ones as other declarators.
+            let mut declarators = declaration
+                .children_by_field_name("declarator", &mut declaration.walk())
+                .filter_map(|declarator| match declarator.kind() {
+                    "identifier" => Some(declarator),
+                    "semantics" => declarator
+                        .named_child(0)
+                        .filter(|constraint| constraint.kind() == "identifier"),
+                    _ => None,
+                })
+                .collect::<Vec<tree_sitter::Node>>()
+                .into_iter();
+            if let Some(label) = declarators.next() {
+                symbols.types.push(create_type_parameter(
+                    shader_content,
+                    file_path,
+                    label,
+                    declarators
+                        .map(|constraint| get_name(shader_content, constraint).into())
+                        .collect(),
+                    &content_scope_stack,
+                ));
+            }
+        }
+        let methods = content_node
+            .named_children(&mut content_node.walk())
+            .filter(|declaration| declaration.kind() == "declaration")
+            .filter_map(|declaration| {
+                let return_type = declaration.child_by_field_name("type")?;
+                let declarator = declaration
+                    .child_by_field_name("declarator")
+                    .filter(|declarator| declarator.kind() == "function_declarator")?;
+                let label = declarator.child_by_field_name("declarator")?;
+                let parameters = declarator.child_by_field_name("parameters")?;
+                Some(ShaderMethod {
+                    label: get_name(shader_content, label).into(),
+                    signature: ShaderSignature {
+                        returnType: get_name(shader_content, return_type).into(),
+                        description: "".into(),
+                        parameters: parameters
+                            .named_children(&mut parameters.walk())
+                            .filter(|parameter| parameter.kind() == "parameter_declaration")
+                            .filter_map(|parameter| {
+                                Some(ShaderParameter {
+                                    ty: get_name(
+                                        shader_content,
+                                        parameter.child_by_field_name("type")?,
+                                    )
+                                    .into(),
+                                    label: get_name(
+                                        shader_content,
+                                        parameter.child_by_field_name("declarator")?,
+                                    )
+                                    .into(),
+                                    description: "".into(),
+                                })
+                            })
+                            .collect(),
+                    },
+                })
+            })
+            .collect();
+        symbols.types.push(ShaderSymbol {
+            label: get_name(shader_content, label_node).into(),
+            description: "".into(),
+            version: "".into(),
+            stages: vec![],
+            link: None,
+            data: ShaderSymbolData::Interface { methods },
+            range: Some(range),
+            scope_stack: Some(scope_stack),
+            snippet: None,
         });
     }
 }
@@ -339,8 +654,13 @@ impl SymbolTreeParser for HlslVariableTreeParser {
         file_path: &Path,
         shader_content: &str,
         scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     ) {
+        // Associated types of interfaces are parsed as declarations.
+        if get_name(shader_content, matches.captures[0].node) == "associatedtype" {
+            return;
+        }
         let label_node = matches.captures[1].node;
         let range = ShaderRange::from_range(label_node.range(), file_path.into());
         let scope_stack = self.compute_scope_stack(&scopes, &range);
@@ -356,9 +676,68 @@ impl SymbolTreeParser for HlslVariableTreeParser {
             link: None,
             data: ShaderSymbolData::Variables {
                 ty: get_name(shader_content, matches.captures[0].node).into(),
+                qualifier: get_qualifiers(shader_content, matches.captures[0].node),
+                register: matches.captures[0]
+                    .node
+                    .parent()
+                    .and_then(|declaration| get_register(shader_content, declaration)),
+            },
+            range: Some(range),
+            scope_stack: Some(scope_stack),
+            snippet: None,
+        });
+    }
+}
+
+pub(super) struct HlslSamplerStateTreeParser {}
+
+impl SymbolTreeParser for HlslSamplerStateTreeParser {
+    fn get_query(&self) -> String {
+        // Static sampler descriptions are parsed as function without parameters.
+        r#"(function_definition
+            type: (type_identifier) @sampler.type
+            declarator: (identifier) @sampler.label
+            body: (compound_statement) @sampler.state
+        )"#
+        .into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
+        symbols: &mut ShaderSymbolList,
+    ) {
+        let ty = get_name(shader_content, matches.captures[0].node);
+        if !matches!(ty, "SamplerState" | "SamplerComparisonState" | "sampler") {
+            return;
+        }
+        let label_node = matches.captures[1].node;
+        let state_node = matches.captures[2].node;
+        let range = ShaderRange::from_range(label_node.range(), file_path.into());
+        let scope_stack = self.compute_scope_stack(scopes, &range);
+        let states: Vec<&str> = state_node
+            .named_children(&mut state_node.walk())
+            .filter_map(|statement| statement.named_child(0))
+            .filter(|expression| expression.kind() == "assignment_expression")
+            .map(|expression| get_name(shader_content, expression))
+            .collect();
+        symbols.variables.push(ShaderSymbol {
+            label: get_name(shader_content, label_node).into(),
+            description: format!("Static sampler ({})", states.join(", ")),
+            version: "".into(),
+            stages: vec![],
+            link: None,
+            data: ShaderSymbolData::Variables {
+                ty: ty.into(),
+                qualifier: "".into(),
+                register: None,
             },
             range: Some(range),
             scope_stack: Some(scope_stack),
+            snippet: None,
         });
     }
 }
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use tree_sitter::Node;
+
+use super::{
+    parser::{get_name, SymbolTree},
+    symbols::{ShaderPosition, ShaderSignature, ShaderSymbol, ShaderSymbolData, ShaderSymbolList},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaderInlayHintKind {
+    // Inferred type after a declaration without type.
+    Type,
+    // Parameter name before a call argument.
+    Parameter,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShaderInlayHint {
+    pub position: ShaderPosition,
+    pub label: String,
+    pub kind: ShaderInlayHintKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShaderInlayHintOptions {
+    pub types: bool,
+    pub parameters: bool,
+}
+
+impl Default for ShaderInlayHintOptions {
+    fn default() -> Self {
+        Self {
+            types: true,
+            parameters: true,
+        }
+    }
+}
+
+const COMPARISON_OPERATORS: [&str; 8] = ["==", "!=", "<", ">", "<=", ">=", "&&", "||"];
+
+// Intrinsics use placeholders such as T, vecN<F> or matRxC<F> for generic types.
+fn is_generic_type(ty: &str) -> bool {
+    ty.starts_with("__")
+        || ty.split(|c: char| !c.is_ascii_alphanumeric()).any(|word| {
+            (word.len() == 1 && word.chars().all(|c| c.is_ascii_uppercase()))
+                || word == "vecN"
+                || word == "matRxC"
+        })
+}
+
+fn get_literal_type(shader_content: &str, literal: Node) -> Option<String> {
+    let literal = literal.named_child(0)?;
+    let value = get_name(shader_content, literal);
+    let ty = match literal.kind() {
+        "bool_literal" => "bool",
+        "float_literal" if value.ends_with('h') => "f16",
+        "float_literal" => "f32",
+        "int_literal" if value.ends_with('u') => "u32",
+        "int_literal" => "i32",
+        _ => return None,
+    };
+    Some(ty.into())
+}
+
+// Argument already named like the parameter, such as uv or input.uv for a parameter uv.
+fn is_argument_named(shader_content: &str, argument: Node, label: &str) -> bool {
+    get_name(shader_content, argument)
+        .rsplit(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .next()
+        .is_some_and(|name| name.eq_ignore_ascii_case(label))
+}
+
+struct InlayHintCollector<'a> {
+    symbol_tree: &'a SymbolTree,
+    symbol_list: &'a ShaderSymbolList,
+    options: &'a ShaderInlayHintOptions,
+    // Types of parameters & locals of the current function, declared or inferred.
+    local_types: HashMap<&'a str, String>,
+    hints: Vec<ShaderInlayHint>,
+}
+
+impl<'a> InlayHintCollector<'a> {
+    fn get_name(&self, node: Node) -> &'a str {
+        get_name(&self.symbol_tree.content, node)
+    }
+    fn get_position(&self, point: tree_sitter::Point) -> ShaderPosition {
+        ShaderPosition {
+            file_path: self.symbol_tree.file_path.clone(),
+            line: point.row as u32,
+            pos: point.column as u32,
+        }
+    }
+    // Function called with its arguments. Methods & constructors are skipped.
+    fn get_call(&self, call: Node<'a>) -> Option<(&'a str, Vec<Node<'a>>)> {
+        let (function, arguments) = match call.kind() {
+            "call_expression" => (
+                call.child_by_field_name("function")?,
+                call.child_by_field_name("arguments")?,
+            ),
+            // WGSL does not distinguish calls from constructors.
+            "type_constructor_or_function_call_expression" => {
+                let ty = call.named_child(0)?;
+                if ty.named_child_count() != 1 {
+                    return None;
+                }
+                (ty.named_child(0)?, call.named_child(1)?)
+            }
+            _ => return None,
+        };
+        if function.kind() != "identifier" {
+            return None;
+        }
+        let arguments = arguments
+            .named_children(&mut arguments.walk())
+            .filter(|argument| argument.kind() != "comment")
+            .collect();
+        Some((self.get_name(function), arguments))
+    }
+    // First overload accepting the arguments. Defines can shadow functions.
+    fn get_called_signature(
+        &self,
+        call: Node<'a>,
+    ) -> Option<(&'a ShaderSymbol, &'a ShaderSignature, Vec<Node<'a>>)> {
+        let (label, arguments) = self.get_call(call)?;
+        if self
+            .symbol_list
+            .constants
+            .iter()
+            .any(|symbol| symbol.label == label)
+        {
+            return None;
+        }
+        self.symbol_list
+            .functions
+            .iter()
+            .filter(|symbol| symbol.label == label)
+            .find_map(|symbol| match &symbol.data {
+                ShaderSymbolData::Functions { signatures, .. } => signatures
+                    .iter()
+                    .find(|signature| signature.accept_argument_count(arguments.len()))
+                    .map(|signature| (symbol, signature)),
+                _ => None,
+            })
+            .map(|(symbol, signature)| (symbol, signature, arguments))
+    }
+    fn get_global_type(&self, name: &str) -> Option<String> {
+        self.symbol_list
+            .constants
+            .iter()
+            .chain(self.symbol_list.variables.iter())
+            .filter(|symbol| symbol.label == name)
+            .find_map(|symbol| match &symbol.data {
+                ShaderSymbolData::Constants { ty, .. } | ShaderSymbolData::Variables { ty, .. }
+                    if !ty.is_empty() =>
+                {
+                    Some(ty.clone())
+                }
+                _ => None,
+            })
+    }
+    // Generic return types of intrinsics are deduced from the argument of the same type.
+    fn get_return_type(
+        &self,
+        symbol: &ShaderSymbol,
+        signature: &ShaderSignature,
+        arguments: &[Node<'a>],
+    ) -> Option<String> {
+        let return_type = &signature.returnType;
+        if return_type == "void" {
+            None
+        } else if symbol.range.is_some() || !is_generic_type(return_type) {
+            Some(return_type.clone())
+        } else {
+            signature
+                .parameters
+                .iter()
+                .zip(arguments)
+                .find(|(parameter, _)| parameter.ty == *return_type)
+                .and_then(|(_, argument)| self.infer_type(*argument))
+        }
+    }
+    fn infer_type(&self, expression: Node<'a>) -> Option<String> {
+        match expression.kind() {
+            "identifier" => {
+                let name = self.get_name(expression);
+                match self.local_types.get(name) {
+                    Some(ty) => Some(ty.clone()),
+                    None => self.get_global_type(name),
+                }
+            }
+            "const_literal" => get_literal_type(&self.symbol_tree.content, expression),
+            "parenthesized_expression" => self.infer_type(expression.named_child(0)?),
+            "binary_expression" => {
+                let operator = self.get_name(expression.child(1)?);
+                if COMPARISON_OPERATORS.contains(&operator) {
+                    return Some("bool".into());
+                }
+                let left = self.infer_type(expression.child_by_field_name("left")?)?;
+                let right = self.infer_type(expression.child_by_field_name("right")?)?;
+                (left == right).then_some(left)
+            }
+            "type_constructor_or_function_call_expression" => {
+                match self.get_called_signature(expression) {
+                    Some((symbol, signature, arguments)) => {
+                        self.get_return_type(symbol, signature, &arguments)
+                    }
+                    None if self.is_constructor(expression) => {
+                        Some(self.get_name(expression.named_child(0)?).into())
+                    }
+                    None => None,
+                }
+            }
+            _ => None,
+        }
+    }
+    // Builtin types such as vec3<f32> or known structs. Unknown identifiers might be functions.
+    fn is_constructor(&self, call: Node<'a>) -> bool {
+        if call.kind() != "type_constructor_or_function_call_expression" {
+            return false;
+        }
+        let Some(ty) = call.named_child(0) else {
+            return false;
+        };
+        match ty.named_child(0) {
+            Some(identifier)
+                if ty.named_child_count() == 1 && identifier.kind() == "identifier" =>
+            {
+                let name = self.get_name(identifier);
+                self.symbol_list
+                    .types
+                    .iter()
+                    .any(|symbol| symbol.label == name)
+            }
+            _ => true,
+        }
+    }
+    // Literals & constructors already show their type.
+    fn is_obvious(&self, expression: Node<'a>) -> bool {
+        expression.kind() == "const_literal" || self.is_constructor(expression)
+    }
+    fn visit_variable_statement(&mut self, statement: Node<'a>) {
+        // Typed declarations are handled with their variable_identifier_declaration.
+        let Some(name) = statement
+            .named_children(&mut statement.walk())
+            .find_map(|child| match child.kind() {
+                "identifier" => Some(child),
+                "variable_declaration" => child
+                    .named_child(0)
+                    .filter(|child| child.kind() == "identifier"),
+                _ => None,
+            })
+        else {
+            return;
+        };
+        let Some(initializer) = statement
+            .children(&mut statement.walk())
+            .skip_while(|child| child.kind() != "=")
+            .nth(1)
+        else {
+            return;
+        };
+        let Some(ty) = self.infer_type(initializer) else {
+            return;
+        };
+        if self.options.types && !self.is_obvious(initializer) {
+            self.hints.push(ShaderInlayHint {
+                position: self.get_position(name.end_position()),
+                label: format!(": {}", ty),
+                kind: ShaderInlayHintKind::Type,
+            });
+        }
+        self.local_types.insert(self.get_name(name), ty);
+    }
+    fn visit_call(&mut self, call: Node<'a>) {
+        if !self.options.parameters {
+            return;
+        }
+        let Some((_, signature, arguments)) = self.get_called_signature(call) else {
+            return;
+        };
+        for (parameter, argument) in signature.parameters.iter().zip(arguments) {
+            if parameter.is_variadic() {
+                break;
+            }
+            if parameter.label.is_empty()
+                || is_argument_named(&self.symbol_tree.content, argument, &parameter.label)
+            {
+                continue;
+            }
+            self.hints.push(ShaderInlayHint {
+                position: self.get_position(argument.start_position()),
+                label: format!("{}:", parameter.label),
+                kind: ShaderInlayHintKind::Parameter,
+            });
+        }
+    }
+    // Visit in source order, so that locals are declared before being used.
+    fn visit(&mut self, node: Node<'a>) {
+        match node.kind() {
+            "function_declaration" | "function_definition" => self.local_types.clear(),
+            "variable_identifier_declaration" => {
+                if let (Some(name), Some(ty)) = (
+                    node.child_by_field_name("name"),
+                    node.child_by_field_name("type"),
+                ) {
+                    self.local_types
+                        .insert(self.get_name(name), self.get_name(ty).into());
+                }
+            }
+            "variable_statement" => self.visit_variable_statement(node),
+            "call_expression" | "type_constructor_or_function_call_expression" => {
+                self.visit_call(node)
+            }
+            _ => {}
+        }
+        for child in node.named_children(&mut node.walk()) {
+            self.visit(child);
+        }
+    }
+}
+
+impl SymbolTree {
+    // Hints are computed from the current tree, so their positions follow edits.
+    pub fn get_inlay_hints(
+        &self,
+        symbol_list: &ShaderSymbolList,
+        options: &ShaderInlayHintOptions,
+    ) -> Vec<ShaderInlayHint> {
+        let mut collector = InlayHintCollector {
+            symbol_tree: self,
+            symbol_list,
+            options,
+            local_types: HashMap::new(),
+            hints: Vec::new(),
+        };
+        collector.visit(self.tree.root_node());
+        collector
+            .hints
+            .sort_by(|lhs, rhs| lhs.position.cmp(&rhs.position));
+        collector.hints
+    }
+}
@@ -0,0 +1,145 @@
+use std::path::Path;
+
+use regex::Regex;
+use tree_sitter::Node;
+
+use crate::{
+    shader_error::{ShaderDiagnosticList, ShaderErrorSeverity},
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    linter::{find_variable_type, node_diagnostic, SymbolTreeLinter},
+    parser::get_name,
+};
+
+const INTEGER_TYPES: [&str; 12] = [
+    "int",
+    "uint",
+    "dword",
+    "min16int",
+    "min12int",
+    "min16uint",
+    "int16_t",
+    "uint16_t",
+    "int32_t",
+    "uint32_t",
+    "int64_t",
+    "uint64_t",
+];
+
+pub(super) struct IntegerDivisionLinter {
+    integer_literal_regex: Regex,
+    float_type_regex: Regex,
+}
+
+impl IntegerDivisionLinter {
+    pub fn new() -> Self {
+        Self {
+            integer_literal_regex: Regex::new(r"^(?:0[xX][0-9a-fA-F]+|[0-9]+)[uUlL]*$").unwrap(),
+            float_type_regex: Regex::new(
+                r"^(?:(?:float|half|double|min16float|min10float|float16_t|float32_t|float64_t)[1-4]?|d?vec[2-4])$",
+            )
+            .unwrap(),
+        }
+    }
+    // Only literals & variables declared as integer, to stay conservative.
+    fn is_integer_operand(&self, shader_content: &str, operand: Node) -> bool {
+        match operand.kind() {
+            "number_literal" => self
+                .integer_literal_regex
+                .is_match(get_name(shader_content, operand)),
+            "identifier" => {
+                find_variable_type(shader_content, operand, get_name(shader_content, operand))
+                    .is_some_and(|ty| INTEGER_TYPES.contains(&ty))
+            }
+            _ => false,
+        }
+    }
+    // Variable name & type receiving the division, through a declaration or an assignment.
+    fn get_assigned_variable<'a>(
+        shader_content: &'a str,
+        division: Node,
+    ) -> Option<(&'a str, &'a str)> {
+        let mut expression = division;
+        let mut parent = division.parent()?;
+        while parent.kind() == "parenthesized_expression" {
+            expression = parent;
+            parent = parent.parent()?;
+        }
+        match parent.kind() {
+            "init_declarator" if parent.child_by_field_name("value") == Some(expression) => {
+                let declarator = parent.child_by_field_name("declarator")?;
+                let ty = parent.parent()?.child_by_field_name("type")?;
+                Some((
+                    get_name(shader_content, declarator),
+                    get_name(shader_content, ty),
+                ))
+            }
+            "assignment_expression" if parent.child_by_field_name("right") == Some(expression) => {
+                if get_name(shader_content, parent.child_by_field_name("operator")?) != "=" {
+                    return None;
+                }
+                let left = parent.child_by_field_name("left")?;
+                if left.kind() != "identifier" {
+                    return None;
+                }
+                let name = get_name(shader_content, left);
+                Some((name, find_variable_type(shader_content, left, name)?))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl SymbolTreeLinter for IntegerDivisionLinter {
+    fn get_code(&self) -> &'static str {
+        "integerDivision"
+    }
+    fn get_query(&self) -> String {
+        r#"(binary_expression operator: "/") @division"#.into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        if !params.lint_integer_division {
+            return;
+        }
+        let division = matches.captures[0].node;
+        let is_integer_division = ["left", "right"].iter().all(|field| {
+            division
+                .child_by_field_name(field)
+                .is_some_and(|operand| self.is_integer_operand(shader_content, operand))
+        });
+        if !is_integer_division {
+            return;
+        }
+        let Some((name, ty)) = Self::get_assigned_variable(shader_content, division) else {
+            return;
+        };
+        if !self.float_type_regex.is_match(ty) {
+            return;
+        }
+        let mut diagnostic = node_diagnostic(
+            division,
+            file_path,
+            ShaderErrorSeverity::Warning,
+            format!(
+                "Integer division '{}' is truncated before being converted to '{}' for '{}'. Use a floating point operand to keep the fractional part.",
+                get_name(shader_content, division),
+                ty,
+                name
+            ),
+        );
+        diagnostic.end = Some((
+            division.end_position().row as u32 + 1,
+            division.end_position().column as u32,
+        ));
+        diagnostics.push(diagnostic);
+    }
+}
@@ -0,0 +1,156 @@
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::{
+    shader::ShadingLanguage,
+    shader_error::{ShaderDiagnosticList, ShaderErrorSeverity},
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    evaluator::evaluate_integer_expression,
+    linter::{node_diagnostic, SymbolTreeLinter},
+    parser::get_name,
+};
+
+pub(super) struct LargeArrayLinter {
+    shading_language: ShadingLanguage,
+    type_regex: Regex,
+}
+
+impl LargeArrayLinter {
+    pub fn new(shading_language: ShadingLanguage) -> Self {
+        // Capture scalar type, then optional vector / matrix dimensions.
+        let type_regex = match shading_language {
+            ShadingLanguage::Hlsl => Regex::new(
+                r"^(bool|int|uint|dword|half|float|double|min16float|min10float|min16int|min12int|min16uint|int16_t|uint16_t|float16_t|int32_t|uint32_t|float32_t|int64_t|uint64_t|float64_t)(?:([1-4])(?:x([1-4]))?)?$",
+            ),
+            ShadingLanguage::Glsl => Regex::new(
+                r"^(?:(bool|int|uint|float|double)|(b|i|u|d)?vec([2-4])|(d)?mat([2-4])(?:x([2-4]))?)$",
+            ),
+            ShadingLanguage::Wgsl => Regex::new(r"^(bool|i32|u32|f32|f16)$"),
+        }
+        .unwrap();
+        Self {
+            shading_language,
+            type_regex,
+        }
+    }
+    fn get_scalar_size(scalar: &str) -> u64 {
+        match scalar {
+            "double" | "int64_t" | "uint64_t" | "float64_t" | "d" => 8,
+            "half" | "min16float" | "min10float" | "min16int" | "min12int" | "min16uint"
+            | "int16_t" | "uint16_t" | "float16_t" | "f16" => 2,
+            _ => 4,
+        }
+    }
+    // Get the size in bytes of a builtin type. None for user types.
+    fn get_type_size(&self, ty: &str) -> Option<u64> {
+        let capture = self.type_regex.captures(ty.trim())?;
+        let dimension = |index: usize| -> u64 {
+            capture
+                .get(index)
+                .and_then(|dimension| dimension.as_str().parse::<u64>().ok())
+                .unwrap_or(1)
+        };
+        match self.shading_language {
+            ShadingLanguage::Hlsl | ShadingLanguage::Wgsl => {
+                Some(Self::get_scalar_size(capture.get(1)?.as_str()) * dimension(2) * dimension(3))
+            }
+            ShadingLanguage::Glsl => {
+                if let Some(scalar) = capture.get(1) {
+                    Some(Self::get_scalar_size(scalar.as_str()))
+                } else if capture.get(3).is_some() {
+                    let scalar = capture.get(2).map_or("float", |scalar| scalar.as_str());
+                    Some(Self::get_scalar_size(scalar) * dimension(3))
+                } else {
+                    // matN is NxN, matNxM is NxM
+                    let scalar = capture.get(4).map_or("float", |scalar| scalar.as_str());
+                    let rows = match capture.get(6) {
+                        Some(_) => dimension(6),
+                        None => dimension(5),
+                    };
+                    Some(Self::get_scalar_size(scalar) * dimension(5) * rows)
+                }
+            }
+        }
+    }
+}
+
+impl SymbolTreeLinter for LargeArrayLinter {
+    fn get_code(&self) -> &'static str {
+        "largeArray"
+    }
+    fn get_query(&self) -> String {
+        r#"(declaration
+            type: (_) @array.type
+            declarator: [
+                (array_declarator) @array.declarator
+                (init_declarator declarator: (array_declarator) @array.declarator)
+            ]
+        )"#
+        .into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        let threshold = match params.large_array_threshold {
+            Some(threshold) => threshold as u64,
+            None => return,
+        };
+        let type_node = matches.captures[0].node;
+        let declarator_node = matches.captures[1].node;
+        // Only local arrays are allocated on the stack.
+        let mut is_local = false;
+        let mut parent = declarator_node.parent();
+        while let Some(node) = parent {
+            if node.kind() == "function_definition" {
+                is_local = true;
+                break;
+            }
+            parent = node.parent();
+        }
+        if !is_local {
+            return;
+        }
+        let mut size = match self.get_type_size(get_name(shader_content, type_node)) {
+            Some(size) => size,
+            None => return, // Struct or unknown type.
+        };
+        // Multi dimensional arrays are nested declarators.
+        let mut declarator = declarator_node;
+        while declarator.kind() == "array_declarator" {
+            let length = declarator
+                .child_by_field_name("size")
+                .and_then(|node| evaluate_integer_expression(shader_content, node, params))
+                .and_then(|length| u64::try_from(length).ok());
+            size = match length.and_then(|length| size.checked_mul(length)) {
+                Some(size) => size,
+                None => return, // Size not known at this point.
+            };
+            declarator = match declarator.child_by_field_name("declarator") {
+                Some(declarator) => declarator,
+                None => return,
+            };
+        }
+        if size > threshold {
+            diagnostics.push(node_diagnostic(
+                declarator,
+                file_path,
+                ShaderErrorSeverity::Warning,
+                format!(
+                    "Local array '{}' uses {} bytes, above the threshold of {} bytes. Large local arrays can cause register spilling.",
+                    get_name(shader_content, declarator),
+                    size,
+                    threshold
+                ),
+            ));
+        }
+    }
+}
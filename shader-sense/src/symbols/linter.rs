@@ -0,0 +1,134 @@
+use std::path::Path;
+
+use tree_sitter::{Node, QueryMatch};
+
+use crate::{
+    shader_error::{ShaderDiagnostic, ShaderDiagnosticList, ShaderErrorSeverity},
+    validator::validator::ValidationParams,
+};
+
+use super::parser::get_name;
+
+// Codes of diagnostics emitted outside of SymbolTreeLinter.
+pub(super) const SYNTAX_FIXES_CODE: &str = "syntaxFixes";
+pub(super) const CALL_ARGUMENTS_CODE: &str = "callArguments";
+pub(super) const REDEFINITION_CODE: &str = "redefinition";
+pub(super) const AMBIGUOUS_ENTRY_POINT_CODE: &str = "ambiguousEntryPoint";
+
+pub trait SymbolTreeLinter {
+    // Code set on emitted diagnostics, named after the lint setting enabling it if any.
+    fn get_code(&self) -> &'static str;
+    // The query to match tree node
+    fn get_query(&self) -> String;
+    // Process the match & emit diagnostics for it
+    fn process_match(
+        &self,
+        matches: QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    );
+}
+
+// Set the code of diagnostics emitted since the given index.
+pub(super) fn set_diagnostics_code(
+    diagnostics: &mut ShaderDiagnosticList,
+    first_diagnostic: usize,
+    code: &str,
+) {
+    for diagnostic in &mut diagnostics.diagnostics[first_diagnostic..] {
+        diagnostic.code = Some(code.into());
+    }
+}
+
+// Create a diagnostic located at the start of the given node.
+// Tree sitter rows are 0 based while diagnostics lines are 1 based.
+pub(super) fn node_diagnostic(
+    node: Node,
+    file_path: &Path,
+    severity: ShaderErrorSeverity,
+    error: String,
+) -> ShaderDiagnostic {
+    ShaderDiagnostic {
+        file_path: Some(file_path.into()),
+        severity,
+        error,
+        line: node.start_position().row as u32 + 1,
+        pos: node.start_position().column as u32,
+        end: None,
+        unnecessary: false,
+        code: None,
+        fix: None,
+    }
+}
+
+// Find the declaration & declarator of a variable visible from the given node by walking up the tree.
+// Only declarations located before the node are considered.
+pub(super) fn find_variable_declaration<'a>(
+    shader_content: &str,
+    node: Node<'a>,
+    variable: &str,
+) -> Option<(Node<'a>, Node<'a>)> {
+    fn get_declarator_name<'a>(shader_content: &'a str, declarator: Node) -> Option<&'a str> {
+        match declarator.kind() {
+            "identifier" => Some(get_name(shader_content, declarator)),
+            "init_declarator" | "array_declarator" => get_declarator_name(
+                shader_content,
+                declarator.child_by_field_name("declarator")?,
+            ),
+            _ => None,
+        }
+    }
+    fn find_declarator<'a>(
+        shader_content: &str,
+        declaration: Node<'a>,
+        variable: &str,
+    ) -> Option<Node<'a>> {
+        declaration
+            .children_by_field_name("declarator", &mut declaration.walk())
+            .find(|declarator| get_declarator_name(shader_content, *declarator) == Some(variable))
+    }
+    let mut current = node;
+    while let Some(parent) = current.parent() {
+        if parent.kind() == "function_definition" {
+            let parameters = parent
+                .child_by_field_name("declarator")
+                .and_then(|declarator| declarator.child_by_field_name("parameters"));
+            if let Some(parameters) = parameters {
+                for parameter in parameters.named_children(&mut parameters.walk()) {
+                    if parameter.kind() == "parameter_declaration" {
+                        if let Some(declarator) =
+                            find_declarator(shader_content, parameter, variable)
+                        {
+                            return Some((parameter, declarator));
+                        }
+                    }
+                }
+            }
+        }
+        for child in parent.named_children(&mut parent.walk()) {
+            if child.start_byte() >= current.start_byte() {
+                break;
+            }
+            if child.kind() == "declaration" {
+                if let Some(declarator) = find_declarator(shader_content, child, variable) {
+                    return Some((child, declarator));
+                }
+            }
+        }
+        current = parent;
+    }
+    None
+}
+
+// Find the declared type of a variable visible from the given node.
+pub(super) fn find_variable_type<'a>(
+    shader_content: &'a str,
+    node: Node,
+    variable: &str,
+) -> Option<&'a str> {
+    let (declaration, _) = find_variable_declaration(shader_content, node, variable)?;
+    let ty = declaration.child_by_field_name("type")?;
+    Some(get_name(shader_content, ty))
+}
@@ -1,13 +1,38 @@
+mod array_size_linter;
+mod block_layout_linter;
+mod call_argument_linter;
+mod control_flow;
+mod evaluator;
+mod formatter;
 mod glsl_filter;
 mod glsl_parser;
 mod hlsl_filter;
 mod hlsl_parser;
+mod inlay_hint;
+mod integer_division_linter;
+mod large_array_linter;
+mod linter;
+mod non_uniform_sampling_linter;
+mod out_parameter_linter;
 mod parser;
+mod preprocessor;
+mod redefinition_linter;
+mod reserved_identifier_linter;
+mod resource_limit_linter;
+mod swizzle_linter;
+mod symbol_tree_cache;
 pub mod symbols;
+mod syntax_fix_linter;
+mod uniformity;
+mod unreachable_code_linter;
 mod wgsl_filter;
 mod wgsl_parser;
 
+pub use formatter::ShaderFormattingOptions;
+pub use inlay_hint::{ShaderInlayHint, ShaderInlayHintKind, ShaderInlayHintOptions};
 pub use parser::SymbolTree;
+pub use preprocessor::{get_shader_pragmas, ShaderPragma};
+pub use symbol_tree_cache::DEFAULT_SYMBOL_TREE_CACHE_CAPACITY;
 use symbols::SymbolProvider;
 
 use crate::shader::ShadingLanguage;
@@ -19,55 +44,41 @@ pub fn create_symbol_provider(shading_language: ShadingLanguage) -> SymbolProvid
 #[cfg(test)]
 mod tests {
     use std::{
-        collections::HashSet,
+        collections::HashMap,
         path::{Path, PathBuf},
     };
 
-    use regex::Regex;
-
     use crate::{
-        include::IncludeHandler, shader::ShadingLanguage, symbols::symbols::ShaderPosition,
-        validator::validator::ValidationParams,
+        include::IncludeHandler,
+        shader::{GlslProfile, GlslTargetClient, HlslShaderModel, ShaderStage, ShadingLanguage},
+        shader_error::ShaderErrorSeverity,
+        symbols::symbols::{ShaderPosition, ShaderRange},
+        validator::validator::{ResourceLimits, ValidationParams},
     };
 
-    use super::symbols::{parse_default_shader_intrinsics, ShaderSymbolList, SymbolProvider};
+    use super::{
+        glsl_filter::GlslVersionFilter,
+        hlsl_filter::HlslShaderModelFilter,
+        symbols::{
+            parse_default_shader_intrinsics, ShaderSymbol, ShaderSymbolData, ShaderSymbolList,
+            SymbolFilter, SymbolProvider,
+        },
+        ShaderFormattingOptions,
+    };
 
-    pub fn find_file_dependencies(
-        include_handler: &mut IncludeHandler,
-        shader_content: &String,
-    ) -> Vec<PathBuf> {
-        let include_regex = Regex::new("\\#include\\s+\"([\\w\\s\\\\/\\.\\-]+)\"").unwrap();
-        let dependencies_paths: Vec<&str> = include_regex
-            .captures_iter(&shader_content)
-            .map(|c| c.get(1).unwrap().as_str())
-            .collect();
-        dependencies_paths
-            .iter()
-            .filter_map(|dependency| include_handler.search_path_in_includes(Path::new(dependency)))
-            .collect::<Vec<PathBuf>>()
-    }
     pub fn find_dependencies(
         include_handler: &mut IncludeHandler,
-        shader_content: &String,
-    ) -> HashSet<(String, PathBuf)> {
-        let dependencies_path = find_file_dependencies(include_handler, shader_content);
-        let dependencies = dependencies_path
-            .into_iter()
-            .map(|e| (std::fs::read_to_string(&e).unwrap(), e))
-            .collect::<Vec<(String, PathBuf)>>();
-
-        // Use hashset to avoid computing dependencies twice.
-        let mut recursed_dependencies = HashSet::new();
-        for dependency in &dependencies {
-            recursed_dependencies.extend(find_dependencies(include_handler, &dependency.0));
-        }
-        recursed_dependencies.extend(dependencies);
-
-        recursed_dependencies
+        shader_content: &str,
+    ) -> Vec<(String, PathBuf)> {
+        include_handler
+            .find_dependencies(shader_content, &mut |path| {
+                std::fs::read_to_string(path).ok()
+            })
+            .0
     }
 
     fn load_file(symbol_provider: &mut SymbolProvider, file_path: &Path, shader_content: &String) {
-        let mut include_handler = IncludeHandler::new(file_path, vec![]);
+        let mut include_handler = IncludeHandler::new(file_path, vec![], HashMap::new());
         let deps = find_dependencies(&mut include_handler, &shader_content);
         symbol_provider
             .create_ast(file_path, &shader_content)
@@ -81,7 +92,7 @@ mod tests {
         file_path: &Path,
         shader_content: &String,
     ) -> ShaderSymbolList {
-        let mut include_handler = IncludeHandler::new(&file_path, vec![]);
+        let mut include_handler = IncludeHandler::new(&file_path, vec![], HashMap::new());
         let deps = find_dependencies(&mut include_handler, &shader_content);
         let mut symbols = symbol_provider.get_intrinsics_symbol().clone();
         let symbol_tree = symbol_provider
@@ -116,7 +127,145 @@ mod tests {
     #[test]
     fn intrinsics_wgsl_ok() {
         // Ensure parsing of intrinsics is OK
-        let _ = parse_default_shader_intrinsics(ShadingLanguage::Wgsl);
+        let intrinsics = parse_default_shader_intrinsics(ShadingLanguage::Wgsl);
+        assert!(!intrinsics.functions.is_empty());
+        assert!(!intrinsics.types.is_empty());
+        assert!(!intrinsics.keywords.is_empty());
+        for label in [
+            "textureSample",
+            "dot",
+            "workgroupBarrier",
+            "vec4f",
+            "texture_2d",
+        ] {
+            assert!(
+                intrinsics.find_symbol(&label.into()).is_some(),
+                "Missing intrinsic {}",
+                label
+            );
+        }
+    }
+    #[test]
+    fn intrinsics_hlsl_shader_model() {
+        let intrinsics = parse_default_shader_intrinsics(ShadingLanguage::Hlsl);
+        let wave_symbol = intrinsics.find_symbol(&"WaveGetLaneCount".into()).unwrap();
+        let mut symbols = ShaderSymbolList::default();
+        for (label, version) in [("WaveGetLaneCount", "sm6"), ("IsHelperLane", "sm6.6")] {
+            symbols.functions.push(ShaderSymbol {
+                label: label.into(),
+                version: version.into(),
+                ..wave_symbol.clone()
+            });
+        }
+        let get_labels = |hlsl_shader_model: HlslShaderModel| -> Vec<String> {
+            let mut symbols = symbols.clone();
+            HlslShaderModelFilter {}.filter_symbols(
+                &mut symbols,
+                &"shader.hlsl".into(),
+                &ValidationParams {
+                    hlsl_shader_model,
+                    ..Default::default()
+                },
+            );
+            symbols.functions.into_iter().map(|s| s.label).collect()
+        };
+        assert_eq!(
+            get_labels(HlslShaderModel::ShaderModel6_5),
+            vec!["WaveGetLaneCount".to_string()]
+        );
+        assert_eq!(
+            get_labels(HlslShaderModel::ShaderModel6_6),
+            vec!["WaveGetLaneCount".to_string(), "IsHelperLane".to_string()]
+        );
+    }
+    #[test]
+    fn intrinsics_glsl_version() {
+        let intrinsics = parse_default_shader_intrinsics(ShadingLanguage::Glsl);
+        let sin_symbol = intrinsics.find_symbol(&"sin".into()).unwrap();
+        let mut symbols = ShaderSymbolList::default();
+        for (label, version) in [
+            ("sin", "110, 100 es"),
+            ("fma", "400, 320 es"),
+            ("fwidthFine", "450"),
+            ("texture2D", "110"),
+            ("textureGatherOffsets", "400"),
+        ] {
+            symbols.functions.push(ShaderSymbol {
+                label: label.into(),
+                version: version.into(),
+                ..sin_symbol.clone()
+            });
+        }
+        let get_labels = |glsl_version: u32, glsl_profile: Option<GlslProfile>| -> Vec<String> {
+            let mut symbols = symbols.clone();
+            GlslVersionFilter {}.filter_symbols(
+                &mut symbols,
+                &"shader.frag.glsl".into(),
+                &ValidationParams {
+                    glsl_version: Some(glsl_version),
+                    glsl_profile,
+                    ..Default::default()
+                },
+            );
+            symbols.functions.into_iter().map(|s| s.label).collect()
+        };
+        assert_eq!(
+            get_labels(330, None),
+            vec!["sin".to_string(), "texture2D".to_string()]
+        );
+        assert_eq!(get_labels(450, None).len(), 5);
+        // Intrinsics without ES version are kept, as intrinsics might only have desktop versions.
+        assert_eq!(
+            get_labels(310, Some(GlslProfile::Es)),
+            vec![
+                "sin".to_string(),
+                "fwidthFine".to_string(),
+                "texture2D".to_string(),
+                "textureGatherOffsets".to_string()
+            ]
+        );
+        assert_eq!(get_labels(320, Some(GlslProfile::Es)).len(), 5);
+        // The #version directive has priority over the configured version.
+        let mut symbol_provider = SymbolProvider::glsl();
+        let file_path = Path::new("./test/glsl/version.frag.glsl");
+        let has_fma = |symbol_provider: &mut SymbolProvider, shader_content: &str| -> bool {
+            let symbol_tree = symbol_provider
+                .create_ast(file_path, shader_content)
+                .unwrap();
+            symbol_provider
+                .get_file_intrinsics_symbol(
+                    &symbol_tree,
+                    &ValidationParams {
+                        glsl_version: Some(450),
+                        ..Default::default()
+                    },
+                )
+                .find_symbol(&"fma".into())
+                .is_some()
+        };
+        assert!(!has_fma(
+            &mut symbol_provider,
+            "#version 330\nvoid main() {}\n"
+        ));
+        assert!(has_fma(&mut symbol_provider, "void main() {}\n"));
+    }
+    #[test]
+    fn symbols_hlsl_2021_logical() {
+        let intrinsics = parse_default_shader_intrinsics(ShadingLanguage::Hlsl);
+        for label in ["and", "or", "select"] {
+            assert!(
+                intrinsics.find_symbol(&label.into()).is_some(),
+                "Missing intrinsic {}",
+                label
+            );
+        }
+        let file_path = Path::new("./test/hlsl/hlsl2021-logical.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        assert!(!symbol_tree.tree.root_node().has_error());
     }
     #[test]
     fn symbols_glsl_ok() {
@@ -147,6 +296,348 @@ mod tests {
         }
     }
     #[test]
+    fn symbols_ast_cache() {
+        // Stable includes should only be parsed once.
+        let file_path = Path::new("./test/glsl/include-once.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::glsl();
+        get_all_symbols(&mut symbol_provider, file_path, &shader_content);
+        let (first_hits, parse_count) = symbol_provider.get_ast_cache_stats();
+        for _ in 0..10 {
+            get_all_symbols(&mut symbol_provider, file_path, &shader_content);
+        }
+        let (hits, misses) = symbol_provider.get_ast_cache_stats();
+        assert!(parse_count >= 3);
+        assert_eq!(misses, parse_count);
+        assert_eq!(hits, first_hits + 10 * (first_hits + parse_count));
+        // Changed file on disk is parsed again.
+        symbol_provider.invalidate_ast_cache(file_path);
+        get_all_symbols(&mut symbol_provider, file_path, &shader_content);
+        assert_eq!(symbol_provider.get_ast_cache_stats().1, parse_count + 1);
+        // Disabled cache parse everything.
+        symbol_provider.set_ast_cache_capacity(0);
+        get_all_symbols(&mut symbol_provider, file_path, &shader_content);
+        assert_eq!(
+            symbol_provider.get_ast_cache_stats().1,
+            2 * parse_count + first_hits + 1
+        );
+    }
+    #[test]
+    fn symbols_glsl_specialization_constant() {
+        let file_path = Path::new("./test/glsl/specialization-constant.comp.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::glsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let get_constant = |params: &ValidationParams| -> ShaderSymbol {
+            let symbols = symbol_provider
+                .get_all_symbols(&symbol_tree, params)
+                .unwrap();
+            assert!(!symbols.variables.iter().any(|v| v.label == "GROUP_COUNT"));
+            symbols
+                .constants
+                .into_iter()
+                .find(|constant| constant.label == "GROUP_COUNT")
+                .expect("Missing specialization constant")
+        };
+        let constant = get_constant(&ValidationParams::default());
+        assert_eq!(constant.description, "Specialization constant (id: 3).");
+        assert_eq!(
+            constant.format(),
+            "layout(constant_id = 3) const int GROUP_COUNT = 4;"
+        );
+        // Value can be overriden by id.
+        let params = ValidationParams {
+            specialization_constants: HashMap::from([(3, "8".into())]),
+            ..Default::default()
+        };
+        match get_constant(&params).data {
+            ShaderSymbolData::Constants { value, .. } => assert_eq!(value, "8"),
+            _ => panic!("Specialization constant should be a constant"),
+        }
+    }
+    #[test]
+    fn symbols_hlsl_inactive_branch() {
+        let file_path = Path::new("./test/hlsl/inactive-branch.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let get_functions = |params: &ValidationParams| -> Vec<String> {
+            symbol_provider
+                .get_all_symbols(&symbol_tree, params)
+                .unwrap()
+                .functions
+                .into_iter()
+                .filter(|function| function.range.is_some())
+                .filter(|function| !matches!(function.data, ShaderSymbolData::Macro { .. }))
+                .map(|function| function.label)
+                .collect()
+        };
+        assert_eq!(
+            get_functions(&ValidationParams::default()),
+            vec!["sampleMedium", "skipFog", "main"]
+        );
+        // Branches follow the define map.
+        let params = ValidationParams {
+            defines: HashMap::from([("USE_FOG".into(), "".into())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_functions(&params),
+            vec!["sampleMedium", "applyFog", "fogShadow", "main"]
+        );
+        // Macros might come from includes, so their branches are kept.
+        let shader_content = "#include \"config.hlsl\"\n\
+                              #ifdef FROM_INCLUDE\n\
+                              void included() {}\n\
+                              #endif\n";
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, shader_content)
+            .unwrap();
+        let symbols = symbol_provider
+            .get_all_symbols(&symbol_tree, &ValidationParams::default())
+            .unwrap();
+        assert!(symbols.find_symbol(&"included".into()).is_some());
+    }
+    #[test]
+    fn symbols_glsl_include_defines() {
+        let file_path = Path::new("./test/glsl/include-umbrella.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::glsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let mut include_provider = SymbolProvider::glsl();
+        let mut included_files = Vec::new();
+        let symbols = symbol_provider
+            .get_all_symbols_with_includes(
+                &symbol_tree,
+                &ValidationParams::default(),
+                &mut |include_path: &Path| {
+                    included_files.push(
+                        include_path
+                            .file_name()
+                            .unwrap()
+                            .to_string_lossy()
+                            .to_string(),
+                    );
+                    let content = std::fs::read_to_string(include_path).ok()?;
+                    include_provider.create_ast(include_path, &content).ok()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            included_files,
+            vec!["umbrella.glsl", "features.glsl", "lighting.glsl"]
+        );
+        // Define of the earlier include select the branch of the later one.
+        assert!(symbols.find_symbol(&"computeLighting".into()).is_some());
+        assert!(symbols.find_symbol(&"computeUnlit".into()).is_none());
+        // Alone, the header does not know about the define.
+        let file_path = Path::new("./test/glsl/inc0/lighting.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let symbols = symbol_provider
+            .get_all_symbols(&symbol_tree, &ValidationParams::default())
+            .unwrap();
+        assert!(symbols.find_symbol(&"computeLighting".into()).is_none());
+        assert!(symbols.find_symbol(&"computeUnlit".into()).is_some());
+    }
+    #[test]
+    fn symbols_glsl_runtime_array() {
+        let file_path = Path::new("./test/glsl/runtime-array.comp.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::glsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        assert!(!symbol_tree.tree.root_node().has_error());
+        let symbols = symbol_provider
+            .get_all_symbols(&symbol_tree, &ValidationParams::default())
+            .unwrap();
+        // Block is a type, its members are resolved through the instance or globally without one.
+        let block = symbols
+            .types
+            .iter()
+            .find(|ty| ty.label == "Particles")
+            .expect("Missing block type");
+        match &block.data {
+            ShaderSymbolData::Struct { members, .. } => {
+                assert_eq!(members[1].label, "particles");
+                assert_eq!(members[1].ty, "Particle");
+            }
+            _ => panic!("Block should be a struct"),
+        }
+        let find_variable_type = |label: &str| match &symbols
+            .variables
+            .iter()
+            .find(|variable| variable.label == label)
+            .unwrap_or_else(|| panic!("Missing variable {}", label))
+            .data
+        {
+            ShaderSymbolData::Variables { ty, .. } => ty.clone(),
+            _ => panic!("{} should be a variable", label),
+        };
+        assert_eq!(find_variable_type("particleBuffer"), "Particles");
+        assert_eq!(find_variable_type("values"), "float");
+    }
+    #[test]
+    fn symbols_incremental_update() {
+        // Symbols of incrementally updated tree should match a full reparse.
+        let file_path = Path::new("/shaders/incremental.hlsl");
+        let mut shader_content = "struct Light {\n\
+                                  \x20   float3 position;\n\
+                                  };\n\
+                                  float4 main() : SV_TARGET {\n\
+                                  \x20   float value = 0.0;\n\
+                                  \x20   return float4(value, 0.0, 0.0, 1.0);\n\
+                                  }\n\
+                                  float other(float param) {\n\
+                                  \x20   float other_value = param;\n\
+                                  \x20   return other_value;\n\
+                                  }\n"
+        .to_string();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let mut symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let position = |line: u32, pos: u32| ShaderPosition {
+            file_path: file_path.into(),
+            line,
+            pos,
+        };
+        let edits = [
+            // Insert lines inside struct.
+            (
+                position(1, 20),
+                position(1, 20),
+                "\n    float intensity;\n    float radius;",
+            ),
+            // Rename a local on a single line.
+            (position(6, 10), position(6, 15), "color"),
+            // Remove whole lines.
+            (position(3, 0), position(4, 0), ""),
+            // Append at the end of the file.
+            (
+                position(12, 0),
+                position(12, 0),
+                "float helper() {\n    return 1.0;\n}\n",
+            ),
+        ];
+        for (start, end, text) in edits {
+            let mut new_content = shader_content.clone();
+            new_content.replace_range(
+                start.to_byte_offset(&shader_content)..end.to_byte_offset(&shader_content),
+                text,
+            );
+            symbol_provider
+                .update_ast(
+                    &mut symbol_tree,
+                    &shader_content,
+                    &new_content,
+                    &ShaderRange::new(start, end),
+                    &text.to_string(),
+                )
+                .unwrap();
+            shader_content = new_content;
+        }
+        let format_symbols = |symbols: ShaderSymbolList| -> Vec<String> {
+            symbols
+                .iter()
+                .flat_map(|(symbols, _)| symbols.iter())
+                .map(|symbol| format!("{} {:?}", symbol.label, symbol.range))
+                .collect()
+        };
+        let full_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        assert!(shader_content.ends_with("}\nfloat helper() {\n    return 1.0;\n}\n"));
+        assert_eq!(symbol_tree.content, full_tree.content);
+        assert_eq!(symbol_tree.dump_ast(), full_tree.dump_ast());
+        let symbols = symbol_provider
+            .get_all_symbols(&symbol_tree, &ValidationParams::default())
+            .unwrap();
+        assert!(symbols.functions.iter().any(|f| f.label == "helper"));
+        assert!(symbols.variables.iter().any(|v| v.label == "color"));
+        assert_eq!(
+            format_symbols(symbols),
+            format_symbols(
+                symbol_provider
+                    .get_all_symbols(&full_tree, &ValidationParams::default())
+                    .unwrap()
+            )
+        );
+    }
+    #[test]
+    fn symbols_virtual_include() {
+        // Include link should resolve virtual paths for goto.
+        let file_path = Path::new("./test/glsl/include-virtual.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::glsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let params = ValidationParams {
+            virtual_includes: HashMap::from([(
+                "/Engine".into(),
+                PathBuf::from("./test/glsl/inc0/"),
+            )]),
+            ..Default::default()
+        };
+        let symbols = symbol_provider
+            .get_all_symbols(&symbol_tree, &params)
+            .unwrap();
+        let link = symbols
+            .functions
+            .iter()
+            .find(|symbol| symbol.label == "/Engine/inc1/level1.glsl")
+            .expect("Missing virtual include link");
+        match &link.data {
+            ShaderSymbolData::Link { target } => assert_eq!(
+                target.file_path,
+                crate::include::canonicalize(Path::new("./test/glsl/inc0/inc1/level1.glsl"))
+                    .unwrap()
+            ),
+            _ => panic!("Include should be a link"),
+        }
+    }
+    #[test]
+    fn symbols_shared_header() {
+        // Shared header should be parsed with the language of the including file.
+        let glsl_file_path = Path::new("./test/glsl/include-shared.frag.glsl");
+        let glsl_content = std::fs::read_to_string(glsl_file_path).unwrap();
+        let mut glsl_symbol_provider = SymbolProvider::glsl();
+        let glsl_symbols =
+            get_all_symbols(&mut glsl_symbol_provider, glsl_file_path, &glsl_content);
+        let hlsl_file_path = Path::new("./test/hlsl/include-shared.hlsl");
+        let hlsl_content = std::fs::read_to_string(hlsl_file_path).unwrap();
+        let mut hlsl_symbol_provider = SymbolProvider::hlsl();
+        let hlsl_symbols =
+            get_all_symbols(&mut hlsl_symbol_provider, hlsl_file_path, &hlsl_content);
+        for (symbols, shading_language) in [
+            (glsl_symbols, ShadingLanguage::Glsl),
+            (hlsl_symbols, ShadingLanguage::Hlsl),
+        ] {
+            assert!(symbols.find_symbol(&"shared_attenuation".into()).is_some());
+            let define = symbols
+                .find_symbol(&"SHARED_LIGHT_COUNT".into())
+                .expect("Failed to find macro from shared header");
+            // Macro description is formatted for the parsing language.
+            assert!(
+                define
+                    .description
+                    .contains(&format!("```{}", shading_language.to_string())),
+                "{:#?}",
+                define
+            );
+        }
+    }
+    #[test]
     fn symbols_wgsl_ok() {
         // Ensure parsing of symbols is OK
         let file_path = Path::new("./test/wgsl/ok.wgsl");
@@ -156,11 +647,756 @@ mod tests {
             .create_ast(file_path, &shader_content)
             .unwrap();
         match symbol_provider.get_all_symbols(&symbol_tree, &ValidationParams::default()) {
-            Ok(symbols) => assert!(symbols.functions.is_empty()),
+            Ok(symbols) => assert!(!symbols.functions.is_empty()),
             Err(error) => panic!("Failed to get_all_symbols: {:#?}", error),
         }
     }
     #[test]
+    fn lint_large_array_hlsl() {
+        let file_path = Path::new("./test/hlsl/large-array.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let params = ValidationParams {
+            large_array_threshold: Some(8192),
+            ..Default::default()
+        };
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &params)
+            .unwrap()
+            .diagnostics;
+        // Only data[4096] (16384 bytes) is above threshold.
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 6);
+        assert!(diagnostics[0].error.contains("'data'"));
+        // Disabled by default
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &ValidationParams::default())
+            .unwrap()
+            .diagnostics;
+        assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+    }
+    #[test]
+    fn lint_resource_limits_hlsl() {
+        let file_path = Path::new("./test/hlsl/resource-limit.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let params = ValidationParams {
+            resource_limits: Some(ResourceLimits {
+                textures: 3,
+                samplers: 16,
+                uavs: 8,
+            }),
+            ..Default::default()
+        };
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &params)
+            .unwrap()
+            .diagnostics;
+        // normals array use 2 slots, so shadow is the fourth texture.
+        assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 8);
+        assert!(diagnostics[0].error.contains("'shadow'"));
+        assert!(diagnostics[0].error.contains("texture count to 4"));
+        assert_eq!(diagnostics[1].line, 9);
+        assert!(diagnostics[1].error.contains("'history' is bound to u8-u8"));
+        // Disabled by default
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &ValidationParams::default())
+            .unwrap()
+            .diagnostics;
+        assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+    }
+
+    #[test]
+    fn lint_array_size_hlsl() {
+        let file_path = Path::new("./test/hlsl/array-size.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &ValidationParams::default())
+            .unwrap()
+            .diagnostics;
+        // Macros & const variables are constant, uniform & local variables are not.
+        assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 8);
+        assert!(matches!(
+            diagnostics[0].severity,
+            ShaderErrorSeverity::Error
+        ));
+        assert!(diagnostics[0].error.contains("'dynamicCount'"));
+        assert_eq!(diagnostics[1].line, 14);
+        assert!(diagnostics[1].error.contains("'count'"));
+    }
+
+    #[test]
+    fn lint_call_arguments_hlsl() {
+        let file_path = Path::new("./test/hlsl/call-arguments.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let params = ValidationParams {
+            lint_call_arguments: true,
+            ..Default::default()
+        };
+        let mut symbol_list = symbol_provider
+            .get_all_symbols(&symbol_tree, &params)
+            .unwrap();
+        symbol_list.append(parse_default_shader_intrinsics(ShadingLanguage::Hlsl));
+        let diagnostics = symbol_provider
+            .get_call_diagnostics(&symbol_tree, &symbol_list, &params)
+            .diagnostics;
+        // Optional, variadic parameters, macros & constructors are not reported.
+        assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 12);
+        assert!(diagnostics[0].error.contains("'max' expects 2"));
+        assert_eq!(diagnostics[1].line, 17);
+        assert!(diagnostics[1].error.contains("'weight' expects 2"));
+        assert!(symbol_provider
+            .get_call_diagnostics(&symbol_tree, &symbol_list, &ValidationParams::default())
+            .diagnostics
+            .is_empty());
+    }
+
+    #[test]
+    fn hlsl_interface() {
+        let file_path = Path::new("./test/hlsl/interface.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let symbols = symbol_provider
+            .get_all_symbols(&symbol_tree, &ValidationParams::default())
+            .unwrap();
+        let interface = symbols
+            .types
+            .iter()
+            .find(|ty| ty.label == "ILight")
+            .expect("Missing interface");
+        assert_eq!(interface.format(), "interface ILight");
+        match &interface.data {
+            ShaderSymbolData::Interface { methods } => {
+                let labels: Vec<&str> =
+                    methods.iter().map(|method| method.label.as_str()).collect();
+                assert_eq!(labels, vec!["illuminate", "getIntensity"]);
+                assert_eq!(methods[0].signature.returnType, "float3");
+                assert_eq!(methods[0].signature.parameters[1].label, "normal");
+            }
+            _ => panic!("ILight should be an interface"),
+        }
+        // Requirements are not global functions.
+        assert!(!symbols
+            .functions
+            .iter()
+            .any(|function| function.label == "ILight"));
+        // Associated types & generic parameters are scoped types with their constraints.
+        let format_type = |label: &str| -> Option<String> {
+            symbols
+                .types
+                .iter()
+                .find(|ty| ty.label == label)
+                .map(|ty| ty.format())
+        };
+        assert_eq!(format_type("Falloff").as_deref(), Some("Falloff : IBRDF"));
+        assert!(!symbols
+            .variables
+            .iter()
+            .any(|variable| variable.label == "Falloff"));
+        assert_eq!(format_type("T").as_deref(), Some("T : ILight"));
+        assert!(symbols
+            .functions
+            .iter()
+            .any(|function| function.label == "shade"));
+        let scoped_symbols = symbols.filter_scoped_symbol(ShaderPosition {
+            file_path: file_path.into(),
+            line: 21,
+            pos: 4,
+        });
+        assert!(scoped_symbols.find_type_symbol(&"T".into()).is_some());
+        assert!(scoped_symbols.find_symbol(&"light".into()).is_some());
+        let global_symbols = symbols.filter_scoped_symbol(ShaderPosition {
+            file_path: file_path.into(),
+            line: 26,
+            pos: 4,
+        });
+        assert!(global_symbols.find_type_symbol(&"T".into()).is_none());
+        assert!(global_symbols.find_type_symbol(&"Falloff".into()).is_none());
+    }
+
+    #[test]
+    fn lint_redefinitions() {
+        let file_path = Path::new("./test/hlsl/redefinition.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let params = ValidationParams {
+            lint_redefinitions: true,
+            ..Default::default()
+        };
+        let mut include_provider = SymbolProvider::hlsl();
+        let symbol_list = symbol_provider
+            .get_all_symbols_with_includes(&symbol_tree, &params, &mut |include_path: &Path| {
+                let content = std::fs::read_to_string(include_path).ok()?;
+                include_provider.create_ast(include_path, &content).ok()
+            })
+            .unwrap();
+        let diagnostics = symbol_provider
+            .get_redefinition_diagnostics(&symbol_tree, &symbol_list, &params)
+            .diagnostics;
+        // Overloads & branches of a condition are not reported.
+        assert_eq!(diagnostics.len(), 3, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 4);
+        assert_eq!(
+            diagnostics[0].error,
+            "'Light' is already defined at redefinition.hlsl:4:8."
+        );
+        assert_eq!(diagnostics[1].line, 8);
+        assert_eq!(
+            diagnostics[1].error,
+            "'shade' is already defined at redefinition.hlsl:8:8."
+        );
+        assert_eq!(diagnostics[2].line, 18);
+        assert_eq!(diagnostics[2].pos, 6);
+        assert_eq!(
+            diagnostics[2].error,
+            "'scale' is already defined at redefinition.hlsl:17:7."
+        );
+        assert_eq!(diagnostics[2].code.as_deref(), Some("redefinition"));
+        assert!(symbol_provider
+            .get_redefinition_diagnostics(&symbol_tree, &symbol_list, &ValidationParams::default())
+            .diagnostics
+            .is_empty());
+    }
+
+    #[test]
+    fn ambiguous_entry_point() {
+        let file_path = Path::new("./test/hlsl/ambiguous-entry-point.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let params = ValidationParams {
+            lint_ambiguous_entry_points: true,
+            ..Default::default()
+        };
+        let diagnostics = symbol_provider
+            .get_entry_point_diagnostics(&symbol_tree, &params)
+            .diagnostics;
+        // Only the vertex stage is ambiguous.
+        assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 7);
+        assert_eq!(diagnostics[0].pos, 9);
+        assert_eq!(diagnostics[1].line, 15);
+        assert_eq!(
+            diagnostics[1].error,
+            "Ambiguous vertex entry point between 'vsMain', 'vsShadow', specify the entry point to validate."
+        );
+        assert_eq!(diagnostics[1].code.as_deref(), Some("ambiguousEntryPoint"));
+        assert!(symbol_provider
+            .get_entry_point_diagnostics(
+                &symbol_tree,
+                &ValidationParams {
+                    entry_point: Some("vsShadow".into()),
+                    ..params
+                }
+            )
+            .diagnostics
+            .is_empty());
+        // Valid as a library, so only reported when enabled.
+        assert!(symbol_provider
+            .get_entry_point_diagnostics(&symbol_tree, &ValidationParams::default())
+            .diagnostics
+            .is_empty());
+    }
+
+    #[test]
+    fn lint_array_size_glsl_runtime_array() {
+        let file_path = Path::new("./test/glsl/runtime-array.comp.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::glsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &ValidationParams::default())
+            .unwrap()
+            .diagnostics;
+        // Trailing runtime arrays of buffer blocks are legal.
+        assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 17);
+        assert!(diagnostics[0].error.contains("'invalidValues[]'"));
+        assert_eq!(diagnostics[1].line, 22);
+        assert!(diagnostics[1].error.contains("greater than zero"));
+    }
+
+    #[test]
+    fn lint_block_layout() {
+        let get_diagnostics = |mut symbol_provider: SymbolProvider, path: &str, enabled: bool| {
+            let file_path = Path::new(path);
+            let shader_content = std::fs::read_to_string(file_path).unwrap();
+            let symbol_tree = symbol_provider
+                .create_ast(file_path, &shader_content)
+                .unwrap();
+            symbol_provider
+                .get_lint_diagnostics(
+                    &symbol_tree,
+                    &ValidationParams {
+                        lint_block_layout: enabled,
+                        ..Default::default()
+                    },
+                )
+                .unwrap()
+                .diagnostics
+        };
+        let diagnostics = get_diagnostics(
+            SymbolProvider::glsl(),
+            "./test/glsl/block-layout.frag.glsl",
+            true,
+        );
+        // Explicit offset is misaligned, array elements & vec3 are padded. Other blocks are tightly packed.
+        assert_eq!(diagnostics.len(), 3, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 11);
+        assert!(
+            diagnostics[0]
+                .error
+                .contains("Explicit offset 20 of 'color' conflicts with std140 layout, which places it at offset 16"),
+            "{}",
+            diagnostics[0].error
+        );
+        assert_eq!(diagnostics[1].line, 12);
+        assert!(diagnostics[1].error.contains("stride of 16 bytes"));
+        assert_eq!(diagnostics[2].line, 15);
+        assert!(diagnostics[2].error.contains("12 bytes of padding"));
+        assert!(get_diagnostics(
+            SymbolProvider::glsl(),
+            "./test/glsl/block-layout.frag.glsl",
+            false
+        )
+        .is_empty());
+        let diagnostics = get_diagnostics(
+            SymbolProvider::hlsl(),
+            "./test/hlsl/block-layout.hlsl",
+            true,
+        );
+        // Arrays elements start on a new register.
+        assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+        assert!(diagnostics[0].error.contains("8 bytes of padding"));
+        assert!(diagnostics[1]
+            .error
+            .contains("stride of 16 bytes in cbuffer layout"));
+    }
+
+    #[test]
+    fn lint_non_uniform_sampling() {
+        let get_diagnostics =
+            |mut symbol_provider: SymbolProvider, path: &str, params: &ValidationParams| {
+                let file_path = Path::new(path);
+                let shader_content = std::fs::read_to_string(file_path).unwrap();
+                let symbol_tree = symbol_provider
+                    .create_ast(file_path, &shader_content)
+                    .unwrap();
+                symbol_provider
+                    .get_lint_diagnostics(&symbol_tree, params)
+                    .unwrap()
+                    .diagnostics
+            };
+        let params = ValidationParams {
+            lint_non_uniform_sampling: true,
+            ..Default::default()
+        };
+        // Uniform branches & explicit LOD are safe.
+        let diagnostics = get_diagnostics(
+            SymbolProvider::glsl(),
+            "./test/glsl/non-uniform-sampling.frag.glsl",
+            &params,
+        );
+        assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 23);
+        assert!(diagnostics[0].error.contains("Use 'textureLod' instead"));
+        assert_eq!(diagnostics[1].line, 28);
+        assert!(diagnostics[1]
+            .error
+            .contains("Use 'textureLodOffset' instead"));
+        let diagnostics = get_diagnostics(
+            SymbolProvider::hlsl(),
+            "./test/hlsl/non-uniform-sampling.hlsl",
+            &params,
+        );
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 16);
+        assert!(diagnostics[0].error.contains("Use 'SampleLevel' instead"));
+        // Only fragment shaders have implicit derivatives.
+        assert!(get_diagnostics(
+            SymbolProvider::hlsl(),
+            "./test/hlsl/non-uniform-sampling.hlsl",
+            &ValidationParams {
+                lint_non_uniform_sampling: true,
+                shader_stage: Some(ShaderStage::Vertex),
+                ..Default::default()
+            },
+        )
+        .is_empty());
+        assert!(get_diagnostics(
+            SymbolProvider::glsl(),
+            "./test/glsl/non-uniform-sampling.frag.glsl",
+            &ValidationParams::default(),
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn lint_unassigned_out_parameters() {
+        let get_diagnostics = |mut symbol_provider: SymbolProvider, path: &str| {
+            let file_path = Path::new(path);
+            let shader_content = std::fs::read_to_string(file_path).unwrap();
+            let symbol_tree = symbol_provider
+                .create_ast(file_path, &shader_content)
+                .unwrap();
+            symbol_provider
+                .get_lint_diagnostics(
+                    &symbol_tree,
+                    &ValidationParams {
+                        lint_unassigned_out_parameters: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap()
+                .diagnostics
+        };
+        // Fully assigned & inout parameters are not reported.
+        let diagnostics = get_diagnostics(
+            SymbolProvider::glsl(),
+            "./test/glsl/out-parameter.frag.glsl",
+        );
+        assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 7);
+        assert!(diagnostics[0].error.contains("'result'"));
+        assert!(diagnostics[0].error.contains("(line 9)"));
+        assert_eq!(diagnostics[1].line, 15);
+        assert!(diagnostics[1].error.contains("(line 19)"));
+        let diagnostics = get_diagnostics(SymbolProvider::hlsl(), "./test/hlsl/out-parameter.hlsl");
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 2);
+        assert!(diagnostics[0].error.contains("(line 6)"));
+    }
+
+    #[test]
+    fn lint_integer_division() {
+        let get_diagnostics =
+            |mut symbol_provider: SymbolProvider, path: &str, lint_integer_division: bool| {
+                let file_path = Path::new(path);
+                let shader_content = std::fs::read_to_string(file_path).unwrap();
+                let symbol_tree = symbol_provider
+                    .create_ast(file_path, &shader_content)
+                    .unwrap();
+                symbol_provider
+                    .get_lint_diagnostics(
+                        &symbol_tree,
+                        &ValidationParams {
+                            lint_integer_division,
+                            ..Default::default()
+                        },
+                    )
+                    .unwrap()
+                    .diagnostics
+            };
+        // Float operands & integer results are not reported.
+        let diagnostics = get_diagnostics(
+            SymbolProvider::glsl(),
+            "./test/glsl/integer-division.frag.glsl",
+            true,
+        );
+        assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+        assert_eq!((diagnostics[0].line, diagnostics[0].pos), (9, 22));
+        assert!(diagnostics[0].error.contains("'1 / 2'"));
+        assert!(diagnostics[0].error.contains("'truncated'"));
+        assert_eq!(diagnostics[1].line, 12);
+        assert!(diagnostics[1].error.contains("'float' for 'ratio'"));
+        let diagnostics = get_diagnostics(
+            SymbolProvider::hlsl(),
+            "./test/hlsl/integer-division.hlsl",
+            true,
+        );
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 4);
+        assert!(diagnostics[0].error.contains("'float2'"));
+        assert!(get_diagnostics(
+            SymbolProvider::glsl(),
+            "./test/glsl/integer-division.frag.glsl",
+            false,
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn lint_array_size_glsl_specialization_constant() {
+        let file_path = Path::new("./test/glsl/array-size.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::glsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let get_diagnostics = |symbol_provider: &mut SymbolProvider,
+                               glsl_client: GlslTargetClient| {
+            symbol_provider
+                .get_lint_diagnostics(
+                    &symbol_tree,
+                    &ValidationParams {
+                        glsl_client,
+                        ..Default::default()
+                    },
+                )
+                .unwrap()
+                .diagnostics
+        };
+        // Specialization constants can size arrays with Vulkan only.
+        let diagnostics = get_diagnostics(&mut symbol_provider, GlslTargetClient::Vulkan1_3);
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 14);
+        assert!(diagnostics[0].error.contains("'count'"));
+        let diagnostics = get_diagnostics(&mut symbol_provider, GlslTargetClient::OpenGL450);
+        assert_eq!(diagnostics.len(), 2, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 6);
+        assert!(diagnostics[0].error.contains("specialization constant"));
+    }
+
+    #[test]
+    fn lint_large_array_glsl() {
+        let file_path = Path::new("./test/glsl/large-array.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::glsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let params = ValidationParams {
+            large_array_threshold: Some(8192),
+            ..Default::default()
+        };
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &params)
+            .unwrap()
+            .diagnostics;
+        // kernel is 1024 bytes, transforms is 16384 bytes.
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 9);
+        assert!(diagnostics[0].error.contains("'transforms'"));
+    }
+    #[test]
+    fn lint_reserved_identifier_glsl() {
+        let file_path = Path::new("./test/glsl/reserved-identifier.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::glsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let params = ValidationParams {
+            lint_reserved_identifiers: true,
+            ..Default::default()
+        };
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &params)
+            .unwrap()
+            .diagnostics;
+        // Only the declaration is reported, not its usage.
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 5);
+        assert!(diagnostics[0].error.contains("'gl_custom'"));
+        // Disabled by default
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &ValidationParams::default())
+            .unwrap()
+            .diagnostics;
+        assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+    }
+    #[test]
+    fn lint_unreachable_code_return_hlsl() {
+        let file_path = Path::new("./test/hlsl/unreachable.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let params = ValidationParams {
+            lint_unreachable_code: true,
+            ..Default::default()
+        };
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &params)
+            .unwrap()
+            .diagnostics;
+        // Break of the if body has no following statement.
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 9);
+        assert_eq!(diagnostics[0].end, Some((10, 17)));
+        assert!(diagnostics[0].unnecessary);
+    }
+    #[test]
+    fn lint_syntax_fixes_hlsl() {
+        let file_path = Path::new("./test/hlsl/syntax-fix.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let params = ValidationParams {
+            lint_syntax_fixes: true,
+            ..Default::default()
+        };
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &params)
+            .unwrap()
+            .diagnostics;
+        let fixes: Vec<(u32, u32, String, String)> = diagnostics
+            .iter()
+            .map(|diagnostic| {
+                let fix = diagnostic.fix.as_ref().unwrap();
+                (
+                    diagnostic.line,
+                    diagnostic.pos,
+                    fix.title.clone(),
+                    fix.new_text.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            fixes,
+            vec![
+                (2, 21, "Insert ';'".into(), ";".into()),
+                (3, 30, "Insert ')'".into(), ")".into()),
+                (4, 39, "Remove ')'".into(), "".into()),
+            ]
+        );
+    }
+    #[test]
+    fn lint_unreachable_code_discard_glsl() {
+        let file_path = Path::new("./test/glsl/unreachable.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::glsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let params = ValidationParams {
+            lint_unreachable_code: true,
+            ..Default::default()
+        };
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &params)
+            .unwrap()
+            .diagnostics;
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 8);
+        assert_eq!(diagnostics[0].pos, 8);
+        // Disabled by default
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &ValidationParams::default())
+            .unwrap()
+            .diagnostics;
+        assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+    }
+    #[test]
+    fn symbols_wgsl_constants() {
+        let file_path = Path::new("./test/wgsl/constants.wgsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::wgsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let symbols = symbol_provider
+            .get_all_symbols(&symbol_tree, &ValidationParams::default())
+            .unwrap();
+        let get_constant = |label: &str| match symbols.find_symbol(&label.into()) {
+            Some(ShaderSymbol {
+                data:
+                    ShaderSymbolData::Constants {
+                        ty,
+                        qualifier,
+                        value,
+                    },
+                ..
+            }) => (ty, qualifier, value),
+            symbol => panic!("Constant {} not found: {:#?}", label, symbol),
+        };
+        assert_eq!(
+            get_constant("PI"),
+            ("f32".into(), "const".into(), "3.14159".into())
+        );
+        assert_eq!(
+            get_constant("COUNT"),
+            ("".into(), "const".into(), "4u".into())
+        );
+        assert_eq!(
+            get_constant("gain"),
+            ("f32".into(), "@id(12) override".into(), "1.0".into())
+        );
+        assert_eq!(
+            get_constant("scale"),
+            ("f32".into(), "override".into(), "".into())
+        );
+        assert_eq!(
+            get_constant("noType"),
+            ("".into(), "override".into(), "1.0".into())
+        );
+    }
+    #[test]
+    fn lint_swizzle_over_length_hlsl() {
+        let file_path = Path::new("./test/hlsl/swizzle.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &ValidationParams::default())
+            .unwrap()
+            .diagnostics;
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 3);
+        assert!(diagnostics[0].error.contains("'.xyzw'"));
+    }
+    #[test]
+    fn lint_swizzle_duplicate_write_glsl() {
+        let file_path = Path::new("./test/glsl/swizzle.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut symbol_provider = SymbolProvider::glsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let diagnostics = symbol_provider
+            .get_lint_diagnostics(&symbol_tree, &ValidationParams::default())
+            .unwrap()
+            .diagnostics;
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert_eq!(diagnostics[0].line, 8);
+        assert!(diagnostics[0].error.contains("'.rr'"));
+    }
+    #[test]
+    fn format_hlsl_ok() {
+        let file_path = Path::new("./test/hlsl/format.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let expected_content =
+            std::fs::read_to_string(Path::new("./test/hlsl/format.expected.hlsl")).unwrap();
+        let mut symbol_provider = SymbolProvider::hlsl();
+        let symbol_tree = symbol_provider
+            .create_ast(file_path, &shader_content)
+            .unwrap();
+        let formatted_content = symbol_tree.format(&ShaderFormattingOptions::default());
+        assert_eq!(formatted_content, expected_content);
+    }
+    #[test]
     fn symbol_scope_glsl_ok() {
         let file_path = Path::new("./test/glsl/scopes.frag.glsl");
         let shader_content = std::fs::read_to_string(file_path).unwrap();
@@ -0,0 +1,131 @@
+use std::path::Path;
+
+use tree_sitter::Node;
+
+use crate::{
+    shader::{ShaderStage, ShadingLanguage},
+    shader_error::{ShaderDiagnosticList, ShaderErrorSeverity},
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    linter::{node_diagnostic, SymbolTreeLinter},
+    parser::get_name,
+    uniformity::find_non_uniform_control_flow,
+};
+
+// Texture methods selecting the mip from derivatives, with their explicit LOD variant.
+const HLSL_IMPLICIT_LOD_METHODS: [(&str, &str); 3] = [
+    ("Sample", "SampleLevel"),
+    ("SampleBias", "SampleLevel"),
+    ("SampleCmp", "SampleCmpLevelZero"),
+];
+const HLSL_IMPLICIT_LOD_FUNCTIONS: [(&str, &str); 5] = [
+    ("tex1D", "tex1Dlod"),
+    ("tex2D", "tex2Dlod"),
+    ("tex2Dbias", "tex2Dlod"),
+    ("tex3D", "tex3Dlod"),
+    ("texCUBE", "texCUBElod"),
+];
+const GLSL_IMPLICIT_LOD_FUNCTIONS: [(&str, &str); 9] = [
+    ("texture", "textureLod"),
+    ("textureOffset", "textureLodOffset"),
+    ("textureProj", "textureProjLod"),
+    ("textureProjOffset", "textureProjLodOffset"),
+    ("texture1D", "texture1DLod"),
+    ("texture2D", "texture2DLod"),
+    ("texture2DProj", "texture2DProjLod"),
+    ("texture3D", "texture3DLod"),
+    ("textureCube", "textureCubeLod"),
+];
+
+pub(super) struct NonUniformSamplingLinter {
+    shading_language: ShadingLanguage,
+}
+
+impl NonUniformSamplingLinter {
+    pub fn new(shading_language: ShadingLanguage) -> Self {
+        Self { shading_language }
+    }
+    // Sampling function name & its explicit LOD variant.
+    fn get_implicit_lod_function<'a>(
+        &self,
+        shader_content: &'a str,
+        function: Node,
+    ) -> Option<(&'a str, &'static str)> {
+        let (name, functions) = match (self.shading_language, function.kind()) {
+            (ShadingLanguage::Hlsl, "field_expression") => (
+                get_name(shader_content, function.child_by_field_name("field")?),
+                HLSL_IMPLICIT_LOD_METHODS.as_slice(),
+            ),
+            (ShadingLanguage::Hlsl, "identifier") => (
+                get_name(shader_content, function),
+                HLSL_IMPLICIT_LOD_FUNCTIONS.as_slice(),
+            ),
+            (ShadingLanguage::Glsl, "identifier") => (
+                get_name(shader_content, function),
+                GLSL_IMPLICIT_LOD_FUNCTIONS.as_slice(),
+            ),
+            _ => return None,
+        };
+        functions
+            .iter()
+            .find(|(function, _)| *function == name)
+            .map(|(_, explicit_lod_function)| (name, *explicit_lod_function))
+    }
+}
+
+impl SymbolTreeLinter for NonUniformSamplingLinter {
+    fn get_code(&self) -> &'static str {
+        "nonUniformSampling"
+    }
+    fn get_query(&self) -> String {
+        r#"(call_expression) @call"#.into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        if !params.lint_non_uniform_sampling {
+            return;
+        }
+        // Derivatives are only implicit in fragment shaders. Check everything if unknown.
+        let shader_stage = params.shader_stage.or_else(|| {
+            let file_name = file_path.file_name()?.to_string_lossy().to_string();
+            ShaderStage::from_file_name(&file_name)
+        });
+        if shader_stage.is_some_and(|shader_stage| shader_stage != ShaderStage::Fragment) {
+            return;
+        }
+        let call = matches.captures[0].node;
+        let Some((name, explicit_lod_function)) = call
+            .child_by_field_name("function")
+            .and_then(|function| self.get_implicit_lod_function(shader_content, function))
+        else {
+            return;
+        };
+        let Some(control_flow) = find_non_uniform_control_flow(shader_content, call) else {
+            return;
+        };
+        let mut diagnostic = node_diagnostic(
+            call,
+            file_path,
+            ShaderErrorSeverity::Warning,
+            format!(
+                "Sampling with '{}' in non-uniform control flow (line {}) is undefined, as implicit LOD relies on derivatives. Use '{}' instead.",
+                name,
+                control_flow.start_position().row + 1,
+                explicit_lod_function
+            ),
+        );
+        diagnostic.end = Some((
+            call.end_position().row as u32 + 1,
+            call.end_position().column as u32,
+        ));
+        diagnostics.push(diagnostic);
+    }
+}
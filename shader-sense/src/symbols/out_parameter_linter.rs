@@ -0,0 +1,95 @@
+use std::path::Path;
+
+use tree_sitter::Node;
+
+use crate::{
+    shader_error::{ShaderDiagnosticList, ShaderErrorSeverity},
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    control_flow::find_unassigned_exits,
+    linter::{node_diagnostic, SymbolTreeLinter},
+    parser::get_name,
+};
+
+pub(super) struct OutParameterLinter {}
+
+impl OutParameterLinter {
+    // Inout parameters (or in out for HLSL) are initialized by the caller, so only out ones matter.
+    fn is_out_parameter(parameter: Node) -> bool {
+        let qualifiers: Vec<&str> = parameter
+            .children(&mut parameter.walk())
+            .map(|child| child.kind())
+            .collect();
+        qualifiers.contains(&"out") && !qualifiers.contains(&"in") && !qualifiers.contains(&"inout")
+    }
+    fn get_parameter_identifier(parameter: Node) -> Option<Node> {
+        let mut declarator = parameter.child_by_field_name("declarator")?;
+        // Array parameters wrap their identifier.
+        while declarator.kind() != "identifier" {
+            declarator = declarator.child_by_field_name("declarator")?;
+        }
+        Some(declarator)
+    }
+}
+
+impl SymbolTreeLinter for OutParameterLinter {
+    fn get_code(&self) -> &'static str {
+        "unassignedOutParameters"
+    }
+    fn get_query(&self) -> String {
+        r#"(function_definition
+            declarator: (function_declarator
+                parameters: (parameter_list) @parameters)
+            body: (compound_statement) @body)"#
+            .into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        if !params.lint_unassigned_out_parameters {
+            return;
+        }
+        let parameters = matches.captures[0].node;
+        let body = matches.captures[1].node;
+        for parameter in parameters.named_children(&mut parameters.walk()) {
+            if parameter.kind() != "parameter_declaration" || !Self::is_out_parameter(parameter) {
+                continue;
+            }
+            let Some(identifier) = Self::get_parameter_identifier(parameter) else {
+                continue;
+            };
+            let name = get_name(shader_content, identifier);
+            let exits = find_unassigned_exits(shader_content, body, name);
+            // Report the first path only, as assigning the parameter earlier usually fix them all.
+            let Some(exit) = exits.first() else {
+                continue;
+            };
+            let exit_line = if *exit == body {
+                exit.end_position().row + 1
+            } else {
+                exit.start_position().row + 1
+            };
+            let mut diagnostic = node_diagnostic(
+                identifier,
+                file_path,
+                ShaderErrorSeverity::Warning,
+                format!(
+                    "Out parameter '{}' might not be assigned when the function returns (line {}).",
+                    name, exit_line
+                ),
+            );
+            diagnostic.end = Some((
+                identifier.end_position().row as u32 + 1,
+                identifier.end_position().column as u32,
+            ));
+            diagnostics.push(diagnostic);
+        }
+    }
+}
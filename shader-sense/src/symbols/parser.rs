@@ -5,25 +5,86 @@ use std::{
 
 use tree_sitter::{InputEdit, Node, Parser, QueryCursor, QueryMatch, Tree, TreeCursor};
 
-use crate::symbols::symbols::{ShaderPosition, ShaderRange, ShaderSymbolList};
+use crate::{
+    shader::{ShaderStage, ShadingLanguage},
+    shader_error::ShaderDiagnosticList,
+    symbols::symbols::{ShaderPosition, ShaderRange, ShaderSymbolList},
+    validator::validator::ValidationParams,
+};
 
 use super::{
+    array_size_linter::ArraySizeLinter,
+    block_layout_linter::BlockLayoutLinter,
     glsl_parser::{
-        GlslDefineTreeParser, GlslFunctionTreeParser, GlslIncludeTreeParser, GlslStructTreeParser,
-        GlslVariableTreeParser,
+        GlslBlockTreeParser, GlslDefineTreeParser, GlslFunctionTreeParser, GlslIncludeTreeParser,
+        GlslStructTreeParser, GlslVariableTreeParser,
     },
     hlsl_parser::{
-        HlslDefineTreeParser, HlslFunctionTreeParser, HlslIncludeTreeParser, HlslStructTreeParser,
+        HlslDefineTreeParser, HlslFunctionTreeParser, HlslIncludeTreeParser,
+        HlslInterfaceTreeParser, HlslSamplerStateTreeParser, HlslStructTreeParser,
         HlslVariableTreeParser,
     },
+    integer_division_linter::IntegerDivisionLinter,
+    large_array_linter::LargeArrayLinter,
+    linter::{set_diagnostics_code, SymbolTreeLinter, SYNTAX_FIXES_CODE},
+    non_uniform_sampling_linter::NonUniformSamplingLinter,
+    out_parameter_linter::OutParameterLinter,
+    reserved_identifier_linter::ReservedIdentifierLinter,
+    resource_limit_linter::ResourceLimitLinter,
+    swizzle_linter::SwizzleLinter,
     symbols::{ShaderScope, SymbolError},
+    syntax_fix_linter::query_syntax_fix_diagnostics,
+    unreachable_code_linter::UnreachableCodeLinter,
+    wgsl_parser::{
+        WgslConstTreeParser, WgslFunctionTreeParser, WgslOverrideTreeParser, WgslVariableTreeParser,
+    },
 };
 
+// Stage names of HLSL shader attributes & WGSL entry point attributes.
+const ENTRY_POINT_STAGES: [(&str, ShaderStage); 15] = [
+    ("vertex", ShaderStage::Vertex),
+    ("pixel", ShaderStage::Fragment),
+    ("fragment", ShaderStage::Fragment),
+    ("compute", ShaderStage::Compute),
+    ("hull", ShaderStage::TesselationControl),
+    ("domain", ShaderStage::TesselationEvaluation),
+    ("geometry", ShaderStage::Geometry),
+    ("mesh", ShaderStage::Mesh),
+    ("amplification", ShaderStage::Task),
+    ("raygeneration", ShaderStage::RayGeneration),
+    ("closesthit", ShaderStage::ClosestHit),
+    ("anyhit", ShaderStage::AnyHit),
+    ("miss", ShaderStage::Miss),
+    ("intersection", ShaderStage::Intersect),
+    ("callable", ShaderStage::Callable),
+];
+
+const WGSL_ADDRESS_SPACES: [&str; 5] = ["function", "private", "workgroup", "uniform", "storage"];
+const WGSL_ACCESS_MODES: [&str; 3] = ["read", "write", "read_write"];
+
 pub(super) fn get_name<'a>(shader_content: &'a str, node: Node) -> &'a str {
     let range = node.range();
     &shader_content[range.start_byte..range.end_byte]
 }
 
+// Get qualifiers declared before the type of a declaration, such as precise or static.
+pub(super) fn get_qualifiers(shader_content: &str, type_node: Node) -> String {
+    let mut qualifiers = Vec::new();
+    let mut sibling = type_node.prev_sibling();
+    while let Some(node) = sibling {
+        if node.kind() != "comment" {
+            qualifiers.push(get_name(shader_content, node));
+        }
+        sibling = node.prev_sibling();
+    }
+    qualifiers.reverse();
+    qualifiers
+        .iter()
+        .flat_map(|qualifier| qualifier.split_whitespace())
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
 impl ShaderRange {
     pub(super) fn from_range(value: tree_sitter::Range, file_path: PathBuf) -> Self {
         ShaderRange {
@@ -51,6 +112,7 @@ pub trait SymbolTreeParser {
         file_path: &Path,
         shader_content: &str,
         scopes: &Vec<ShaderScope>,
+        params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     );
     fn compute_scope_stack(
@@ -75,10 +137,84 @@ pub trait SymbolTreeParser {
 pub struct SymbolTree {
     pub file_path: PathBuf,
     pub content: String,
-    tree: Tree,
+    pub(super) tree: Tree,
 }
 
 impl SymbolTree {
+    // Stage shared by all entry points, declared with [shader("pixel")] in HLSL or @fragment in WGSL.
+    pub fn get_entry_point_stage(&self) -> Option<ShaderStage> {
+        let mut entry_point_stage = None;
+        for (_, stage, _) in self.get_entry_points() {
+            match entry_point_stage {
+                Some(entry_point_stage) if entry_point_stage != stage => return None,
+                _ => entry_point_stage = Some(stage),
+            }
+        }
+        entry_point_stage
+    }
+    // Functions declared as entry point with their stage & the range of their name.
+    pub fn get_entry_points(&self) -> Vec<(String, ShaderStage, ShaderRange)> {
+        let root_node = self.tree.root_node();
+        let mut entry_points = Vec::new();
+        for function in root_node.named_children(&mut root_node.walk()) {
+            if !matches!(
+                function.kind(),
+                "function_definition" | "function_declaration"
+            ) {
+                continue;
+            }
+            for attribute in function.named_children(&mut function.walk()) {
+                let name = match attribute.kind() {
+                    "hlsl_attribute" => {
+                        let Some(call) = attribute
+                            .named_child(0)
+                            .filter(|call| call.kind() == "call_expression")
+                        else {
+                            continue;
+                        };
+                        match (
+                            call.child_by_field_name("function"),
+                            call.child_by_field_name("arguments")
+                                .and_then(|arguments| arguments.named_child(0)),
+                        ) {
+                            (Some(function), Some(stage))
+                                if get_name(&self.content, function) == "shader" =>
+                            {
+                                get_name(&self.content, stage).trim_matches('"')
+                            }
+                            _ => continue,
+                        }
+                    }
+                    "attribute" => match attribute.named_child(0) {
+                        Some(identifier) => get_name(&self.content, identifier),
+                        None => continue,
+                    },
+                    _ => continue,
+                };
+                let Some((_, stage)) = ENTRY_POINT_STAGES
+                    .iter()
+                    .find(|(stage_name, _)| *stage_name == name)
+                else {
+                    continue;
+                };
+                let name = match function.child_by_field_name("name") {
+                    Some(name) => Some(name),
+                    None => function
+                        .child_by_field_name("declarator")
+                        .and_then(|declarator| declarator.child_by_field_name("declarator")),
+                };
+                if let Some(name) = name {
+                    entry_points.push((
+                        get_name(&self.content, name).into(),
+                        *stage,
+                        ShaderRange::from_range(name.range(), self.file_path.clone()),
+                    ));
+                }
+                break;
+            }
+        }
+        entry_points
+    }
     pub fn dump_ast(&self) -> String {
         fn format_debug_cursor(cursor: &mut TreeCursor, depth: usize) -> String {
             let mut debug_tree = String::new();
@@ -101,10 +237,184 @@ impl SymbolTree {
         }
         format_debug_cursor(&mut self.tree.root_node().walk(), 0)
     }
+    // Get range of scopes & block comments, with a flag set for comments.
+    pub fn get_block_ranges(&self) -> Vec<(ShaderRange, bool)> {
+        fn visit(tree: &SymbolTree, node: Node, ranges: &mut Vec<(ShaderRange, bool)>) {
+            let is_comment = match node.kind() {
+                "comment" => get_name(&tree.content, node).starts_with("/*"),
+                "block_comment" => true,
+                _ => false,
+            };
+            let is_scope = match node.kind() {
+                // WGSL struct has no body node.
+                "compound_statement" | "field_declaration_list" | "struct_declaration" => true,
+                _ => false,
+            };
+            if (is_comment || is_scope) && node.start_position().row < node.end_position().row {
+                ranges.push((
+                    ShaderRange::from_range(node.range(), tree.file_path.clone()),
+                    is_comment,
+                ));
+            }
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                visit(tree, child, ranges);
+            }
+        }
+        let mut ranges = Vec::new();
+        visit(self, self.tree.root_node(), &mut ranges);
+        ranges
+    }
+    // Name of the pragma under the cursor, such as `once` in `#pragma once`.
+    pub fn get_pragma_at_position(
+        &self,
+        position: &ShaderPosition,
+    ) -> Option<(String, ShaderRange)> {
+        let line = self.content.lines().nth(position.line as usize)?;
+        let arguments = line.trim_start().strip_prefix("#pragma")?;
+        let name = arguments.trim_start();
+        let start = line.len() - name.len();
+        let end = start
+            + name
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(name.len());
+        if start == end || (position.pos as usize) < start || (position.pos as usize) > end {
+            return None;
+        }
+        let get_position =
+            |pos: usize| ShaderPosition::new(self.file_path.clone(), position.line, pos as u32);
+        Some((
+            line[start..end].to_string(),
+            ShaderRange::new(get_position(start), get_position(end)),
+        ))
+    }
+    // Whether the word at position is the name of a pragma, such as `#pragma on`.
+    pub fn is_pragma_position(&self, position: &ShaderPosition) -> bool {
+        let offset = position
+            .to_byte_offset(&self.content)
+            .min(self.content.len());
+        let line_start = self.content[..offset]
+            .rfind('\n')
+            .map_or(0, |start| start + 1);
+        let text = self.content[line_start..offset]
+            .trim_end_matches(|c: char| c.is_ascii_alphanumeric() || c == '_');
+        text.trim() == "#pragma" && text.ends_with(char::is_whitespace)
+    }
+    // Whether the word at position follows the colon of an HLSL semantic, such as `float4 pos : SV_Po`.
+    // Colons of ternary operators, case & labels are not semantics.
+    pub fn is_semantic_position(&self, position: &ShaderPosition) -> bool {
+        let offset = position
+            .to_byte_offset(&self.content)
+            .min(self.content.len());
+        let text = self.content[..offset]
+            .trim_end_matches(|c: char| c.is_ascii_alphanumeric() || c == '_')
+            .trim_end();
+        let Some(before_colon) = text.strip_suffix(':') else {
+            return false;
+        };
+        // Scope operator such as Type::member.
+        if before_colon.ends_with(':') {
+            return false;
+        }
+        // Semantics follow a declarator, an array size or a parameter list.
+        if !before_colon
+            .trim_end()
+            .ends_with(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == ')' || c == ']')
+        {
+            return false;
+        }
+        // Incomplete ternaries are not always parsed as such.
+        let statement_start = before_colon
+            .rfind([';', '{', '}'])
+            .map_or(0, |start| start + 1);
+        if before_colon[statement_start..].contains('?') {
+            return false;
+        }
+        let colon_offset = text.len() - 1;
+        let mut parent = self
+            .tree
+            .root_node()
+            .descendant_for_byte_range(colon_offset, colon_offset + 1);
+        while let Some(node) = parent {
+            if matches!(
+                node.kind(),
+                "conditional_expression" | "case_statement" | "labeled_statement"
+            ) {
+                return false;
+            }
+            parent = node.parent();
+        }
+        true
+    }
+    // Keywords expected in the template list of a WGSL var or ptr, such as `var<stor` or `ptr<storage, f32, re`.
+    pub fn get_address_space_keywords(
+        &self,
+        position: &ShaderPosition,
+    ) -> Option<&'static [&'static str]> {
+        let offset = position
+            .to_byte_offset(&self.content)
+            .min(self.content.len());
+        let text = self.content[..offset]
+            .trim_end_matches(|c: char| c.is_ascii_alphanumeric() || c == '_')
+            .trim_end();
+        if !text.ends_with(['<', ',']) {
+            return None;
+        }
+        // Find the opening of the template list, skipping nested ones such as array<f32>.
+        let mut depth = 0;
+        let mut argument_index = 0;
+        let mut template_start = None;
+        for (index, c) in text.char_indices().rev() {
+            match c {
+                '>' => depth += 1,
+                '<' if depth == 0 => {
+                    template_start = Some(index);
+                    break;
+                }
+                '<' => depth -= 1,
+                ',' if depth == 0 => argument_index += 1,
+                ';' | '{' | '}' | '(' | ')' | '=' => return None,
+                _ => {}
+            }
+        }
+        let template = text[..template_start?].trim_end();
+        let template = &template[template
+            .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .map_or(0, |start| start + 1)..];
+        match (template, argument_index) {
+            ("var" | "ptr", 0) => Some(&WGSL_ADDRESS_SPACES),
+            ("var", 1) | ("ptr", 2) => Some(&WGSL_ACCESS_MODES),
+            _ => None,
+        }
+    }
+    // Semantics used in the file, such as SV_Target or user defined ones.
+    // Struct members semantics are parsed as bitfields.
+    pub fn get_semantics(&self) -> Vec<String> {
+        fn visit(tree: &SymbolTree, node: Node, semantics: &mut Vec<String>) {
+            if matches!(node.kind(), "semantics" | "bitfield_clause") {
+                if let Some(semantic) = node
+                    .named_child(0)
+                    .filter(|semantic| semantic.kind() == "identifier")
+                {
+                    let semantic = get_name(&tree.content, semantic).to_string();
+                    if !semantics.contains(&semantic) {
+                        semantics.push(semantic);
+                    }
+                }
+            }
+            for child in node.named_children(&mut node.walk()) {
+                visit(tree, child, semantics);
+            }
+        }
+        let mut semantics = Vec::new();
+        visit(self, self.tree.root_node(), &mut semantics);
+        semantics
+    }
 }
 pub struct SymbolParser {
     parser: Parser,
     symbol_parsers: Vec<(Box<dyn SymbolTreeParser>, tree_sitter::Query)>,
+    symbol_linters: Vec<(Box<dyn SymbolTreeLinter>, tree_sitter::Query)>,
     scope_query: tree_sitter::Query,
 }
 
@@ -117,6 +427,27 @@ fn create_symbol_parser(
     (symbol_parser, query)
 }
 
+fn create_symbol_linter(
+    symbol_linter: Box<dyn SymbolTreeLinter>,
+    language: &tree_sitter::Language,
+) -> (Box<dyn SymbolTreeLinter>, tree_sitter::Query) {
+    let query = tree_sitter::Query::new(*language, symbol_linter.get_query().as_str()).unwrap();
+    (symbol_linter, query)
+}
+
+// Path of an include directive without quotes or angle brackets, with the range of the path token.
+pub(super) fn get_include_path(
+    symbol_tree: &SymbolTree,
+    include_node: Node,
+) -> Option<(String, ShaderRange)> {
+    let path_node = include_node.child_by_field_name("path")?;
+    let path = get_name(&symbol_tree.content, path_node);
+    Some((
+        path.get(1..path.len().checked_sub(1)?)?.into(),
+        ShaderRange::from_range(path_node.range(), symbol_tree.file_path.clone()),
+    ))
+}
+
 impl SymbolParser {
     pub fn hlsl() -> Self {
         let lang = tree_sitter_hlsl::language();
@@ -129,9 +460,31 @@ impl SymbolParser {
             symbol_parsers: vec![
                 create_symbol_parser(Box::new(HlslFunctionTreeParser { is_field: false }), &lang),
                 create_symbol_parser(Box::new(HlslStructTreeParser::new()), &lang),
+                create_symbol_parser(Box::new(HlslInterfaceTreeParser {}), &lang),
                 create_symbol_parser(Box::new(HlslVariableTreeParser { is_field: false }), &lang),
                 create_symbol_parser(Box::new(HlslIncludeTreeParser {}), &lang),
                 create_symbol_parser(Box::new(HlslDefineTreeParser {}), &lang),
+                create_symbol_parser(Box::new(HlslSamplerStateTreeParser {}), &lang),
+            ],
+            symbol_linters: vec![
+                create_symbol_linter(Box::new(SwizzleLinter::new(ShadingLanguage::Hlsl)), &lang),
+                create_symbol_linter(
+                    Box::new(LargeArrayLinter::new(ShadingLanguage::Hlsl)),
+                    &lang,
+                ),
+                create_symbol_linter(Box::new(UnreachableCodeLinter {}), &lang),
+                create_symbol_linter(Box::new(ResourceLimitLinter {}), &lang),
+                create_symbol_linter(Box::new(ArraySizeLinter {}), &lang),
+                create_symbol_linter(
+                    Box::new(BlockLayoutLinter::new(ShadingLanguage::Hlsl)),
+                    &lang,
+                ),
+                create_symbol_linter(
+                    Box::new(NonUniformSamplingLinter::new(ShadingLanguage::Hlsl)),
+                    &lang,
+                ),
+                create_symbol_linter(Box::new(OutParameterLinter {}), &lang),
+                create_symbol_linter(Box::new(IntegerDivisionLinter::new()), &lang),
             ],
             scope_query: tree_sitter::Query::new(lang.clone(), r#"(compound_statement) @scope"#)
                 .unwrap(),
@@ -148,10 +501,31 @@ impl SymbolParser {
             symbol_parsers: vec![
                 create_symbol_parser(Box::new(GlslFunctionTreeParser {}), &lang),
                 create_symbol_parser(Box::new(GlslStructTreeParser {}), &lang),
+                create_symbol_parser(Box::new(GlslBlockTreeParser {}), &lang),
                 create_symbol_parser(Box::new(GlslVariableTreeParser {}), &lang),
                 create_symbol_parser(Box::new(GlslIncludeTreeParser {}), &lang),
                 create_symbol_parser(Box::new(GlslDefineTreeParser {}), &lang),
             ],
+            symbol_linters: vec![
+                create_symbol_linter(Box::new(SwizzleLinter::new(ShadingLanguage::Glsl)), &lang),
+                create_symbol_linter(
+                    Box::new(LargeArrayLinter::new(ShadingLanguage::Glsl)),
+                    &lang,
+                ),
+                create_symbol_linter(Box::new(ReservedIdentifierLinter {}), &lang),
+                create_symbol_linter(Box::new(UnreachableCodeLinter {}), &lang),
+                create_symbol_linter(Box::new(ArraySizeLinter {}), &lang),
+                create_symbol_linter(
+                    Box::new(BlockLayoutLinter::new(ShadingLanguage::Glsl)),
+                    &lang,
+                ),
+                create_symbol_linter(
+                    Box::new(NonUniformSamplingLinter::new(ShadingLanguage::Glsl)),
+                    &lang,
+                ),
+                create_symbol_linter(Box::new(OutParameterLinter {}), &lang),
+                create_symbol_linter(Box::new(IntegerDivisionLinter::new()), &lang),
+            ],
             scope_query: tree_sitter::Query::new(lang.clone(), r#"(compound_statement) @scope"#)
                 .unwrap(),
         }
@@ -164,7 +538,13 @@ impl SymbolParser {
             .expect("Error loading WGSL grammar");
         Self {
             parser,
-            symbol_parsers: vec![],
+            symbol_parsers: vec![
+                create_symbol_parser(Box::new(WgslFunctionTreeParser {}), &lang),
+                create_symbol_parser(Box::new(WgslConstTreeParser {}), &lang),
+                create_symbol_parser(Box::new(WgslOverrideTreeParser {}), &lang),
+                create_symbol_parser(Box::new(WgslVariableTreeParser {}), &lang),
+            ],
+            symbol_linters: vec![],
             scope_query: tree_sitter::Query::new(lang.clone(), r#"(compound_statement) @scope"#)
                 .unwrap(),
         }
@@ -214,21 +594,24 @@ impl SymbolParser {
         range: tree_sitter::Range,
         new_text: &String,
     ) -> Result<(), SymbolError> {
-        let line_count = new_text.lines().count();
+        // Column are in bytes from the start of the line.
+        let new_end_position = match new_text.rfind('\n') {
+            Some(last_line_start) => tree_sitter::Point {
+                row: range.start_point.row + new_text.matches('\n').count(),
+                column: new_text.len() - last_line_start - 1,
+            },
+            None => tree_sitter::Point {
+                row: range.start_point.row,
+                column: range.start_point.column + new_text.len(),
+            },
+        };
         symbol_tree.tree.edit(&InputEdit {
             start_byte: range.start_byte,
             old_end_byte: range.end_byte,
             new_end_byte: range.start_byte + new_text.len(),
             start_position: range.start_point,
             old_end_position: range.end_point,
-            new_end_position: tree_sitter::Point {
-                row: if line_count == 0 {
-                    range.start_point.row + new_text.len()
-                } else {
-                    new_text.lines().last().as_slice().len()
-                },
-                column: range.start_point.column + line_count,
-            },
+            new_end_position,
         });
         // Update the tree.
         match self
@@ -249,6 +632,7 @@ impl SymbolParser {
     pub fn query_local_symbols(
         &self,
         symbol_tree: &SymbolTree,
+        params: &ValidationParams,
     ) -> Result<ShaderSymbolList, SymbolError> {
         let scopes = self.query_scopes(
             &symbol_tree.file_path,
@@ -268,12 +652,44 @@ impl SymbolParser {
                     &symbol_tree.file_path,
                     &symbol_tree.content,
                     &scopes,
+                    params,
                     &mut symbols,
                 );
             }
         }
         Ok(symbols)
     }
+    pub fn query_diagnostics(
+        &self,
+        symbol_tree: &SymbolTree,
+        params: &ValidationParams,
+    ) -> Result<ShaderDiagnosticList, SymbolError> {
+        let mut diagnostics = ShaderDiagnosticList::empty();
+        for linter in &self.symbol_linters {
+            let first_diagnostic = diagnostics.diagnostics.len();
+            let mut query_cursor = QueryCursor::new();
+            for matches in query_cursor.matches(
+                &linter.1,
+                symbol_tree.tree.root_node(),
+                symbol_tree.content.as_bytes(),
+            ) {
+                linter.0.process_match(
+                    matches,
+                    &symbol_tree.file_path,
+                    &symbol_tree.content,
+                    params,
+                    &mut diagnostics,
+                );
+            }
+            set_diagnostics_code(&mut diagnostics, first_diagnostic, linter.0.get_code());
+        }
+        if params.lint_syntax_fixes {
+            let first_diagnostic = diagnostics.diagnostics.len();
+            query_syntax_fix_diagnostics(symbol_tree, &mut diagnostics);
+            set_diagnostics_code(&mut diagnostics, first_diagnostic, SYNTAX_FIXES_CODE);
+        }
+        Ok(diagnostics)
+    }
     pub fn find_label_at_position(
         &self,
         symbol_tree: &SymbolTree,
@@ -281,6 +697,47 @@ impl SymbolParser {
     ) -> Result<(String, ShaderRange), SymbolError> {
         self.find_label_at_position_in_node(symbol_tree, symbol_tree.tree.root_node(), position)
     }
+    // Path of the include directive at position, without quotes or angle brackets.
+    pub fn find_include_at_position(
+        &self,
+        symbol_tree: &SymbolTree,
+        position: &ShaderPosition,
+    ) -> Option<(String, ShaderRange)> {
+        let root = symbol_tree.tree.root_node();
+        let point = tree_sitter::Point {
+            row: position.line as usize,
+            column: position.pos as usize,
+        };
+        let mut node = root.named_descendant_for_point_range(point, point)?;
+        while node.kind() != "preproc_include" {
+            node = node.parent()?;
+        }
+        let (path, range) = get_include_path(symbol_tree, node)?;
+        if range.contain(position) {
+            Some((path, range))
+        } else {
+            None
+        }
+    }
+    // Every include directive of the file, with the range of its path.
+    pub fn find_includes(&self, symbol_tree: &SymbolTree) -> Vec<(String, ShaderRange)> {
+        fn collect_includes(
+            symbol_tree: &SymbolTree,
+            node: Node,
+            includes: &mut Vec<(String, ShaderRange)>,
+        ) {
+            for child in node.named_children(&mut node.walk()) {
+                if child.kind() == "preproc_include" {
+                    includes.extend(get_include_path(symbol_tree, child));
+                } else if child.kind().starts_with("preproc") {
+                    collect_includes(symbol_tree, child, includes);
+                }
+            }
+        }
+        let mut includes = Vec::new();
+        collect_includes(symbol_tree, symbol_tree.tree.root_node(), &mut includes);
+        includes
+    }
     pub fn find_label_chain_at_position(
         &mut self,
         symbol_tree: &SymbolTree,
@@ -292,6 +749,41 @@ impl SymbolParser {
             position,
         )
     }
+    // Find every identifier in the tree matching label.
+    pub fn find_label_occurences(&self, symbol_tree: &SymbolTree, label: &str) -> Vec<ShaderRange> {
+        self.find_identifiers(symbol_tree)
+            .into_iter()
+            .filter_map(|(name, range)| if name == label { Some(range) } else { None })
+            .collect()
+    }
+    // Find every identifier in the tree, in order.
+    pub fn find_identifiers<'a>(&self, symbol_tree: &'a SymbolTree) -> Vec<(&'a str, ShaderRange)> {
+        let mut identifiers = Vec::new();
+        let mut cursor = symbol_tree.tree.walk();
+        loop {
+            let node = cursor.node();
+            match node.kind() {
+                "identifier" | "type_identifier" => {
+                    identifiers.push((
+                        get_name(&symbol_tree.content, node),
+                        ShaderRange::from_range(node.range(), symbol_tree.file_path.clone()),
+                    ));
+                }
+                // Macro parameters are not symbols.
+                "preproc_params" => {}
+                _ => {
+                    if cursor.goto_first_child() {
+                        continue;
+                    }
+                }
+            }
+            while !cursor.goto_next_sibling() {
+                if !cursor.goto_parent() {
+                    return identifiers;
+                }
+            }
+        }
+    }
     fn find_label_at_position_in_node(
         &self,
         symbol_tree: &SymbolTree,
@@ -367,30 +859,55 @@ impl SymbolParser {
                         ShaderRange::from_range(node.range(), symbol_tree.file_path.clone()),
                     )])
                 }
+                // Cursor after an array element, resolve array instead.
+                // WGSL name these fields value & subscript.
+                "subscript_expression"
+                    if !node
+                        .child_by_field_name("index")
+                        .or_else(|| node.child_by_field_name("subscript"))
+                        .is_some_and(|index| range_contain(index.range(), position.clone())) =>
+                {
+                    let argument = node
+                        .child_by_field_name("argument")
+                        .or_else(|| node.child_by_field_name("value"))
+                        .unwrap();
+                    let argument_end = ShaderPosition::new(
+                        position.file_path.clone(),
+                        argument.end_position().row as u32,
+                        argument.end_position().column as u32,
+                    );
+                    return self.find_label_chain_at_position_in_node(
+                        symbol_tree,
+                        argument,
+                        argument_end,
+                    );
+                }
                 "field_identifier" => {
-                    let mut chain = Vec::new();
+                    let mut chain = vec![(
+                        get_name(&symbol_tree.content, node).into(),
+                        ShaderRange::from_range(node.range(), symbol_tree.file_path.clone()),
+                    )];
                     let mut current_node = node.prev_named_sibling().unwrap();
                     loop {
-                        let field = current_node.next_named_sibling().unwrap();
-                        if field.kind() == "field_identifier" {
-                            chain.push((
-                                get_name(&symbol_tree.content, field).into(),
-                                ShaderRange::from_range(
-                                    field.range(),
-                                    symbol_tree.file_path.clone(),
-                                ),
-                            ));
-                        } else {
-                            return Err(SymbolError::InternalErr(format!(
-                                "Unhandled case in find_label_chain_at_position_in_node: {}",
-                                field.kind()
-                            )));
-                        }
-                        match current_node.child_by_field_name("argument") {
-                            Some(child) => {
-                                current_node = child;
+                        match current_node.kind() {
+                            "field_expression" => {
+                                let field = current_node.child_by_field_name("field").unwrap();
+                                chain.push((
+                                    get_name(&symbol_tree.content, field).into(),
+                                    ShaderRange::from_range(
+                                        field.range(),
+                                        symbol_tree.file_path.clone(),
+                                    ),
+                                ));
+                                current_node =
+                                    current_node.child_by_field_name("argument").unwrap();
+                            }
+                            // Array element has same type as array. Skip index.
+                            "subscript_expression" => {
+                                current_node =
+                                    current_node.child_by_field_name("argument").unwrap();
                             }
-                            None => {
+                            _ => {
                                 let identifier = current_node;
                                 chain.push((
                                     get_name(&symbol_tree.content, identifier).into(),
@@ -400,7 +917,7 @@ impl SymbolParser {
                                     ),
                                 ));
                                 break;
-                            } // Should have already break here
+                            }
                         }
                     }
                     return Ok(chain);
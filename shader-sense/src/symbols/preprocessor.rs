@@ -0,0 +1,363 @@
+use std::{collections::HashMap, path::Path};
+
+use tree_sitter::Node;
+
+use crate::{
+    include::{IncludeHandler, IncludeVisit},
+    shader::ShadingLanguage,
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    evaluator::parse_integer_literal,
+    parser::{get_include_path, get_name, SymbolTree},
+    symbols::{ShaderPosition, ShaderRange},
+};
+
+// Avoid infinite recursion with macros referencing each other.
+const MAX_EXPANSION_DEPTH: u32 = 16;
+
+// Pragma documented for hover & completion. Others are passed as is to the validator.
+pub struct ShaderPragma {
+    pub label: &'static str,
+    pub usage: &'static str,
+    pub description: &'static str,
+    pub languages: &'static [ShadingLanguage],
+}
+
+const SHADER_PRAGMAS: [ShaderPragma; 4] = [
+    ShaderPragma {
+        label: "once",
+        usage: "#pragma once",
+        description: "Include the file only once, even if it is included several times.",
+        languages: &[ShadingLanguage::Hlsl, ShadingLanguage::Glsl],
+    },
+    ShaderPragma {
+        label: "shader_stage",
+        usage: "#pragma shader_stage(vertex)",
+        description: "Set the stage of the file, for compilers that can't deduce it from the file name.",
+        languages: &[ShadingLanguage::Glsl],
+    },
+    ShaderPragma {
+        label: "pack_matrix",
+        usage: "#pragma pack_matrix(row_major)",
+        description: "Set the default packing of matrices that follow, row_major or column_major.",
+        languages: &[ShadingLanguage::Hlsl],
+    },
+    ShaderPragma {
+        label: "warning",
+        usage: "#pragma warning(disable: code)",
+        description: "Disable warnings with the given codes in the file, such as compiler warning numbers or lint names.",
+        languages: &[ShadingLanguage::Hlsl, ShadingLanguage::Glsl],
+    },
+];
+
+pub fn get_shader_pragmas(shading_language: ShadingLanguage) -> Vec<&'static ShaderPragma> {
+    SHADER_PRAGMAS
+        .iter()
+        .filter(|pragma| pragma.languages.contains(&shading_language))
+        .collect()
+}
+
+// Codes of #pragma warning(disable: 3206 4000; once: 3205), only disable specifiers are kept.
+fn parse_disabled_warnings(pragma: &str) -> Vec<String> {
+    let Some(specifiers) = pragma
+        .trim()
+        .strip_prefix("warning")
+        .map(|specifiers| specifiers.trim())
+        .and_then(|specifiers| specifiers.strip_prefix('('))
+        .and_then(|specifiers| specifiers.strip_suffix(')'))
+    else {
+        return Vec::new();
+    };
+    specifiers
+        .split(';')
+        .filter_map(|specifier| specifier.split_once(':'))
+        .filter(|(specifier, _)| specifier.trim() == "disable")
+        .flat_map(|(_, codes)| {
+            codes
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|code| !code.is_empty())
+                .map(String::from)
+        })
+        .collect()
+}
+
+// Resolve included files to process them in place, so that their defines apply to what follows.
+pub(super) struct IncludeResolver<'a> {
+    pub include_handler: IncludeHandler,
+    pub include_callback: &'a mut dyn FnMut(&Path) -> Option<SymbolTree>,
+}
+
+// Macros known while walking the file in order.
+struct PreprocessorState<'a> {
+    defines: HashMap<String, String>,
+    // Names may be defined by an unresolved include, so undefined names can't be decided.
+    has_include: bool,
+    include_resolver: Option<IncludeResolver<'a>>,
+    // Included files in the order they were processed.
+    included_trees: Vec<SymbolTree>,
+    inactive_regions: Vec<ShaderRange>,
+    disabled_warnings: Vec<String>,
+}
+
+impl<'a> PreprocessorState<'a> {
+    fn new(params: &ValidationParams, include_resolver: Option<IncludeResolver<'a>>) -> Self {
+        Self {
+            defines: params.defines.clone(),
+            has_include: false,
+            include_resolver,
+            included_trees: Vec::new(),
+            inactive_regions: Vec::new(),
+            disabled_warnings: Vec::new(),
+        }
+    }
+    fn is_defined(&self, name: &str) -> Option<bool> {
+        if self.defines.contains_key(name) {
+            Some(true)
+        } else if self.has_include {
+            None
+        } else {
+            Some(false)
+        }
+    }
+    fn evaluate_value(&self, value: &str, depth: u32) -> Option<i64> {
+        if depth > MAX_EXPANSION_DEPTH {
+            return None;
+        }
+        match self.defines.get(value.trim()) {
+            Some(value) => self.evaluate_value(value, depth + 1),
+            None => parse_integer_literal(value.trim()),
+        }
+    }
+    // Evaluate an #if condition, None if it can't be decided.
+    fn evaluate(&self, content: &str, node: Node) -> Option<i64> {
+        match node.kind() {
+            "number_literal" => parse_integer_literal(get_name(content, node)),
+            "parenthesized_expression" => self.evaluate(content, node.named_child(0)?),
+            "preproc_defined" => self
+                .is_defined(get_name(content, node.named_child(0)?))
+                .map(i64::from),
+            "identifier" => {
+                let name = get_name(content, node);
+                match self.defines.get(name) {
+                    Some(value) => self.evaluate_value(value, 0),
+                    // Undefined identifiers evaluate to 0.
+                    None => self.is_defined(name).map(|_| 0),
+                }
+            }
+            "unary_expression" => {
+                let value = self.evaluate(content, node.child_by_field_name("argument")?)?;
+                match get_name(content, node.child_by_field_name("operator")?) {
+                    "!" => Some(i64::from(value == 0)),
+                    "-" => Some(-value),
+                    "+" => Some(value),
+                    "~" => Some(!value),
+                    _ => None,
+                }
+            }
+            "binary_expression" => {
+                let operator = get_name(content, node.child_by_field_name("operator")?);
+                let left = self.evaluate(content, node.child_by_field_name("left")?);
+                let right = self.evaluate(content, node.child_by_field_name("right")?);
+                // Short circuit allow deciding with a single known operand.
+                let is_true = |value: Option<i64>| value.is_some_and(|value| value != 0);
+                if operator == "&&" && (left == Some(0) || right == Some(0)) {
+                    return Some(0);
+                }
+                if operator == "||" && (is_true(left) || is_true(right)) {
+                    return Some(1);
+                }
+                let (left, right) = (left?, right?);
+                match operator {
+                    "&&" => Some(i64::from(left != 0 && right != 0)),
+                    "||" => Some(i64::from(left != 0 || right != 0)),
+                    "==" => Some(i64::from(left == right)),
+                    "!=" => Some(i64::from(left != right)),
+                    "<" => Some(i64::from(left < right)),
+                    ">" => Some(i64::from(left > right)),
+                    "<=" => Some(i64::from(left <= right)),
+                    ">=" => Some(i64::from(left >= right)),
+                    "+" => left.checked_add(right),
+                    "-" => left.checked_sub(right),
+                    "*" => left.checked_mul(right),
+                    "/" => left.checked_div(right),
+                    "%" => left.checked_rem(right),
+                    "<<" => left.checked_shl(u32::try_from(right).ok()?),
+                    ">>" => left.checked_shr(u32::try_from(right).ok()?),
+                    "&" => Some(left & right),
+                    "|" => Some(left | right),
+                    "^" => Some(left ^ right),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+    // Condition of an #if, #elif, #ifdef or #ifndef branch.
+    fn evaluate_branch(&self, content: &str, node: Node) -> Option<bool> {
+        if node.kind() == "preproc_ifdef" {
+            let defined = self.is_defined(get_name(content, node.child_by_field_name("name")?))?;
+            let is_ifndef = node
+                .child(0)
+                .is_some_and(|directive| directive.kind() == "#ifndef");
+            Some(defined != is_ifndef)
+        } else {
+            self.evaluate(content, node.child_by_field_name("condition")?)
+                .map(|value| value != 0)
+        }
+    }
+    // Walk the included file with the current state. Return false if it could not be resolved.
+    fn process_include(&mut self, symbol_tree: &SymbolTree, node: Node) -> bool {
+        let Some(include_resolver) = &mut self.include_resolver else {
+            return false;
+        };
+        let Some((relative_path, _)) = get_include_path(symbol_tree, node) else {
+            return false;
+        };
+        let Some(absolute_path) = include_resolver
+            .include_handler
+            .search_path_in_includes(Path::new(&relative_path))
+        else {
+            return false;
+        };
+        match include_resolver
+            .include_handler
+            .enter_include(&absolute_path)
+        {
+            IncludeVisit::First => {
+                let included_tree = (include_resolver.include_callback)(&absolute_path);
+                let is_resolved = included_tree.is_some();
+                if let Some(included_tree) = included_tree {
+                    collect_inactive_regions(&included_tree, included_tree.tree.root_node(), self);
+                    self.included_trees.push(included_tree);
+                }
+                if let Some(include_resolver) = &mut self.include_resolver {
+                    include_resolver.include_handler.exit_include();
+                }
+                is_resolved
+            }
+            // Defines were already applied.
+            IncludeVisit::AlreadyVisited | IncludeVisit::Circular(_) => true,
+        }
+    }
+}
+
+fn get_branch_range(file_path: &Path, node: Node) -> Option<ShaderRange> {
+    // Body start after the condition, and end at the next branch.
+    let start = node
+        .child_by_field_name("condition")
+        .or_else(|| node.child_by_field_name("name"))?
+        .end_position();
+    let end = match node.child_by_field_name("alternative") {
+        Some(alternative) => alternative.start_position(),
+        None => node.end_position(),
+    };
+    let position = |point: tree_sitter::Point| {
+        ShaderPosition::new(file_path.into(), point.row as u32, point.column as u32)
+    };
+    Some(ShaderRange::new(position(start), position(end)))
+}
+
+fn collect_inactive_regions(symbol_tree: &SymbolTree, node: Node, state: &mut PreprocessorState) {
+    let content = symbol_tree.content.as_str();
+    match node.kind() {
+        "preproc_def" | "preproc_function_def" => {
+            if let Some(name) = node.child_by_field_name("name") {
+                let value = node
+                    .child_by_field_name("value")
+                    .map_or("", |value| get_name(content, value));
+                state
+                    .defines
+                    .insert(get_name(content, name).into(), value.into());
+            }
+            return;
+        }
+        "preproc_call" => {
+            let directive = node.child_by_field_name("directive");
+            let argument = node.child_by_field_name("argument");
+            if let (Some(directive), Some(argument)) = (directive, argument) {
+                match get_name(content, directive) {
+                    "#undef" => {
+                        state.defines.remove(get_name(content, argument).trim());
+                    }
+                    "#pragma" => state
+                        .disabled_warnings
+                        .extend(parse_disabled_warnings(get_name(content, argument))),
+                    _ => {}
+                }
+            }
+            return;
+        }
+        "preproc_include" => {
+            if !state.process_include(symbol_tree, node) {
+                state.has_include = true;
+            }
+            return;
+        }
+        _ => {}
+    }
+    let is_branch = matches!(node.kind(), "preproc_if" | "preproc_ifdef" | "preproc_elif");
+    let alternative = node.child_by_field_name("alternative");
+    let (is_active, is_alternative_active) = if is_branch {
+        match state.evaluate_branch(content, node) {
+            Some(true) => (true, false),
+            Some(false) => (false, true),
+            None => (true, true),
+        }
+    } else {
+        (true, true)
+    };
+    if !is_active {
+        state
+            .inactive_regions
+            .extend(get_branch_range(&symbol_tree.file_path, node));
+    }
+    if let (false, Some(alternative)) = (is_alternative_active, alternative) {
+        state.inactive_regions.push(ShaderRange::from_range(
+            alternative.range(),
+            symbol_tree.file_path.clone(),
+        ));
+    }
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let is_child_alternative = alternative.is_some_and(|alternative| alternative == child);
+        if (is_child_alternative && is_alternative_active) || (!is_child_alternative && is_active) {
+            collect_inactive_regions(symbol_tree, child, state);
+        }
+    }
+}
+
+// Ranges of the conditional branches discarded by the preprocessor with the given defines.
+// Branches depending on macros that can't be resolved are considered active.
+pub(super) fn get_inactive_regions(
+    symbol_tree: &SymbolTree,
+    params: &ValidationParams,
+) -> Vec<ShaderRange> {
+    let mut state = PreprocessorState::new(params, None);
+    collect_inactive_regions(symbol_tree, symbol_tree.tree.root_node(), &mut state);
+    state.inactive_regions
+}
+
+// Warning codes disabled with #pragma warning in active branches of the file.
+pub(super) fn get_disabled_warnings(
+    symbol_tree: &SymbolTree,
+    params: &ValidationParams,
+) -> Vec<String> {
+    let mut state = PreprocessorState::new(params, None);
+    collect_inactive_regions(symbol_tree, symbol_tree.tree.root_node(), &mut state);
+    state.disabled_warnings
+}
+
+// Process includes in order as the preprocessor would, so that defines of earlier includes
+// select the active branches of later ones. Includes of inactive branches are skipped.
+// Return the discarded ranges of every file & the included trees in processing order.
+pub(super) fn process_includes(
+    symbol_tree: &SymbolTree,
+    params: &ValidationParams,
+    include_resolver: IncludeResolver,
+) -> (Vec<ShaderRange>, Vec<SymbolTree>) {
+    let mut state = PreprocessorState::new(params, Some(include_resolver));
+    collect_inactive_regions(symbol_tree, symbol_tree.tree.root_node(), &mut state);
+    (state.inactive_regions, state.included_trees)
+}
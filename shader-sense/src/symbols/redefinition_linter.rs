@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use tree_sitter::Node;
+
+use crate::shader_error::{ShaderDiagnostic, ShaderDiagnosticList, ShaderErrorSeverity};
+
+use super::{
+    parser::{get_name, SymbolTree},
+    symbols::{ShaderRange, ShaderSymbol, ShaderSymbolData, ShaderSymbolList},
+};
+
+// Include guards such as #ifndef HEADER_H followed by #define HEADER_H wrap the whole file.
+fn is_include_guard(shader_content: &str, node: Node) -> bool {
+    let Some(name) = node.child_by_field_name("name") else {
+        return false;
+    };
+    node.child(0).is_some_and(|child| child.kind() == "#ifndef")
+        && node
+            .named_children(&mut node.walk())
+            .find(|child| child.kind() == "preproc_def")
+            .and_then(|define| define.child_by_field_name("name"))
+            .is_some_and(|define| {
+                get_name(shader_content, define) == get_name(shader_content, name)
+            })
+}
+
+// Innermost conditional branch of a declaration. Both branches of a condition that can't be
+// resolved are kept by the preprocessor pass, so declarations in different branches are legal.
+fn get_conditional_branch<'a>(
+    symbol_tree: &'a SymbolTree,
+    range: &ShaderRange,
+) -> Option<Node<'a>> {
+    let start = tree_sitter::Point::new(range.start.line as usize, range.start.pos as usize);
+    let end = tree_sitter::Point::new(range.end.line as usize, range.end.pos as usize);
+    let mut node = symbol_tree
+        .tree
+        .root_node()
+        .descendant_for_point_range(start, end)?;
+    while let Some(parent) = node.parent() {
+        match parent.kind() {
+            "preproc_ifdef" if is_include_guard(&symbol_tree.content, parent) => {}
+            "preproc_if" | "preproc_ifdef" | "preproc_else" | "preproc_elif"
+            | "preproc_elifdef" => return Some(parent),
+            _ => {}
+        }
+        node = parent;
+    }
+    None
+}
+
+// Overloads with different parameter types are legal, so functions are keyed by their signature.
+fn get_redefinition_key(symbol: &ShaderSymbol) -> Option<(String, Option<Vec<String>>)> {
+    let is_global = match &symbol.scope_stack {
+        Some(scope_stack) => scope_stack.is_empty(),
+        None => true,
+    };
+    if symbol.range.is_none() || !is_global {
+        return None;
+    }
+    match &symbol.data {
+        ShaderSymbolData::Struct { .. } | ShaderSymbolData::Interface { .. } => {
+            Some((symbol.label.clone(), None))
+        }
+        ShaderSymbolData::Functions { signatures, .. } => Some((
+            symbol.label.clone(),
+            Some(
+                signatures
+                    .first()?
+                    .parameters
+                    .iter()
+                    .map(|parameter| {
+                        parameter
+                            .ty
+                            .split_whitespace()
+                            .collect::<Vec<&str>>()
+                            .join(" ")
+                    })
+                    .collect(),
+            ),
+        )),
+        _ => None,
+    }
+}
+
+// Functions & structs declared twice in a file or its includes. Only declarations of the file
+// are reported, includes being reported when they are opened.
+pub(super) fn query_redefinition_diagnostics(
+    symbol_tree: &SymbolTree,
+    symbol_list: &ShaderSymbolList,
+    diagnostics: &mut ShaderDiagnosticList,
+) {
+    let is_in_file = |range: &ShaderRange| range.start.file_path == symbol_tree.file_path;
+    // Included declarations come first, as includes are usually at the top of the file.
+    let mut symbols: Vec<&ShaderSymbol> = symbol_list
+        .types
+        .iter()
+        .chain(symbol_list.functions.iter())
+        .filter(|symbol| symbol.range.is_some())
+        .collect();
+    symbols.sort_by_key(|symbol| {
+        let range = symbol.range.as_ref().unwrap();
+        (is_in_file(range), range.start.line, range.start.pos)
+    });
+    let mut declarations: HashMap<(String, Option<Vec<String>>), &ShaderRange> = HashMap::new();
+    for symbol in symbols {
+        let Some(key) = get_redefinition_key(symbol) else {
+            continue;
+        };
+        let range = symbol.range.as_ref().unwrap();
+        let Some(first_range) = declarations.get(&key) else {
+            declarations.insert(key, range);
+            continue;
+        };
+        if !is_in_file(range) {
+            continue;
+        }
+        let branch = get_conditional_branch(symbol_tree, range);
+        let is_redefinition = if is_in_file(first_range) {
+            branch == get_conditional_branch(symbol_tree, first_range)
+        } else {
+            branch.is_none()
+        };
+        if !is_redefinition {
+            continue;
+        }
+        diagnostics.push(ShaderDiagnostic {
+            file_path: Some(symbol_tree.file_path.clone()),
+            severity: ShaderErrorSeverity::Warning,
+            error: format!(
+                "'{}' is already defined at {}:{}:{}.",
+                symbol.label,
+                first_range
+                    .start
+                    .file_path
+                    .file_name()
+                    .map(|file_name| file_name.to_string_lossy())
+                    .unwrap_or_default(),
+                first_range.start.line + 1,
+                first_range.start.pos + 1
+            ),
+            line: range.start.line + 1,
+            pos: range.start.pos,
+            end: Some((range.end.line + 1, range.end.pos)),
+            unnecessary: false,
+            code: None,
+            fix: None,
+        });
+    }
+}
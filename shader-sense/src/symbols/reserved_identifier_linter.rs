@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use crate::{
+    shader_error::{ShaderDiagnosticList, ShaderErrorSeverity},
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    linter::{node_diagnostic, SymbolTreeLinter},
+    parser::get_name,
+};
+
+// Keywords reserved for future use by the GLSL specification.
+const GLSL_RESERVED_KEYWORDS: [&str; 38] = [
+    "common",
+    "partition",
+    "active",
+    "asm",
+    "class",
+    "union",
+    "enum",
+    "typedef",
+    "template",
+    "this",
+    "resource",
+    "goto",
+    "inline",
+    "noinline",
+    "public",
+    "static",
+    "extern",
+    "external",
+    "interface",
+    "long",
+    "short",
+    "half",
+    "fixed",
+    "unsigned",
+    "superp",
+    "input",
+    "output",
+    "hvec2",
+    "hvec3",
+    "hvec4",
+    "fvec2",
+    "fvec3",
+    "fvec4",
+    "filter",
+    "sizeof",
+    "cast",
+    "namespace",
+    "using",
+];
+
+pub(super) struct ReservedIdentifierLinter {}
+
+impl ReservedIdentifierLinter {
+    fn get_reserved_reason(identifier: &str) -> Option<&'static str> {
+        if identifier.starts_with("gl_") {
+            Some("names starting with 'gl_' are reserved for builtins")
+        } else if identifier.contains("__") {
+            Some("names containing '__' are reserved")
+        } else if GLSL_RESERVED_KEYWORDS.contains(&identifier) {
+            Some("it is a keyword reserved for future use")
+        } else {
+            None
+        }
+    }
+}
+
+impl SymbolTreeLinter for ReservedIdentifierLinter {
+    fn get_code(&self) -> &'static str {
+        "reservedIdentifier"
+    }
+    fn get_query(&self) -> String {
+        r#"[
+            (declaration declarator: (identifier) @identifier)
+            (init_declarator declarator: (identifier) @identifier)
+            (array_declarator declarator: (identifier) @identifier)
+            (array_declarator declarator: (field_identifier) @identifier)
+            (function_declarator declarator: (identifier) @identifier)
+            (parameter_declaration declarator: (identifier) @identifier)
+            (field_declaration declarator: (field_identifier) @identifier)
+            (struct_specifier name: (type_identifier) @identifier)
+            (preproc_def name: (identifier) @identifier)
+            (preproc_function_def name: (identifier) @identifier)
+        ]"#
+        .into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        if !params.lint_reserved_identifiers {
+            return;
+        }
+        let identifier_node = matches.captures[0].node;
+        let identifier = get_name(shader_content, identifier_node);
+        if let Some(reason) = Self::get_reserved_reason(identifier) {
+            diagnostics.push(node_diagnostic(
+                identifier_node,
+                file_path,
+                ShaderErrorSeverity::Warning,
+                format!("'{}' is a reserved identifier: {}.", identifier, reason),
+            ));
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::path::Path;
+
+use tree_sitter::Node;
+
+use crate::{
+    shader_error::{ShaderDiagnosticList, ShaderErrorSeverity},
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    evaluator::evaluate_integer_expression,
+    hlsl_parser::get_register,
+    linter::{node_diagnostic, SymbolTreeLinter},
+    parser::get_name,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResourceKind {
+    Texture,
+    Sampler,
+    Uav,
+}
+
+impl ResourceKind {
+    // Register type takes precedence over the resource type.
+    fn from_declaration(ty: &str, register_type: Option<&str>) -> Option<Self> {
+        match register_type {
+            Some("t") => Some(ResourceKind::Texture),
+            Some("s") => Some(ResourceKind::Sampler),
+            Some("u") => Some(ResourceKind::Uav),
+            Some(_) => None,
+            None => {
+                if ty.starts_with("RW")
+                    || ty.starts_with("RasterizerOrdered")
+                    || ty == "AppendStructuredBuffer"
+                    || ty == "ConsumeStructuredBuffer"
+                {
+                    Some(ResourceKind::Uav)
+                } else if ty.starts_with("Sampler") {
+                    Some(ResourceKind::Sampler)
+                } else if ty.starts_with("Texture")
+                    || ty == "Buffer"
+                    || ty == "StructuredBuffer"
+                    || ty == "ByteAddressBuffer"
+                {
+                    Some(ResourceKind::Texture)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+    fn get_name(&self) -> &'static str {
+        match self {
+            ResourceKind::Texture => "texture",
+            ResourceKind::Sampler => "sampler",
+            ResourceKind::Uav => "UAV",
+        }
+    }
+}
+
+// Count global resources of the whole file, so match the root only.
+pub(super) struct ResourceLimitLinter {}
+
+impl ResourceLimitLinter {
+    // Global declarations, including the ones in preprocessor blocks, which are all counted.
+    fn collect_declarations<'a>(node: Node<'a>, declarations: &mut Vec<Node<'a>>) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            match child.kind() {
+                "declaration" => declarations.push(child),
+                "function_definition" | "struct_specifier" => {}
+                _ => Self::collect_declarations(child, declarations),
+            }
+        }
+    }
+}
+
+impl SymbolTreeLinter for ResourceLimitLinter {
+    fn get_code(&self) -> &'static str {
+        "resourceLimits"
+    }
+    fn get_query(&self) -> String {
+        r#"(translation_unit) @resource.root"#.into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        let limits = match params.resource_limits {
+            Some(limits) => limits,
+            None => return,
+        };
+        let mut declarations = Vec::new();
+        Self::collect_declarations(matches.captures[0].node, &mut declarations);
+        let mut counts = [0u32; 3];
+        for declaration in declarations {
+            let ty = match declaration.child_by_field_name("type") {
+                Some(ty) => match ty.child_by_field_name("name") {
+                    Some(name) => get_name(shader_content, name), // Template type
+                    None => get_name(shader_content, ty),
+                },
+                None => continue,
+            };
+            let register = get_register(shader_content, declaration);
+            let kind = match ResourceKind::from_declaration(
+                ty,
+                register.as_ref().map(|register| register.ty.as_str()),
+            ) {
+                Some(kind) => kind,
+                None => continue,
+            };
+            let (count, limit) = match kind {
+                ResourceKind::Texture => (&mut counts[0], limits.textures),
+                ResourceKind::Sampler => (&mut counts[1], limits.samplers),
+                ResourceKind::Uav => (&mut counts[2], limits.uavs),
+            };
+            let mut cursor = declaration.walk();
+            for declarator in declaration.children_by_field_name("declarator", &mut cursor) {
+                if declarator.kind() == "semantics" {
+                    continue;
+                }
+                // Arrays use one slot per element. Unbounded arrays count as one.
+                let mut size: u32 = 1;
+                let mut identifier = declarator;
+                while identifier.kind() == "array_declarator" {
+                    let length = identifier
+                        .child_by_field_name("size")
+                        .and_then(|node| evaluate_integer_expression(shader_content, node, params))
+                        .and_then(|length| u32::try_from(length).ok())
+                        .unwrap_or(1);
+                    size = size.saturating_mul(length);
+                    identifier = match identifier.child_by_field_name("declarator") {
+                        Some(identifier) => identifier,
+                        None => break,
+                    };
+                }
+                *count = count.saturating_add(size);
+                let register_end = register
+                    .as_ref()
+                    .map_or(0, |register| register.index.saturating_add(size));
+                let name = get_name(shader_content, identifier);
+                let error = if *count > limit {
+                    format!(
+                        "Declaring '{}' brings {} count to {}, above the limit of {}.",
+                        name,
+                        kind.get_name(),
+                        count,
+                        limit
+                    )
+                } else if register_end > limit {
+                    let register = register.as_ref().unwrap();
+                    format!(
+                        "'{}' is bound to {}{}-{}{}, above the limit of {} {} slots.",
+                        name,
+                        register.ty,
+                        register.index,
+                        register.ty,
+                        register_end - 1,
+                        limit,
+                        kind.get_name()
+                    )
+                } else {
+                    continue;
+                };
+                let mut diagnostic =
+                    node_diagnostic(identifier, file_path, ShaderErrorSeverity::Warning, error);
+                diagnostic.end = Some((
+                    identifier.end_position().row as u32 + 1,
+                    identifier.end_position().column as u32,
+                ));
+                diagnostics.push(diagnostic);
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::{
+    shader::ShadingLanguage,
+    shader_error::{ShaderDiagnosticList, ShaderErrorSeverity},
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    linter::{find_variable_type, node_diagnostic, SymbolTreeLinter},
+    parser::get_name,
+};
+
+pub(super) struct SwizzleLinter {
+    shading_language: ShadingLanguage,
+    vector_regex: Regex,
+}
+
+impl SwizzleLinter {
+    pub fn new(shading_language: ShadingLanguage) -> Self {
+        // Scalar types can also be swizzled, so they are considered as vector of dimension 1.
+        let vector_regex = match shading_language {
+            ShadingLanguage::Hlsl => Regex::new(
+                r"^(?:bool|int|uint|dword|half|float|double|min16float|min10float|min16int|min12int|min16uint|int16_t|uint16_t|float16_t|int32_t|uint32_t|float32_t|int64_t|uint64_t|float64_t)([1-4])?$",
+            ),
+            ShadingLanguage::Glsl => {
+                Regex::new(r"^(?:(?:bool|int|uint|float|double)|(?:b|i|u|d|f16|i64|u64)?vec([2-4]))$")
+            }
+            ShadingLanguage::Wgsl => Regex::new(r"^(?:bool|i32|u32|f32|f16|vec([2-4])[ifuh]?)$"),
+        }
+        .unwrap();
+        Self {
+            shading_language,
+            vector_regex,
+        }
+    }
+    // Get the number of components of a vector type. None if the type is not a vector.
+    fn get_vector_dimension(&self, ty: &str) -> Option<usize> {
+        let capture = self.vector_regex.captures(ty.trim())?;
+        match capture.get(1) {
+            Some(dimension) => dimension.as_str().parse::<usize>().ok(),
+            None => Some(1),
+        }
+    }
+    fn get_component_sets(&self) -> &'static [&'static str] {
+        match self.shading_language {
+            ShadingLanguage::Glsl => &["xyzw", "rgba", "stpq"],
+            ShadingLanguage::Hlsl | ShadingLanguage::Wgsl => &["xyzw", "rgba"],
+        }
+    }
+    // Get the dimension of the swizzled expression if it is a vector.
+    fn get_expression_dimension(
+        &self,
+        shader_content: &str,
+        node: tree_sitter::Node,
+    ) -> Option<usize> {
+        match node.kind() {
+            "identifier" => {
+                let ty = find_variable_type(shader_content, node, get_name(shader_content, node))?;
+                self.get_vector_dimension(ty)
+            }
+            // Vector constructor such as float3(...)
+            "call_expression" => {
+                let function = node.child_by_field_name("function")?;
+                self.get_vector_dimension(get_name(shader_content, function))
+            }
+            // Chained swizzle such as v.xy.x
+            "field_expression" => {
+                self.get_expression_dimension(
+                    shader_content,
+                    node.child_by_field_name("argument")?,
+                )?;
+                let field = node.child_by_field_name("field")?;
+                Some(get_name(shader_content, field).len())
+            }
+            "parenthesized_expression" => {
+                self.get_expression_dimension(shader_content, node.named_child(0)?)
+            }
+            _ => None,
+        }
+    }
+    fn validate_swizzle(&self, swizzle: &str, dimension: usize) -> Result<(), String> {
+        if swizzle.len() > 4 {
+            return Err(format!(
+                "Swizzle '.{}' has more than 4 components.",
+                swizzle
+            ));
+        }
+        let component_set = match self
+            .get_component_sets()
+            .iter()
+            .find(|set| swizzle.chars().all(|c| set.contains(c)))
+        {
+            Some(component_set) => component_set,
+            None => {
+                return Err(
+                    if swizzle
+                        .chars()
+                        .all(|c| self.get_component_sets().iter().any(|set| set.contains(c)))
+                    {
+                        format!(
+                            "Swizzle '.{}' mixes components from different sets.",
+                            swizzle
+                        )
+                    } else {
+                        format!("'.{}' is not a valid swizzle.", swizzle)
+                    },
+                )
+            }
+        };
+        for component in swizzle.chars() {
+            if component_set.find(component).unwrap() >= dimension {
+                return Err(format!(
+                    "Swizzle '.{}' accesses component '{}' out of range for a vector of {} component(s).",
+                    swizzle, component, dimension
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SymbolTreeLinter for SwizzleLinter {
+    fn get_code(&self) -> &'static str {
+        "swizzle"
+    }
+    fn get_query(&self) -> String {
+        r#"(field_expression
+            argument: (_) @swizzle.argument
+            field: (field_identifier) @swizzle.field
+        ) @swizzle"#
+            .into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        _params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        let expression_node = matches.captures[0].node;
+        let argument_node = matches.captures[1].node;
+        let field_node = matches.captures[2].node;
+        let dimension = match self.get_expression_dimension(shader_content, argument_node) {
+            Some(dimension) => dimension,
+            None => return, // Not a vector (struct, matrix, unknown...)
+        };
+        let swizzle = get_name(shader_content, field_node);
+        if let Err(error) = self.validate_swizzle(swizzle, dimension) {
+            diagnostics.push(node_diagnostic(
+                field_node,
+                file_path,
+                ShaderErrorSeverity::Error,
+                error,
+            ));
+            return;
+        }
+        // Writing multiple time the same component is invalid.
+        let is_assignment_target = match expression_node.parent() {
+            Some(parent) => {
+                parent.kind() == "assignment_expression"
+                    && parent.child_by_field_name("left") == Some(expression_node)
+            }
+            None => false,
+        };
+        if is_assignment_target {
+            let component_set = self
+                .get_component_sets()
+                .iter()
+                .find(|set| swizzle.chars().all(|c| set.contains(c)))
+                .unwrap();
+            let mut written = [false; 4];
+            for component in swizzle.chars() {
+                let index = component_set.find(component).unwrap();
+                if written[index] {
+                    diagnostics.push(node_diagnostic(
+                        field_node,
+                        file_path,
+                        ShaderErrorSeverity::Error,
+                        format!(
+                            "Swizzle '.{}' cannot be used as an assignment target as it writes component '{}' more than once.",
+                            swizzle, component
+                        ),
+                    ));
+                    break;
+                }
+                written[index] = true;
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use super::parser::SymbolTree;
+
+pub const DEFAULT_SYMBOL_TREE_CACHE_CAPACITY: usize = 64;
+
+// Least recently used cache of parsed trees, keyed by file path & content hash.
+pub(super) struct SymbolTreeCache {
+    entries: Vec<((PathBuf, u64), SymbolTree)>, // Most recently used last.
+    capacity: usize,
+    hits: usize,
+    misses: usize,
+}
+
+fn hash_content(shader_content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    shader_content.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl SymbolTreeCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            capacity,
+            hits: 0,
+            misses: 0,
+        }
+    }
+    pub fn get(&mut self, file_path: &Path, shader_content: &str) -> Option<SymbolTree> {
+        let key = (file_path.to_path_buf(), hash_content(shader_content));
+        // Compare content too in case of hash collision.
+        match self.entries.iter().position(|(entry_key, symbol_tree)| {
+            *entry_key == key && symbol_tree.content == shader_content
+        }) {
+            Some(index) => {
+                self.hits += 1;
+                let entry = self.entries.remove(index);
+                let symbol_tree = entry.1.clone();
+                self.entries.push(entry);
+                Some(symbol_tree)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+    pub fn insert(&mut self, symbol_tree: &SymbolTree) {
+        if self.capacity == 0 {
+            return;
+        }
+        let key = (
+            symbol_tree.file_path.clone(),
+            hash_content(&symbol_tree.content),
+        );
+        self.entries.retain(|(entry_key, _)| *entry_key != key);
+        self.entries.push((key, symbol_tree.clone()));
+        self.evict();
+    }
+    pub fn invalidate(&mut self, file_path: &Path) {
+        self.entries
+            .retain(|((entry_path, _), _)| entry_path != file_path);
+    }
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+    // Cache hits & misses. Misses are the number of parsed trees.
+    pub fn get_stats(&self) -> (usize, usize) {
+        (self.hits, self.misses)
+    }
+    fn evict(&mut self) {
+        if self.entries.len() > self.capacity {
+            let count = self.entries.len() - self.capacity;
+            self.entries.drain(..count);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     cmp::Ordering,
+    collections::HashMap,
     fmt::Display,
     path::{Path, PathBuf},
 };
@@ -7,13 +8,28 @@ use std::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    include::IncludeHandler,
     shader::{ShaderStage, ShadingLanguage},
+    shader_error::{ShaderDiagnostic, ShaderDiagnosticList, ShaderErrorSeverity},
     validator::validator::ValidationParams,
 };
 
 use super::{
-    glsl_filter::{GlslStageFilter, GlslVersionFilter},
+    call_argument_linter::query_call_argument_diagnostics,
+    glsl_filter::{
+        get_glsl_enabled_extensions, get_glsl_version_directive, is_glsl_extension,
+        GlslVersionFilter,
+    },
+    hlsl_filter::HlslShaderModelFilter,
+    linter::{
+        set_diagnostics_code, AMBIGUOUS_ENTRY_POINT_CODE, CALL_ARGUMENTS_CODE, REDEFINITION_CODE,
+    },
     parser::{SymbolParser, SymbolTree},
+    preprocessor::{
+        get_disabled_warnings, get_inactive_regions, process_includes, IncludeResolver,
+    },
+    redefinition_linter::query_redefinition_diagnostics,
+    symbol_tree_cache::{SymbolTreeCache, DEFAULT_SYMBOL_TREE_CACHE_CAPACITY},
 };
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -23,6 +39,34 @@ pub struct ShaderParameter {
     pub description: String,
 }
 
+// HLSL resource binding, such as register(t0, space1).
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct ShaderRegister {
+    pub ty: String, // Register type: t, s, b or u.
+    pub index: u32,
+    pub space: u32,
+}
+
+impl Display for ShaderRegister {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.space == 0 {
+            write!(f, "register({}{})", self.ty, self.index)
+        } else {
+            write!(
+                f,
+                "register({}{}, space{})",
+                self.ty, self.index, self.space
+            )
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ShaderVectorType {
+    pub scalar: String,
+    pub dimension: u32,
+}
+
 #[allow(non_snake_case)] // for JSON
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct ShaderSignature {
@@ -38,7 +82,30 @@ pub struct ShaderLabelSignature {
     pub signature: ShaderSignature,
 }
 
+impl ShaderParameter {
+    pub fn is_variadic(&self) -> bool {
+        self.label == "..."
+    }
+    // Intrinsics mark optional parameters in their description.
+    pub fn is_optional(&self) -> bool {
+        self.is_variadic() || self.description.starts_with("Optional")
+    }
+}
+
 impl ShaderSignature {
+    // Trailing optional & variadic parameters can be omitted.
+    pub fn accept_argument_count(&self, count: usize) -> bool {
+        let required_count = self
+            .parameters
+            .iter()
+            .take_while(|parameter| !parameter.is_optional())
+            .count();
+        let is_variadic = self
+            .parameters
+            .last()
+            .is_some_and(|parameter| parameter.is_variadic());
+        count >= required_count && (is_variadic || count <= self.parameters.len())
+    }
     pub fn format(&self, label: &str) -> String {
         let signature = self
             .parameters
@@ -94,7 +161,8 @@ impl ShaderPosition {
         }
     }
     pub fn to_byte_offset(&self, content: &str) -> usize {
-        match content.lines().nth(self.line as usize) {
+        // Split on new line to handle position on the empty last line.
+        match content.split('\n').nth(self.line as usize) {
             Some(line) => {
                 let pos = line.as_ptr() as usize - content.as_ptr() as usize;
                 pos + self.pos as usize
@@ -162,9 +230,12 @@ impl ShaderMember {
             link: None,
             data: ShaderSymbolData::Variables {
                 ty: self.ty.clone(),
+                qualifier: "".into(),
+                register: None,
             },
             range: None, // Should have a position ?
             scope_stack: None,
+            snippet: None,
         }
     }
 }
@@ -179,9 +250,11 @@ impl ShaderMethod {
             link: None,
             data: ShaderSymbolData::Functions {
                 signatures: vec![self.signature.clone()],
+                workgroup_size: None,
             },
             range: None, // Should have a position ?
             scope_stack: None,
+            snippet: None,
         }
     }
 }
@@ -192,11 +265,22 @@ pub enum ShaderSymbolData {
     None,
     Types {
         ty: String,
+        // Scalar & number of components of vector types. Scalars have a single component.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        vector: Option<ShaderVectorType>,
     },
     Struct {
         members: Vec<ShaderMember>,
         methods: Vec<ShaderMethod>,
     },
+    // Methods an implementing struct must provide.
+    Interface {
+        methods: Vec<ShaderMethod>,
+    },
+    // Generic type parameter or associated type, with the interfaces it must implement.
+    TypeParameter {
+        constraints: Vec<String>,
+    },
     Constants {
         ty: String,
         qualifier: String,
@@ -204,9 +288,25 @@ pub enum ShaderSymbolData {
     },
     Variables {
         ty: String,
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        qualifier: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        register: Option<ShaderRegister>,
     },
     Functions {
         signatures: Vec<ShaderSignature>,
+        // Compute entry point dimensions, as expressions written in source.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        workgroup_size: Option<[String; 3]>,
+    },
+    Macro {
+        // Parameters of function-like macros, None for object-like macros.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parameters: Option<Vec<String>>,
+        value: String,
+        // Supplied through ValidationParams defines rather than the source.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        external: bool,
     },
     Keyword {},
     Link {
@@ -222,6 +322,8 @@ pub struct ShaderSymbol {
     pub version: String,          // Minimum version required for the item.
     pub stages: Vec<ShaderStage>, // Shader stages of the item
     pub link: Option<String>,     // Link to some external documentation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>, // Custom snippet to insert on completion
     pub data: ShaderSymbolData,   // Data for the variable
     // Runtime info. No serialization.
     #[serde(skip)]
@@ -286,6 +388,25 @@ impl ShaderSymbolList {
             .find(|s| s.label == *label)
             .map(|s| s.clone())
     }
+    // Vector type of the given scalar & dimension, such as float2 for float & 2.
+    // Scalars are preferred over single component vectors such as float1.
+    pub fn find_vector_type_symbol(&self, scalar: &str, dimension: u32) -> Option<ShaderSymbol> {
+        if dimension == 1 {
+            if let Some(symbol) = self.types.iter().find(|s| s.label == scalar) {
+                return Some(symbol.clone());
+            }
+        }
+        self.types
+            .iter()
+            .find(|s| match &s.data {
+                ShaderSymbolData::Types {
+                    vector: Some(vector),
+                    ..
+                } => vector.scalar == scalar && vector.dimension == dimension,
+                _ => false,
+            })
+            .cloned()
+    }
     pub fn append(&mut self, shader_symbol_list: ShaderSymbolList) {
         let mut shader_symbol_list_mut = shader_symbol_list;
         self.functions.append(&mut shader_symbol_list_mut.functions);
@@ -351,7 +472,6 @@ impl ShaderSymbolList {
                 None => true,
             }
         };
-        // TODO: should add a filter for when multiple same definition: pick latest (shadowing)
         let filter_all = |shader_symbols: &ShaderSymbol| -> Option<ShaderSymbol> {
             if filter_position(shader_symbols) && filter_scope(shader_symbols) {
                 Some(shader_symbols.clone())
@@ -359,13 +479,37 @@ impl ShaderSymbolList {
                 None
             }
         };
-        ShaderSymbolList {
+        let mut scoped_symbols = ShaderSymbolList {
             functions: self.functions.iter().filter_map(filter_all).collect(),
             types: self.types.iter().filter_map(filter_all).collect(),
             constants: self.constants.iter().filter_map(filter_all).collect(),
             variables: self.variables.iter().filter_map(filter_all).collect(),
             keywords: self.keywords.iter().filter_map(filter_all).collect(),
+        };
+        // Shadowing: only keep the innermost declarations of a variable or constant.
+        let scope_depth = |shader_symbol: &ShaderSymbol| -> usize {
+            shader_symbol
+                .scope_stack
+                .as_ref()
+                .map_or(0, |scope_stack| scope_stack.len())
+        };
+        let mut innermost_depth: HashMap<String, usize> = HashMap::new();
+        for shader_symbol in scoped_symbols
+            .variables
+            .iter()
+            .chain(scoped_symbols.constants.iter())
+        {
+            let depth = innermost_depth
+                .entry(shader_symbol.label.clone())
+                .or_insert(0);
+            *depth = (*depth).max(scope_depth(shader_symbol));
         }
+        let is_innermost = |shader_symbol: &ShaderSymbol| -> bool {
+            scope_depth(shader_symbol) == innermost_depth[&shader_symbol.label]
+        };
+        scoped_symbols.variables.retain(is_innermost);
+        scoped_symbols.constants.retain(is_innermost);
+        scoped_symbols
     }
 }
 
@@ -473,18 +617,41 @@ impl ShaderSymbol {
     pub fn format(&self) -> String {
         match &self.data {
             ShaderSymbolData::None => format!("Unknown {}", self.label.clone()),
-            ShaderSymbolData::Types { ty } => format!("{}", ty), // ty == label
+            ShaderSymbolData::Types { ty, .. } => format!("{}", ty), // ty == label
             ShaderSymbolData::Struct {
                 members: _,
                 methods: _,
             } => format!("struct {}", self.label.clone()),
+            ShaderSymbolData::Interface { .. } => format!("interface {}", self.label),
+            ShaderSymbolData::TypeParameter { constraints } => match constraints.is_empty() {
+                true => self.label.clone(),
+                false => format!("{} : {}", self.label, constraints.join(", ")),
+            },
             ShaderSymbolData::Constants {
                 ty,
                 qualifier,
                 value,
             } => format!("{} {} {} = {};", qualifier, ty, self.label.clone(), value),
-            ShaderSymbolData::Variables { ty } => format!("{} {}", ty, self.label),
-            ShaderSymbolData::Functions { signatures } => signatures[0].format(&self.label), // TODO: append +1 symbol
+            ShaderSymbolData::Variables {
+                ty,
+                qualifier,
+                register,
+            } => {
+                let declaration = if qualifier.is_empty() {
+                    format!("{} {}", ty, self.label)
+                } else {
+                    format!("{} {} {}", qualifier, ty, self.label)
+                };
+                match register {
+                    Some(register) => format!("{} : {}", declaration, register),
+                    None => declaration,
+                }
+            }
+            ShaderSymbolData::Functions { signatures, .. } => signatures[0].format(&self.label), // TODO: append +1 symbol
+            ShaderSymbolData::Macro { parameters, .. } => match parameters {
+                Some(parameters) => format!("#define {}({})", self.label, parameters.join(", ")),
+                None => format!("#define {}", self.label),
+            },
             ShaderSymbolData::Keyword {} => format!("{}", self.label.clone()),
             ShaderSymbolData::Link { target } => {
                 format!("\"{}\":{}:{}", self.label, target.line, target.pos)
@@ -523,7 +690,12 @@ pub fn parse_default_shader_intrinsics(shading_language: ShadingLanguage) -> Sha
 // scope of oui = [1, 5]
 
 pub(super) trait SymbolFilter {
-    fn filter_symbols(&self, shader_symbols: &mut ShaderSymbolList, file_name: &String);
+    fn filter_symbols(
+        &self,
+        shader_symbols: &mut ShaderSymbolList,
+        file_name: &String,
+        params: &ValidationParams,
+    );
 }
 
 #[derive(Debug, Clone)]
@@ -547,8 +719,10 @@ impl Display for SymbolError {
 // It should even return all available symbols aswell as scopes, that are then recomputed
 pub struct SymbolProvider {
     shader_intrinsics: ShaderSymbolList,
+    shading_language: ShadingLanguage,
     symbol_parser: SymbolParser,
     filters: Vec<Box<dyn SymbolFilter>>,
+    symbol_tree_cache: SymbolTreeCache,
 }
 
 impl SymbolProvider {
@@ -556,21 +730,27 @@ impl SymbolProvider {
         Self {
             symbol_parser: SymbolParser::glsl(),
             shader_intrinsics: parse_default_shader_intrinsics(ShadingLanguage::Glsl),
-            filters: vec![Box::new(GlslVersionFilter {}), Box::new(GlslStageFilter {})],
+            shading_language: ShadingLanguage::Glsl,
+            filters: vec![Box::new(GlslVersionFilter {})],
+            symbol_tree_cache: SymbolTreeCache::new(DEFAULT_SYMBOL_TREE_CACHE_CAPACITY),
         }
     }
     pub fn hlsl() -> Self {
         Self {
             symbol_parser: SymbolParser::hlsl(),
             shader_intrinsics: parse_default_shader_intrinsics(ShadingLanguage::Hlsl),
-            filters: vec![],
+            shading_language: ShadingLanguage::Hlsl,
+            filters: vec![Box::new(HlslShaderModelFilter {})],
+            symbol_tree_cache: SymbolTreeCache::new(DEFAULT_SYMBOL_TREE_CACHE_CAPACITY),
         }
     }
     pub fn wgsl() -> Self {
         Self {
             symbol_parser: SymbolParser::wgsl(),
             shader_intrinsics: parse_default_shader_intrinsics(ShadingLanguage::Wgsl),
+            shading_language: ShadingLanguage::Wgsl,
             filters: vec![],
+            symbol_tree_cache: SymbolTreeCache::new(DEFAULT_SYMBOL_TREE_CACHE_CAPACITY),
         }
     }
     pub fn from(shading_language: ShadingLanguage) -> Self {
@@ -583,12 +763,111 @@ impl SymbolProvider {
     pub fn get_intrinsics_symbol(&self) -> &ShaderSymbolList {
         &self.shader_intrinsics
     }
+    // Get intrinsics available for this file, such as stage builtins or enabled extensions.
+    pub fn get_file_intrinsics_symbol(
+        &self,
+        symbol_tree: &SymbolTree,
+        params: &ValidationParams,
+    ) -> ShaderSymbolList {
+        let mut shader_intrinsics = self.shader_intrinsics.clone();
+        let file_name = symbol_tree
+            .file_path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        // Version of the file has priority over the configured one.
+        let params = match get_glsl_version_directive(symbol_tree) {
+            Some((glsl_version, glsl_profile)) => ValidationParams {
+                glsl_version: Some(glsl_version),
+                glsl_profile,
+                ..params.clone()
+            },
+            None => params.clone(),
+        };
+        for filter in &self.filters {
+            filter.filter_symbols(&mut shader_intrinsics, &file_name, &params);
+        }
+        // Stage from the file name, or from the entry points of the file.
+        let default_stage = match self.shading_language {
+            ShadingLanguage::Glsl => params.glsl_default_stage,
+            _ => None,
+        };
+        let shader_stage = params
+            .shader_stage
+            .or_else(|| ShaderStage::from_file_name(&file_name))
+            .or_else(|| symbol_tree.get_entry_point_stage())
+            .or(default_stage);
+        let extensions = get_glsl_enabled_extensions(symbol_tree);
+        // Intrinsics without stages are available in all of them.
+        let is_enabled = |symbol: &ShaderSymbol| {
+            let is_stage_available = match shader_stage {
+                Some(shader_stage) => {
+                    symbol.stages.is_empty() || symbol.stages.contains(&shader_stage)
+                }
+                None => true,
+            };
+            is_stage_available
+                && (!is_glsl_extension(&symbol.version) || extensions.contains(&symbol.version))
+        };
+        shader_intrinsics.types.retain(is_enabled);
+        shader_intrinsics.constants.retain(is_enabled);
+        shader_intrinsics.variables.retain(is_enabled);
+        shader_intrinsics.functions.retain(is_enabled);
+        shader_intrinsics.keywords.retain(is_enabled);
+        // Sorted, as maps have no stable order.
+        let mut math_constants: Vec<(&String, &String)> = params.math_constants.iter().collect();
+        math_constants.sort();
+        for (label, value) in math_constants {
+            shader_intrinsics.constants.push(ShaderSymbol {
+                label: label.clone(),
+                description: "Math constant.".into(),
+                version: "".into(),
+                stages: Vec::new(),
+                link: None,
+                data: ShaderSymbolData::Constants {
+                    ty: match self.shading_language {
+                        ShadingLanguage::Wgsl => "f32".into(),
+                        _ => "float".into(),
+                    },
+                    qualifier: "const".into(),
+                    value: value.clone(),
+                },
+                range: None,
+                scope_stack: None,
+                snippet: None,
+            });
+        }
+        shader_intrinsics
+    }
     pub fn create_ast(
         &mut self,
         file_path: &Path,
         shader_content: &str,
     ) -> Result<SymbolTree, SymbolError> {
-        self.symbol_parser.create_ast(&file_path, &shader_content)
+        match self.symbol_tree_cache.get(file_path, shader_content) {
+            Some(symbol_tree) => Ok(symbol_tree),
+            None => {
+                let symbol_tree = self.symbol_parser.create_ast(&file_path, &shader_content)?;
+                self.symbol_tree_cache.insert(&symbol_tree);
+                Ok(symbol_tree)
+            }
+        }
+    }
+    // Parsed trees are cached to avoid reparsing unchanged files such as includes.
+    pub fn clear_ast_cache(&mut self) {
+        self.symbol_tree_cache.clear();
+    }
+    // Drop cached trees of a file, when it changed on disk.
+    pub fn invalidate_ast_cache(&mut self, file_path: &Path) {
+        self.symbol_tree_cache.invalidate(file_path);
+    }
+    pub fn set_ast_cache_capacity(&mut self, capacity: usize) {
+        self.symbol_tree_cache.set_capacity(capacity);
+    }
+    // Cache hits & misses of create_ast.
+    pub fn get_ast_cache_stats(&self) -> (usize, usize) {
+        self.symbol_tree_cache.get_stats()
     }
     pub fn update_ast(
         &mut self,
@@ -623,22 +902,82 @@ impl SymbolProvider {
         symbol_tree: &SymbolTree,
         params: &ValidationParams,
     ) -> Result<ShaderSymbolList, SymbolError> {
-        let mut shader_symbols = self.symbol_parser.query_local_symbols(&symbol_tree)?;
+        let shader_symbols = self
+            .symbol_parser
+            .query_local_symbols(&symbol_tree, params)?;
+        let inactive_regions = get_inactive_regions(symbol_tree, params);
+        Ok(self.finalize_symbols(symbol_tree, shader_symbols, &inactive_regions, params))
+    }
+    // Get all symbols of a file & its includes, which are processed in order as the preprocessor
+    // would. Defines of earlier includes select the active branches & symbols of later ones.
+    pub fn get_all_symbols_with_includes(
+        &self,
+        symbol_tree: &SymbolTree,
+        params: &ValidationParams,
+        include_callback: &mut dyn FnMut(&Path) -> Option<SymbolTree>,
+    ) -> Result<ShaderSymbolList, SymbolError> {
+        let include_resolver = IncludeResolver {
+            include_handler: IncludeHandler::new(
+                &symbol_tree.file_path,
+                params.includes.clone(),
+                params.virtual_includes.clone(),
+            ),
+            include_callback,
+        };
+        let (inactive_regions, included_trees) =
+            process_includes(symbol_tree, params, include_resolver);
+        let mut shader_symbols = self
+            .symbol_parser
+            .query_local_symbols(symbol_tree, params)?;
+        for included_tree in &included_trees {
+            shader_symbols.append(
+                self.symbol_parser
+                    .query_local_symbols(included_tree, params)?,
+            );
+        }
+        Ok(self.finalize_symbols(symbol_tree, shader_symbols, &inactive_regions, params))
+    }
+    fn finalize_symbols(
+        &self,
+        symbol_tree: &SymbolTree,
+        mut shader_symbols: ShaderSymbolList,
+        inactive_regions: &[ShaderRange],
+        params: &ValidationParams,
+    ) -> ShaderSymbolList {
+        // Symbols of branches discarded by the preprocessor are not visible to the compiler.
+        if !inactive_regions.is_empty() {
+            let is_active = |symbol: &ShaderSymbol| match &symbol.range {
+                Some(range) => !inactive_regions
+                    .iter()
+                    .any(|region| region.contain_bounds(range)),
+                None => true,
+            };
+            shader_symbols.types.retain(is_active);
+            shader_symbols.constants.retain(is_active);
+            shader_symbols.variables.retain(is_active);
+            shader_symbols.functions.retain(is_active);
+            shader_symbols.keywords.retain(is_active);
+        }
         // Add custom macros to symbol list.
         for define in &params.defines {
             shader_symbols.constants.push(ShaderSymbol {
                 label: define.0.clone(),
-                description: format!("Preprocessor macro (value: {})", define.1),
+                description: if define.1.is_empty() {
+                    "Preprocessor macro.".into()
+                } else {
+                    format!("Preprocessor macro. Expanding to \n```\n{}\n```", define.1)
+                },
                 version: "".into(),
                 stages: Vec::new(),
                 link: None,
-                data: ShaderSymbolData::Constants {
-                    ty: "".into(),
-                    qualifier: "".into(),
+                data: ShaderSymbolData::Macro {
+                    parameters: None,
                     value: define.1.clone(),
+                    external: true,
                 },
                 range: None,
                 scope_stack: None,
+                snippet: None,
             });
         }
         // Should be run directly on symbol add.
@@ -649,9 +988,94 @@ impl SymbolProvider {
             .to_string_lossy()
             .to_string();
         for filter in &self.filters {
-            filter.filter_symbols(&mut shader_symbols, &file_name);
+            filter.filter_symbols(&mut shader_symbols, &file_name, params);
         }
-        Ok(shader_symbols)
+        shader_symbols
+    }
+    // Get diagnostics from linting the AST.
+    pub fn get_lint_diagnostics(
+        &self,
+        symbol_tree: &SymbolTree,
+        params: &ValidationParams,
+    ) -> Result<ShaderDiagnosticList, SymbolError> {
+        self.symbol_parser.query_diagnostics(symbol_tree, params)
+    }
+    // Get diagnostics for calls matching no overload. Require every symbol of the file, intrinsics included.
+    pub fn get_call_diagnostics(
+        &self,
+        symbol_tree: &SymbolTree,
+        symbol_list: &ShaderSymbolList,
+        params: &ValidationParams,
+    ) -> ShaderDiagnosticList {
+        let mut diagnostics = ShaderDiagnosticList::empty();
+        if params.lint_call_arguments {
+            query_call_argument_diagnostics(symbol_tree, symbol_list, &mut diagnostics);
+            set_diagnostics_code(&mut diagnostics, 0, CALL_ARGUMENTS_CODE);
+        }
+        diagnostics
+    }
+    // Get diagnostics for functions & structs defined twice, in the file or its includes.
+    pub fn get_redefinition_diagnostics(
+        &self,
+        symbol_tree: &SymbolTree,
+        symbol_list: &ShaderSymbolList,
+        params: &ValidationParams,
+    ) -> ShaderDiagnosticList {
+        let mut diagnostics = ShaderDiagnosticList::empty();
+        if params.lint_redefinitions {
+            query_redefinition_diagnostics(symbol_tree, symbol_list, &mut diagnostics);
+            set_diagnostics_code(&mut diagnostics, 0, REDEFINITION_CODE);
+        }
+        diagnostics
+    }
+    // HLSL entry points sharing a stage, as validation targets a single one of them.
+    pub fn get_entry_point_diagnostics(
+        &self,
+        symbol_tree: &SymbolTree,
+        params: &ValidationParams,
+    ) -> ShaderDiagnosticList {
+        let mut diagnostics = ShaderDiagnosticList::empty();
+        if !params.lint_ambiguous_entry_points
+            || self.shading_language != ShadingLanguage::Hlsl
+            || params.entry_point.is_some()
+        {
+            return diagnostics;
+        }
+        let entry_points = symbol_tree.get_entry_points();
+        for (_, stage, range) in &entry_points {
+            let names: Vec<String> = entry_points
+                .iter()
+                .filter(|(_, other_stage, _)| other_stage == stage)
+                .map(|(name, _, _)| format!("'{}'", name))
+                .collect();
+            if names.len() < 2 {
+                continue;
+            }
+            diagnostics.push(ShaderDiagnostic {
+                file_path: Some(symbol_tree.file_path.clone()),
+                severity: ShaderErrorSeverity::Warning,
+                error: format!(
+                    "Ambiguous {} entry point between {}, specify the entry point to validate.",
+                    stage.to_string(),
+                    names.join(", ")
+                ),
+                line: range.start.line + 1,
+                pos: range.start.pos,
+                end: Some((range.end.line + 1, range.end.pos)),
+                unnecessary: false,
+                code: Some(AMBIGUOUS_ENTRY_POINT_CODE.into()),
+                fix: None,
+            });
+        }
+        diagnostics
+    }
+    // Get codes disabled with #pragma warning(disable: code).
+    pub fn get_disabled_warnings(
+        &self,
+        symbol_tree: &SymbolTree,
+        params: &ValidationParams,
+    ) -> Vec<String> {
+        get_disabled_warnings(symbol_tree, params)
     }
     pub fn get_word_range_at_position(
         &self,
@@ -661,6 +1085,23 @@ impl SymbolProvider {
         self.symbol_parser
             .find_label_at_position(symbol_tree, position)
     }
+    pub fn get_include_at_position(
+        &self,
+        symbol_tree: &SymbolTree,
+        position: &ShaderPosition,
+    ) -> Option<(String, ShaderRange)> {
+        self.symbol_parser
+            .find_include_at_position(symbol_tree, position)
+    }
+    pub fn get_includes(&self, symbol_tree: &SymbolTree) -> Vec<(String, ShaderRange)> {
+        self.symbol_parser.find_includes(symbol_tree)
+    }
+    pub fn get_word_occurences(&self, symbol_tree: &SymbolTree, word: &str) -> Vec<ShaderRange> {
+        self.symbol_parser.find_label_occurences(symbol_tree, word)
+    }
+    pub fn get_all_words<'a>(&self, symbol_tree: &'a SymbolTree) -> Vec<(&'a str, ShaderRange)> {
+        self.symbol_parser.find_identifiers(symbol_tree)
+    }
     pub fn get_word_chain_range_at_position(
         &mut self,
         symbol_tree: &SymbolTree,
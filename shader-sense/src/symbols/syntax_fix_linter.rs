@@ -0,0 +1,69 @@
+use tree_sitter::Node;
+
+use crate::shader_error::{ShaderDiagnosticFix, ShaderDiagnosticList, ShaderErrorSeverity};
+
+use super::{
+    linter::node_diagnostic,
+    parser::{get_name, SymbolTree},
+};
+
+// Tokens that can be removed when unexpected.
+const REMOVABLE_TOKENS: [&str; 4] = [")", "]", "}", ";"];
+
+// Syntax errors that can be fixed automatically, such as a missing semicolon.
+// These nodes can't be matched by queries, so the tree is walked instead.
+pub(super) fn query_syntax_fix_diagnostics(
+    symbol_tree: &SymbolTree,
+    diagnostics: &mut ShaderDiagnosticList,
+) {
+    fn visit(symbol_tree: &SymbolTree, node: Node, diagnostics: &mut ShaderDiagnosticList) {
+        if !node.has_error() {
+            return;
+        }
+        if node.is_missing() {
+            // Named nodes such as identifiers can't be guessed.
+            if !node.is_named() {
+                let token = node.kind();
+                let mut diagnostic = node_diagnostic(
+                    node,
+                    &symbol_tree.file_path,
+                    ShaderErrorSeverity::Error,
+                    format!("Missing '{}'.", token),
+                );
+                diagnostic.end = Some((diagnostic.line, diagnostic.pos));
+                diagnostic.fix = Some(ShaderDiagnosticFix {
+                    title: format!("Insert '{}'", token),
+                    new_text: token.into(),
+                });
+                diagnostics.push(diagnostic);
+            }
+            return;
+        }
+        if node.is_error() {
+            let text = get_name(&symbol_tree.content, node).trim();
+            if REMOVABLE_TOKENS.contains(&text) {
+                let mut diagnostic = node_diagnostic(
+                    node,
+                    &symbol_tree.file_path,
+                    ShaderErrorSeverity::Error,
+                    format!("Unexpected '{}'.", text),
+                );
+                diagnostic.end = Some((
+                    node.end_position().row as u32 + 1,
+                    node.end_position().column as u32,
+                ));
+                diagnostic.fix = Some(ShaderDiagnosticFix {
+                    title: format!("Remove '{}'", text),
+                    new_text: "".into(),
+                });
+                diagnostics.push(diagnostic);
+                return;
+            }
+        }
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            visit(symbol_tree, child, diagnostics);
+        }
+    }
+    visit(symbol_tree, symbol_tree.tree.root_node(), diagnostics);
+}
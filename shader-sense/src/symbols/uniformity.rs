@@ -0,0 +1,103 @@
+use tree_sitter::Node;
+
+use super::{linter::find_variable_declaration, parser::get_name};
+
+// Avoid infinite recursion with variables initialized from each other.
+const MAX_UNIFORMITY_DEPTH: u32 = 16;
+
+// Builtins sharing the same value for a whole dispatch, or workgroup for gl_WorkGroupID.
+const UNIFORM_BUILTINS: [&str; 3] = ["gl_NumWorkGroups", "gl_WorkGroupSize", "gl_WorkGroupID"];
+
+// Storage qualifiers of global values that differ per invocation.
+const NON_UNIFORM_QUALIFIERS: [&str; 4] = ["in", "varying", "attribute", "buffer"];
+
+fn is_uniform_identifier(shader_content: &str, node: Node, depth: u32) -> bool {
+    let name = get_name(shader_content, node);
+    match find_variable_declaration(shader_content, node, name) {
+        Some((declaration, declarator)) => {
+            if declaration.kind() == "parameter_declaration" {
+                // Parameters might hold per invocation inputs.
+                return false;
+            }
+            let mut parent = declaration.parent();
+            while let Some(node) = parent {
+                if node.kind() == "function_definition" {
+                    // Locals are as uniform as their initial value, assuming they are not reassigned.
+                    return declarator
+                        .child_by_field_name("value")
+                        .is_some_and(|value| {
+                            is_uniform_expression(shader_content, value, depth + 1)
+                        });
+                }
+                parent = node.parent();
+            }
+            !declaration
+                .children(&mut declaration.walk())
+                .any(|child| NON_UNIFORM_QUALIFIERS.contains(&child.kind()))
+        }
+        // Unresolved names are usually cbuffer members, macros or builtins.
+        None => !name.starts_with("gl_") || UNIFORM_BUILTINS.contains(&name),
+    }
+}
+
+// Heuristic checking if an expression has the same value for all invocations.
+fn is_uniform_expression(shader_content: &str, node: Node, depth: u32) -> bool {
+    if depth > MAX_UNIFORMITY_DEPTH {
+        return false;
+    }
+    match node.kind() {
+        "identifier" => is_uniform_identifier(shader_content, node, depth),
+        // Only the object is relevant, not the member.
+        "field_expression" => node
+            .child_by_field_name("argument")
+            .is_some_and(|argument| is_uniform_expression(shader_content, argument, depth)),
+        "call_expression" => {
+            // Function name is not a value, but a method call depends on its object.
+            let is_function_uniform = match node.child_by_field_name("function") {
+                Some(function) if function.kind() != "identifier" => {
+                    is_uniform_expression(shader_content, function, depth)
+                }
+                _ => true,
+            };
+            is_function_uniform
+                && node
+                    .child_by_field_name("arguments")
+                    .into_iter()
+                    .all(|arguments| is_uniform_expression(shader_content, arguments, depth))
+        }
+        _ => node
+            .named_children(&mut node.walk())
+            .all(|child| is_uniform_expression(shader_content, child, depth)),
+    }
+}
+
+// Innermost branch or loop of the function containing the node whose condition might differ
+// between invocations. Operations such as implicit derivatives or barriers are undefined there.
+pub(super) fn find_non_uniform_control_flow<'a>(
+    shader_content: &str,
+    node: Node<'a>,
+) -> Option<Node<'a>> {
+    let mut current = node;
+    while let Some(parent) = current.parent() {
+        match parent.kind() {
+            "function_definition" => return None,
+            "if_statement" | "while_statement" | "do_statement" | "for_statement"
+            | "switch_statement" => {
+                // Condition & loop initializer are executed by all invocations.
+                let condition = parent.child_by_field_name("condition");
+                let initializer = parent.child_by_field_name("initializer");
+                if let Some(condition) = condition {
+                    if condition != current
+                        && initializer != Some(current)
+                        && !is_uniform_expression(shader_content, condition, 0)
+                    {
+                        return Some(parent);
+                    }
+                }
+            }
+            _ => {}
+        }
+        current = parent;
+    }
+    None
+}
@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use crate::{
+    shader_error::{ShaderDiagnosticList, ShaderErrorSeverity},
+    validator::validator::ValidationParams,
+};
+
+use super::{
+    linter::{node_diagnostic, SymbolTreeLinter},
+    parser::get_name,
+};
+
+pub(super) struct UnreachableCodeLinter {}
+
+impl SymbolTreeLinter for UnreachableCodeLinter {
+    fn get_code(&self) -> &'static str {
+        "unreachableCode"
+    }
+    fn get_query(&self) -> String {
+        r#"[
+            (compound_statement (_) @statement)
+            (case_statement (_) @statement)
+        ]"#
+        .into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        if !params.lint_unreachable_code {
+            return;
+        }
+        let statement = matches.captures[0].node;
+        // GLSL grammar parse discard as an identifier while HLSL has a discard_statement.
+        let is_terminator = match statement.kind() {
+            "return_statement" | "break_statement" | "continue_statement" | "discard_statement" => {
+                true
+            }
+            "expression_statement" => statement
+                .named_child(0)
+                .is_some_and(|child| get_name(shader_content, child) == "discard"),
+            _ => false,
+        };
+        if !is_terminator {
+            return;
+        }
+        // Preprocessor might make following code reachable, stop there.
+        let mut unreachable_statements = Vec::new();
+        let mut next = statement.next_named_sibling();
+        while let Some(sibling) = next {
+            if sibling.kind().starts_with("preproc_") || sibling.kind() == "case_statement" {
+                break;
+            }
+            if sibling.kind() != "comment" {
+                unreachable_statements.push(sibling);
+            }
+            next = sibling.next_named_sibling();
+        }
+        if let (Some(first), Some(last)) = (
+            unreachable_statements.first(),
+            unreachable_statements.last(),
+        ) {
+            let mut diagnostic = node_diagnostic(
+                *first,
+                file_path,
+                ShaderErrorSeverity::Hint,
+                "Unreachable code.".into(),
+            );
+            diagnostic.end = Some((
+                last.end_position().row as u32 + 1,
+                last.end_position().column as u32,
+            ));
+            diagnostic.unnecessary = true;
+            diagnostics.push(diagnostic);
+        }
+    }
+}
@@ -1,9 +1,14 @@
 use std::path::Path;
 
+use tree_sitter::Node;
+
+use crate::validator::validator::ValidationParams;
+
 use super::{
     parser::{get_name, SymbolTreeParser},
     symbols::{
-        ShaderParameter, ShaderRange, ShaderScope, ShaderSymbol, ShaderSymbolData, ShaderSymbolList,
+        ShaderParameter, ShaderRange, ShaderScope, ShaderSignature, ShaderSymbol, ShaderSymbolData,
+        ShaderSymbolList,
     },
 };
 
@@ -29,6 +34,7 @@ impl SymbolTreeParser for WgslStructTreeParser {
         file_path: &Path,
         shader_content: &str,
         scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
         symbols: &mut ShaderSymbolList,
     ) {
         let label_node = matches.captures[0].node;
@@ -53,6 +59,330 @@ impl SymbolTreeParser for WgslStructTreeParser {
             },
             range: Some(range),
             scope_stack: Some(scope_stack),
+            snippet: None,
+        });
+    }
+}
+
+pub(super) struct WgslConstTreeParser {}
+
+impl SymbolTreeParser for WgslConstTreeParser {
+    fn get_query(&self) -> String {
+        // Grammar does not support const yet, so we match it from error nodes.
+        r#"(ERROR
+            .
+            (identifier) @const.qualifier
+            .
+            (identifier) @const.label
+            (#eq? @const.qualifier "const")
+        ) @const"#
+            .into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
+        symbols: &mut ShaderSymbolList,
+    ) {
+        let const_node = matches.captures[0].node;
+        let label_node = matches.captures[2].node;
+        // Remaining text is of the form ": type = value"
+        let declaration = &shader_content[label_node.end_byte()..const_node.end_byte()];
+        let (ty, value) = match declaration.split_once('=') {
+            Some((ty, value)) => (ty, value.trim()),
+            None => (declaration, ""),
+        };
+        let ty = ty.trim().trim_start_matches(':').trim();
+        let range = ShaderRange::from_range(label_node.range(), file_path.into());
+        let scope_stack = self.compute_scope_stack(scopes, &range);
+        symbols.constants.push(ShaderSymbol {
+            label: get_name(shader_content, label_node).into(),
+            description: "Compile-time constant.".into(),
+            version: "".into(),
+            stages: vec![],
+            link: None,
+            data: ShaderSymbolData::Constants {
+                ty: ty.into(),
+                qualifier: "const".into(),
+                value: value.into(),
+            },
+            range: Some(range),
+            scope_stack: Some(scope_stack),
+            snippet: None,
+        });
+    }
+}
+
+pub(super) struct WgslOverrideTreeParser {}
+
+impl SymbolTreeParser for WgslOverrideTreeParser {
+    fn get_query(&self) -> String {
+        r#"(global_constant_declaration "override") @override"#.into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
+        symbols: &mut ShaderSymbolList,
+    ) {
+        let override_node = matches.captures[0].node;
+        // Attributes such as @id(12) are stored in the qualifier.
+        let mut qualifier = String::new();
+        let mut label_node = None;
+        let mut ty = "";
+        let mut value = "";
+        let mut is_value = false;
+        for child in override_node.children(&mut override_node.walk()) {
+            match child.kind() {
+                "attribute" => {
+                    qualifier.push_str(get_name(shader_content, child));
+                    qualifier.push(' ');
+                }
+                "identifier" => label_node = Some(child),
+                "variable_identifier_declaration" => {
+                    label_node = child.child_by_field_name("name");
+                    if let Some(type_node) = child.child_by_field_name("type") {
+                        ty = get_name(shader_content, type_node);
+                    }
+                }
+                "=" => is_value = true,
+                _ => {
+                    if is_value {
+                        value = get_name(shader_content, child);
+                    }
+                }
+            }
+        }
+        qualifier.push_str("override");
+        let label_node = match label_node {
+            Some(label_node) => label_node,
+            None => return,
+        };
+        let range = ShaderRange::from_range(label_node.range(), file_path.into());
+        let scope_stack = self.compute_scope_stack(scopes, &range);
+        symbols.constants.push(ShaderSymbol {
+            label: get_name(shader_content, label_node).into(),
+            description: "Pipeline-overridable constant.".into(),
+            version: "".into(),
+            stages: vec![],
+            link: None,
+            data: ShaderSymbolData::Constants {
+                ty: ty.into(),
+                qualifier,
+                value: value.into(),
+            },
+            range: Some(range),
+            scope_stack: Some(scope_stack),
+            snippet: None,
+        });
+    }
+}
+
+// Parse compute workgroup size of a function, such as @workgroup_size(64, 2).
+fn get_workgroup_size(
+    shader_content: &str,
+    function_node: tree_sitter::Node,
+) -> Option<[String; 3]> {
+    let attribute = function_node
+        .children(&mut function_node.walk())
+        .filter(|child| child.kind() == "attribute")
+        .find(|attribute| {
+            attribute
+                .named_child(0)
+                .is_some_and(|name| get_name(shader_content, name) == "workgroup_size")
+        })?;
+    // Omitted dimensions default to 1.
+    let mut dimensions = ["1".to_string(), "1".to_string(), "1".to_string()];
+    for (dimension, argument) in dimensions
+        .iter_mut()
+        .zip(attribute.named_children(&mut attribute.walk()).skip(1))
+    {
+        *dimension = get_name(shader_content, argument).into();
+    }
+    Some(dimensions)
+}
+
+pub(super) struct WgslFunctionTreeParser {}
+
+impl SymbolTreeParser for WgslFunctionTreeParser {
+    fn get_query(&self) -> String {
+        r#"(function_declaration) @function"#.into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
+        symbols: &mut ShaderSymbolList,
+    ) {
+        let function_node = matches.captures[0].node;
+        let label_node = match function_node.child_by_field_name("name") {
+            Some(label_node) => label_node,
+            None => return,
+        };
+        let parameters = match function_node.child_by_field_name("parameters") {
+            Some(parameter_list) => parameter_list
+                .named_children(&mut parameter_list.walk())
+                .filter_map(|parameter| {
+                    let declaration = parameter
+                        .named_children(&mut parameter.walk())
+                        .find(|child| child.kind() == "variable_identifier_declaration")?;
+                    Some(ShaderParameter {
+                        ty: get_name(shader_content, declaration.child_by_field_name("type")?)
+                            .into(),
+                        label: get_name(shader_content, declaration.child_by_field_name("name")?)
+                            .into(),
+                        description: "".into(),
+                    })
+                })
+                .collect(),
+            None => vec![],
+        };
+        // Return type might be decorated with attributes such as @location(0).
+        let return_type = function_node
+            .child_by_field_name("type")
+            .and_then(|return_type| {
+                return_type
+                    .named_children(&mut return_type.walk())
+                    .find(|child| child.kind() == "type_declaration")
+            })
+            .map_or("void", |return_type| get_name(shader_content, return_type));
+        let range = ShaderRange::from_range(label_node.range(), file_path.into());
+        let scope_stack = self.compute_scope_stack(scopes, &range);
+        symbols.functions.push(ShaderSymbol {
+            label: get_name(shader_content, label_node).into(),
+            description: "".into(),
+            version: "".into(),
+            stages: vec![],
+            link: None,
+            data: ShaderSymbolData::Functions {
+                signatures: vec![ShaderSignature {
+                    returnType: return_type.into(),
+                    description: "".into(),
+                    parameters,
+                }],
+                workgroup_size: get_workgroup_size(shader_content, function_node),
+            },
+            range: Some(range),
+            scope_stack: Some(scope_stack),
+            snippet: None,
+        });
+    }
+}
+
+fn get_child_name<'a>(shader_content: &'a str, node: Node, kind: &str) -> Option<&'a str> {
+    node.named_children(&mut node.walk())
+        .find(|child| child.kind() == kind)
+        .map(|child| get_name(shader_content, child))
+}
+
+// Pointer type such as ptr<storage, f32, read_write>, as address space, pointee type & access mode.
+fn get_pointer_type<'a>(
+    shader_content: &'a str,
+    type_node: Node,
+) -> Option<(&'a str, &'a str, Option<&'a str>)> {
+    if type_node.child(0)?.kind() != "ptr" {
+        return None;
+    }
+    Some((
+        get_child_name(shader_content, type_node, "address_space")?,
+        get_child_name(shader_content, type_node, "type_declaration")?,
+        get_child_name(shader_content, type_node, "access_mode"),
+    ))
+}
+
+fn format_address_space(address_space: &str, access_mode: Option<&str>) -> String {
+    match access_mode {
+        Some(access_mode) => format!(
+            "the {} address space with {} access",
+            address_space, access_mode
+        ),
+        None => format!("the {} address space", address_space),
+    }
+}
+
+pub(super) struct WgslVariableTreeParser {}
+
+impl SymbolTreeParser for WgslVariableTreeParser {
+    fn get_query(&self) -> String {
+        r#"(variable_identifier_declaration) @variable"#.into()
+    }
+    fn process_match(
+        &self,
+        matches: tree_sitter::QueryMatch,
+        file_path: &Path,
+        shader_content: &str,
+        scopes: &Vec<ShaderScope>,
+        _params: &ValidationParams,
+        symbols: &mut ShaderSymbolList,
+    ) {
+        let declaration_node = matches.captures[0].node;
+        let (Some(label_node), Some(type_node), Some(parent)) = (
+            declaration_node.child_by_field_name("name"),
+            declaration_node.child_by_field_name("type"),
+            declaration_node.parent(),
+        ) else {
+            return;
+        };
+        // Struct members & overrides are parsed with their declaration.
+        let body_node = match parent.kind() {
+            "variable_declaration" | "variable_statement" => None,
+            "parameter" => parent
+                .parent()
+                .and_then(|parameter_list| parameter_list.parent())
+                .and_then(|function| function.child_by_field_name("body")),
+            _ => return,
+        };
+        // Keyword & address space such as var<private>, or attributes of parameters.
+        let qualifier = shader_content[parent.start_byte()..declaration_node.start_byte()].trim();
+        let description = match get_pointer_type(shader_content, type_node) {
+            Some((address_space, pointee, access_mode)) => format!(
+                "Pointer to {} in {}.",
+                pointee,
+                format_address_space(address_space, access_mode)
+            ),
+            None => parent
+                .named_children(&mut parent.walk())
+                .find(|child| child.kind() == "variable_qualifier")
+                .and_then(|variable_qualifier| {
+                    Some(format!(
+                        "Variable in {}.",
+                        format_address_space(
+                            get_child_name(shader_content, variable_qualifier, "address_space")?,
+                            get_child_name(shader_content, variable_qualifier, "access_mode"),
+                        )
+                    ))
+                })
+                .unwrap_or_default(),
+        };
+        let range = ShaderRange::from_range(label_node.range(), file_path.into());
+        let mut scope_stack = self.compute_scope_stack(scopes, &range);
+        // Parameters are variables scoped to the function body.
+        if let Some(body_node) = body_node {
+            scope_stack.push(ShaderRange::from_range(body_node.range(), file_path.into()));
+        }
+        symbols.variables.push(ShaderSymbol {
+            label: get_name(shader_content, label_node).into(),
+            description,
+            version: "".into(),
+            stages: vec![],
+            link: None,
+            data: ShaderSymbolData::Variables {
+                ty: get_name(shader_content, type_node).into(),
+                qualifier: qualifier.into(),
+                register: None,
+            },
+            range: Some(range),
+            scope_stack: Some(scope_stack),
+            snippet: None,
         });
     }
 }
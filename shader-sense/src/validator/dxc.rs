@@ -1,9 +1,12 @@
 use hassle_rs::*;
-use std::path::Path;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 use crate::{
     include::{Dependencies, IncludeHandler},
-    shader::{HlslShaderModel, HlslVersion},
+    shader::{HlslShaderModel, HlslVersion, ShaderStage},
     shader_error::{
         ShaderDiagnostic, ShaderDiagnosticList, ShaderError, ShaderErrorSeverity, ValidatorError,
     },
@@ -31,10 +34,11 @@ impl<'a> DxcIncludeHandler<'a> {
     pub fn new(
         file: &Path,
         includes: Vec<String>,
+        virtual_includes: HashMap<String, PathBuf>,
         include_callback: &'a mut dyn FnMut(&Path) -> Option<String>,
     ) -> Self {
         Self {
-            include_handler: IncludeHandler::new(file, includes),
+            include_handler: IncludeHandler::new(file, includes, virtual_includes),
             include_callback: include_callback,
         }
     }
@@ -75,10 +79,10 @@ impl Dxc {
             validator,
         })
     }
-    fn parse_dxc_errors(
+    pub(super) fn parse_dxc_errors(
         errors: &String,
         file: &Path,
-        includes: &Vec<String>,
+        params: &ValidationParams,
     ) -> Result<ShaderDiagnosticList, ValidatorError> {
         let mut shader_error_list = ShaderDiagnosticList::empty();
 
@@ -91,7 +95,13 @@ impl Dxc {
         }
         starts.push(errors.len());
         let internal_reg = regex::Regex::new(r"(?s)^(.*?):(\d+):(\d+): (.*?):(.*)")?;
-        let mut include_handler = IncludeHandler::new(file, includes.clone());
+        // Clang warnings end with the flag controlling them.
+        let flag_reg = regex::Regex::new(r"\[(-W[\w\-]+)\]")?;
+        let mut include_handler = IncludeHandler::new(
+            file,
+            params.includes.clone(),
+            params.virtual_includes.clone(),
+        );
         for start in 0..starts.len() - 1 {
             let first = starts[start];
             let length = starts[start + 1] - starts[start];
@@ -101,6 +111,11 @@ impl Dxc {
                 let line = capture.get(2).map_or("", |m| m.as_str());
                 let pos = capture.get(3).map_or("", |m| m.as_str());
                 let level = capture.get(4).map_or("", |m| m.as_str());
+                // Level might be followed by an error code, such as error X3004.
+                let (level, level_code) = match level.split_once(' ') {
+                    Some((level, code)) => (level, Some(code.trim())),
+                    None => (level, None),
+                };
                 let msg = capture.get(5).map_or("", |m| m.as_str());
                 shader_error_list.push(ShaderDiagnostic {
                     file_path: include_handler.search_path_in_includes(Path::new(relative_path)),
@@ -114,6 +129,22 @@ impl Dxc {
                     error: String::from(msg),
                     line: line.parse::<u32>().unwrap_or(0),
                     pos: pos.parse::<u32>().unwrap_or(0),
+                    end: None,
+                    unnecessary: false,
+                    code: match level_code {
+                        Some(level_code) => Some(level_code.to_string()),
+                        None => msg
+                            .lines()
+                            .next()
+                            .and_then(|message| {
+                                flag_reg
+                                    .captures(message)
+                                    .and_then(|capture| capture.get(1))
+                                    .map(|flag| flag.as_str().to_string())
+                            })
+                            .or_else(|| ShaderDiagnostic::get_message_code(msg)),
+                    },
+                    fix: None,
                 });
             }
         }
@@ -135,7 +166,7 @@ impl Dxc {
     ) -> ShaderError {
         match error {
             HassleError::CompileError(err) => {
-                match Dxc::parse_dxc_errors(&err, file_path, &params.includes) {
+                match Dxc::parse_dxc_errors(&err, file_path, params) {
                     Ok(diagnostic) => ShaderError::DiagnosticList(diagnostic),
                     Err(error) => ShaderError::Validator(error),
                 }
@@ -147,6 +178,10 @@ impl Dxc {
                     error: err.to_string(),
                     line: 0,
                     pos: 0,
+                    end: None,
+                    unnecessary: false,
+                    code: None,
+                    fix: None,
                 }))
             }
             HassleError::LibLoadingError(err) => {
@@ -167,16 +202,64 @@ impl Dxc {
             )),
         }
     }
+    // Wave matrix types are only available from SM6.8, use it if the configured model is lower.
+    pub(super) fn get_shader_model(
+        shader_source: &str,
+        params: &ValidationParams,
+    ) -> HlslShaderModel {
+        if params.hlsl_shader_model.get_version() < (6, 8) && shader_source.contains("WaveMatrix") {
+            HlslShaderModel::ShaderModel6_8
+        } else {
+            params.hlsl_shader_model
+        }
+    }
+    // Target a single entry point when the stage is known, otherwise use lib profile to avoid specifying entry point.
+    pub(super) fn get_entry_point_and_profile(params: &ValidationParams) -> (String, String) {
+        let shader_model = match params.hlsl_shader_model {
+            HlslShaderModel::ShaderModel6 => "6_0",
+            HlslShaderModel::ShaderModel6_1 => "6_1",
+            HlslShaderModel::ShaderModel6_2 => "6_2",
+            HlslShaderModel::ShaderModel6_3 => "6_3",
+            HlslShaderModel::ShaderModel6_4 => "6_4",
+            HlslShaderModel::ShaderModel6_5 => "6_5",
+            HlslShaderModel::ShaderModel6_6 => "6_6",
+            HlslShaderModel::ShaderModel6_7 => "6_7",
+            HlslShaderModel::ShaderModel6_8 => "6_8",
+        };
+        // Raytracing stages only exist in library.
+        let stage_profile = params
+            .shader_stage
+            .and_then(|shader_stage| match shader_stage {
+                ShaderStage::Vertex => Some("vs"),
+                ShaderStage::Fragment => Some("ps"),
+                ShaderStage::Compute => Some("cs"),
+                ShaderStage::TesselationControl => Some("hs"),
+                ShaderStage::TesselationEvaluation => Some("ds"),
+                ShaderStage::Geometry => Some("gs"),
+                ShaderStage::Mesh => Some("ms"),
+                ShaderStage::Task => Some("as"),
+                _ => None,
+            });
+        match stage_profile {
+            Some(stage_profile) => (
+                params.entry_point.clone().unwrap_or_else(|| "main".into()),
+                format!("{}_{}", stage_profile, shader_model),
+            ),
+            None => ("".into(), format!("lib_{}", shader_model)),
+        }
+    }
 }
+
 impl Validator for Dxc {
     fn validate_shader(
         &mut self,
         shader_source: String,
         file_path: &Path,
-        params: ValidationParams,
+        mut params: ValidationParams,
         include_callback: &mut dyn FnMut(&Path) -> Option<String>,
     ) -> Result<(ShaderDiagnosticList, Dependencies), ValidatorError> {
         let file_name = self.get_file_name(file_path);
+        params.hlsl_shader_model = Self::get_shader_model(&shader_source, &params);
 
         let blob = self
             .library
@@ -188,8 +271,12 @@ impl Validator for Dxc {
             .iter()
             .map(|v| (&v.0 as &str, Some(&v.1 as &str)))
             .collect();
-        let mut include_handler =
-            DxcIncludeHandler::new(file_path, params.includes.clone(), include_callback);
+        let mut include_handler = DxcIncludeHandler::new(
+            file_path,
+            params.includes.clone(),
+            params.virtual_includes.clone(),
+            include_callback,
+        );
         let dxc_options = {
             let hlsl_version = format!(
                 "-HV {}",
@@ -208,25 +295,12 @@ impl Validator for Dxc {
             }
         };
         let dxc_options_str: Vec<&str> = dxc_options.iter().map(|s| s.as_str()).collect();
+        let (entry_point, profile) = Self::get_entry_point_and_profile(&params);
         let result = self.compiler.compile(
             &blob,
             file_name.as_str(),
-            "", // TODO: Could have a command to validate specific entry point (specify stage & entry point)
-            format!(
-                "lib_{}", // Using lib profile to avoid specifying entry point
-                match params.hlsl_shader_model {
-                    HlslShaderModel::ShaderModel6 => "6_0",
-                    HlslShaderModel::ShaderModel6_1 => "6_1",
-                    HlslShaderModel::ShaderModel6_2 => "6_2",
-                    HlslShaderModel::ShaderModel6_3 => "6_3",
-                    HlslShaderModel::ShaderModel6_4 => "6_4",
-                    HlslShaderModel::ShaderModel6_5 => "6_5",
-                    HlslShaderModel::ShaderModel6_6 => "6_6",
-                    HlslShaderModel::ShaderModel6_7 => "6_7",
-                    HlslShaderModel::ShaderModel6_8 => "6_8",
-                }
-            )
-            .as_str(),
+            entry_point.as_str(),
+            profile.as_str(),
             &dxc_options_str,
             Some(&mut include_handler),
             &defines,
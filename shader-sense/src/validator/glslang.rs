@@ -1,7 +1,10 @@
-use super::validator::{ValidationParams, Validator};
+use super::{
+    spirv::get_capabilities_and_extensions,
+    validator::{ValidationParams, Validator},
+};
 use crate::{
     include::{Dependencies, IncludeHandler},
-    shader::{GlslSpirvVersion, GlslTargetClient, ShaderStage},
+    shader::{GlslProfile, GlslSpirvVersion, GlslTargetClient, ShaderStage},
     shader_error::{
         ShaderDiagnostic, ShaderDiagnosticList, ShaderError, ShaderErrorSeverity, ValidatorError,
     },
@@ -13,6 +16,7 @@ use glslang::{
 };
 use std::{
     borrow::Borrow,
+    collections::HashMap,
     path::{Path, PathBuf},
 };
 
@@ -71,11 +75,12 @@ impl<'a> GlslangIncludeHandler<'a> {
     pub fn new(
         file: &'a Path,
         includes: Vec<String>,
+        virtual_includes: HashMap<String, PathBuf>,
         content: Option<&'a String>,
         include_callback: &'a mut dyn FnMut(&Path) -> Option<String>,
     ) -> Self {
         Self {
-            include_handler: IncludeHandler::new(file, includes),
+            include_handler: IncludeHandler::new(file, includes, virtual_includes),
             content: content,
             file_name: file,
             include_callback: include_callback,
@@ -138,10 +143,53 @@ void main() {
 "#;
 
 impl Glslang {
+    // Find the #version directive of the file with its 0 based line.
+    fn find_version_directive(content: &str) -> Option<(u32, u32, Option<GlslProfile>)> {
+        content.lines().enumerate().find_map(|(line, text)| {
+            let mut tokens = text.trim().strip_prefix("#version")?.split_whitespace();
+            let version = tokens.next()?.parse().ok()?;
+            let profile = tokens.next().and_then(GlslProfile::from_directive);
+            Some((line as u32, version, profile))
+        })
+    }
+    // Warn for capabilities & extensions used by the module that are not supported by the target.
+    fn check_spirv_support(
+        spirv: &[u32],
+        file_path: &Path,
+        params: &ValidationParams,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        let (capabilities, extensions) = get_capabilities_and_extensions(spirv);
+        let unsupported = [
+            ("capability", capabilities, &params.spirv_capabilities),
+            ("extension", extensions, &params.spirv_extensions),
+        ];
+        for (kind, used, supported) in unsupported {
+            let Some(supported) = supported else {
+                continue;
+            };
+            for name in used.iter().filter(|name| !supported.contains(name)) {
+                diagnostics.push(ShaderDiagnostic {
+                    file_path: Some(file_path.into()),
+                    severity: ShaderErrorSeverity::Warning,
+                    error: format!(
+                        "Shader requires SPIR-V {} {} which is not supported by the target.",
+                        kind, name
+                    ),
+                    line: 1,
+                    pos: 0,
+                    end: None,
+                    unnecessary: false,
+                    code: None,
+                    fix: None,
+                });
+            }
+        }
+    }
     fn parse_errors(
         errors: &String,
         file_path: &Path,
-        includes: &Vec<String>,
+        params: &ValidationParams,
     ) -> Result<ShaderDiagnosticList, ValidatorError> {
         let mut shader_error_list = ShaderDiagnosticList::empty();
 
@@ -156,7 +204,11 @@ impl Glslang {
         let internal_reg = regex::Regex::new(
             r"(?s)^(.*?):(?: ((?:[a-zA-Z]:)?[\d\w\.\/\\\-]+):(\d+):(\d+):)?(.+)",
         )?;
-        let mut include_handler = IncludeHandler::new(file_path, includes.clone());
+        let mut include_handler = IncludeHandler::new(
+            file_path,
+            params.includes.clone(),
+            params.virtual_includes.clone(),
+        );
         for start in 0..starts.len() - 1 {
             let first = starts[start];
             let length = starts[start + 1] - starts[start];
@@ -191,6 +243,10 @@ impl Glslang {
                     error: String::from(msg),
                     line: line.parse::<u32>().unwrap_or(1),
                     pos: pos.parse::<u32>().unwrap_or(0),
+                    end: None,
+                    unnecessary: false,
+                    code: ShaderDiagnostic::get_message_code(msg),
+                    fix: None,
                 });
             } else {
                 return Err(ValidatorError::internal(format!(
@@ -217,19 +273,19 @@ impl Glslang {
     ) -> ShaderError {
         match err {
             GlslangError::PreprocessError(error) => {
-                match Glslang::parse_errors(&error, file_path, &params.includes) {
+                match Glslang::parse_errors(&error, file_path, params) {
                     Ok(diag) => ShaderError::DiagnosticList(diag),
                     Err(err) => ShaderError::Validator(err),
                 }
             }
             GlslangError::ParseError(error) => {
-                match Glslang::parse_errors(&error, file_path, &params.includes) {
+                match Glslang::parse_errors(&error, file_path, params) {
                     Ok(diag) => ShaderError::DiagnosticList(diag),
                     Err(err) => ShaderError::Validator(err),
                 }
             }
             GlslangError::LinkError(error) => {
-                match Glslang::parse_errors(&error, file_path, &params.includes) {
+                match Glslang::parse_errors(&error, file_path, params) {
                     Ok(diag) => ShaderError::DiagnosticList(diag),
                     Err(err) => ShaderError::Validator(err),
                 }
@@ -266,6 +322,14 @@ impl Validator for Glslang {
         let (shader_stage, shader_source) =
             if let Some(shader_stage) = ShaderStage::from_file_name(&file_name) {
                 (shader_stage, content.clone())
+            } else if self.hlsl {
+                // HLSL files do not carry their stage in their name & template is GLSL only.
+                (
+                    params.shader_stage.unwrap_or(ShaderStage::Fragment),
+                    content.clone(),
+                )
+            } else if let Some(shader_stage) = params.glsl_default_stage {
+                (shader_stage, content.clone())
             } else {
                 // If we dont have a stage, treat it as an include by including it in template file.
                 // GLSLang requires to have stage for linting.
@@ -286,6 +350,7 @@ impl Validator for Glslang {
         let mut include_handler = GlslangIncludeHandler::new(
             file_path,
             params.includes.clone(),
+            params.virtual_includes.clone(),
             Some(&content),
             include_callback,
         );
@@ -299,6 +364,43 @@ impl Validator for Glslang {
             GlslSpirvVersion::SPIRV1_5 => glslang::SpirvVersion::SPIRV1_5,
             GlslSpirvVersion::SPIRV1_6 => glslang::SpirvVersion::SPIRV1_6,
         };
+        // Forced version only apply to files without #version directive.
+        let mut version_diagnostics = Vec::new();
+        let version_profile = match (params.glsl_version, self.hlsl) {
+            (Some(glsl_version), false) => match Self::find_version_directive(&content) {
+                Some((line, version, profile)) => {
+                    if version != glsl_version
+                        || (params.glsl_profile.is_some() && profile != params.glsl_profile)
+                    {
+                        version_diagnostics.push(ShaderDiagnostic {
+                            file_path: Some(file_path.into()),
+                            severity: ShaderErrorSeverity::Information,
+                            error: format!(
+                                "File declares #version {}, configured version {} is ignored.",
+                                version, glsl_version
+                            ),
+                            line: line + 1,
+                            pos: 0,
+                            end: None,
+                            unnecessary: false,
+                            code: None,
+                            fix: None,
+                        });
+                    }
+                    None
+                }
+                None => Some((
+                    glsl_version as i32,
+                    match params.glsl_profile {
+                        Some(GlslProfile::Core) => glslang::GlslProfile::Core,
+                        Some(GlslProfile::Compatibility) => glslang::GlslProfile::Compatibility,
+                        Some(GlslProfile::Es) => glslang::GlslProfile::ES,
+                        None => glslang::GlslProfile::None,
+                    },
+                )),
+            },
+            _ => None,
+        };
         let input = match ShaderInput::new(
             &source,
             shader_stage.into(),
@@ -331,10 +433,10 @@ impl Validator for Glslang {
                         }
                     }
                 },
+                version_profile,
                 messages: glslang::ShaderMessage::CASCADING_ERRORS
                     | glslang::ShaderMessage::DEBUG_INFO
                     | glslang::ShaderMessage::DISPLAY_ERROR_COLUMN,
-                ..Default::default()
             },
             Some(&defines),
             Some(&mut include_handler),
@@ -344,19 +446,21 @@ impl Validator for Glslang {
             Ok(value) => value,
             Err(error) => match error {
                 ShaderError::Validator(error) => return Err(error),
-                ShaderError::DiagnosticList(diag) => {
-                    return Ok((diag, include_handler.get_dependencies().clone()))
+                ShaderError::DiagnosticList(mut diag) => {
+                    diag.diagnostics.extend(version_diagnostics);
+                    return Ok((diag, include_handler.get_dependencies().clone()));
                 }
             },
         };
-        let _shader = match glslang::Shader::new(&self.compiler, input)
+        let shader = match glslang::Shader::new(&self.compiler, input)
             .map_err(|e| self.from_glslang_error(e, file_path, &params))
         {
             Ok(value) => value,
             Err(error) => match error {
                 ShaderError::Validator(error) => return Err(error),
-                ShaderError::DiagnosticList(diag) => {
-                    return Ok((diag, include_handler.get_dependencies().clone()))
+                ShaderError::DiagnosticList(mut diag) => {
+                    diag.diagnostics.extend(version_diagnostics);
+                    return Ok((diag, include_handler.get_dependencies().clone()));
                 }
             },
         };
@@ -369,9 +473,15 @@ impl Validator for Glslang {
             },
         };*/
 
-        Ok((
-            ShaderDiagnosticList::empty(),
-            include_handler.get_dependencies().clone(),
-        )) // No error detected.
+        let mut diagnostics = ShaderDiagnosticList {
+            diagnostics: version_diagnostics,
+        };
+        if params.spirv_capabilities.is_some() || params.spirv_extensions.is_some() {
+            // Generating SPIR-V fail without entry point or language target. Skip check in this case.
+            if let Ok(spirv) = shader.compile() {
+                Self::check_spirv_support(&spirv, file_path, &params, &mut diagnostics);
+            }
+        }
+        Ok((diagnostics, include_handler.get_dependencies().clone())) // No error detected.
     }
 }
@@ -1,35 +1,161 @@
+#[cfg(feature = "tokio")]
+use std::path::{Path, PathBuf};
+
+use registry::ValidatorRegistry;
+#[cfg(feature = "tokio")]
+use validator::ValidationParams;
 use validator::Validator;
 
-use crate::shader::ShadingLanguage;
+use crate::shader::{HlslBackend, ShadingLanguage};
+#[cfg(feature = "tokio")]
+use crate::{
+    include::Dependencies,
+    shader_error::{ShaderDiagnosticList, ValidatorError},
+};
 
 #[cfg(not(target_os = "wasi"))]
 pub mod dxc;
 pub mod glslang;
 pub mod naga;
+pub mod registry;
+mod spirv;
 pub mod validator;
 
-pub fn create_validator(shading_language: ShadingLanguage) -> Box<dyn Validator> {
-    match shading_language {
-        ShadingLanguage::Wgsl => Box::new(naga::Naga::new()),
-        #[cfg(not(target_os = "wasi"))]
-        ShadingLanguage::Hlsl => Box::new(dxc::Dxc::new().unwrap()),
-        #[cfg(target_os = "wasi")]
-        ShadingLanguage::Hlsl => Box::new(glslang::Glslang::hlsl()),
-        ShadingLanguage::Glsl => Box::new(glslang::Glslang::glsl()),
-    }
+// Builtin validator of the language. The HLSL backend falls back to the default one if it is not
+// available, as DXC on WASI.
+pub fn create_validator(
+    shading_language: ShadingLanguage,
+    hlsl_backend: HlslBackend,
+) -> Box<dyn Validator> {
+    let registry = ValidatorRegistry::default();
+    let backend = match shading_language {
+        ShadingLanguage::Hlsl => hlsl_backend.get_name(),
+        _ => registry.get_default_backend(shading_language).unwrap(),
+    };
+    let backend = registry.resolve_backend(shading_language, backend).unwrap();
+    registry.create(shading_language, backend).unwrap()
+}
+
+// Validate on the blocking thread pool, so that embedding runtimes are not blocked by DXC.
+// Validators are not Send, so one is created for each validation on the blocking thread.
+#[cfg(feature = "tokio")]
+pub async fn validate_shader_async(
+    shading_language: ShadingLanguage,
+    hlsl_backend: HlslBackend,
+    shader_content: String,
+    file_path: PathBuf,
+    params: ValidationParams,
+    mut include_callback: impl FnMut(&Path) -> Option<String> + Send + 'static,
+) -> Result<(ShaderDiagnosticList, Dependencies), ValidatorError> {
+    tokio::task::spawn_blocking(move || {
+        let mut validator = create_validator(shading_language, hlsl_backend);
+        validator.validate_shader(shader_content, &file_path, params, &mut include_callback)
+    })
+    .await
+    .map_err(|err| ValidatorError::internal(format!("Validation task failed: {}", err)))?
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, path::Path};
+    use std::{
+        collections::HashMap,
+        path::{Path, PathBuf},
+    };
 
     use super::validator::*;
     use super::*;
+    use crate::shader::{GlslProfile, GlslTargetClient, HlslShaderModel, ShaderStage};
+    use crate::shader_error::ShaderErrorSeverity;
 
     fn include_callback(path: &Path) -> Option<String> {
         Some(std::fs::read_to_string(path).unwrap())
     }
 
+    #[test]
+    fn glsl_forced_version() {
+        let mut validator = glslang::Glslang::glsl();
+        let file_path = Path::new("./test/glsl/no-version.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        // Default version 100 does not support layout location.
+        match validator.validate_shader(
+            shader_content.clone(),
+            file_path,
+            ValidationParams::default(),
+            &mut include_callback,
+        ) {
+            Ok(result) => assert!(!result.0.is_empty()),
+            Err(err) => panic!("{}", err),
+        }
+        match validator.validate_shader(
+            shader_content,
+            file_path,
+            ValidationParams {
+                glsl_version: Some(450),
+                glsl_profile: Some(GlslProfile::Core),
+                ..Default::default()
+            },
+            &mut include_callback,
+        ) {
+            Ok(result) => {
+                println!("Diagnostic should be empty: {:#?}", result.0);
+                assert!(result.0.is_empty())
+            }
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    #[test]
+    fn glsl_forced_version_mismatch() {
+        let mut validator = glslang::Glslang::glsl();
+        let file_path = Path::new("./test/glsl/ok.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        match validator.validate_shader(
+            shader_content,
+            file_path,
+            ValidationParams {
+                glsl_version: Some(460),
+                ..Default::default()
+            },
+            &mut include_callback,
+        ) {
+            Ok(result) => {
+                // In file directive is kept.
+                assert_eq!(result.0.diagnostics.len(), 1, "{:#?}", result.0);
+                let diagnostic = &result.0.diagnostics[0];
+                assert!(matches!(
+                    diagnostic.severity,
+                    ShaderErrorSeverity::Information
+                ));
+                assert_eq!(diagnostic.line, 1);
+            }
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    #[test]
+    fn glsl_spirv_capability() {
+        let mut validator = glslang::Glslang::glsl();
+        let file_path = Path::new("./test/glsl/spirv-capability.comp.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut validate = |capabilities: Vec<&str>| match validator.validate_shader(
+            shader_content.clone(),
+            file_path,
+            ValidationParams {
+                spirv_capabilities: Some(capabilities.into_iter().map(|c| c.into()).collect()),
+                ..Default::default()
+            },
+            &mut include_callback,
+        ) {
+            Ok(result) => result.0.diagnostics,
+            Err(err) => panic!("{}", err),
+        };
+        let diagnostics = validate(vec!["Shader"]);
+        assert_eq!(diagnostics.len(), 1, "{:#?}", diagnostics);
+        assert!(diagnostics[0].error.contains("Float64"));
+        let diagnostics = validate(vec!["Shader", "Float64"]);
+        assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+    }
+
     #[test]
     fn glsl_ok() {
         let mut validator = glslang::Glslang::glsl();
@@ -49,6 +175,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn glsl_int64() {
+        let mut validator = glslang::Glslang::glsl();
+        let file_path = Path::new("./test/glsl/int64.comp.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        match validator.validate_shader(
+            shader_content,
+            file_path,
+            ValidationParams::default(),
+            &mut include_callback,
+        ) {
+            Ok(result) => {
+                println!("Diagnostic should be empty: {:#?}", result.0);
+                assert!(result.0.is_empty())
+            }
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    #[test]
+    fn glsl_atomic_counter() {
+        // Atomic counters are only available when targeting OpenGL.
+        let mut validator = glslang::Glslang::glsl();
+        let file_path = Path::new("./test/glsl/atomic-counter.comp.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        match validator.validate_shader(
+            shader_content,
+            file_path,
+            ValidationParams {
+                glsl_client: GlslTargetClient::OpenGL450,
+                ..Default::default()
+            },
+            &mut include_callback,
+        ) {
+            Ok(result) => {
+                println!("Diagnostic should be empty: {:#?}", result.0);
+                assert!(result.0.is_empty())
+            }
+            Err(err) => panic!("{}", err),
+        }
+    }
+
     #[test]
     fn glsl_include_config() {
         let mut validator = glslang::Glslang::glsl();
@@ -71,6 +239,31 @@ mod tests {
         };
     }
 
+    #[test]
+    fn glsl_include_virtual() {
+        let mut validator = glslang::Glslang::glsl();
+        let file_path = Path::new("./test/glsl/include-virtual.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        match validator.validate_shader(
+            shader_content,
+            file_path,
+            ValidationParams {
+                virtual_includes: HashMap::from([(
+                    "/Engine".into(),
+                    PathBuf::from("./test/glsl/inc0/"),
+                )]),
+                ..Default::default()
+            },
+            &mut include_callback,
+        ) {
+            Ok(result) => {
+                println!("Diagnostic should be empty: {:#?}", result.0);
+                assert!(result.0.is_empty())
+            }
+            Err(err) => panic!("{}", err),
+        };
+    }
+
     #[test]
     fn glsl_include_level() {
         let mut validator = glslang::Glslang::glsl();
@@ -115,6 +308,35 @@ mod tests {
         };
     }
 
+    #[test]
+    fn glsl_default_stage() {
+        let mut validator = glslang::Glslang::glsl();
+        let file_path = Path::new("./test/glsl/default-stage.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let mut validate = |glsl_default_stage: ShaderStage| {
+            validator
+                .validate_shader(
+                    shader_content.clone(),
+                    file_path,
+                    ValidationParams {
+                        glsl_default_stage: Some(glsl_default_stage),
+                        ..Default::default()
+                    },
+                    &mut include_callback,
+                )
+                .unwrap()
+                .0
+        };
+        // Validated as a shader of the default stage instead of an included header.
+        let diagnostics = validate(ShaderStage::Vertex);
+        assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+        let diagnostics = validate(ShaderStage::Fragment);
+        assert!(diagnostics
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.error.contains("gl_Position")));
+    }
+
     #[test]
     fn glsl_macro() {
         let mut validator = glslang::Glslang::glsl();
@@ -154,6 +376,10 @@ mod tests {
                 assert!(diags[0].file_path.is_some());
                 assert!(diags[0].file_path.as_ref().unwrap().exists());
                 assert_eq!(diags[0].error, String::from(" '#include' : Could not process include directive for header name: ./level1.glsl\n"));
+                assert_eq!(
+                    diags[0].code.as_deref(),
+                    Some("could-not-process-include-directive-for")
+                );
             }
             Err(err) => panic!("{}", err),
         };
@@ -178,6 +404,56 @@ mod tests {
         };
     }
 
+    #[test]
+    fn hlsl_entry_point_profile() {
+        let library = dxc::Dxc::get_entry_point_and_profile(&ValidationParams::default());
+        assert_eq!(library, ("".into(), "lib_6_8".into()));
+        let compute = dxc::Dxc::get_entry_point_and_profile(&ValidationParams {
+            entry_point: Some("csMain".into()),
+            shader_stage: Some(ShaderStage::Compute),
+            hlsl_shader_model: HlslShaderModel::ShaderModel6_6,
+            ..Default::default()
+        });
+        assert_eq!(compute, ("csMain".into(), "cs_6_6".into()));
+        // Raytracing stages are validated as library.
+        let raygen = dxc::Dxc::get_entry_point_and_profile(&ValidationParams {
+            entry_point: Some("rayGen".into()),
+            shader_stage: Some(ShaderStage::RayGeneration),
+            ..Default::default()
+        });
+        assert_eq!(raygen, ("".into(), "lib_6_8".into()));
+    }
+
+    #[test]
+    fn hlsl_error_codes() {
+        let errors = "shader.hlsl:3:5: error X3004: undeclared identifier 'value'\n\
+                      shader.hlsl:4:5: error: use of undeclared identifier 'value'\n\
+                      shader.hlsl:5:5: warning: implicit truncation of vector type [-Wconversion]\n";
+        let diagnostics = dxc::Dxc::parse_dxc_errors(
+            &errors.into(),
+            Path::new("./test/hlsl/ok.hlsl"),
+            &ValidationParams::default(),
+        )
+        .unwrap()
+        .diagnostics;
+        let codes: Vec<Option<&str>> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.code.as_deref())
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                Some("X3004"),
+                Some("use-of-undeclared-identifier"),
+                Some("-Wconversion")
+            ]
+        );
+        assert!(matches!(
+            diagnostics[0].severity,
+            ShaderErrorSeverity::Error
+        ));
+    }
+
     #[test]
     fn hlsl_include_config() {
         let mut validator = dxc::Dxc::new().unwrap();
@@ -288,6 +564,146 @@ mod tests {
         };
     }
 
+    #[test]
+    fn hlsl_wave_matrix() {
+        let file_path = Path::new("./test/hlsl/wave-matrix.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        // Lower models are raised, as wave matrices would not compile.
+        let params = ValidationParams {
+            hlsl_shader_model: HlslShaderModel::ShaderModel6_6,
+            ..Default::default()
+        };
+        assert_eq!(
+            dxc::Dxc::get_shader_model(&shader_content, &params),
+            HlslShaderModel::ShaderModel6_8
+        );
+        assert_eq!(
+            dxc::Dxc::get_shader_model("void main() {}", &params),
+            HlslShaderModel::ShaderModel6_6
+        );
+        let mut validator = dxc::Dxc::new().unwrap();
+        match validator.validate_shader(
+            shader_content,
+            file_path,
+            ValidationParams {
+                hlsl_shader_model: HlslShaderModel::ShaderModel6_8,
+                ..Default::default()
+            },
+            &mut include_callback,
+        ) {
+            Ok(result) => {
+                println!("Diagnostic should be empty: {:#?}", result.0);
+                assert!(result.0.is_empty())
+            }
+            Err(err) => panic!("{}", err),
+        };
+    }
+
+    #[test]
+    fn hlsl_glslang_backend() {
+        let mut validator = create_validator(ShadingLanguage::Hlsl, HlslBackend::Glslang);
+        let file_path = Path::new("./test/hlsl/include-config.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        match validator.validate_shader(
+            shader_content,
+            file_path,
+            ValidationParams {
+                includes: vec!["./test/hlsl/inc0/".into()],
+                ..Default::default()
+            },
+            &mut include_callback,
+        ) {
+            Ok(result) => {
+                println!("Diagnostic should be empty: {:#?}", result.0);
+                assert!(result.0.is_empty())
+            }
+            Err(err) => panic!("{}", err),
+        };
+        // 16 bits types require DXC.
+        let file_path = Path::new("./test/hlsl/16bit-types.hlsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        match validator.validate_shader(
+            shader_content,
+            file_path,
+            ValidationParams {
+                hlsl_enable16bit_types: true,
+                ..Default::default()
+            },
+            &mut include_callback,
+        ) {
+            Ok(result) => assert!(!result.0.is_empty()),
+            Err(err) => panic!("{}", err),
+        };
+    }
+
+    #[test]
+    fn glsl_validate_files() {
+        let mut validator = glslang::Glslang::glsl();
+        // All files include the same header, the last one with an error of its own.
+        let statements = [
+            "outColor = sharedBlock.tint;",
+            "outColor = vec4(shared_attenuation(1.0, 2.0));",
+            "outColor = undefinedColor;",
+        ];
+        let files: Vec<(PathBuf, String)> = statements
+            .iter()
+            .enumerate()
+            .map(|(index, statement)| {
+                let file_path = PathBuf::from(format!("./test/glsl/batch-{}.frag.glsl", index));
+                let shader_content = format!(
+                    "#version 450\n\
+                     #extension GL_GOOGLE_include_directive : require\n\
+                     #include \"../shared/shared.h\"\n\
+                     layout(location = 0) out vec4 outColor;\n\
+                     void main() {{\n\
+                     \x20   {}\n\
+                     }}\n",
+                    statement
+                );
+                (file_path, shader_content)
+            })
+            .collect();
+        let results = validator.validate_files(&files, ValidationParams::default());
+        assert_eq!(results.len(), 3);
+        for (file_path, _) in &files[..2] {
+            match &results[file_path] {
+                Ok(diagnostics) => assert!(diagnostics.is_empty(), "{:#?}", diagnostics),
+                Err(err) => panic!("{}", err),
+            }
+        }
+        match &results[&files[2].0] {
+            Ok(diagnostics) => {
+                assert!(!diagnostics.is_empty());
+                assert!(diagnostics.diagnostics[0].error.contains("undefinedColor"));
+                assert_eq!(diagnostics.diagnostics[0].line, 6);
+            }
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn glsl_async() {
+        let file_path = Path::new("./test/glsl/ok.frag.glsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        match validate_shader_async(
+            ShadingLanguage::Glsl,
+            HlslBackend::default(),
+            shader_content,
+            file_path.into(),
+            ValidationParams::default(),
+            include_callback,
+        )
+        .await
+        {
+            Ok(result) => {
+                println!("Diagnostic should be empty: {:#?}", result.0);
+                assert!(result.0.is_empty())
+            }
+            Err(err) => panic!("{}", err),
+        };
+    }
+
     #[test]
     fn wgsl_ok() {
         let mut validator = naga::Naga::new();
@@ -306,4 +722,156 @@ mod tests {
             Err(err) => panic!("{}", err),
         };
     }
+
+    #[test]
+    fn wgsl_include() {
+        let mut validator = naga::Naga::new();
+        let file_path = Path::new("./test/wgsl/include.wgsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let params = ValidationParams {
+            wgsl_preprocess_includes: true,
+            ..Default::default()
+        };
+        // Naga does not support includes.
+        match validator.validate_shader(
+            shader_content.clone(),
+            file_path,
+            ValidationParams::default(),
+            &mut include_callback,
+        ) {
+            Ok(result) => assert!(!result.0.is_empty()),
+            Err(err) => panic!("{}", err),
+        };
+        match validator.validate_shader(shader_content, file_path, params, &mut include_callback) {
+            Ok(result) => {
+                println!("Diagnostic should be empty: {:#?}", result.0);
+                assert!(result.0.is_empty())
+            }
+            Err(err) => panic!("{}", err),
+        };
+    }
+
+    #[test]
+    fn wgsl_include_error_location() {
+        let mut validator = naga::Naga::new();
+        let file_path = Path::new("./test/wgsl/include-error.wgsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let params = ValidationParams {
+            wgsl_preprocess_includes: true,
+            ..Default::default()
+        };
+        match validator.validate_shader(shader_content, file_path, params, &mut include_callback) {
+            Ok(result) => {
+                let diagnostic = &result.0.diagnostics[0];
+                assert_eq!(
+                    diagnostic.file_path,
+                    Some(
+                        crate::include::canonicalize(Path::new("./test/wgsl/inc0/error.wgsl"))
+                            .unwrap()
+                    )
+                );
+                // Function declaration of the included file.
+                assert_eq!(diagnostic.line, 2);
+                assert_eq!(diagnostic.code.as_deref(), Some("function-is-invalid"));
+            }
+            Err(err) => panic!("{}", err),
+        };
+    }
+
+    #[test]
+    fn wgsl_include_circular() {
+        let mut validator = naga::Naga::new();
+        let file_path = Path::new("./test/wgsl/include-circular.wgsl");
+        let shader_content = std::fs::read_to_string(file_path).unwrap();
+        let params = ValidationParams {
+            wgsl_preprocess_includes: true,
+            ..Default::default()
+        };
+        match validator.validate_shader(shader_content, file_path, params, &mut include_callback) {
+            Ok(result) => {
+                assert_eq!(result.0.diagnostics.len(), 1, "{:#?}", result.0);
+                assert_eq!(
+                    result.0.diagnostics[0].error,
+                    "Circular include: include-circular.wgsl -> circular.wgsl -> include-circular.wgsl"
+                );
+            }
+            Err(err) => panic!("{}", err),
+        };
+    }
+
+    // Validator reporting a single diagnostic, to check which one is resolved.
+    struct CustomValidator {}
+
+    impl Validator for CustomValidator {
+        fn validate_shader(
+            &mut self,
+            _shader_content: String,
+            file_path: &Path,
+            _params: ValidationParams,
+            _include_callback: &mut dyn FnMut(&Path) -> Option<String>,
+        ) -> Result<
+            (
+                crate::shader_error::ShaderDiagnosticList,
+                crate::include::Dependencies,
+            ),
+            crate::shader_error::ValidatorError,
+        > {
+            Ok((
+                crate::shader_error::ShaderDiagnostic {
+                    file_path: Some(file_path.into()),
+                    severity: ShaderErrorSeverity::Information,
+                    error: "custom".into(),
+                    line: 1,
+                    pos: 0,
+                    end: None,
+                    unnecessary: false,
+                    code: None,
+                    fix: None,
+                }
+                .into(),
+                crate::include::Dependencies::new(),
+            ))
+        }
+    }
+
+    #[test]
+    fn validator_registry() {
+        let mut registry = registry::ValidatorRegistry::default();
+        assert_eq!(
+            registry.get_default_backend(ShadingLanguage::Glsl),
+            Some("glslang")
+        );
+        assert!(registry.create(ShadingLanguage::Glsl, "custom").is_err());
+        registry.register(ShadingLanguage::Glsl, "custom", || {
+            Ok(Box::new(CustomValidator {}))
+        });
+        assert_eq!(
+            registry.get_backends(ShadingLanguage::Glsl),
+            vec!["custom", "glslang"]
+        );
+        // Last registered backend is the default one.
+        assert_eq!(
+            registry.get_default_backend(ShadingLanguage::Glsl),
+            Some("custom")
+        );
+        // Unknown backends resolve to the default one.
+        assert_eq!(
+            registry.resolve_backend(ShadingLanguage::Glsl, "slang"),
+            Some("custom")
+        );
+        let mut validator = registry.create_default(ShadingLanguage::Glsl).unwrap();
+        let (diagnostics, _) = validator
+            .validate_shader(
+                "void main() {}".into(),
+                Path::new("./test/glsl/custom.frag.glsl"),
+                ValidationParams::default(),
+                &mut include_callback,
+            )
+            .unwrap();
+        assert_eq!(diagnostics.diagnostics.len(), 1);
+        assert_eq!(diagnostics.diagnostics[0].error, "custom");
+        // Builtin validators are still available.
+        registry.set_default_backend(ShadingLanguage::Glsl, "glslang");
+        assert!(registry.create_default(ShadingLanguage::Glsl).is_ok());
+    }
 }
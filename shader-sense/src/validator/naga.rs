@@ -2,10 +2,11 @@ use naga::{
     front::wgsl::{self, ParseError},
     valid::{Capabilities, ValidationFlags},
 };
-use std::path::Path;
+use regex::Regex;
+use std::path::{Path, PathBuf};
 
 use crate::{
-    include::Dependencies,
+    include::{Dependencies, IncludeHandler, IncludeVisit},
     shader_error::{ShaderDiagnostic, ShaderDiagnosticList, ShaderErrorSeverity, ValidatorError},
 };
 
@@ -15,14 +16,111 @@ pub struct Naga {
     validator: naga::valid::Validator,
 }
 
+// Source with #include directives expanded, as naga does not support them.
+#[derive(Default)]
+struct ExpandedSource {
+    content: String,
+    lines: Vec<(Option<PathBuf>, u32)>, // Origin file (None for main file) & line of each line.
+}
+
+impl ExpandedSource {
+    fn push_line(&mut self, text: &str, file_path: Option<&Path>, line: u32) {
+        self.content.push_str(text);
+        self.content.push('\n');
+        self.lines.push((file_path.map(PathBuf::from), line));
+    }
+    // Report diagnostic against the file & line it originates from.
+    fn remap(&self, mut diagnostic: ShaderDiagnostic) -> ShaderDiagnostic {
+        let origin = (diagnostic.line as usize)
+            .checked_sub(1)
+            .and_then(|line| self.lines.get(line));
+        if let Some((file_path, line)) = origin {
+            diagnostic.file_path = file_path.clone();
+            diagnostic.line = line + 1;
+        }
+        diagnostic
+    }
+}
+
 impl Naga {
     pub fn new() -> Self {
         Self {
             validator: naga::valid::Validator::new(ValidationFlags::all(), Capabilities::all()),
         }
     }
+    // Headers are only expanded once, as with #pragma once.
+    fn expand_includes(
+        shader_content: &str,
+        file_path: Option<&Path>,
+        include_handler: &mut IncludeHandler,
+        include_callback: &mut dyn FnMut(&Path) -> Option<String>,
+        expanded_source: &mut ExpandedSource,
+        diagnostics: &mut ShaderDiagnosticList,
+    ) {
+        let include_regex = Regex::new("^\\s*\\#include\\s+\"([\\w\\s\\\\/\\.\\-]+)\"").unwrap();
+        for (line, text) in shader_content.lines().enumerate() {
+            let line = line as u32;
+            let relative_path = match include_regex.captures(text) {
+                Some(capture) => capture.get(1).unwrap().as_str(),
+                None => {
+                    expanded_source.push_line(text, file_path, line);
+                    continue;
+                }
+            };
+            let mut push_error = |error: String| {
+                diagnostics.push(ShaderDiagnostic {
+                    file_path: file_path.map(PathBuf::from),
+                    severity: ShaderErrorSeverity::Error,
+                    error,
+                    line: line + 1,
+                    pos: 0,
+                    end: None,
+                    unnecessary: false,
+                    code: None,
+                    fix: None,
+                })
+            };
+            let absolute_path =
+                match include_handler.search_path_in_includes(Path::new(relative_path)) {
+                    Some(absolute_path) => absolute_path,
+                    None => {
+                        push_error(format!("Failed to find include file {}", relative_path));
+                        continue;
+                    }
+                };
+            match include_handler.enter_include(&absolute_path) {
+                IncludeVisit::First => {
+                    match include_callback(&absolute_path) {
+                        Some(content) => Self::expand_includes(
+                            &content,
+                            Some(&absolute_path),
+                            include_handler,
+                            include_callback,
+                            expanded_source,
+                            diagnostics,
+                        ),
+                        None => push_error(format!(
+                            "Failed to read include file {}",
+                            absolute_path.display()
+                        )),
+                    }
+                    include_handler.exit_include();
+                }
+                IncludeVisit::AlreadyVisited => {}
+                IncludeVisit::Circular(chain) => push_error(format!(
+                    "Circular include: {}",
+                    chain
+                        .iter()
+                        .map(|path| path.file_name().unwrap().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join(" -> ")
+                )),
+            }
+        }
+    }
     fn from_parse_err(err: ParseError, src: &str) -> ShaderDiagnostic {
         let error = err.emit_to_string(src);
+        let code = ShaderDiagnostic::get_message_code(&error);
         let loc = err.location(src);
         if let Some(loc) = loc {
             ShaderDiagnostic {
@@ -31,6 +129,10 @@ impl Naga {
                 error,
                 line: loc.line_number,
                 pos: loc.line_position,
+                end: None,
+                unnecessary: false,
+                code,
+                fix: None,
             }
         } else {
             ShaderDiagnostic {
@@ -39,6 +141,10 @@ impl Naga {
                 error,
                 line: 0,
                 pos: 0,
+                end: None,
+                unnecessary: false,
+                code,
+                fix: None,
             }
         }
     }
@@ -47,16 +153,45 @@ impl Validator for Naga {
     fn validate_shader(
         &mut self,
         shader_content: String,
-        _file_path: &Path,
-        _params: ValidationParams,
-        _include_callback: &mut dyn FnMut(&Path) -> Option<String>,
+        file_path: &Path,
+        params: ValidationParams,
+        include_callback: &mut dyn FnMut(&Path) -> Option<String>,
     ) -> Result<(ShaderDiagnosticList, Dependencies), ValidatorError> {
+        let (shader_content, expanded_source, dependencies) = if params.wgsl_preprocess_includes {
+            let mut include_handler =
+                IncludeHandler::new(file_path, params.includes, params.virtual_includes);
+            let mut expanded_source = ExpandedSource::default();
+            let mut diagnostics = ShaderDiagnosticList::empty();
+            Self::expand_includes(
+                &shader_content,
+                None,
+                &mut include_handler,
+                include_callback,
+                &mut expanded_source,
+                &mut diagnostics,
+            );
+            let dependencies = include_handler.get_dependencies().clone();
+            if !diagnostics.is_empty() {
+                return Ok((diagnostics, dependencies));
+            }
+            (
+                expanded_source.content.clone(),
+                Some(expanded_source),
+                dependencies,
+            )
+        } else {
+            (shader_content, None, Dependencies::new())
+        };
+        let remap = |diagnostic: ShaderDiagnostic| match &expanded_source {
+            Some(expanded_source) => expanded_source.remap(diagnostic),
+            None => diagnostic,
+        };
         let module = match wgsl::parse_str(&shader_content)
             .map_err(|err| Self::from_parse_err(err, &shader_content))
         {
             Ok(module) => module,
             Err(diag) => {
-                return Ok((ShaderDiagnosticList::from(diag), Dependencies::new()));
+                return Ok((ShaderDiagnosticList::from(remap(diag)), dependencies));
             }
         };
 
@@ -64,23 +199,27 @@ impl Validator for Naga {
             let mut list = ShaderDiagnosticList::empty();
             for (span, _) in error.spans() {
                 let loc = span.location(&shader_content);
-                list.push(ShaderDiagnostic {
+                list.push(remap(ShaderDiagnostic {
                     file_path: None,
                     severity: ShaderErrorSeverity::Error,
                     error: error.emit_to_string(""),
                     line: loc.line_number,
                     pos: loc.line_position,
-                });
+                    end: None,
+                    unnecessary: false,
+                    code: ShaderDiagnostic::get_message_code(&error.emit_to_string("")),
+                    fix: None,
+                }));
             }
             if list.is_empty() {
                 Err(ValidatorError::internal(
                     error.emit_to_string(&shader_content),
                 ))
             } else {
-                Ok((list, Dependencies::new()))
+                Ok((list, dependencies))
             }
         } else {
-            Ok((ShaderDiagnosticList::empty(), Dependencies::new()))
+            Ok((ShaderDiagnosticList::empty(), dependencies))
         }
     }
 }
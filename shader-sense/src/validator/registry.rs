@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use crate::{shader::ShadingLanguage, shader_error::ValidatorError};
+
+use super::{glslang::Glslang, naga::Naga, validator::Validator};
+
+pub type ValidatorFactory = Box<dyn Fn() -> Result<Box<dyn Validator>, ValidatorError>>;
+
+// Validators available for each language, identified by a backend name such as "dxc".
+// Embedders can register their own validators or override the builtin ones.
+pub struct ValidatorRegistry {
+    factories: Vec<(ShadingLanguage, &'static str, ValidatorFactory)>,
+    default_backends: HashMap<ShadingLanguage, &'static str>,
+}
+
+impl Default for ValidatorRegistry {
+    // Builtin validators. DXC is not available on WASI, so HLSL uses glslang there.
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register(ShadingLanguage::Wgsl, "naga", || Ok(Box::new(Naga::new())));
+        registry.register(ShadingLanguage::Glsl, "glslang", || {
+            Ok(Box::new(Glslang::glsl()))
+        });
+        registry.register(ShadingLanguage::Hlsl, "glslang", || {
+            Ok(Box::new(Glslang::hlsl()))
+        });
+        #[cfg(not(target_os = "wasi"))]
+        registry.register(ShadingLanguage::Hlsl, "dxc", || {
+            super::dxc::Dxc::new()
+                .map(|dxc| Box::new(dxc) as Box<dyn Validator>)
+                .map_err(|err| ValidatorError::internal(format!("Failed to load DXC: {}", err)))
+        });
+        registry
+    }
+}
+
+impl ValidatorRegistry {
+    // Registry without any validator.
+    pub fn new() -> Self {
+        Self {
+            factories: Vec::new(),
+            default_backends: HashMap::new(),
+        }
+    }
+    // The last backend registered for a language is its default one.
+    // Registering an existing backend replaces it.
+    pub fn register(
+        &mut self,
+        shading_language: ShadingLanguage,
+        backend: &'static str,
+        factory: impl Fn() -> Result<Box<dyn Validator>, ValidatorError> + 'static,
+    ) {
+        self.factories
+            .retain(|(language, name, _)| *language != shading_language || *name != backend);
+        self.factories
+            .push((shading_language, backend, Box::new(factory)));
+        self.default_backends.insert(shading_language, backend);
+    }
+    fn find(
+        &self,
+        shading_language: ShadingLanguage,
+        backend: &str,
+    ) -> Option<&(ShadingLanguage, &'static str, ValidatorFactory)> {
+        self.factories
+            .iter()
+            .find(|(language, name, _)| *language == shading_language && *name == backend)
+    }
+    pub fn set_default_backend(
+        &mut self,
+        shading_language: ShadingLanguage,
+        backend: &'static str,
+    ) {
+        self.default_backends.insert(shading_language, backend);
+    }
+    pub fn get_default_backend(&self, shading_language: ShadingLanguage) -> Option<&'static str> {
+        self.default_backends.get(&shading_language).copied()
+    }
+    pub fn get_backends(&self, shading_language: ShadingLanguage) -> Vec<&'static str> {
+        let mut backends: Vec<&'static str> = self
+            .factories
+            .iter()
+            .filter(|(language, _, _)| *language == shading_language)
+            .map(|(_, backend, _)| *backend)
+            .collect();
+        backends.sort();
+        backends
+    }
+    pub fn has_backend(&self, shading_language: ShadingLanguage, backend: &str) -> bool {
+        self.find(shading_language, backend).is_some()
+    }
+    // Backend used when requesting one, falling back to the default one if it is not registered.
+    pub fn resolve_backend(
+        &self,
+        shading_language: ShadingLanguage,
+        backend: &str,
+    ) -> Option<&'static str> {
+        match self.find(shading_language, backend) {
+            Some((_, backend, _)) => Some(*backend),
+            None => self.get_default_backend(shading_language),
+        }
+    }
+    pub fn create(
+        &self,
+        shading_language: ShadingLanguage,
+        backend: &str,
+    ) -> Result<Box<dyn Validator>, ValidatorError> {
+        match self.find(shading_language, backend) {
+            Some((_, _, factory)) => factory(),
+            None => Err(ValidatorError::internal(format!(
+                "No validator registered for {} with backend {}",
+                shading_language.to_string(),
+                backend
+            ))),
+        }
+    }
+    pub fn create_default(
+        &self,
+        shading_language: ShadingLanguage,
+    ) -> Result<Box<dyn Validator>, ValidatorError> {
+        match self.get_default_backend(shading_language) {
+            Some(backend) => self.create(shading_language, backend),
+            None => Err(ValidatorError::internal(format!(
+                "No validator registered for {}",
+                shading_language.to_string()
+            ))),
+        }
+    }
+}
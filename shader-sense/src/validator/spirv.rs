@@ -0,0 +1,145 @@
+// Minimal SPIR-V reader to list the capabilities & extensions declared by a module.
+
+const SPIRV_MAGIC_NUMBER: u32 = 0x07230203;
+const SPIRV_HEADER_SIZE: usize = 5;
+const OP_EXTENSION: u32 = 10;
+const OP_CAPABILITY: u32 = 17;
+
+const SPIRV_CAPABILITIES: [(u32, &str); 89] = [
+    (0, "Matrix"),
+    (1, "Shader"),
+    (2, "Geometry"),
+    (3, "Tessellation"),
+    (4, "Addresses"),
+    (5, "Linkage"),
+    (6, "Kernel"),
+    (7, "Vector16"),
+    (8, "Float16Buffer"),
+    (9, "Float16"),
+    (10, "Float64"),
+    (11, "Int64"),
+    (12, "Int64Atomics"),
+    (13, "ImageBasic"),
+    (14, "ImageReadWrite"),
+    (15, "ImageMipmap"),
+    (17, "Pipes"),
+    (18, "Groups"),
+    (19, "DeviceEnqueue"),
+    (20, "LiteralSampler"),
+    (21, "AtomicStorage"),
+    (22, "Int16"),
+    (23, "TessellationPointSize"),
+    (24, "GeometryPointSize"),
+    (25, "ImageGatherExtended"),
+    (27, "StorageImageMultisample"),
+    (28, "UniformBufferArrayDynamicIndexing"),
+    (29, "SampledImageArrayDynamicIndexing"),
+    (30, "StorageBufferArrayDynamicIndexing"),
+    (31, "StorageImageArrayDynamicIndexing"),
+    (32, "ClipDistance"),
+    (33, "CullDistance"),
+    (34, "ImageCubeArray"),
+    (35, "SampleRateShading"),
+    (36, "ImageRect"),
+    (37, "SampledRect"),
+    (38, "GenericPointer"),
+    (39, "Int8"),
+    (40, "InputAttachment"),
+    (41, "SparseResidency"),
+    (42, "MinLod"),
+    (43, "Sampled1D"),
+    (44, "Image1D"),
+    (45, "SampledCubeArray"),
+    (46, "SampledBuffer"),
+    (47, "ImageBuffer"),
+    (48, "ImageMSArray"),
+    (49, "StorageImageExtendedFormats"),
+    (50, "ImageQuery"),
+    (51, "DerivativeControl"),
+    (52, "InterpolationFunction"),
+    (53, "TransformFeedback"),
+    (54, "GeometryStreams"),
+    (55, "StorageImageReadWithoutFormat"),
+    (56, "StorageImageWriteWithoutFormat"),
+    (57, "MultiViewport"),
+    (58, "SubgroupDispatch"),
+    (59, "NamedBarrier"),
+    (60, "PipeStorage"),
+    (61, "GroupNonUniform"),
+    (62, "GroupNonUniformVote"),
+    (63, "GroupNonUniformArithmetic"),
+    (64, "GroupNonUniformBallot"),
+    (65, "GroupNonUniformShuffle"),
+    (66, "GroupNonUniformShuffleRelative"),
+    (67, "GroupNonUniformClustered"),
+    (68, "GroupNonUniformQuad"),
+    (69, "ShaderLayer"),
+    (70, "ShaderViewportIndex"),
+    (4423, "SubgroupBallotKHR"),
+    (4427, "DrawParameters"),
+    (4431, "SubgroupVoteKHR"),
+    (4433, "StorageBuffer16BitAccess"),
+    (4434, "UniformAndStorageBuffer16BitAccess"),
+    (4435, "StoragePushConstant16"),
+    (4436, "StorageInputOutput16"),
+    (4437, "DeviceGroup"),
+    (4439, "MultiView"),
+    (4441, "VariablePointersStorageBuffer"),
+    (4442, "VariablePointers"),
+    (4448, "StorageBuffer8BitAccess"),
+    (4449, "UniformAndStorageBuffer8BitAccess"),
+    (4450, "StoragePushConstant8"),
+    (4472, "RayQueryKHR"),
+    (4479, "RayTracingKHR"),
+    (5283, "MeshShadingEXT"),
+    (5301, "ShaderNonUniform"),
+    (5302, "RuntimeDescriptorArray"),
+    (5347, "PhysicalStorageBufferAddresses"),
+];
+
+// Name of the capability as written in the SPIR-V specification, or its value if unknown.
+pub fn get_capability_name(capability: u32) -> String {
+    match SPIRV_CAPABILITIES
+        .iter()
+        .find(|(value, _)| *value == capability)
+    {
+        Some((_, name)) => name.to_string(),
+        None => capability.to_string(),
+    }
+}
+
+fn read_literal_string(words: &[u32]) -> String {
+    let bytes: Vec<u8> = words
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .take_while(|byte| *byte != 0)
+        .collect();
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+// Get the capabilities & extensions declared by the module.
+pub fn get_capabilities_and_extensions(spirv: &[u32]) -> (Vec<String>, Vec<String>) {
+    let mut capabilities = Vec::new();
+    let mut extensions = Vec::new();
+    if spirv.len() < SPIRV_HEADER_SIZE || spirv[0] != SPIRV_MAGIC_NUMBER {
+        return (capabilities, extensions);
+    }
+    let mut offset = SPIRV_HEADER_SIZE;
+    while offset < spirv.len() {
+        let word_count = (spirv[offset] >> 16) as usize;
+        let opcode = spirv[offset] & 0xffff;
+        if word_count == 0 || offset + word_count > spirv.len() {
+            break;
+        }
+        let operands = &spirv[offset + 1..offset + word_count];
+        match opcode {
+            OP_CAPABILITY if !operands.is_empty() => {
+                capabilities.push(get_capability_name(operands[0]))
+            }
+            OP_EXTENSION => extensions.push(read_literal_string(operands)),
+            _ => {}
+        }
+        offset += word_count;
+    }
+    (capabilities, extensions)
+}
@@ -1,20 +1,76 @@
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
 
 use crate::{
     include::Dependencies,
-    shader::{GlslSpirvVersion, GlslTargetClient, HlslShaderModel, HlslVersion},
+    shader::{
+        GlslProfile, GlslSpirvVersion, GlslTargetClient, HlslShaderModel, HlslVersion, ShaderStage,
+    },
     shader_error::{ShaderDiagnosticList, ValidatorError},
 };
 
-#[derive(Debug, Default)]
+// Resource slots available to a shader, such as D3D11 limits.
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceLimits {
+    pub textures: u32,
+    pub samplers: u32,
+    pub uavs: u32,
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct ValidationParams {
     pub includes: Vec<String>,
+    // Virtual path prefix such as /Engine mapped to its filesystem root.
+    pub virtual_includes: HashMap<String, PathBuf>,
     pub defines: HashMap<String, String>,
+    // Constants such as PI expected by projects, completed as builtins with their value.
+    pub math_constants: HashMap<String, String>,
     pub hlsl_shader_model: HlslShaderModel,
     pub hlsl_version: HlslVersion,
     pub hlsl_enable16bit_types: bool,
+    // Entry point & stage to validate. Whole file is validated as a library when stage is not set.
+    pub entry_point: Option<String>,
+    pub shader_stage: Option<ShaderStage>,
     pub glsl_client: GlslTargetClient,
     pub glsl_spirv: GlslSpirvVersion,
+    // Version & profile used when the file does not declare a #version.
+    pub glsl_version: Option<u32>,
+    pub glsl_profile: Option<GlslProfile>,
+    // Stage of GLSL files without stage extension. None to validate them as included headers.
+    pub glsl_default_stage: Option<ShaderStage>,
+    // Capabilities & extensions supported by the SPIR-V target. None to allow all of them.
+    pub spirv_capabilities: Option<Vec<String>>,
+    pub spirv_extensions: Option<Vec<String>>,
+    // Values of GLSL specialization constants by constant_id, overriding their default.
+    pub specialization_constants: HashMap<u32, String>,
+    // Expand #include directives of WGSL files before validating them with naga.
+    pub wgsl_preprocess_includes: bool,
+    // Warn for local arrays above this size in bytes. None to disable.
+    pub large_array_threshold: Option<u32>,
+    // Warn for declarations using identifiers reserved by the language.
+    pub lint_reserved_identifiers: bool,
+    // Hint for statements following a return, discard, break or continue.
+    pub lint_unreachable_code: bool,
+    // Report syntax errors that can be fixed automatically, such as a missing semicolon.
+    pub lint_syntax_fixes: bool,
+    // Warn for calls whose argument count match none of the function overloads.
+    pub lint_call_arguments: bool,
+    // Warn for padding & explicit offsets conflicting with std140/std430 or cbuffer layouts.
+    pub lint_block_layout: bool,
+    // Warn for implicit LOD texture sampling in branches or loops that might diverge.
+    pub lint_non_uniform_sampling: bool,
+    // Warn for out parameters that might not be assigned when the function returns.
+    pub lint_unassigned_out_parameters: bool,
+    // Warn for divisions of integers assigned to a float, truncated before the conversion.
+    pub lint_integer_division: bool,
+    // Warn for functions & structs defined twice. Overloads are not reported.
+    pub lint_redefinitions: bool,
+    // Warn for HLSL entry points sharing a stage when no entry point is selected.
+    pub lint_ambiguous_entry_points: bool,
+    // Warn for resources declared above these limits. None to disable.
+    pub resource_limits: Option<ResourceLimits>,
 }
 
 pub trait Validator {
@@ -26,6 +82,36 @@ pub trait Validator {
         include_callback: &mut dyn FnMut(&Path) -> Option<String>,
     ) -> Result<(ShaderDiagnosticList, Dependencies), ValidatorError>;
 
+    // Validate files sharing the same include configuration. Headers are read once for all files,
+    // but are still compiled by each file including them.
+    fn validate_files(
+        &mut self,
+        files: &[(PathBuf, String)],
+        params: ValidationParams,
+    ) -> HashMap<PathBuf, Result<ShaderDiagnosticList, ValidatorError>> {
+        let mut headers: HashMap<PathBuf, Option<String>> = HashMap::new();
+        let mut include_callback = |path: &Path| {
+            headers
+                .entry(PathBuf::from(path))
+                .or_insert_with(|| std::fs::read_to_string(path).ok())
+                .clone()
+        };
+        files
+            .iter()
+            .map(|(file_path, shader_content)| {
+                let result = self
+                    .validate_shader(
+                        shader_content.clone(),
+                        file_path,
+                        params.clone(),
+                        &mut include_callback,
+                    )
+                    .map(|(diagnostic_list, _)| diagnostic_list);
+                (file_path.clone(), result)
+            })
+            .collect()
+    }
+
     fn get_file_name(&self, path: &Path) -> String {
         String::from(path.file_name().unwrap().to_string_lossy())
     }
@@ -0,0 +1,16 @@
+#version 450
+layout(constant_id = 0) const int SAMPLE_COUNT = 4;
+const int KERNEL_SIZE = 3;
+
+layout(binding = 0) uniform Samples {
+    vec4 offsets[SAMPLE_COUNT];
+    vec4 weights[KERNEL_SIZE * KERNEL_SIZE];
+};
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    int count = 2;
+    float values[count];
+    outColor = offsets[0];
+}
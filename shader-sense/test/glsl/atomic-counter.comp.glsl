@@ -0,0 +1,11 @@
+#version 460
+
+layout(local_size_x = 64) in;
+
+layout(binding = 0, offset = 4) uniform atomic_uint counter;
+
+void main() {
+    uint index = atomicCounterIncrement(counter);
+    atomicCounterAdd(counter, index);
+    atomicCounterCompSwap(counter, 0u, index);
+}
@@ -0,0 +1,28 @@
+#version 450
+
+struct Light {
+    vec3 direction;
+    float intensity;
+};
+
+layout(std140, binding = 0) uniform Camera {
+    vec3 position;
+    float exposure;
+    layout(offset = 20) vec4 color;
+    float weights[4];
+    Light light;
+    float time;
+    vec3 tint;
+} camera;
+
+layout(std430, binding = 1) buffer Values {
+    float values[];
+};
+
+layout(push_constant) uniform Push {
+    vec2 uv;
+    layout(offset = 16) vec4 scale;
+} push;
+
+void main() {
+}
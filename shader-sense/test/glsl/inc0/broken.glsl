@@ -0,0 +1,3 @@
+float getBroken() {
+    return undeclaredValue;
+}
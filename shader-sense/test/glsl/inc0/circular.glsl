@@ -0,0 +1,3 @@
+#include "../include-circular.frag.glsl"
+
+const float circular = 1.0;
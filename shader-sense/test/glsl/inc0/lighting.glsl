@@ -0,0 +1,9 @@
+#ifdef USE_LIGHTING
+vec3 computeLighting(vec3 normal) {
+    return vec3(max(normal.z, 0.0));
+}
+#else
+vec3 computeUnlit() {
+    return vec3(1.0);
+}
+#endif
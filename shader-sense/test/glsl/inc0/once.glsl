@@ -0,0 +1,3 @@
+#pragma once
+
+const float once = 1.0;
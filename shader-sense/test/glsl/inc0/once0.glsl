@@ -0,0 +1,3 @@
+#include "./once.glsl"
+
+const float once0 = once;
@@ -0,0 +1,3 @@
+// Feature flags must be defined before the headers using them.
+#include "features.glsl"
+#include "lighting.glsl"
@@ -0,0 +1,8 @@
+#version 450
+
+#extension GL_GOOGLE_include_directive : require
+
+#include "./inc0/circular.glsl"
+
+void main() {
+}
@@ -0,0 +1,9 @@
+#version 450
+
+#extension GL_GOOGLE_include_directive : require
+
+#include "inc0/broken.glsl"
+
+void main() {
+    float value = getBroken();
+}
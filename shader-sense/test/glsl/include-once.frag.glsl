@@ -0,0 +1,10 @@
+#version 450
+
+#extension GL_GOOGLE_include_directive : require
+
+#include "./inc0/once0.glsl"
+#include "./inc0/once1.glsl"
+
+void main() {
+    float value = once0 + once1 + once;
+}
@@ -0,0 +1,8 @@
+#version 450
+#include "../shared/shared.h"
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    outColor = sharedBlock.tint * shared_attenuation(1.0, 2.0);
+}
@@ -0,0 +1,16 @@
+#version 450
+
+#extension GL_GOOGLE_include_directive : require
+
+#include "./inc0/umbrella.glsl"
+
+layout(location = 0) in vec3 normal;
+layout(location = 0) out vec4 color;
+
+void main() {
+#ifdef USE_LIGHTING
+    color = vec4(computeLighting(normal), 1.0);
+#else
+    color = vec4(computeUnlit(), 1.0);
+#endif
+}
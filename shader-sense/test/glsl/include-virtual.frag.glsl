@@ -0,0 +1,10 @@
+#version 450
+
+#extension GL_GOOGLE_include_directive : require
+
+// Virtual path resolved through a mapped root.
+#include "/Engine/inc1/level1.glsl"
+
+void main() {
+    level1;
+}
\ No newline at end of file
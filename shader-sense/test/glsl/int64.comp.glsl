@@ -0,0 +1,15 @@
+#version 450
+#extension GL_ARB_gpu_shader_int64 : require
+
+layout(std430, binding = 0) buffer Values {
+    dvec2 doubles;
+    u64vec2 handles;
+};
+
+void main() {
+    double value = packDouble2x32(uvec2(1u, 2u));
+    uint64_t handle = packUint2x32(unpackDouble2x32(value));
+    int64_t signed = packInt2x32(ivec2(-1, 0));
+    doubles = dvec2(value, int64BitsToDouble(signed));
+    handles = u64vec2(handle, doubleBitsToUint64(value));
+}
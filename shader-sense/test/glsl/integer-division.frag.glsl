@@ -0,0 +1,18 @@
+#version 450
+
+layout(location = 0) out vec4 color;
+
+const int count = 4;
+
+void main() {
+    // Truncated to 0.
+    float truncated = 1 / 2;
+    int index = 3;
+    float ratio;
+    ratio = (index / count);
+    // Float operands or integer results are fine.
+    float floated = 1.0 / 2;
+    int quotient = index / 2;
+    float casted = float(index) / count;
+    color = vec4(truncated, ratio, floated, casted + quotient);
+}
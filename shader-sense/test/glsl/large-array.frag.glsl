@@ -0,0 +1,12 @@
+#version 450
+
+layout(location = 0) out vec4 outColor;
+
+const int KERNEL_SIZE = 8;
+
+void main() {
+    vec4 kernel[KERNEL_SIZE * KERNEL_SIZE];
+    mat4 transforms[256];
+    kernel[0] = vec4(1.0);
+    outColor = transforms[0] * kernel[0];
+}
@@ -0,0 +1,6 @@
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    uint value = 1u << 2;
+    outColor = vec4(float(value));
+}
@@ -0,0 +1,31 @@
+#version 450
+
+layout(binding = 0) uniform sampler2D albedo;
+layout(binding = 1) uniform Parameters {
+    float threshold;
+    int count;
+};
+
+layout(location = 0) in vec2 uv;
+layout(location = 0) out vec4 color;
+
+void main() {
+    color = vec4(0.0);
+    // Uniform branch & loop, safe.
+    if (threshold > 0.5) {
+        color += texture(albedo, uv);
+    }
+    for (int i = 0; i < count; i++) {
+        color += texture(albedo, uv * float(i));
+    }
+    // Divergent branch, derivatives are undefined.
+    if (uv.x > threshold) {
+        color += texture(albedo, uv);
+        color += textureLod(albedo, uv, 0.0);
+    }
+    vec4 value = texture(albedo, uv);
+    while (value.a > 0.5) {
+        value = textureOffset(albedo, uv, ivec2(1, 0));
+    }
+    color += value;
+}
@@ -0,0 +1,66 @@
+#version 450
+
+layout(location = 0) in vec2 uv;
+layout(location = 0) out vec4 color;
+
+// Not assigned when returning early.
+void getEarly(float value, out float result) {
+    if (value > 0.5) {
+        return;
+    }
+    result = value;
+}
+
+// Not assigned when the branch is not taken.
+void getBranch(float value, out vec2 result) {
+    if (value > 0.5) {
+        result = vec2(value);
+    }
+}
+
+// Assigned on all paths.
+void getAssigned(float value, out float result, inout float accumulated) {
+    if (value > 0.5) {
+        result = 1.0;
+        return;
+    } else if (value > 0.25) {
+        result = 0.5;
+    } else {
+        result = 0.0;
+    }
+    accumulated += result;
+}
+
+// Assigned in all cases, or by another call.
+void getSwitch(int value, out float result, out float other) {
+    switch (value) {
+    case 0:
+        result = 1.0;
+        break;
+    default:
+        result = 0.0;
+        break;
+    }
+    getEarly(result, other);
+}
+
+// Inout parameters are initialized by the caller.
+void accumulate(float value, inout float accumulated) {
+    if (value > 0.5) {
+        accumulated += value;
+    }
+}
+
+void main() {
+    float early;
+    vec2 branch;
+    float assigned;
+    float accumulated = 0.0;
+    float other;
+    getEarly(uv.x, early);
+    getBranch(uv.y, branch);
+    getAssigned(uv.x, assigned, accumulated);
+    getSwitch(int(uv.y), assigned, other);
+    accumulate(uv.x, accumulated);
+    color = vec4(early, branch, assigned + accumulated + other);
+}
@@ -0,0 +1,10 @@
+#version 450
+
+layout(location = 0) out vec4 outColor;
+
+float gl_custom = 1.0;
+
+void main() {
+    float value = gl_custom;
+    outColor = vec4(value);
+}
@@ -0,0 +1,27 @@
+#version 450
+
+struct Particle {
+    vec4 position;
+};
+
+layout(std430, binding = 0) buffer Particles {
+    uint count;
+    Particle particles[];
+} particleBuffer;
+
+layout(std430, binding = 1) buffer Values {
+    float values[];
+};
+
+layout(std430, binding = 2) buffer Invalid {
+    float invalidValues[];
+    uint invalidCount;
+};
+
+struct Empty {
+    float emptyValues[0];
+};
+
+void main() {
+    particleBuffer.particles[gl_GlobalInvocationID.x].position.x = values[0];
+}
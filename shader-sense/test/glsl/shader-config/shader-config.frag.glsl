@@ -0,0 +1,14 @@
+#version 450
+
+#extension GL_GOOGLE_include_directive : require
+
+// Include directory & macro are set by .shader-config.json
+#include "inc1/level1.glsl"
+
+#if CONFIG_VALUE != 2
+#error CONFIG_VALUE is not set
+#endif
+
+void main() {
+    level1;
+}
\ No newline at end of file
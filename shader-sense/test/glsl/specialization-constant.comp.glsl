@@ -0,0 +1,11 @@
+#version 450
+
+layout(constant_id = 3) const int GROUP_COUNT = 4;
+const int GROUP_SIZE = 64;
+
+layout(local_size_x = 64) in;
+
+void main() {
+    float values[GROUP_COUNT * GROUP_SIZE];
+    values[0] = 0.0;
+}
@@ -0,0 +1,11 @@
+#version 450
+
+layout(local_size_x = 1) in;
+
+layout(std430, binding = 0) buffer Values {
+    double values[];
+};
+
+void main() {
+    values[gl_GlobalInvocationID.x] *= 2.0lf;
+}
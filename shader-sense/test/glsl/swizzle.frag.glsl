@@ -0,0 +1,10 @@
+#version 450
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    vec3 color = vec3(1.0);
+    color.xy = vec2(0.5);
+    color.rr = vec2(0.0);
+    outColor = vec4(color, 1.0);
+}
@@ -0,0 +1,11 @@
+#version 450
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    if (outColor.a < 0.5) {
+        discard;
+        outColor = vec4(0.0);
+    }
+    outColor = vec4(1.0);
+}
@@ -0,0 +1,26 @@
+struct VSOutput
+{
+    float4 position : SV_Position;
+};
+
+[shader("vertex")]
+VSOutput vsMain(float4 position : POSITION)
+{
+    VSOutput output;
+    output.position = position;
+    return output;
+}
+
+[shader("vertex")]
+VSOutput vsShadow(float4 position : POSITION)
+{
+    VSOutput output;
+    output.position = position * 0.5;
+    return output;
+}
+
+[shader("pixel")]
+float4 psMain(VSOutput input) : SV_Target
+{
+    return input.position;
+}
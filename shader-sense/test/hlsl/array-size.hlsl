@@ -0,0 +1,16 @@
+#define LIGHT_COUNT 4
+static const uint SHADOW_COUNT = LIGHT_COUNT * 2;
+uint dynamicCount;
+
+cbuffer Lights : register(b0) {
+    float4 lightColors[LIGHT_COUNT];
+    float4 shadowMatrices[SHADOW_COUNT];
+    float4 dynamicColors[dynamicCount];
+};
+
+float4 main(float2 uv : TEXCOORD0) : SV_TARGET {
+    uint count = 2;
+    float weights[SHADOW_COUNT + 1];
+    float values[count * 2];
+    return lightColors[0];
+}
@@ -0,0 +1,10 @@
+cbuffer Camera {
+    float3 position;
+    float exposure;
+    float2 uv;
+    float weights[2];
+};
+
+float4 main() : SV_Target {
+    return float4(position, exposure);
+}
@@ -0,0 +1,20 @@
+#define SCALE(x) ((x) * 2.0)
+
+RWByteAddressBuffer counters;
+RWStructuredBuffer<uint> values;
+
+float weight(float x, float y) {
+    return x * y;
+}
+
+[numthreads(8, 8, 1)]
+void main(uint3 id : SV_DispatchThreadID) {
+    float a = max(1.0, 2.0, 3.0);
+    uint previous;
+    InterlockedAdd(values[0], 1);
+    InterlockedAdd(values[0], 1, previous);
+    printf("%d %d %d", id.x, id.y, id.z);
+    float b = weight(a);
+    float c = SCALE(a) + weight(a, b);
+    float4 d = float4(a, b, c, 1.0);
+}
@@ -0,0 +1,24 @@
+#include "inc0/level0.hlsl"
+
+struct Output {
+float4 color : SV_Target0;
+        float depth : SV_Depth;
+};
+
+Output fs_main(float2 uv : TEXCOORD0)
+{
+Output output;
+  float value=uv.x+uv.y*2.0;
+    if (value>1.0) {
+value -= 1.0;
+        } else {
+  value   =   0.5;
+}
+	/* Multiline
+	   comment */
+    output.color = float4(value,
+                          uv,
+                          1.0);
+output.depth=value;
+return output;
+}
@@ -0,0 +1,9 @@
+// HLSL 2021 logical intrinsics replacing short-circuit operators on vectors.
+float4 main(float4 color : COLOR0) : SV_Target {
+    bool2 a = color.xy > 0.5;
+    bool2 b = color.zw > 0.5;
+    bool2 both = and(a, b);
+    bool2 either = or(a, b);
+    float2 value = select(both, color.xy, color.zw);
+    return float4(value, either.x ? 1.0 : 0.0, 1.0);
+}
@@ -0,0 +1,25 @@
+#define QUALITY 2
+
+#if QUALITY > 2
+float4 sampleHigh() { return 1.0; }
+#elif QUALITY == 2
+float4 sampleMedium() { return 0.5; }
+#else
+float4 sampleLow() { return 0.0; }
+#endif
+
+#ifdef USE_FOG
+float applyFog(float value) { return value; }
+#endif
+
+#ifndef USE_FOG
+float skipFog(float value) { return value; }
+#endif
+
+#if defined(USE_FOG) && !defined(NO_SHADOW)
+float fogShadow() { return 0.0; }
+#endif
+
+float4 main() : SV_Target {
+    return sampleMedium();
+}
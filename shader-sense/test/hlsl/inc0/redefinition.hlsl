@@ -0,0 +1,12 @@
+#ifndef REDEFINITION_H
+#define REDEFINITION_H
+
+struct Light {
+    float3 color;
+};
+
+float3 shade(float3 color) {
+    return color;
+}
+
+#endif
@@ -0,0 +1,6 @@
+#include "../shared/shared.h"
+
+float4 main(SharedVertex input) : SV_Target0
+{
+    return float4(input.uv, 0.0, 1.0) * shared_attenuation(1.0, 2.0);
+}
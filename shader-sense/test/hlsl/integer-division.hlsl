@@ -0,0 +1,9 @@
+float4 main(float4 position : SV_Position) : SV_Target {
+    uint width = 1920;
+    // Truncated to 0.
+    float2 uv = width / 4096u;
+    // Float operands are fine.
+    float scale = 1.0f / width;
+    float ratio = width / 2.0;
+    return float4(uv, scale, ratio);
+}
@@ -0,0 +1,29 @@
+interface IBRDF
+{
+    float evaluate(float3 normal);
+};
+
+interface ILight
+{
+    associatedtype Falloff : IBRDF;
+    float3 illuminate(float3 position, float3 normal);
+    float getIntensity();
+};
+
+struct PointLight : ILight
+{
+    float3 color;
+    float3 illuminate(float3 position, float3 normal) { return color * dot(position, normal); }
+    float getIntensity() { return 1.0; }
+};
+
+float3 shade<T : ILight>(T light, float3 normal)
+{
+    return light.illuminate(float3(0.0, 0.0, 0.0), normal) * light.getIntensity();
+}
+
+float4 main() : SV_Target
+{
+    PointLight light;
+    return float4(shade(light, float3(0.0, 1.0, 0.0)), 1.0);
+}
@@ -0,0 +1,13 @@
+#define HISTORY_SIZE 16
+static const uint SAMPLE_COUNT = HISTORY_SIZE * 2;
+
+float4 main(float2 uv : TEXCOORD0) : SV_Target0
+{
+    float data[4096];
+    float4 samples[SAMPLE_COUNT];
+    float history[HISTORY_SIZE][4];
+    data[0] = uv.x;
+    samples[0] = float4(uv, 0.0, 1.0);
+    history[0][0] = data[0];
+    return samples[0] * history[0][0];
+}
@@ -0,0 +1,20 @@
+Texture2D albedo : register(t0);
+SamplerState linearSampler : register(s0);
+
+cbuffer Parameters : register(b0) {
+    float threshold;
+};
+
+float4 main(float2 uv : TEXCOORD0) : SV_Target {
+    float4 color = 0;
+    // Uniform branch, safe.
+    if (threshold > 0.5) {
+        color += albedo.Sample(linearSampler, uv);
+    }
+    // Divergent branch, derivatives are undefined.
+    if (uv.x > threshold) {
+        color += albedo.Sample(linearSampler, uv);
+        color += albedo.SampleLevel(linearSampler, uv, 0);
+    }
+    return color;
+}
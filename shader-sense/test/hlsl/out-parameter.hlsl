@@ -0,0 +1,28 @@
+// Not assigned when the loop is not executed.
+void getLoop(uint count, out float result) {
+    for (uint i = 0; i < count; i++) {
+        result = i;
+    }
+}
+
+// Assigned on all paths, in out parameters are initialized by the caller.
+void getAssigned(uint count, out float2 result, in out float accumulated) {
+    result.x = 0.0;
+    [loop]
+    while (true) {
+        if (count == 0) {
+            break;
+        }
+        count--;
+    }
+    accumulated += result.x;
+}
+
+float4 main(float4 position : SV_Position) : SV_Target {
+    float value;
+    float2 vector;
+    float accumulated = 0.0;
+    getLoop(4, value);
+    getAssigned(4, vector, accumulated);
+    return float4(value, vector, accumulated);
+}
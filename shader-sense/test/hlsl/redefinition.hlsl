@@ -0,0 +1,29 @@
+#include "./inc0/redefinition.hlsl"
+
+// Redefinitions of the include.
+struct Light {
+    float3 color;
+};
+
+float3 shade(float3 color) {
+    return color * 0.5;
+}
+
+// Overloads are legal.
+float3 shade(float3 color, float intensity) {
+    return color * intensity;
+}
+
+float scale(float value) { return value; }
+float scale(float value) { return value * 2.0; }
+
+// Branches of a condition that can't be resolved.
+#if QUALITY > 1
+float blur(float value) { return value; }
+#else
+float blur(float value) { return value * 0.5; }
+#endif
+
+float4 main() : SV_Target {
+    return float4(shade(float3(1.0, 1.0, 1.0)), scale(1.0));
+}
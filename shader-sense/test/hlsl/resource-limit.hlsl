@@ -0,0 +1,14 @@
+Texture2D albedo : register(t0);
+Texture2D normals[2] : register(t1);
+SamplerState linearSampler : register(s0);
+RWTexture2D<float4> output : register(u0);
+cbuffer Constants : register(b0) {
+    float4 color;
+};
+Texture2D shadow : register(t3);
+RWTexture2D<float4> history : register(u8);
+
+float4 main(float2 uv : TEXCOORD0) : SV_TARGET {
+    Texture2D local = albedo;
+    return local.Sample(linearSampler, uv) * color;
+}
@@ -0,0 +1,5 @@
+float4 fs_main(float2 uv : TEXCOORD0) : SV_Target0 {
+    float2 valid = uv.yx;
+    float4 overLength = uv.xyzw;
+    return float4(valid, overLength.xy);
+}
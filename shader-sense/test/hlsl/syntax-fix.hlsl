@@ -0,0 +1,5 @@
+float4 main() : SV_TARGET {
+    float value = 0.0
+    float other = (value + 1.0;
+    return float4(value, 0.0, 0.0, 1.0));
+}
@@ -0,0 +1,11 @@
+float compute(float value) {
+    for (int i = 0; i < 4; i++) {
+        if (value > 1.0)
+            break;
+        value *= 2.0;
+    }
+    return value;
+    // Leftover debug code
+    value = 0.0;
+    return value;
+}
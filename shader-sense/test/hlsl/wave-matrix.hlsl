@@ -0,0 +1,17 @@
+ByteAddressBuffer inputBuffer;
+RWByteAddressBuffer outputBuffer;
+
+[shader("compute")]
+[numthreads(64, 1, 1)]
+void main() {
+    WaveMatrixLeft<float, 16, 16> left;
+    WaveMatrixRight<float, 16, 16> right;
+    WaveMatrixAccumulator<float, 16, 16> accumulator;
+    uint stride = 16 * 4;
+    left.Load(inputBuffer, 0, stride, false);
+    right.Load(inputBuffer, 16 * stride, stride, true);
+    accumulator.Fill(0.0);
+    accumulator.MultiplyAccumulate(left, right);
+    accumulator.ScalarMultiply(0.5);
+    accumulator.Store(outputBuffer, 0, stride, false);
+}
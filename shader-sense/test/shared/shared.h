@@ -0,0 +1,17 @@
+// Header shared between GLSL & HLSL shaders.
+#define SHARED_LIGHT_COUNT 4
+
+#ifdef __HLSL_VERSION
+struct SharedVertex {
+    float4 position : SV_Position;
+    float2 uv : TEXCOORD0;
+};
+#else
+layout(std140, binding = 0) uniform SharedBlock {
+    vec4 tint;
+} sharedBlock;
+#endif
+
+float shared_attenuation(float distance, float range) {
+    return clamp(1.0 - distance / range, 0.0, 1.0);
+}
@@ -0,0 +1,10 @@
+const PI: f32 = 3.14159;
+const COUNT = 4u;
+@id(12) override gain: f32 = 1.0;
+override scale: f32;
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(PI * gain * scale, 0.0, 0.0, 1.0);
+}
+override noType = 1.0;
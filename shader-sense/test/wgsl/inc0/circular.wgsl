@@ -0,0 +1 @@
+#include "../include-circular.wgsl"
@@ -0,0 +1,3 @@
+fn get_value() -> f32 {
+    return 1.0;
+}
@@ -0,0 +1,4 @@
+// Returns a bool instead of a float.
+fn get_error() -> f32 {
+    return true;
+}
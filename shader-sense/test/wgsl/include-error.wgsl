@@ -0,0 +1,6 @@
+#include "./inc0/error.wgsl"
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(get_error(), 0.0, 0.0, 1.0);
+}
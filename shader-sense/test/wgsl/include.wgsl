@@ -0,0 +1,7 @@
+#include "./inc0/common.wgsl"
+#include "./inc0/common.wgsl"
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(get_value(), 0.0, 0.0, 1.0);
+}